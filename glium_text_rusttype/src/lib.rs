@@ -35,7 +35,8 @@ glium_text::draw(&text, &system, &mut display.draw(), matrix, (1.0, 1.0, 0.0, 1.
 # }
 ```
 
-`FontTexture` will rasterize exact characters it's told to.  It can be passed
+`FontTexture` will rasterize the characters it's told to up front, and any
+others the first time they're drawn or measured.  It can be passed
 anything that implements `IntoIterator<char>` trait.  It is possible to use
 chained ranges with it like this:
 `(0 .. 0x7f+1).chain(0x400 .. 0x4ff+1).filter_map(std::char::from_u32)`
@@ -179,19 +180,41 @@ extern crate rusttype;
 #[macro_use]
 extern crate glium;
 
-use std::{borrow::Cow, collections::HashMap, default::Default, io::Read, ops::Deref, rc::Rc};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, default::Default, io::Read, ops::Deref, rc::Rc};
 
 use rusttype::{Point, Rect};
 
 use glium::{
     backend::{Context, Facade},
-    DrawParameters,
+    CapabilitiesSource, DrawParameters,
 };
 
+// number of different lines of text to remember the layout of
+const MAX_CACHED_LAYOUTS: usize = 1024;
+
 /// Texture which contains the characters of the font.
+///
+/// Characters that weren't rasterized up front are added the first time
+/// they are laid out, so any text can be drawn.
 pub struct FontTexture {
-    texture: glium::texture::Texture2d,
-    character_infos: HashMap<char, CharacterInfos>,
+    context: Rc<Context>,
+    font: rusttype::Font<'static>,
+    font_size: u32,
+
+    // rebuilt whenever characters are added to the atlas
+    texture: RefCell<glium::texture::Texture2d>,
+    atlas: RefCell<Atlas>,
+    character_infos: RefCell<HashMap<char, CharacterInfos>>,
+
+    // glyphs of text that was laid out recently, since the same text
+    // tends to be measured and drawn over and over again
+    layouts: RefCell<HashMap<String, Rc<Layout>>>,
+}
+
+// glyphs of a line of text along with their (left, top, right, bottom) coordinates
+struct Layout {
+    glyphs: Vec<(CharacterInfos, [f32; 4])>,
+    width: f32,
 }
 
 ///
@@ -215,6 +238,7 @@ impl From<rusttype::Error> for Error {
 pub struct TextSystem {
     context: Rc<Context>,
     program: glium::Program,
+    batch_program: glium::Program,
 }
 
 /// Object that will allow you to draw a text.
@@ -234,10 +258,11 @@ where
 // structure containing informations about a character of a font
 #[derive(Copy, Clone, Debug)]
 struct CharacterInfos {
-    // coordinates of the character top-left hand corner on the font's texture
+    // coordinates of the character top-left hand corner on the font's texture, in pixels
+    // (pixels rather than texture units, since the texture grows as characters are added)
     tex_coords: (f32, f32),
 
-    // width and height of character in texture units
+    // width and height of character in pixels
     tex_size: (f32, f32),
 
     // size of the character in EMs
@@ -259,6 +284,19 @@ struct TextureData {
     height: u32,
 }
 
+// characters rasterized so far, packed left to right and then top to bottom,
+// so that characters already in the atlas never move when more are added
+struct Atlas {
+    data: Vec<f32>,
+    width: u32,
+
+    // top-left pixel of where the next character will go
+    cursor_offset: (u32, u32),
+
+    // number of rows to skip at next carriage return
+    rows_to_skip: u32,
+}
+
 impl<'a> glium::texture::Texture2dDataSource<'a> for &'a TextureData {
     type Data = f32;
 
@@ -280,6 +318,31 @@ struct VertexFormat {
 
 implement_vertex!(VertexFormat, position, tex_coords);
 
+#[derive(Copy, Clone)]
+struct BatchVertexFormat {
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+    color: [f32; 4],
+}
+
+implement_vertex!(BatchVertexFormat, position, tex_coords, color);
+
+/// Collection of text that is drawn all at once.
+///
+/// Unlike `TextDisplay`, no GPU resources are created when text is added,
+/// glyph quads are instead accumulated (already transformed by their matrix)
+/// and uploaded in a single vertex buffer when the batch is drawn.
+/// All text in a batch must use the same `FontTexture`.
+pub struct TextBatch<F>
+where
+    F: Deref<Target = FontTexture>,
+{
+    context: Rc<Context>,
+    texture: F,
+    vertices: Vec<BatchVertexFormat>,
+    indices: Vec<u32>,
+}
+
 impl FontTexture {
     /// Vec<char> of complete ASCII range (from 0 to 255 bytes)
     pub fn ascii_character_list() -> Vec<char> {
@@ -287,8 +350,9 @@ impl FontTexture {
     }
 
     /// Creates a new texture representing a font stored in a `FontTexture`.
-    /// This function is very expensive as it needs to rasterize font into a
-    /// texture.  Complexity grows as `font_size**2 * characters_list.len()`.
+    /// The characters in `characters_list` are rasterized into the texture
+    /// right away, and any others are added the first time they're needed.
+    /// Complexity grows as `font_size**2 * characters_list.len()`.
     /// **Avoid rasterizing everything at once as it will be slow and end up in
     /// out of memory abort.**
     pub fn new<R, F, I>(facade: &F, font: R, font_size: u32, characters_list: I) -> Result<FontTexture, Error>
@@ -300,20 +364,155 @@ impl FontTexture {
         // building the freetype face object
         let font: Vec<u8> = font.bytes().map(|c| c.unwrap()).collect();
 
-        let collection = ::rusttype::FontCollection::from_bytes(&font[..])?;
-        let font = collection.into_font().unwrap();
+        let collection = ::rusttype::FontCollection::from_bytes(font)?;
+        let font = collection.into_font()?;
 
-        // building the infos
-        let (texture_data, chr_infos) = build_font_image(&font, characters_list.into_iter(), font_size)?;
+        // the width is chosen more or less arbitrarily, because we can store
+        // everything as long as the texture is at least as wide as the widest
+        // character we just try to estimate a width so that width ~= height
+        let characters_list: Vec<char> = characters_list.into_iter().collect();
+        let texture_width = get_nearest_po2(std::cmp::max(
+            font_size * 2_u32,
+            ((((characters_list.len() as u32) * font_size * font_size) as f32).sqrt()) as u32,
+        ));
+
+        let mut atlas = Atlas::new(texture_width);
+        let character_infos = characters_list
+            .into_iter()
+            .map(|character| (character, atlas.add(&font, font_size, character)))
+            .collect();
 
         // we load the texture in the display
-        let texture = glium::texture::Texture2d::new(facade, &texture_data).unwrap();
+        let texture = glium::texture::Texture2d::new(facade, &atlas.get_texture_data()).unwrap();
 
         Ok(FontTexture {
-            texture,
-            character_infos: chr_infos,
+            context: facade.get_context().clone(),
+            font,
+            font_size,
+            texture: RefCell::new(texture),
+            atlas: RefCell::new(atlas),
+            character_infos: RefCell::new(character_infos),
+            layouts: RefCell::new(HashMap::new()),
         })
     }
+
+    // rasterizes characters that aren't in the atlas yet and uploads the grown texture.
+    // characters that would make the texture taller than the GPU allows are left out and aren't drawn
+    fn add_characters(&self, characters: &[char]) {
+        let max_height = self.context.get_capabilities().max_texture_size.max(1) as u32;
+        let mut atlas = self.atlas.borrow_mut();
+        let mut character_infos = self.character_infos.borrow_mut();
+        let mut added = Vec::new();
+
+        for &character in characters {
+            if character_infos.contains_key(&character) {
+                continue;
+            }
+
+            if atlas.get_texture_height_with_room_for(2 * self.font_size) > max_height {
+                break;
+            }
+
+            character_infos.insert(character, atlas.add(&self.font, self.font_size, character));
+            added.push(character);
+        }
+
+        if added.is_empty() {
+            return;
+        }
+
+        match glium::texture::Texture2d::new(&self.context, &atlas.get_texture_data()) {
+            Ok(texture) => {
+                self.texture.replace(texture);
+            }
+            // the old texture is kept, which doesn't have the new characters in it
+            Err(_) => {
+                for character in added {
+                    character_infos.remove(&character);
+                }
+            }
+        }
+    }
+
+    // dimensions of the texture in pixels, which the shaders divide texture coordinates by
+    fn get_texture_size(&self) -> [f32; 2] {
+        let texture = self.texture.borrow();
+        [texture.get_width() as f32, texture.get_height().unwrap_or(1) as f32]
+    }
+
+    /// Returns the width in GL units of a line of text, without building anything.
+    pub fn measure(&self, text: &str) -> f32 {
+        self.layout(text, |_, _| ())
+    }
+
+    // Lays out a line of text, calling `quad` for each glyph with its
+    // infos and its (left, top, right, bottom) coordinates.
+    // Returns the total width of the text.
+    fn layout<Q>(&self, text: &str, mut quad: Q) -> f32
+    where
+        Q: FnMut(&CharacterInfos, [f32; 4]),
+    {
+        let cached = self.layouts.borrow().get(text).cloned();
+
+        let layout = cached.unwrap_or_else(|| {
+            let layout = Rc::new(self.build_layout(text));
+            let mut layouts = self.layouts.borrow_mut();
+
+            // forget everything once there's too much, what's still in use will come right back
+            if layouts.len() >= MAX_CACHED_LAYOUTS {
+                layouts.clear();
+            }
+
+            layouts.insert(text.to_string(), layout.clone());
+            layout
+        });
+
+        for (infos, coords) in &layout.glyphs {
+            quad(infos, *coords);
+        }
+
+        layout.width
+    }
+
+    fn build_layout(&self, text: &str) -> Layout {
+        let missing: Vec<char> = {
+            let character_infos = self.character_infos.borrow();
+            text.chars().filter(|character| !character_infos.contains_key(character)).collect()
+        };
+
+        if !missing.is_empty() {
+            self.add_characters(&missing);
+        }
+
+        let character_infos = self.character_infos.borrow();
+        let mut glyphs = Vec::with_capacity(text.len());
+        let mut total_text_width = 0.0;
+
+        for character in text.chars() {
+            let infos = match character_infos.get(&character) {
+                Some(infos) => infos,
+                None => continue,
+            };
+
+            total_text_width += infos.left_padding;
+
+            // calculating coords
+            let left_coord = total_text_width;
+            let right_coord = left_coord + infos.size.0;
+            let top_coord = infos.height_over_line;
+            let bottom_coord = infos.height_over_line - infos.size.1;
+
+            glyphs.push((*infos, [left_coord, top_coord, right_coord, bottom_coord]));
+
+            // going to next char
+            total_text_width = right_coord + infos.right_padding;
+        }
+
+        Layout {
+            glyphs,
+            width: total_text_width,
+        }
+    }
 }
 
 impl TextSystem {
@@ -330,6 +529,7 @@ impl TextSystem {
                         #version 140
 
                         uniform mat4 matrix;
+                        uniform vec2 tex_size;
                         in vec2 position;
                         in vec2 tex_coords;
 
@@ -337,7 +537,7 @@ impl TextSystem {
 
                         void main() {
                             gl_Position = matrix * vec4(position, 0.0, 1.0);
-                            v_tex_coords = tex_coords;
+                            v_tex_coords = tex_coords / tex_size;
                         }
                     ",
                     fragment: "
@@ -365,10 +565,11 @@ impl TextSystem {
                         attribute vec2 tex_coords;
                         varying vec2 v_tex_coords;
                         uniform mat4 matrix;
+                        uniform vec2 tex_size;
 
                         void main() {
                             gl_Position = matrix * vec4(position.x, position.y, 0.0, 1.0);
-                            v_tex_coords = tex_coords;
+                            v_tex_coords = tex_coords / tex_size;
 
                         }
                     ",
@@ -390,6 +591,77 @@ impl TextSystem {
 
             )
             .unwrap(),
+            batch_program: program!(facade,
+                140 => {
+                    vertex: "
+                        #version 140
+
+                        uniform vec2 tex_size;
+                        in vec2 position;
+                        in vec2 tex_coords;
+                        in vec4 color;
+
+                        out vec2 v_tex_coords;
+                        out vec4 v_color;
+
+                        void main() {
+                            gl_Position = vec4(position, 0.0, 1.0);
+                            v_tex_coords = tex_coords / tex_size;
+                            v_color = color;
+                        }
+                    ",
+                    fragment: "
+                        #version 140
+                        in vec2 v_tex_coords;
+                        in vec4 v_color;
+                        out vec4 f_color;
+                        uniform sampler2D tex;
+                        void main() {
+                            vec4 c = vec4(v_color.rgb, v_color.a * texture(tex, v_tex_coords));
+                            if (c.a <= 0.01) {
+                                discard;
+                            } else {
+                                f_color = c;
+                            }
+                        }
+                    "
+                },
+
+                110 => {
+                    vertex: "
+                        #version 110
+
+                        attribute vec2 position;
+                        attribute vec2 tex_coords;
+                        attribute vec4 color;
+                        varying vec2 v_tex_coords;
+                        varying vec4 v_color;
+                        uniform vec2 tex_size;
+
+                        void main() {
+                            gl_Position = vec4(position.x, position.y, 0.0, 1.0);
+                            v_tex_coords = tex_coords / tex_size;
+                            v_color = color;
+                        }
+                    ",
+                    fragment: "
+                        #version 110
+
+                        varying vec2 v_tex_coords;
+                        varying vec4 v_color;
+                        uniform sampler2D tex;
+
+                        void main() {
+                            gl_FragColor = vec4(v_color.rgb, v_color.a * texture2D(tex, v_tex_coords));
+                            if (gl_FragColor.a <= 0.01) {
+                                discard;
+                            }
+                        }
+                    "
+                },
+
+            )
+            .unwrap(),
         }
     }
 }
@@ -440,16 +712,9 @@ where
         // these arrays will contain the vertex buffer and index buffer data
         let mut vertex_buffer_data = Vec::with_capacity(text.len() * 4 * 4);
         let mut index_buffer_data = Vec::with_capacity(text.len() * 6);
+        let mut text_height = self.text_height;
 
-        // iterating over the characters of the string
-        for character in text.chars() {
-            let infos = match self.texture.character_infos.get(&character) {
-                Some(infos) => infos,
-                None => continue,
-            };
-
-            self.is_empty = false;
-
+        self.total_text_width = self.texture.layout(text, |infos, [left_coord, top_coord, right_coord, bottom_coord]| {
             // adding the quad in the index buffer
             {
                 let first_vertex_offset = vertex_buffer_data.len() as u16;
@@ -461,15 +726,6 @@ where
                 index_buffer_data.push(first_vertex_offset + 3);
             }
 
-            //
-            self.total_text_width += infos.left_padding;
-
-            // calculating coords
-            let left_coord = self.total_text_width;
-            let right_coord = left_coord + infos.size.0;
-            let top_coord = infos.height_over_line;
-            let bottom_coord = infos.height_over_line - infos.size.1;
-
             // top-left vertex
             vertex_buffer_data.push(VertexFormat {
                 position: [left_coord, top_coord],
@@ -494,13 +750,13 @@ where
                 tex_coords: [infos.tex_coords.0 + infos.tex_size.0, infos.tex_coords.1 + infos.tex_size.1],
             });
 
-            // going to next char
-            self.total_text_width = right_coord + infos.right_padding;
-
-            if top_coord > self.text_height {
-                self.text_height = top_coord;
+            if top_coord > text_height {
+                text_height = top_coord;
             }
-        }
+        });
+
+        self.text_height = text_height;
+        self.is_empty = vertex_buffer_data.is_empty();
 
         if !vertex_buffer_data.len() != 0 {
             // building the vertex buffer
@@ -512,6 +768,125 @@ where
     }
 }
 
+impl<F> TextBatch<F>
+where
+    F: Deref<Target = FontTexture>,
+{
+    /// Builds a new empty batch of text that uses a font texture.
+    pub fn new(system: &TextSystem, texture: F) -> TextBatch<F> {
+        TextBatch {
+            context: system.context.clone(),
+            texture,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Returns the font texture used by this batch.
+    pub fn get_texture(&self) -> &F {
+        &self.texture
+    }
+
+    /// Returns whether there is anything to draw.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Removes all text from the batch.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Adds a line of text to the batch.
+    ///
+    /// The matrix has the same meaning as for `draw`, and is applied immediately.
+    pub fn push<M>(&mut self, text: &str, matrix: M, color: (f32, f32, f32, f32))
+    where
+        M: Into<[[f32; 4]; 4]>,
+    {
+        let matrix = matrix.into();
+        let color = [color.0, color.1, color.2, color.3];
+
+        // only 2D transformations with no perspective are supported
+        let transform = |x: f32, y: f32| {
+            [
+                matrix[0][0] * x + matrix[1][0] * y + matrix[3][0],
+                matrix[0][1] * x + matrix[1][1] * y + matrix[3][1],
+            ]
+        };
+
+        let TextBatch {
+            vertices, indices, texture, ..
+        } = self;
+
+        texture.layout(text, |infos, [left_coord, top_coord, right_coord, bottom_coord]| {
+            let first_vertex_offset = vertices.len() as u32;
+            indices.extend_from_slice(&[0, 1, 2, 2, 1, 3].map(|offset| first_vertex_offset + offset));
+
+            let (u, v) = infos.tex_coords;
+            let (u_size, v_size) = infos.tex_size;
+
+            vertices.push(BatchVertexFormat {
+                position: transform(left_coord, top_coord),
+                tex_coords: [u, v],
+                color,
+            });
+
+            vertices.push(BatchVertexFormat {
+                position: transform(right_coord, top_coord),
+                tex_coords: [u + u_size, v],
+                color,
+            });
+
+            vertices.push(BatchVertexFormat {
+                position: transform(left_coord, bottom_coord),
+                tex_coords: [u, v + v_size],
+                color,
+            });
+
+            vertices.push(BatchVertexFormat {
+                position: transform(right_coord, bottom_coord),
+                tex_coords: [u + u_size, v + v_size],
+                color,
+            });
+        });
+    }
+}
+
+/// Draws all text in a batch using a single draw call, then clears the batch.
+pub fn draw_batch_with_params<F, S>(
+    batch: &mut TextBatch<F>,
+    system: &TextSystem,
+    target: &mut S,
+    sampler_behavior: glium::uniforms::SamplerBehavior,
+    parameters: &DrawParameters,
+) -> Result<(), glium::DrawError>
+where
+    S: glium::Surface + ?Sized,
+    F: Deref<Target = FontTexture>,
+{
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let vertex_buffer = glium::VertexBuffer::new(&batch.context, &batch.vertices).unwrap();
+    let index_buffer = glium::IndexBuffer::new(&batch.context, glium::index::PrimitiveType::TrianglesList, &batch.indices).unwrap();
+
+    let result = {
+        let texture = batch.texture.texture.borrow();
+        let uniforms = uniform! {
+            tex: glium::uniforms::Sampler(&*texture, sampler_behavior),
+            tex_size: batch.texture.get_texture_size(),
+        };
+
+        target.draw(&vertex_buffer, &index_buffer, &system.batch_program, &uniforms, parameters)
+    };
+
+    batch.clear();
+    result
+}
+
 /// Draws linear-filtered text.
 ///
 /// ## About the matrix
@@ -527,29 +902,35 @@ where
     M: Into<[[f32; 4]; 4]>,
     F: Deref<Target = FontTexture>,
 {
-    let behavior = glium::uniforms::SamplerBehavior {
+    draw_with_params(text, system, target, matrix, color, default_sampler_behavior(), &default_draw_parameters())
+}
+
+/// Sampler behavior used by `draw`.
+pub fn default_sampler_behavior() -> glium::uniforms::SamplerBehavior {
+    glium::uniforms::SamplerBehavior {
         magnify_filter: glium::uniforms::MagnifySamplerFilter::Linear,
         minify_filter: glium::uniforms::MinifySamplerFilter::Linear,
         ..Default::default()
-    };
-
-    let params = {
-        use glium::{BlendingFunction::Addition, LinearBlendingFactor::*};
+    }
+}
 
-        let blending_function = Addition {
-            source: SourceAlpha,
-            destination: OneMinusSourceAlpha,
-        };
+/// Draw parameters used by `draw`, useful as a base when only a few
+/// parameters (such as the scissor) need to be changed.
+pub fn default_draw_parameters<'a>() -> DrawParameters<'a> {
+    use glium::{BlendingFunction::Addition, LinearBlendingFactor::*};
 
-        let blend = glium::Blend {
-            color: blending_function,
-            alpha: blending_function,
-            constant_value: (1.0, 1.0, 1.0, 1.0),
-        };
+    let blending_function = Addition {
+        source: SourceAlpha,
+        destination: OneMinusSourceAlpha,
+    };
 
-        DrawParameters { blend, ..Default::default() }
+    let blend = glium::Blend {
+        color: blending_function,
+        alpha: blending_function,
+        constant_value: (1.0, 1.0, 1.0, 1.0),
     };
-    draw_with_params(text, system, target, matrix, color, behavior, &params)
+
+    DrawParameters { blend, ..Default::default() }
 }
 
 /// More advanced variant of `draw` which also takes sampler behavior and draw
@@ -587,194 +968,133 @@ where
     let vertex_buffer = vertex_buffer.as_ref().unwrap();
     let index_buffer = index_buffer.as_ref().unwrap();
 
+    let tex_size = texture.get_texture_size();
+    let texture = texture.texture.borrow();
     let uniforms = uniform! {
         matrix: matrix,
         color: color,
-        tex: glium::uniforms::Sampler(&texture.texture, sampler_behavior)
+        tex: glium::uniforms::Sampler(&*texture, sampler_behavior),
+        tex_size: tex_size,
     };
 
     target.draw(vertex_buffer, index_buffer, &system.program, &uniforms, parameters)
 }
 
-fn build_font_image<I>(font: &rusttype::Font, characters_list: I, font_size: u32) -> Result<(TextureData, HashMap<char, CharacterInfos>), Error>
-where
-    I: Iterator<Item = char>,
-{
-    use std::iter;
-
+impl Atlas {
     // a margin around each character to prevent artifacts
     const MARGIN: u32 = 2;
 
-    // glyph size for characters not presented in font.
-    let invalid_character_width = font_size / 2;
+    fn new(width: u32) -> Atlas {
+        Atlas {
+            data: Vec::new(),
+            width,
+            cursor_offset: (0, 0),
+            rows_to_skip: 0,
+        }
+    }
 
-    let size_estimation = characters_list.size_hint().1.unwrap_or(0);
+    // rasterizes a character into the atlas and returns where it ended up
+    fn add(&mut self, font: &rusttype::Font, font_size: u32, character: char) -> CharacterInfos {
+        use std::iter;
+
+        // glyph size for characters not presented in font.
+        let invalid_character_width = font_size / 2;
+        let em_pixels = font_size as f32;
+
+        // loading wanted glyph in the font face
+        // hope scale will set the right pixel size
+        let scaled_glyph = font.glyph(character).scaled(::rusttype::Scale {
+            x: font_size as f32,
+            y: font_size as f32,
+        });
+        let h_metrics = scaled_glyph.h_metrics();
+        let glyph = scaled_glyph.positioned(::rusttype::Point { x: 0.0, y: 0.0 });
+
+        // if no bounding box - we suppose that its invalid character but want it to be draw as empty quad
+        let bb = glyph.pixel_bounding_box().unwrap_or(Rect {
+            min: Point { x: 0, y: 0 },
+            max: Point {
+                x: invalid_character_width as i32,
+                y: 0,
+            },
+        });
+
+        let (bitmap_width, bitmap_rows) = (bb.width() as u32, bb.height() as u32);
+        let mut buffer = vec![0; (bitmap_rows * bitmap_width) as usize];
+
+        glyph.draw(|x, y, v| {
+            buffer[(y * bitmap_width + x) as usize] = (v * 255.0) as u8;
+        });
+
+        // adding a left margin before our character to prevent artifacts
+        self.cursor_offset.0 += Self::MARGIN;
+
+        // carriage return our cursor if we don't have enough room to write the next caracter
+        // we add a margin to prevent artifacts
+        if self.cursor_offset.0 + bitmap_width + Self::MARGIN >= self.width {
+            assert!(bitmap_width <= self.width); // if this fails, we should increase texture_width
+            self.cursor_offset.0 = 0;
+            self.cursor_offset.1 += self.rows_to_skip;
+            self.rows_to_skip = 0;
+        }
 
-    // this variable will store the texture data
-    // we set an arbitrary capacity that we think will match what we will need
-    let mut texture_data: Vec<f32> = Vec::with_capacity(size_estimation * font_size as usize * font_size as usize);
+        // if the texture data buffer has not enough lines, adding some
+        if self.rows_to_skip < Self::MARGIN + bitmap_rows {
+            let diff = Self::MARGIN + bitmap_rows - self.rows_to_skip;
+            self.rows_to_skip = Self::MARGIN + bitmap_rows;
+            self.data.extend(iter::repeat(0.0).take((diff * self.width) as usize));
+        }
 
-    // the width is chosen more or less arbitrarily, because we can store
-    // everything as long as the texture is at least as wide as the widest
-    // character we just try to estimate a width so that width ~= height
-    let texture_width = get_nearest_po2(std::cmp::max(
-        font_size * 2_u32,
-        ((((size_estimation as u32) * font_size * font_size) as f32).sqrt()) as u32,
-    ));
+        // copying the data to the texture
+        let offset_x_before_copy = self.cursor_offset.0;
+        if bitmap_rows >= 1 {
+            let destination = &mut self.data[(self.cursor_offset.0 + self.cursor_offset.1 * self.width) as usize..];
 
-    // we store the position of the "cursor" in the destination texture
-    // this cursor points to the top-left pixel of the next character to write on the texture
-    let mut cursor_offset = (0u32, 0u32);
+            for y in 0..bitmap_rows {
+                let source = &buffer[(y * bitmap_width) as usize..];
+                let destination = &mut destination[(y * self.width) as usize..];
 
-    // number of rows to skip at next carriage return
-    let mut rows_to_skip = 0u32;
-
-    // now looping through the list of characters, filling the texture and returning the informations
-    let em_pixels = font_size as f32;
-    let characters_infos = characters_list
-        .map(|character| {
-            struct Bitmap {
-                rows: i32,
-                width: i32,
-                buffer: Vec<u8>,
-            }
-            // loading wanted glyph in the font face
-            // hope scale will set the right pixel size
-            let scaled_glyph = font.glyph(character).scaled(::rusttype::Scale {
-                x: font_size as f32,
-                y: font_size as f32,
-            });
-            let h_metrics = scaled_glyph.h_metrics();
-            let glyph = scaled_glyph.positioned(::rusttype::Point { x: 0.0, y: 0.0 });
-
-            let bb = glyph.pixel_bounding_box();
-            // if no bounding box - we suppose that its invalid character but want it to be draw as empty quad
-            let bb = if let Some(bb) = bb {
-                bb
-            } else {
-                Rect {
-                    min: Point { x: 0, y: 0 },
-                    max: Point {
-                        x: invalid_character_width as i32,
-                        y: 0,
-                    },
+                for x in 0..bitmap_width as usize {
+                    // the values in source are bytes between 0 and 255, but we want floats between 0 and 1
+                    destination[x] = f32::from(source[x]) / f32::from(u8::MAX);
                 }
-            };
-
-            let mut buffer = vec![0; (bb.height() * bb.width()) as usize];
-
-            glyph.draw(|x, y, v| {
-                let x = x;
-                let y = y;
-                buffer[(y * bb.width() as u32 + x) as usize] = (v * 255.0) as u8;
-            });
-            let bitmap: Bitmap = Bitmap {
-                rows: bb.height(),
-                width: bb.width(),
-                buffer,
-            };
-
-            // adding a left margin before our character to prevent artifacts
-            cursor_offset.0 += MARGIN;
-
-            // carriage return our cursor if we don't have enough room to write the next caracter
-            // we add a margin to prevent artifacts
-            if cursor_offset.0 + (bitmap.width as u32) + MARGIN >= texture_width {
-                assert!(bitmap.width as u32 <= texture_width); // if this fails, we should increase texture_width
-                cursor_offset.0 = 0;
-                cursor_offset.1 += rows_to_skip;
-                rows_to_skip = 0;
             }
 
-            // if the texture data buffer has not enough lines, adding some
-            if rows_to_skip < MARGIN + bitmap.rows as u32 {
-                let diff = MARGIN + (bitmap.rows as u32) - rows_to_skip;
-                rows_to_skip = MARGIN + bitmap.rows as u32;
-                texture_data.extend(iter::repeat(0.0).take((diff * texture_width) as usize));
-            }
-
-            // copying the data to the texture
-            let offset_x_before_copy = cursor_offset.0;
-            if bitmap.rows >= 1 {
-                let destination = &mut texture_data[(cursor_offset.0 + cursor_offset.1 * texture_width) as usize..];
-                let source = &bitmap.buffer;
-                //ylet source = std::slice::from_raw_parts(source, destination.len());
-
-                for y in 0..bitmap.rows as u32 {
-                    let source = &source[(y * bitmap.width as u32) as usize..];
-                    let destination = &mut destination[(y * texture_width) as usize..];
-
-                    for x in 0..bitmap.width {
-                        // the values in source are bytes between 0 and 255, but we want floats between 0 and 1
-                        let val: u8 = source[x as usize];
-                        let val = f32::from(val) / f32::from(std::u8::MAX);
-                        let dest = &mut destination[x as usize];
-                        *dest = val;
-                    }
-                }
-
-                cursor_offset.0 += bitmap.width as u32;
-                debug_assert!(cursor_offset.0 <= texture_width);
-            }
+            self.cursor_offset.0 += bitmap_width;
+            debug_assert!(self.cursor_offset.0 <= self.width);
+        }
 
-            // filling infos about that character
-            // tex_size and tex_coords are in pixels for the moment ; they will be divided
-            // by the texture dimensions later
-            Ok((
-                character,
-                CharacterInfos {
-                    tex_size: (bitmap.width as f32, bitmap.rows as f32),
-                    tex_coords: (offset_x_before_copy as f32, cursor_offset.1 as f32),
-                    size: (bitmap.width as f32, bitmap.rows as f32),
-                    left_padding: h_metrics.left_side_bearing as f32,
-                    right_padding: (h_metrics.advance_width - bitmap.width as f32 - h_metrics.left_side_bearing as f32) as f32 / 64.0,
-                    height_over_line: -bb.min.y as f32,
-                },
-            ))
-        })
-        .collect::<Result<Vec<_>, Error>>()?;
+        // filling infos about that character
+        CharacterInfos {
+            tex_size: (bitmap_width as f32, bitmap_rows as f32),
+            tex_coords: (offset_x_before_copy as f32, self.cursor_offset.1 as f32),
+            size: (bitmap_width as f32 / em_pixels, bitmap_rows as f32 / em_pixels),
+            left_padding: h_metrics.left_side_bearing as f32 / em_pixels,
+            right_padding: (h_metrics.advance_width - bitmap_width as f32 - h_metrics.left_side_bearing as f32) / 64.0 / em_pixels,
+            height_over_line: -bb.min.y as f32 / em_pixels,
+        }
+    }
 
-    // adding blank lines at the end until the height of the texture is a power of two
-    {
-        let current_height = texture_data.len() as u32 / texture_width;
-        let requested_height = get_nearest_po2(current_height);
-        texture_data.extend(iter::repeat(0.0).take((texture_width * (requested_height - current_height)) as usize));
+    // the atlas with blank lines added at the end until the height of the texture is a power of two
+    // height the texture would have if another row of characters this tall were started
+    fn get_texture_height_with_room_for(&self, rows: u32) -> u32 {
+        get_nearest_po2(self.cursor_offset.1 + self.rows_to_skip + Self::MARGIN + rows)
     }
 
-    // now our texture is finished
-    // we know its final dimensions, so we can divide all the pixels values into (0,1) range
-    assert!((texture_data.len() as u32 % texture_width) == 0);
-    let texture_height = (texture_data.len() as u32 / texture_width) as f32;
-    let float_texture_width = texture_width as f32;
-    let mut characters_infos = characters_infos
-        .into_iter()
-        .map(|mut chr| {
-            chr.1.tex_size.0 /= float_texture_width;
-            chr.1.tex_size.1 /= texture_height;
-            chr.1.tex_coords.0 /= float_texture_width;
-            chr.1.tex_coords.1 /= texture_height;
-            chr.1.size.0 /= em_pixels;
-            chr.1.size.1 /= em_pixels;
-            chr.1.left_padding /= em_pixels;
-            chr.1.right_padding /= em_pixels;
-            chr.1.height_over_line /= em_pixels;
-            chr
-        })
-        .collect::<HashMap<_, _>>();
+    fn get_texture_data(&self) -> TextureData {
+        let current_height = std::cmp::max(self.data.len() as u32 / self.width, 1);
+        let height = get_nearest_po2(current_height);
 
-    // this HashMap will not be used mutably any more and it makes sense to
-    // compact it
-    characters_infos.shrink_to_fit();
+        let mut data = Vec::with_capacity((self.width * height) as usize);
+        data.extend_from_slice(&self.data);
+        data.resize((self.width * height) as usize, 0.0);
 
-    // returning
-    Ok((
         TextureData {
-            data: texture_data,
-            width: texture_width,
-            height: texture_height as u32,
-        },
-        characters_infos,
-    ))
+            data,
+            width: self.width,
+            height,
+        }
+    }
 }
 
 /// Function that will calculate the nearest power of two.
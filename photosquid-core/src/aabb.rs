@@ -0,0 +1,118 @@
+use nalgebra_glm as glm;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, PartialEq)]
+pub struct AABB {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl AABB {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x + w,
+            max_y: y + h,
+        }
+    }
+
+    // Smallest box containing every point, or None if there aren't any
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a glm::Vec2>) -> Option<Self> {
+        points.into_iter().fold(None, |aabb: Option<Self>, point| {
+            Some(match aabb {
+                Some(aabb) => Self {
+                    min_x: aabb.min_x.min(point.x),
+                    min_y: aabb.min_y.min(point.y),
+                    max_x: aabb.max_x.max(point.x),
+                    max_y: aabb.max_y.max(point.y),
+                },
+                None => Self::new(point.x, point.y, 0.0, 0.0),
+            })
+        })
+    }
+
+    // Boxes that only touch are considered intersecting
+    pub fn intersecting_aabb(&self, other: &AABB) -> bool {
+        self.min_x <= other.max_x && other.min_x <= self.max_x && self.min_y <= other.max_y && other.min_y <= self.max_y
+    }
+
+    // Grows the box by a margin on every side (or shrinks it, if negative)
+    pub fn padded(&self, margin: f32) -> Self {
+        Self {
+            min_x: self.min_x - margin,
+            min_y: self.min_y - margin,
+            max_x: (self.max_x + margin).max(self.min_x - margin),
+            max_y: (self.max_y + margin).max(self.min_y - margin),
+        }
+    }
+
+    pub fn intersecting_point(&self, x: f32, y: f32) -> bool {
+        x > self.min_x && x < self.max_x && y > self.min_y && y < self.max_y
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max_y - self.min_y
+    }
+
+    pub fn center_x(&self) -> f32 {
+        (self.min_x + self.max_x) / 2.0
+    }
+
+    pub fn center_y(&self) -> f32 {
+        (self.min_y + self.max_y) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AABB;
+    use nalgebra_glm as glm;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn aabb_from_points_contains_points() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..256 {
+            let points: Vec<glm::Vec2> = (0..rng.gen_range(1..8))
+                .map(|_| glm::vec2(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)))
+                .collect();
+            let aabb = AABB::from_points(&points).unwrap();
+
+            for point in &points {
+                assert!(point.x >= aabb.min_x && point.x <= aabb.max_x && point.y >= aabb.min_y && point.y <= aabb.max_y);
+                assert!(aabb.intersecting_aabb(&AABB::new(point.x, point.y, 0.0, 0.0)));
+            }
+        }
+
+        assert!(AABB::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn aabb_intersection_is_symmetric() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut random_aabb = || {
+            AABB::new(
+                rng.gen_range(-50.0..50.0),
+                rng.gen_range(-50.0..50.0),
+                rng.gen_range(0.0..40.0),
+                rng.gen_range(0.0..40.0),
+            )
+        };
+
+        for _ in 0..256 {
+            let (a, b) = (random_aabb(), random_aabb());
+            assert_eq!(a.intersecting_aabb(&b), b.intersecting_aabb(&a));
+            assert!(a.intersecting_aabb(&a));
+        }
+
+        assert!(!AABB::new(0.0, 0.0, 10.0, 10.0).intersecting_aabb(&AABB::new(20.0, 0.0, 10.0, 10.0)));
+    }
+}
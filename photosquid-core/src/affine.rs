@@ -0,0 +1,157 @@
+use crate::as_values::AsValues;
+use nalgebra_glm as glm;
+use std::ops::Mul;
+
+// 2D affine transformation (stored in homogeneous coordinates)
+//
+// Builder methods post-multiply like their `glm::translate`/`glm::rotate`/`glm::scale`
+// counterparts, so operations apply to points in reverse order of how they're chained
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine2 {
+    matrix: glm::Mat3,
+}
+
+impl Affine2 {
+    pub fn identity() -> Self {
+        Self { matrix: glm::Mat3::identity() }
+    }
+
+    pub fn translation(offset: &glm::Vec2) -> Self {
+        Self {
+            matrix: glm::translation2d(offset),
+        }
+    }
+
+    // Counter-clockwise rotation in radians
+    pub fn rotation(angle: f32) -> Self {
+        Self {
+            matrix: glm::rotation2d(angle),
+        }
+    }
+
+    pub fn scaling(scale: &glm::Vec2) -> Self {
+        Self { matrix: glm::scaling2d(scale) }
+    }
+
+    // Slants the y axis sideways by 'shear.x' and the x axis up or down by 'shear.y'
+    pub fn shearing(shear: &glm::Vec2) -> Self {
+        Self {
+            matrix: glm::mat3(1.0, shear.x, 0.0, shear.y, 1.0, 0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    // Moves the x and y axes onto the given vectors, and the origin onto the given point
+    pub fn from_basis(x_axis: &glm::Vec2, y_axis: &glm::Vec2, origin: &glm::Vec2) -> Self {
+        Self {
+            matrix: glm::mat3(x_axis.x, y_axis.x, origin.x, x_axis.y, y_axis.y, origin.y, 0.0, 0.0, 1.0),
+        }
+    }
+
+    pub fn translate(self, offset: &glm::Vec2) -> Self {
+        self * Self::translation(offset)
+    }
+
+    pub fn rotate(self, angle: f32) -> Self {
+        self * Self::rotation(angle)
+    }
+
+    pub fn scale(self, scale: &glm::Vec2) -> Self {
+        self * Self::scaling(scale)
+    }
+
+    pub fn shear(self, shear: &glm::Vec2) -> Self {
+        self * Self::shearing(shear)
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        self.matrix.try_inverse().map(|matrix| Self { matrix })
+    }
+
+    // Point vectors
+    pub fn apply(&self, point: &glm::Vec2) -> glm::Vec2 {
+        (self.matrix * glm::vec3(point.x, point.y, 1.0)).xy()
+    }
+
+    // Directional vectors
+    pub fn apply_to_vector(&self, vector: &glm::Vec2) -> glm::Vec2 {
+        (self.matrix * glm::vec3(vector.x, vector.y, 0.0)).xy()
+    }
+
+    // Value for the 'transform' attribute of svg elements
+    pub fn to_svg_transform(&self) -> String {
+        let m = &self.matrix;
+        format!("matrix({} {} {} {} {} {})", m[(0, 0)], m[(1, 0)], m[(0, 1)], m[(1, 1)], m[(0, 2)], m[(1, 2)])
+    }
+
+    // Equivalent 3D transformation for APIs that require one
+    pub fn to_mat4(self) -> glm::Mat4 {
+        let mut result = glm::Mat4::identity();
+        result.fixed_view_mut::<2, 2>(0, 0).copy_from(&self.matrix.fixed_view::<2, 2>(0, 0));
+        result.fixed_view_mut::<2, 1>(0, 3).copy_from(&self.matrix.fixed_view::<2, 1>(0, 2));
+        result
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Affine2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            matrix: self.matrix * rhs.matrix,
+        }
+    }
+}
+
+impl AsValues for Affine2 {
+    type ValuesType = [[f32; 3]; 3];
+
+    fn as_values(&self) -> Self::ValuesType {
+        *self.matrix.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Affine2;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn affine_matches_mat4() {
+        let affine = Affine2::translation(&glm::vec2(10.0, -4.0)).rotate(0.7).scale(&glm::vec2(2.0, 3.0));
+
+        let mut matrix = glm::translation(&glm::vec3(10.0, -4.0, 0.0));
+        matrix = glm::rotate(&matrix, 0.7, &glm::vec3(0.0, 0.0, 1.0));
+        matrix = glm::scale(&matrix, &glm::vec3(2.0, 3.0, 1.0));
+
+        let point = glm::vec2(5.0, 8.0);
+        let expected = glm::vec4_to_vec2(&(matrix * glm::vec4(point.x, point.y, 0.0, 1.0)));
+
+        assert!(glm::distance(&affine.apply(&point), &expected) < 0.001);
+        assert!(glm::comp_max(&(affine.to_mat4() - matrix).abs()) < 0.001);
+        assert!(glm::distance(&affine.inverse().unwrap().apply(&affine.apply(&point)), &point) < 0.001);
+    }
+
+    #[test]
+    fn affine_from_basis() {
+        let affine = Affine2::from_basis(&glm::vec2(2.0, 1.0), &glm::vec2(-1.0, 3.0), &glm::vec2(5.0, 5.0));
+
+        assert!(glm::distance(&affine.apply(&glm::vec2(0.0, 0.0)), &glm::vec2(5.0, 5.0)) < 0.001);
+        assert!(glm::distance(&affine.apply(&glm::vec2(1.0, 0.0)), &glm::vec2(7.0, 6.0)) < 0.001);
+        assert!(glm::distance(&affine.apply(&glm::vec2(0.0, 1.0)), &glm::vec2(4.0, 8.0)) < 0.001);
+    }
+
+    #[test]
+    fn affine_shearing() {
+        let affine = Affine2::translation(&glm::vec2(5.0, 5.0)).shear(&glm::vec2(0.5, -1.0));
+
+        assert!(glm::distance(&affine.apply(&glm::vec2(0.0, 2.0)), &glm::vec2(6.0, 7.0)) < 0.001);
+        assert!(glm::distance(&affine.apply(&glm::vec2(2.0, 0.0)), &glm::vec2(7.0, 3.0)) < 0.001);
+        assert_eq!(affine.to_svg_transform(), "matrix(1 -1 0.5 1 5 5)");
+    }
+}
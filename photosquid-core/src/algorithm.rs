@@ -0,0 +1,348 @@
+use nalgebra_glm as glm;
+
+// Geometry helpers shared by squids for hit testing and measurement
+
+// Relative tolerance for comparing areas, so points on an edge aren't lost to rounding
+const AREA_EPSILON: f32 = 1.0e-4;
+
+pub fn is_point_inside_rectangle(a: glm::Vec2, b: glm::Vec2, c: glm::Vec2, d: glm::Vec2, point: glm::Vec2) -> bool {
+    // Returns whether point 'p' is inside the rectangle 'abcd'
+    // Where 'a', 'b', 'c', 'd' form edges between each other and the next
+    // e.g.
+    // A ------------------- B
+    // |     P.              |
+    // |                     |
+    // D ------------------- C
+    //
+    // The rectangle does not have to be axis-aligned
+
+    fn triangle_area(a: glm::Vec2, b: glm::Vec2, c: glm::Vec2) -> f32 {
+        0.5 * ((b.x * a.y - a.x * b.y) + (c.x * b.y - b.x * c.y) + (a.x * c.y - c.x * a.y)).abs()
+    }
+
+    let cumulative_area = triangle_area(a, point, d) + triangle_area(d, point, c) + triangle_area(c, point, b) + triangle_area(point, b, a);
+    let area = triangle_area(a, b, c) + triangle_area(c, d, a);
+
+    cumulative_area <= area * (1.0 + AREA_EPSILON)
+}
+
+pub fn is_point_inside_triangle(single_point: glm::Vec2, p: [glm::Vec2; 3]) -> bool {
+    fn sign(a: glm::Vec2, b: glm::Vec2, c: glm::Vec2) -> f32 {
+        (a.x - c.x) * (b.y - c.y) - (b.x - c.x) * (a.y - c.y)
+    }
+
+    let d = [(p[0], p[1]), (p[1], p[2]), (p[2], p[0])].map(|(a, b)| sign(single_point, a, b));
+
+    let has_neg = d.iter().any(|distance| *distance < 0.0);
+    let has_pos = d.iter().any(|distance| *distance > 0.0);
+
+    !(has_neg && has_pos)
+}
+
+// Signed distance from a point to the nearest line along a side of a triangle,
+// negative when inside and positive when outside
+pub fn get_distance_between_point_and_triangle(single_point: &glm::Vec2, p: &[glm::Vec2; 3]) -> f32 {
+    let ordered = counter_clockwise(*p);
+    let [a, b, c] = ordered.each_ref();
+
+    let ab_width = glm::distance(a, b);
+    let bc_width = glm::distance(b, c);
+    let ca_width = glm::distance(c, a);
+
+    fn get_distance_to_side(point: &glm::Vec2, p1: &glm::Vec2, p2: &glm::Vec2, side_width: f32) -> f32 {
+        ((p2.y - p1.y) * point.x - (p2.x - p1.x) * point.y + p2.x * p1.y - p2.y * p1.x) / side_width
+    }
+
+    let ab_distance = get_distance_to_side(single_point, a, b, ab_width);
+    let bc_distance = get_distance_to_side(single_point, b, c, bc_width);
+    let ca_distance = get_distance_to_side(single_point, c, a, ca_width);
+
+    ab_distance.max(bc_distance).max(ca_distance)
+}
+
+fn counter_clockwise(points: [glm::Vec2; 3]) -> [glm::Vec2; 3] {
+    use std::cmp::Ordering;
+
+    let mut points = points;
+    let center = get_triangle_center(points);
+
+    points.sort_by(|u, v| {
+        if is_point_less_in_clockwise(&center, u, v) {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    });
+
+    points
+}
+
+fn is_point_less_in_clockwise(center: &glm::Vec2, a: &glm::Vec2, b: &glm::Vec2) -> bool {
+    if a.x - center.x >= 0.0 && b.x - center.x < 0.0 {
+        return true;
+    }
+
+    if a.x - center.x < 0.0 && b.x - center.x >= 0.0 {
+        return false;
+    }
+
+    if a.x - center.x == 0.0 && b.x - center.x == 0.0 {
+        if a.y - center.y >= 0.0 || b.y - center.y >= 0.0 {
+            return a.y > b.y;
+        }
+        return b.y > a.y;
+    }
+
+    // Compared as floats, since truncating to integers makes small triangles unorderable
+    let det = (a.x - center.x) * (b.y - center.y) - (b.x - center.x) * (a.y - center.y);
+
+    if det != 0.0 {
+        return det < 0.0;
+    }
+
+    glm::distance2(a, center) > glm::distance2(b, center)
+}
+
+pub fn get_triangle_center(p: [glm::Vec2; 3]) -> glm::Vec2 {
+    p.iter().sum::<glm::Vec2>() / 3.0
+}
+
+// Drops points from a polyline that are within 'tolerance' of the line it would take without them (Ramer-Douglas-Peucker)
+pub fn simplify_polyline(points: &[glm::Vec2], tolerance: f32) -> Vec<glm::Vec2> {
+    fn get_distance_to_segment(point: &glm::Vec2, a: &glm::Vec2, b: &glm::Vec2) -> f32 {
+        let along = b - a;
+        let t = (point - a).dot(&along) / along.norm_squared();
+
+        if t.is_finite() {
+            glm::distance(point, &(a + along * t.clamp(0.0, 1.0)))
+        } else {
+            glm::distance(point, a)
+        }
+    }
+
+    fn mark(points: &[glm::Vec2], keep: &mut [bool], tolerance: f32) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let (first, last) = (points[0], points[points.len() - 1]);
+
+        let (index, distance) = points[1..points.len() - 1]
+            .iter()
+            .map(|point| get_distance_to_segment(point, &first, &last))
+            .enumerate()
+            .fold(
+                (0, 0.0),
+                |farthest, (i, distance)| if distance > farthest.1 { (i + 1, distance) } else { farthest },
+            );
+
+        if distance > tolerance {
+            keep[index] = true;
+            mark(&points[..=index], &mut keep[..=index], tolerance);
+            mark(&points[index..], &mut keep[index..], tolerance);
+        }
+    }
+
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    mark(points, &mut keep, tolerance);
+
+    points.iter().zip(keep).filter(|(_, keep)| *keep).map(|(point, _)| *point).collect()
+}
+
+// Cuts a convex polygon in two along the line through 'a' and 'b',
+// as long as the segment between them goes all the way across it
+pub fn split_convex_polygon(points: &[glm::Vec2], a: &glm::Vec2, b: &glm::Vec2) -> Option<[Vec<glm::Vec2>; 2]> {
+    let direction = b - a;
+    let side = |point: &glm::Vec2| direction.perp(&(point - a));
+
+    // Both sides of the line need some of the polygon on them
+    if !points.iter().any(|point| side(point) > 0.0) || !points.iter().any(|point| side(point) < 0.0) {
+        return None;
+    }
+
+    // Where a point on the line is along the cut, from 0 at 'a' to 1 at 'b'
+    let is_within_cut = |point: &glm::Vec2| (0.0..=1.0).contains(&((point - a).dot(&direction) / direction.norm_squared()));
+
+    let mut halves = [vec![], vec![]];
+
+    for (i, from) in points.iter().enumerate() {
+        let to = &points[(i + 1) % points.len()];
+        let (from_side, to_side) = (side(from), side(to));
+
+        if from_side == 0.0 && !is_within_cut(from) {
+            return None;
+        }
+
+        if from_side >= 0.0 {
+            halves[0].push(*from);
+        }
+
+        if from_side <= 0.0 {
+            halves[1].push(*from);
+        }
+
+        if from_side * to_side < 0.0 {
+            let crossing = from + (to - from) * (from_side / (from_side - to_side));
+
+            if !is_within_cut(&crossing) {
+                return None;
+            }
+
+            halves[0].push(crossing);
+            halves[1].push(crossing);
+        }
+    }
+
+    Some(halves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_distance_between_point_and_triangle, get_triangle_center, is_point_inside_rectangle, is_point_inside_triangle, simplify_polyline,
+        split_convex_polygon,
+    };
+    use crate::affine::Affine2;
+    use nalgebra_glm as glm;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const CASES: usize = 1000;
+
+    fn random_triangle(rng: &mut StdRng, scale: f32) -> [glm::Vec2; 3] {
+        loop {
+            let p = [(); 3].map(|_| glm::vec2(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * scale);
+            let area = 0.5 * ((p[1] - p[0]).perp(&(p[2] - p[0]))).abs();
+
+            // Avoid degenerate triangles
+            if area > 0.05 * scale * scale {
+                return p;
+            }
+        }
+    }
+
+    // Random point strictly inside the triangle, using barycentric weights
+    fn random_point_inside(rng: &mut StdRng, p: &[glm::Vec2; 3]) -> glm::Vec2 {
+        let weights = [(); 3].map(|_| rng.gen_range(0.05..1.0f32));
+        let total: f32 = weights.iter().sum();
+        (p[0] * weights[0] + p[1] * weights[1] + p[2] * weights[2]) / total
+    }
+
+    // Random point beyond one of the corners of the triangle
+    fn random_point_outside(rng: &mut StdRng, p: &[glm::Vec2; 3]) -> glm::Vec2 {
+        let center = get_triangle_center(*p);
+        let corner = p[rng.gen_range(0..3)];
+        corner + (corner - center) * rng.gen_range(0.1..2.0)
+    }
+
+    #[test]
+    fn point_inside_rectangle() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..CASES {
+            let size = glm::vec2(rng.gen_range(1.0..500.0), rng.gen_range(1.0..500.0));
+            let transformation = Affine2::translation(&glm::vec2(rng.gen_range(-1000.0..1000.0), rng.gen_range(-1000.0..1000.0)))
+                .rotate(rng.gen_range(0.0..std::f32::consts::TAU));
+            let corners = [glm::vec2(-0.5, -0.5), glm::vec2(0.5, -0.5), glm::vec2(0.5, 0.5), glm::vec2(-0.5, 0.5)]
+                .map(|corner| transformation.apply(&corner.component_mul(&size)));
+
+            let local = glm::vec2(rng.gen_range(-0.49..0.49), rng.gen_range(-0.49..0.49)).component_mul(&size);
+            let inside = transformation.apply(&local);
+            assert!(is_point_inside_rectangle(corners[0], corners[1], corners[2], corners[3], inside));
+
+            let local = glm::vec2(rng.gen_range(0.55..2.0), rng.gen_range(-2.0..2.0)).component_mul(&size);
+            let outside = transformation.apply(&local);
+            assert!(!is_point_inside_rectangle(corners[0], corners[1], corners[2], corners[3], outside));
+        }
+    }
+
+    #[test]
+    fn point_inside_triangle() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..CASES {
+            let p = random_triangle(&mut rng, 100.0);
+
+            assert!(is_point_inside_triangle(random_point_inside(&mut rng, &p), p));
+            assert!(!is_point_inside_triangle(random_point_outside(&mut rng, &p), p));
+        }
+    }
+
+    #[test]
+    fn distance_to_triangle() {
+        let mut rng = StdRng::seed_from_u64(2);
+
+        // Includes triangles small enough to have been misordered by integer rounding
+        for scale in [0.01, 1.0, 100.0] {
+            for _ in 0..CASES {
+                let p = random_triangle(&mut rng, scale);
+                let inside = random_point_inside(&mut rng, &p);
+                let outside = random_point_outside(&mut rng, &p);
+
+                assert!(get_distance_between_point_and_triangle(&inside, &p) < 0.0);
+                assert!(get_distance_between_point_and_triangle(&outside, &p) > 0.0);
+
+                // Point order shouldn't matter
+                let distance = get_distance_between_point_and_triangle(&outside, &p);
+                let reordered = get_distance_between_point_and_triangle(&outside, &[p[2], p[0], p[1]]);
+                assert!((distance - reordered).abs() <= 1.0e-3 * scale);
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_polyline_keeps_corners() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // Jittery strokes along two sides of a square
+        let corners = [glm::vec2(0.0, 0.0), glm::vec2(100.0, 0.0), glm::vec2(100.0, 100.0)];
+        let points: Vec<glm::Vec2> = corners
+            .windows(2)
+            .flat_map(|side| (0..50).map(move |i| glm::lerp(&side[0], &side[1], i as f32 / 50.0)))
+            .map(|point| point + glm::vec2(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5)))
+            .chain([corners[2]])
+            .collect();
+
+        let simplified = simplify_polyline(&points, 2.0);
+        assert_eq!(simplified.len(), 3);
+        assert_eq!(simplified[0], points[0]);
+        assert!(glm::distance(&simplified[1], &corners[1]) < 2.0);
+        assert_eq!(simplified[2], corners[2]);
+
+        // Nothing gets removed when there's no tolerance for it
+        assert_eq!(simplify_polyline(&points, 0.0).len(), points.len());
+    }
+
+    #[test]
+    fn split_convex_polygon_across() {
+        let square = [glm::vec2(0.0, 0.0), glm::vec2(100.0, 0.0), glm::vec2(100.0, 100.0), glm::vec2(0.0, 100.0)];
+
+        let close = |actual: &[glm::Vec2], expected: &[glm::Vec2]| {
+            actual.len() == expected.len() && actual.iter().zip(expected).all(|(a, b)| glm::distance(a, b) < 0.001)
+        };
+
+        let [left, right] = split_convex_polygon(&square, &glm::vec2(30.0, -10.0), &glm::vec2(30.0, 110.0)).unwrap();
+        assert!(close(
+            &left,
+            &[glm::vec2(0.0, 0.0), glm::vec2(30.0, 0.0), glm::vec2(30.0, 100.0), glm::vec2(0.0, 100.0)]
+        ));
+        assert!(close(
+            &right,
+            &[glm::vec2(30.0, 0.0), glm::vec2(100.0, 0.0), glm::vec2(100.0, 100.0), glm::vec2(30.0, 100.0)]
+        ));
+
+        // Corner to corner makes two triangles
+        let halves = split_convex_polygon(&square, &glm::vec2(-1.0, -1.0), &glm::vec2(101.0, 101.0)).unwrap();
+        assert!(halves.iter().all(|half| half.len() == 3));
+
+        // Cuts that stop short, or miss entirely, leave it whole
+        assert!(split_convex_polygon(&square, &glm::vec2(30.0, -10.0), &glm::vec2(30.0, 50.0)).is_none());
+        assert!(split_convex_polygon(&square, &glm::vec2(200.0, -10.0), &glm::vec2(200.0, 110.0)).is_none());
+        assert!(split_convex_polygon(&square, &glm::vec2(50.0, 50.0), &glm::vec2(50.0, 50.0)).is_none());
+    }
+}
@@ -0,0 +1,467 @@
+use crate::{
+    affine::Affine2,
+    smooth::{Lerpable, Smooth},
+};
+use angular_units::Rad;
+use lazy_static::lazy_static;
+use more_asserts::assert_le;
+use nalgebra_glm as glm;
+
+lazy_static! {
+    pub static ref IDENTITY_CAMERA: Camera = Camera::identity(glm::zero());
+}
+
+#[derive(Copy, Clone)]
+pub struct Camera {
+    pub position: glm::Vec2,
+    pub zoom: f32,
+    pub window: glm::Vec2,
+
+    // Rotation of the view around the center of the window, in radians
+    pub rotation: f32,
+
+    // Size of the interface relative to the document, which the view is shrunk by
+    // so that the document stays the same size on screen no matter the UI scale
+    pub ui_scale: f32,
+}
+
+impl Camera {
+    pub fn identity(window: glm::Vec2) -> Camera {
+        Camera {
+            position: glm::zero(),
+            zoom: 1.0,
+            window,
+            rotation: 0.0,
+            ui_scale: 1.0,
+        }
+    }
+
+    // Window units per world unit
+    pub fn get_scale(&self) -> f32 {
+        use crate::math::DivOrZero;
+        self.zoom.div_or_zero(self.ui_scale)
+    }
+
+    pub fn mat(&self) -> Affine2 {
+        let center = 0.5 * self.window;
+        let scale = self.get_scale();
+
+        Affine2::translation(&center)
+            .rotate(self.rotation)
+            .translate(&-center)
+            .translate(&(-self.position * scale))
+            .translate(&(0.5 * self.window))
+            .scale(&glm::vec2(scale, scale))
+            .translate(&(-0.5 * self.window))
+    }
+
+    pub fn inv_mat(&self) -> Affine2 {
+        self.mat().inverse().unwrap_or_default()
+    }
+
+    // Translation part of the transform described by `mat` (before rotation), since the
+    // camera only ever translates, uniformly scales, and rotates, points can be
+    // transformed directly without building and inverting matrices
+    fn offset(&self) -> glm::Vec2 {
+        (1.0 - self.get_scale()) * 0.5 * self.window - self.position * self.get_scale()
+    }
+
+    // Rotates a point on screen around the center of the window
+    fn rotate_on_screen(&self, point: &glm::Vec2, angle: f32) -> glm::Vec2 {
+        let center = 0.5 * self.window;
+        center + glm::rotate_vec2(&(point - center), angle)
+    }
+
+    // Point vectors
+    pub fn apply(&self, point: &glm::Vec2) -> glm::Vec2 {
+        self.rotate_on_screen(&(self.get_scale() * point + self.offset()), self.rotation)
+    }
+
+    // Point vectors
+    pub fn apply_reverse(&self, point: &glm::Vec2) -> glm::Vec2 {
+        let unrotated = self.rotate_on_screen(point, -self.rotation);
+        (unrotated - self.offset()).map(|component| self.apply_reverse_to_scale(component))
+    }
+
+    // Undoes just the rotation of the view, giving where a point on screen
+    // would be if the view wasn't rotated
+    pub fn unrotate(&self, point: &glm::Vec2) -> glm::Vec2 {
+        self.rotate_on_screen(point, -self.rotation)
+    }
+
+    // Sizes / Distances
+    pub fn apply_to_scale(&self, object_scale: f32) -> f32 {
+        object_scale * self.get_scale()
+    }
+
+    // Sizes / Distances
+    pub fn apply_reverse_to_scale(&self, object_scale: f32) -> f32 {
+        use crate::math::DivOrZero;
+        object_scale.div_or_zero(self.get_scale())
+    }
+
+    // Directional vectors
+    pub fn apply_to_vector(&self, point: &glm::Vec2) -> glm::Vec2 {
+        glm::rotate_vec2(&(self.get_scale() * point), self.rotation)
+    }
+
+    // Directional vectors
+    pub fn apply_reverse_to_vector(&self, point: &glm::Vec2) -> glm::Vec2 {
+        glm::rotate_vec2(point, -self.rotation).map(|component| self.apply_reverse_to_scale(component))
+    }
+
+    // Converts an angle measured on screen into the world
+    pub fn apply_reverse_to_rotation(&self, rotation: Rad<f32>) -> Rad<f32> {
+        rotation + Rad(self.rotation)
+    }
+
+    pub fn with_position(&self, position: glm::Vec2) -> Camera {
+        Camera { position, ..*self }
+    }
+
+    pub fn with_zoom(&self, zoom: f32) -> Camera {
+        Camera { zoom, ..*self }
+    }
+
+    pub fn with_rotation(&self, rotation: f32) -> Camera {
+        Camera { rotation, ..*self }
+    }
+
+    // Creates a camera centered on a point, zoomed so that a
+    // circle of the given radius fits comfortably within the window
+    pub fn framing(&self, point: &glm::Vec2, radius: f32) -> Camera {
+        let zoom = if radius > 0.0 {
+            (0.35 * glm::comp_min(&self.window) * self.ui_scale / radius).clamp(0.05, 20.0)
+        } else {
+            self.zoom
+        };
+
+        Camera {
+            position: point - 0.5 * self.window,
+            zoom,
+            ..*self
+        }
+    }
+
+    // Creates a camera zoomed in on an area of the screen, the area grows to match the
+    // shape of the window so that all of it stays visible
+    pub fn framing_area(&self, corner: &glm::Vec2, opposite_corner: &glm::Vec2) -> Camera {
+        let size_on_screen = (opposite_corner - corner).abs();
+        let scale = (size_on_screen.x / self.window.x).max(size_on_screen.y / self.window.y);
+
+        if scale <= 0.0 {
+            return *self;
+        }
+
+        // Views are measured from the camera position, which sits half a window before the center
+        let center = self.apply_reverse(&(0.5 * (corner + opposite_corner))) - 0.5 * self.window;
+        let view_size = scale * self.window / self.get_scale();
+        let (position, scale) = Camera::view_to_components(&self.window, (center - 0.5 * view_size, center + 0.5 * view_size));
+
+        Camera {
+            position,
+            zoom: scale * self.ui_scale,
+            ..*self
+        }
+    }
+
+    pub fn view(&self) -> (glm::Vec2, glm::Vec2) {
+        let view_size = self.window / self.get_scale();
+        (self.position - 0.5 * view_size, self.position + 0.5 * view_size)
+    }
+
+    pub fn view_to_components(window: &glm::Vec2, view: (glm::Vec2, glm::Vec2)) -> (glm::Vec2, f32) {
+        assert_le!(view.0, view.1);
+
+        let view_size = view.1 - view.0;
+
+        // Determine position by top-right anchor plus half view size
+        let position = view.0 + 0.5 * view_size;
+
+        // Determine zoom only by x component
+        let zoom = window.x / view_size.x;
+
+        (position, zoom)
+    }
+}
+
+impl Lerpable for Camera {
+    type Scalar = f32;
+
+    fn lerp(&self, other: &Self, scalar: Self::Scalar) -> Self {
+        Camera {
+            position: Lerpable::lerp(&self.position, &other.position, scalar),
+            zoom: self.zoom.lerp(&other.zoom, scalar),
+            window: other.window,
+            rotation: self.rotation.lerp(&other.rotation, scalar),
+            ui_scale: other.ui_scale,
+        }
+    }
+}
+
+pub trait EasySmoothCamera {
+    fn set_location(&mut self, location: glm::Vec2);
+
+    fn zoom(&mut self, zoom: f32);
+
+    fn zoom_point(&mut self, zoom_multiplier: f32, point: &glm::Vec2);
+
+    fn increase_zoom(&mut self);
+
+    fn rotate(&mut self, angle: f32);
+
+    fn decrease_zoom(&mut self);
+}
+
+impl EasySmoothCamera for Smooth<Camera> {
+    fn set_location(&mut self, location: glm::Vec2) {
+        self.set(self.get_animated().with_position(location));
+    }
+
+    fn zoom(&mut self, zoom_multiplier: f32) {
+        let center = self.get_real().position;
+        self.zoom_point(zoom_multiplier, &center);
+    }
+
+    fn zoom_point(&mut self, zoom_multiplier: f32, point_in_world_space: &glm::Vec2) {
+        let window = self.get_real().window;
+        let original_view = self.get_real().view();
+
+        let original_view_size = original_view.1 - original_view.0;
+        let ratios = (point_in_world_space - original_view.0).component_div(&original_view_size);
+
+        assert!(ratios.x >= 0.0 && ratios.x <= 1.0);
+        assert!(ratios.y >= 0.0 && ratios.y <= 1.0);
+
+        let top_left = point_in_world_space - ratios.component_mul(&original_view_size) / zoom_multiplier;
+        let bottom_right = top_left + original_view_size / zoom_multiplier;
+
+        let (position, scale) = Camera::view_to_components(&window, (top_left, bottom_right));
+        self.set(Camera {
+            position,
+            zoom: scale * self.get_real().ui_scale,
+            ..*self.get_real()
+        });
+    }
+
+    fn increase_zoom(&mut self) {
+        self.zoom(1.2);
+    }
+
+    fn decrease_zoom(&mut self) {
+        self.zoom(1.0 / 1.2);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        let camera = self.get_real();
+        self.set(camera.with_rotation(camera.rotation + angle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Camera, EasySmoothCamera};
+    use crate::smooth::Smooth;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn camera_1() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(0.0, 0.0), glm::vec2(100.0, 200.0));
+        let components = Camera::view_to_components(&window, view);
+
+        assert_eq!(components.0, glm::vec2(50.0, 100.0));
+        assert_eq!(components.1, 10.0);
+    }
+
+    #[test]
+    fn camera_2() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(900.0, 1800.0), glm::vec2(1000.0, 2000.0));
+        let components = Camera::view_to_components(&window, view);
+
+        assert_eq!(components.0, glm::vec2(950.0, 1900.0));
+        assert_eq!(components.1, 10.0);
+    }
+
+    #[test]
+    fn camera_3() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(900.0, 1800.0), glm::vec2(1000.0, 2000.0));
+        let components = Camera::view_to_components(&window, view);
+
+        let camera = Camera {
+            position: components.0,
+            zoom: components.1,
+            window,
+            rotation: 0.0,
+            ui_scale: 1.0,
+        };
+
+        assert_eq!(view, camera.view());
+    }
+
+    #[test]
+    fn camera_4() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(0.0, 0.0), glm::vec2(1000.0, 2000.0));
+        let components = Camera::view_to_components(&window, view);
+
+        let mut camera = Smooth::new(Camera::identity(glm::zero()), None);
+
+        camera.set(Camera {
+            position: components.0,
+            zoom: components.1,
+            window,
+            rotation: 0.0,
+            ui_scale: 1.0,
+        });
+
+        let view = camera.get_real().view();
+        assert_eq!(view.0, glm::vec2(0.0, 0.0));
+        assert_eq!(view.1, glm::vec2(1000.0, 2000.0));
+
+        let focus = glm::zero();
+        camera.zoom_point(2.0, &focus);
+
+        let view = camera.get_real().view();
+        assert_eq!(view, (glm::vec2(0.0, 0.0), glm::vec2(500.0, 1000.0)));
+
+        let components = Camera::view_to_components(&window, view);
+        assert_eq!(components.0, glm::vec2(250.0, 500.0));
+        assert_eq!(components.1, 2.0);
+    }
+
+    #[test]
+    fn camera_5() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(0.0, 0.0), glm::vec2(1000.0, 2000.0));
+        let components = Camera::view_to_components(&window, view);
+
+        let mut camera = Smooth::new(Camera::identity(glm::zero()), None);
+
+        camera.set(Camera {
+            position: components.0,
+            zoom: components.1,
+            window,
+            rotation: 0.0,
+            ui_scale: 1.0,
+        });
+
+        let view = camera.get_real().view();
+        assert_eq!(view.0, glm::vec2(0.0, 0.0));
+        assert_eq!(view.1, glm::vec2(1000.0, 2000.0));
+
+        let focus = components.0;
+        camera.zoom_point(2.0, &focus);
+
+        let view = camera.get_real().view();
+        assert_eq!(view, (glm::vec2(250.0, 500.0), glm::vec2(750.0, 1500.0)));
+
+        let components = Camera::view_to_components(&window, view);
+        assert_eq!(components.0, glm::vec2(500.0, 1000.0));
+        assert_eq!(components.1, 2.0);
+    }
+
+    #[test]
+    fn camera_6() {
+        let window = glm::vec2(1000.0, 2000.0);
+        let view = (glm::vec2(200.0, 400.0), glm::vec2(400.0, 800.0));
+        let components = Camera::view_to_components(&window, view);
+
+        let mut camera = Smooth::new(Camera::identity(glm::zero()), None);
+
+        camera.set(Camera {
+            position: components.0,
+            zoom: components.1,
+            window,
+            rotation: 0.0,
+            ui_scale: 1.0,
+        });
+
+        let view = camera.get_real().view();
+        assert_eq!(view.0, glm::vec2(200.0, 400.0));
+        assert_eq!(view.1, glm::vec2(400.0, 800.0));
+
+        let focus = glm::vec2(200.0, 400.0);
+        camera.zoom_point(2.0, &focus);
+
+        let view = camera.get_real().view();
+        assert_eq!(view, (glm::vec2(200.0, 400.0), glm::vec2(300.0, 600.0)));
+
+        let components = Camera::view_to_components(&window, view);
+        assert_eq!(components.0, glm::vec2(250.0, 500.0));
+        assert_eq!(components.1, 10.0);
+    }
+
+    #[test]
+    fn camera_framing() {
+        let camera = Camera::identity(glm::vec2(1000.0, 800.0));
+        let point = glm::vec2(-300.0, 1200.0);
+        let framed = camera.framing(&point, 40.0);
+
+        assert_eq!(framed.apply(&point), glm::vec2(500.0, 400.0));
+        assert_eq!(framed.apply_to_scale(40.0), 0.35 * 800.0);
+    }
+
+    #[test]
+    fn camera_framing_area() {
+        let camera = Camera {
+            position: glm::vec2(-120.0, 45.0),
+            zoom: 2.5,
+            window: glm::vec2(1000.0, 800.0),
+            rotation: 0.6,
+            ui_scale: 1.0,
+        };
+
+        let (corner, opposite_corner) = (glm::vec2(300.0, 500.0), glm::vec2(100.0, 400.0));
+        let framed = camera.framing_area(&corner, &opposite_corner);
+
+        // Wider than the window, so the width decides how far to zoom
+        assert!((framed.zoom - 5.0 * camera.zoom).abs() < 0.001);
+        assert_eq!(framed.rotation, camera.rotation);
+
+        let center = camera.apply_reverse(&glm::vec2(200.0, 450.0));
+        assert!(glm::distance(&framed.apply(&center), &glm::vec2(500.0, 400.0)) < 0.01);
+
+        for point in [corner, opposite_corner] {
+            let on_screen = framed.apply(&camera.apply_reverse(&point));
+            assert!(on_screen.x > -0.01 && on_screen.x < 1000.01 && on_screen.y > -0.01 && on_screen.y < 800.01);
+        }
+    }
+
+    #[test]
+    fn camera_ui_scale() {
+        let camera = Camera {
+            zoom: 2.0,
+            ui_scale: 1.5,
+            ..Camera::identity(glm::vec2(1000.0, 800.0))
+        };
+
+        // The interface is larger, but the document stays the same size on screen
+        assert_eq!(camera.apply_to_scale(30.0) * camera.ui_scale, 30.0 * camera.zoom);
+        assert!(glm::distance(&camera.apply_reverse(&camera.mat().apply(&glm::vec2(12.0, -7.0))), &glm::vec2(12.0, -7.0)) < 0.001);
+
+        let framed = camera.framing(&glm::vec2(50.0, 50.0), 40.0);
+        assert!((framed.apply_to_scale(40.0) - 0.35 * 800.0).abs() < 0.001);
+        assert!((framed.zoom - 0.35 * 800.0 * 1.5 / 40.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn camera_matches_matrix() {
+        let camera = Camera {
+            position: glm::vec2(-120.0, 45.0),
+            zoom: 2.5,
+            window: glm::vec2(1000.0, 800.0),
+            rotation: 0.6,
+            ui_scale: 1.0,
+        };
+
+        for point in [glm::vec2(0.0, 0.0), glm::vec2(300.0, -20.0), glm::vec2(-75.5, 610.0)] {
+            assert!(glm::distance(&camera.apply(&point), &camera.mat().apply(&point)) < 0.001);
+            assert!(glm::distance(&camera.apply_reverse(&camera.apply(&point)), &point) < 0.001);
+
+            assert!(glm::distance(&camera.apply_to_vector(&point), &camera.mat().apply_to_vector(&point)) < 0.001);
+        }
+    }
+}
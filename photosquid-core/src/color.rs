@@ -41,6 +41,15 @@ impl Color {
         (hue, saturation, value)
     }
 
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        Self { a: self.a * opacity, ..self }
+    }
+
+    // Formats as #RRGGBB, ignoring alpha
+    pub fn to_rgb_hex(self) -> String {
+        RasterColor::from(Self { a: 1.0, ..self }).to_hex()
+    }
+
     pub fn white() -> Self {
         Self::new(1.0, 1.0, 1.0, 1.0)
     }
@@ -96,6 +105,13 @@ impl From<&Color> for [u8; 4] {
     }
 }
 
+impl From<Color> for RasterColor {
+    fn from(c: Color) -> Self {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::new(channel(c.r), channel(c.g), channel(c.b), channel(c.a))
+    }
+}
+
 impl From<RasterColor> for Color {
     fn from(c: RasterColor) -> Self {
         Self {
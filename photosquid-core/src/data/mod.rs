@@ -1,7 +1,9 @@
 pub mod circle;
+pub mod path;
 pub mod rect;
 pub mod tri;
 
 pub use circle::CircleData;
+pub use path::PathData;
 pub use rect::RectData;
 pub use tri::TriData;
@@ -0,0 +1,189 @@
+use crate::{
+    color::Color,
+    smooth::{Lerpable, MultiLerp, NoLerp},
+};
+use angular_units::Rad;
+use nalgebra_glm as glm;
+use serde::{Deserialize, Serialize};
+
+// Point on a bezier path, along with the control handles on either side of it
+// (handles are relative to the point)
+#[derive(Default, Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PathAnchor {
+    pub point: glm::Vec2,
+    pub handle_in: glm::Vec2,
+    pub handle_out: glm::Vec2,
+}
+
+impl PathAnchor {
+    pub fn new(point: glm::Vec2) -> Self {
+        Self {
+            point,
+            handle_in: glm::zero(),
+            handle_out: glm::zero(),
+        }
+    }
+
+    // Anchors for a smooth curve through each point in order (Catmull-Rom),
+    // the ends only lean towards their single neighbor
+    pub fn smooth_through(points: &[glm::Vec2]) -> Vec<Self> {
+        (0..points.len())
+            .map(|i| {
+                let previous = points[i.saturating_sub(1)];
+                let next = points[(i + 1).min(points.len() - 1)];
+                Self::new(points[i]).with_handle((next - previous) / 6.0)
+            })
+            .collect()
+    }
+
+    // Drags out symmetric handles, as if pulling the curve through the point
+    pub fn with_handle(&self, handle_out: glm::Vec2) -> Self {
+        Self {
+            point: self.point,
+            handle_in: -handle_out,
+            handle_out,
+        }
+    }
+}
+
+// Replaces each sharp corner between two straight segments with a pair of anchors that curve around it,
+// where the radius is shrunk for corners that don't have room for it
+pub fn round_corners(anchors: &[PathAnchor], closed: bool, radius: f32) -> Vec<PathAnchor> {
+    round_each_corner(anchors, closed, &vec![radius; anchors.len()])
+}
+
+// Same as 'round_corners', but with a radius for each anchor
+pub fn round_each_corner(anchors: &[PathAnchor], closed: bool, radii: &[f32]) -> Vec<PathAnchor> {
+    let count = anchors.len();
+
+    if radii.iter().all(|radius| *radius <= 0.0) || count < 3 {
+        return anchors.to_vec();
+    }
+
+    let mut rounded = Vec::with_capacity(count * 2);
+
+    for (i, anchor) in anchors.iter().enumerate() {
+        let neighbors = if closed || (i > 0 && i + 1 < count) {
+            Some((anchors[(i + count - 1) % count], anchors[(i + 1) % count]))
+        } else {
+            None
+        };
+
+        let radius = radii.get(i).copied().unwrap_or_default();
+
+        match neighbors
+            .filter(|_| radius > 0.0)
+            .and_then(|(previous, next)| round_corner(&previous, anchor, &next, radius))
+        {
+            Some(pair) => rounded.extend(pair),
+            None => rounded.push(*anchor),
+        }
+    }
+
+    rounded
+}
+
+fn round_corner(previous: &PathAnchor, corner: &PathAnchor, next: &PathAnchor, radius: f32) -> Option<[PathAnchor; 2]> {
+    let is_zero = |handle: &glm::Vec2| *handle == glm::Vec2::zeros();
+
+    // Only corners where both sides are straight lines
+    if !(is_zero(&previous.handle_out) && is_zero(&corner.handle_in) && is_zero(&corner.handle_out) && is_zero(&next.handle_in)) {
+        return None;
+    }
+
+    let to_previous = previous.point - corner.point;
+    let to_next = next.point - corner.point;
+    let (previous_length, next_length) = (glm::length(&to_previous), glm::length(&to_next));
+
+    if previous_length < f32::EPSILON || next_length < f32::EPSILON {
+        return None;
+    }
+
+    let (to_previous, to_next) = (to_previous / previous_length, to_next / next_length);
+    let angle = glm::dot(&to_previous, &to_next).clamp(-1.0, 1.0).acos();
+
+    // Straight through or folded back on itself
+    if !(0.01..=std::f32::consts::PI - 0.01).contains(&angle) {
+        return None;
+    }
+
+    // How far along each side the curve starts, which can use up at most half of either side
+    let half_angle_tan = (angle * 0.5).tan();
+    let reach = (radius / half_angle_tan).min(previous_length * 0.5).min(next_length * 0.5);
+    let radius = reach * half_angle_tan;
+
+    // Handle length for a cubic that's close to a circular arc
+    let handle = 4.0 / 3.0 * ((std::f32::consts::PI - angle) * 0.25).tan() * radius;
+
+    Some([
+        PathAnchor {
+            point: corner.point + to_previous * reach,
+            handle_in: glm::zero(),
+            handle_out: -to_previous * handle,
+        },
+        PathAnchor {
+            point: corner.point + to_next * reach,
+            handle_in: -to_next * handle,
+            handle_out: glm::zero(),
+        },
+    ])
+}
+
+// Animatable part of a path, the anchors themselves are kept alongside
+// since there can be any number of them
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct PathData {
+    pub position: MultiLerp<glm::Vec2>,
+    pub rotation: Rad<f32>,
+    pub scale: f32,
+    pub color: NoLerp<Color>,
+
+    // Rounds sharp corners when made into a mesh or exported, see 'round_corners'
+    #[serde(default)]
+    pub corner_radius: f32,
+}
+
+impl Lerpable for PathData {
+    type Scalar = f32;
+
+    fn lerp(&self, other: &Self, scalar: Self::Scalar) -> Self {
+        Self {
+            position: self.position.lerp(&other.position, scalar),
+            rotation: self.rotation.lerp(&other.rotation, scalar),
+            scale: self.scale.lerp(&other.scale, scalar),
+            color: self.color.lerp(&other.color, scalar),
+            corner_radius: self.corner_radius.lerp(&other.corner_radius, scalar),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{round_corners, PathAnchor};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn round_square_corners() {
+        let square: Vec<PathAnchor> = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]
+            .iter()
+            .map(|&(x, y)| PathAnchor::new(glm::vec2(x, y)))
+            .collect();
+
+        // Each corner becomes two anchors, a radius away from it along each side
+        let rounded = round_corners(&square, true, 10.0);
+        assert_eq!(rounded.len(), 8);
+        assert!(glm::distance(&rounded[0].point, &glm::vec2(0.0, 10.0)) < 0.001);
+        assert!(glm::distance(&rounded[1].point, &glm::vec2(10.0, 0.0)) < 0.001);
+        assert!((glm::length(&rounded[0].handle_out) - 5.523).abs() < 0.01);
+
+        // Corners can't be rounded past the middle of their sides
+        assert!(glm::distance(&round_corners(&square, true, 500.0)[1].point, &glm::vec2(50.0, 0.0)) < 0.001);
+
+        // The ends of open paths and curved corners stay sharp
+        assert_eq!(round_corners(&square, false, 10.0).len(), 6);
+
+        let mut curved = square.clone();
+        curved[0] = curved[0].with_handle(glm::vec2(10.0, 0.0));
+        assert_eq!(round_corners(&curved, true, 10.0).len(), 5);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    affine::Affine2,
     color::Color,
     smooth::{Lerpable, MultiLerp, NoLerp},
 };
@@ -15,6 +16,17 @@ pub struct RectData {
     pub rotation: Rad<f32>,
     pub radii: BorderRadii,
     pub is_viewport: bool,
+
+    // Slant of the rect before it's rotated, see 'Affine2::shearing'
+    #[serde(default)]
+    pub shear: glm::Vec2,
+}
+
+impl RectData {
+    // Takes offsets from the center of the rect (before it's sheared and rotated) to offsets in world space
+    pub fn get_frame(&self) -> Affine2 {
+        Affine2::rotation(-self.rotation.0).shear(&self.shear)
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Serialize, Deserialize)]
@@ -35,6 +47,22 @@ impl BorderRadii {
             bottom_right: r,
         }
     }
+
+    // Radii in the order top left, top right, bottom left, then bottom right
+    pub fn to_array(self) -> [f32; 4] {
+        [self.top_left, self.top_right, self.bottom_left, self.bottom_right]
+    }
+}
+
+impl From<[f32; 4]> for BorderRadii {
+    fn from([top_left, top_right, bottom_left, bottom_right]: [f32; 4]) -> Self {
+        BorderRadii {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        }
+    }
 }
 
 impl From<BorderRadii> for LyonBorderRadii {
@@ -75,6 +103,7 @@ impl Lerpable for RectData {
             color: self.color.lerp(&other.color, scalar),
             radii: self.radii.lerp(&other.radii, scalar),
             is_viewport: self.is_viewport,
+            shear: self.shear.lerp(&other.shear, scalar),
         }
     }
 }
@@ -15,6 +15,10 @@ pub struct TriData {
     pub position: MultiLerp<glm::Vec2>,
     pub color: NoLerp<Color>,
     pub rotation: Rad<f32>,
+
+    // Rounds the corners when made into a mesh or exported, see 'path::round_corners'
+    #[serde(default)]
+    pub corner_radius: f32,
 }
 
 impl Lerpable for TriData {
@@ -35,6 +39,7 @@ impl Lerpable for TriData {
             position: self.position.lerp(&other.position, scalar),
             rotation: self.rotation.lerp(&other.rotation, scalar),
             color: self.color.lerp(&other.color, scalar),
+            corner_radius: self.corner_radius.lerp(&other.corner_radius, scalar),
         }
     }
 }
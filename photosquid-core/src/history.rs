@@ -0,0 +1,117 @@
+// Snapshots of a document that can be stepped back and forth through
+pub struct History<T> {
+    history: Vec<T>,
+    time_travel: usize,
+
+    // Number of snapshots dropped from the start, so positions still line up after the oldest are let go
+    dropped: usize,
+
+    // Position of the snapshot that was last saved, if it's still reachable
+    saved: Option<usize>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            history: vec![],
+            time_travel: 0,
+            dropped: 0,
+            saved: Some(0),
+        }
+    }
+}
+
+impl<T> History<T> {
+    fn get_position(&self) -> usize {
+        self.dropped + self.time_travel
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved = Some(self.get_position());
+    }
+
+    // Whether the document has been changed, undone or redone since it was last saved
+    pub fn is_changed(&self) -> bool {
+        self.saved != Some(self.get_position())
+    }
+}
+
+impl<T: Clone + Default> History<T> {
+    const MAX_HISTORY: usize = 100;
+
+    pub fn push(&mut self, value: T) {
+        if self.history.is_empty() {
+            self.history.push(T::default());
+        } else {
+            // The saved snapshot can't be gotten back to once it's been undone and replaced
+            if self.saved.is_some_and(|saved| saved > self.get_position()) {
+                self.saved = None;
+            }
+
+            while self.time_travel < self.history.len() - 1 {
+                self.history.pop();
+            }
+        }
+
+        while self.history.len() >= Self::MAX_HISTORY {
+            self.history.remove(0);
+            self.time_travel -= 1;
+            self.dropped += 1;
+        }
+
+        self.history.push(value);
+        self.time_travel = self.history.len() - 1;
+    }
+
+    pub fn undo(&mut self) -> Option<T> {
+        if self.time_travel > 0 {
+            self.time_travel -= 1;
+            Some(self.history[self.time_travel].clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn redo(&mut self) -> Option<T> {
+        if self.time_travel + 1 < self.history.len() {
+            self.time_travel += 1;
+            Some(self.history[self.time_travel].clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn history_changed_since_save() {
+        let mut history = History::<i32>::default();
+        assert!(!history.is_changed());
+
+        history.push(1);
+        assert!(history.is_changed());
+        history.mark_saved();
+        assert!(!history.is_changed());
+
+        // Undoing away from the saved snapshot and redoing back to it
+        history.undo();
+        assert!(history.is_changed());
+        history.redo();
+        assert!(!history.is_changed());
+
+        // Replacing the saved snapshot after undoing it
+        history.undo();
+        history.push(2);
+        assert!(history.is_changed());
+
+        // Positions still line up once the oldest snapshots are dropped
+        history.mark_saved();
+        (0..200).for_each(|value| history.push(value));
+        assert!(history.is_changed());
+        history.mark_saved();
+        assert!(!history.is_changed());
+    }
+}
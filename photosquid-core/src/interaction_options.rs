@@ -0,0 +1,99 @@
+use angular_units::Rad;
+use nalgebra_glm as glm;
+
+pub struct InteractionOptions {
+    pub translation_snapping: f32,
+    pub rotation_snapping: Rad<f32>,
+    pub duplication_offset: glm::Vec2,
+    pub treat_selection_as_group: bool,
+    pub radial_menu: bool,
+
+    // Turns every kind of snapping on or off at once
+    pub snapping: bool,
+
+    // Snapping is left off for a moment while a key is held during a drag
+    pub snapping_suspended: bool,
+
+    // Kinds of snapping, which only apply while snapping is on.
+    // Grid snapping moves and turns squids in steps of the translation and rotation snapping
+    pub snap_to_grid: bool,
+    pub snap_to_objects: bool,
+}
+
+impl InteractionOptions {
+    pub fn is_snapping(&self) -> bool {
+        self.snapping && !self.snapping_suspended
+    }
+
+    // Step that moves are snapped to, where zero means moving freely
+    pub fn get_translation_snapping(&self) -> f32 {
+        if self.is_snapping() && self.snap_to_grid {
+            self.translation_snapping
+        } else {
+            0.0
+        }
+    }
+
+    // Step that turns are snapped to, where zero means turning freely
+    pub fn get_rotation_snapping(&self) -> Rad<f32> {
+        if self.is_snapping() && self.snap_to_grid {
+            self.rotation_snapping
+        } else {
+            Rad(0.0)
+        }
+    }
+
+    pub fn snaps_to_objects(&self) -> bool {
+        self.is_snapping() && self.snap_to_objects
+    }
+}
+
+impl Default for InteractionOptions {
+    fn default() -> Self {
+        Self {
+            translation_snapping: 1.0,
+            rotation_snapping: Rad(0.0),
+            duplication_offset: glm::zero(),
+            treat_selection_as_group: false,
+            radial_menu: false,
+            snapping: true,
+            snapping_suspended: false,
+            snap_to_grid: true,
+            snap_to_objects: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionOptions;
+    use angular_units::Rad;
+
+    #[test]
+    fn snapping_toggles() {
+        let mut options = InteractionOptions {
+            translation_snapping: 10.0,
+            rotation_snapping: Rad(0.5),
+            snap_to_objects: true,
+            ..Default::default()
+        };
+        assert_eq!(options.get_translation_snapping(), 10.0);
+        assert!(options.snaps_to_objects());
+
+        // Each kind of snapping is separate
+        options.snap_to_grid = false;
+        assert_eq!(options.get_translation_snapping(), 0.0);
+        assert_eq!(options.get_rotation_snapping(), Rad(0.0));
+        assert!(options.snaps_to_objects());
+
+        // Holding the key while dragging turns off everything until it's let go
+        options.snap_to_grid = true;
+        options.snapping_suspended = true;
+        assert_eq!(options.get_rotation_snapping(), Rad(0.0));
+        assert!(!options.snaps_to_objects());
+
+        options.snapping_suspended = false;
+        options.snapping = false;
+        assert_eq!(options.get_translation_snapping(), 0.0);
+    }
+}
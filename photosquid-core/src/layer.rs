@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{color::Color, squid::SquidRef};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Layer {
+    name: String,
+    squids: Vec<SquidRef>,
+
+    #[serde(default)]
+    hidden: bool,
+
+    #[serde(default)]
+    locked: bool,
+
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+
+    #[serde(default)]
+    tag: LayerTag,
+
+    // Radius of the gaussian blur the whole layer is drawn with, in world units
+    #[serde(default)]
+    blur: f32,
+}
+
+// Color label that can be given to a layer for organization
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayerTag {
+    #[default]
+    None,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl LayerTag {
+    pub fn next(self) -> Self {
+        use LayerTag::*;
+
+        match self {
+            None => Red,
+            Red => Orange,
+            Orange => Yellow,
+            Yellow => Green,
+            Green => Blue,
+            Blue => Purple,
+            Purple => None,
+        }
+    }
+
+    pub fn get_color(self) -> Color {
+        use LayerTag::*;
+
+        Color::from_hex(match self {
+            None => "#555555",
+            Red => "#ED4245",
+            Orange => "#F0883E",
+            Yellow => "#FEE75C",
+            Green => "#57F287",
+            Blue => "#5865F2",
+            Purple => "#B45FE6",
+        })
+    }
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+impl Layer {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            squids: vec![],
+            hidden: false,
+            locked: false,
+            opacity: default_opacity(),
+            tag: LayerTag::None,
+            blur: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, reference: SquidRef) {
+        self.squids.insert(0, reference);
+    }
+
+    pub fn remove_mention(&mut self, reference: SquidRef) {
+        self.squids.retain(|squid_reference| !squid_reference.eq(&reference));
+    }
+
+    // Moves a squid to a position within this layer, where 0 is the highest
+    pub fn reorder(&mut self, reference: SquidRef, new_index: usize) -> bool {
+        match self.squids.iter().position(|squid_reference| *squid_reference == reference) {
+            Some(old_index) => {
+                self.squids.remove(old_index);
+                self.squids.insert(new_index.min(self.squids.len()), reference);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_index_of(&self, reference: SquidRef) -> Option<usize> {
+        self.squids.iter().position(|squid_reference| *squid_reference == reference)
+    }
+
+    pub fn get_lowest(&self) -> impl Iterator<Item = SquidRef> + '_ {
+        self.squids.iter().rev().copied()
+    }
+
+    pub fn get_highest(&self) -> impl Iterator<Item = SquidRef> + '_ {
+        self.squids.iter().copied()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    pub fn get_blur(&self) -> f32 {
+        self.blur
+    }
+
+    pub fn set_blur(&mut self, blur: f32) {
+        self.blur = blur.max(0.0);
+    }
+
+    pub fn get_tag(&self) -> LayerTag {
+        self.tag
+    }
+
+    pub fn set_tag(&mut self, tag: LayerTag) {
+        self.tag = tag;
+    }
+
+    // Whether squids in this layer can be selected/manipulated on the canvas
+    pub fn is_interactable(&self) -> bool {
+        !self.hidden && !self.locked
+    }
+
+    pub fn contains(&self, reference: SquidRef) -> bool {
+        self.squids.contains(&reference)
+    }
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self::new("Default Layer".into())
+    }
+}
@@ -0,0 +1,30 @@
+#![warn(
+    clippy::explicit_iter_loop,
+    clippy::semicolon_if_nothing_returned,
+    clippy::wildcard_imports,
+    clippy::too_many_lines,
+    clippy::redundant_else
+)]
+
+// Headless building blocks of photosquid documents, which don't depend on a window or GPU
+
+pub mod aabb;
+pub mod accumulator;
+pub mod affine;
+pub mod algorithm;
+pub mod approx_instant;
+pub mod as_values;
+pub mod camera;
+pub mod color;
+pub mod data;
+pub mod history;
+pub mod interaction_options;
+pub mod layer;
+pub mod math;
+pub mod png;
+pub mod raster_color;
+pub mod smooth;
+pub mod spatial_index;
+pub mod squid;
+pub mod style;
+pub mod units;
@@ -0,0 +1,82 @@
+use std::convert::TryInto;
+
+// Minimal PNG encoder for 8-bit RGBA images
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+const COMPRESSION_LEVEL: u8 = 6;
+
+// Encodes rows of RGBA pixels (top to bottom) as a PNG file
+pub fn encode(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let row_length = width as usize * 4;
+    assert_eq!(rgba.len(), row_length * height as usize);
+
+    let mut header = vec![];
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+
+    // Bit depth, color type (RGBA), compression, filter, and interlace methods
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    // Each row is prefixed with its filter type, which is always 'None'
+    let mut scanlines = Vec::with_capacity((row_length + 1) * height as usize);
+
+    for row in rgba.chunks_exact(row_length.max(1)).take(height as usize) {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &miniz_oxide::deflate::compress_to_vec_zlib(&scanlines, COMPRESSION_LEVEL));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let length: u32 = data.len().try_into().unwrap();
+    png.extend_from_slice(&length.to_be_bytes());
+
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xEDB88320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, encode};
+    use std::convert::TryInto;
+
+    #[test]
+    fn png_encode() {
+        let pixels = [255, 0, 0, 255, 0, 255, 0, 128];
+        let png = encode(1, 2, &pixels);
+
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+
+        // Image data is two filtered scanlines
+        let idat_length = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        let scanlines = miniz_oxide::inflate::decompress_to_vec_zlib(&png[41..41 + idat_length]).unwrap();
+        assert_eq!(scanlines, [0, 255, 0, 0, 255, 0, 0, 255, 0, 128]);
+
+        assert_eq!(crc32(b"IEND"), 0xAE426082);
+    }
+}
@@ -20,6 +20,11 @@ impl RasterColor {
     }
 
     pub fn hex(hex: &str) -> Result<Self, RasterError> {
+        // Lengths below are in bytes, so anything else can't be sliced into digits
+        if !hex.is_ascii() {
+            return Err(RasterError::InvalidHex);
+        }
+
         if hex.len() == 9 && hex.starts_with('#') {
             // #FFFFFFFF (Red Green Blue Alpha)
             Ok(RasterColor {
@@ -40,8 +45,35 @@ impl RasterColor {
             Err(RasterError::InvalidHex)
         }
     }
+
+    // Formats as #RRGGBB, or as #RRGGBBAA when not fully opaque
+    pub fn to_hex(&self) -> String {
+        if self.a == 255 {
+            format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
 }
 
 fn hex_dec(hex_string: &str) -> Result<u8, RasterError> {
     u8::from_str_radix(hex_string, 16).map(|o| o as u8).map_err(RasterError::HexParse)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{RasterColor, RasterError};
+
+    #[test]
+    fn raster_color_hex() {
+        let color = RasterColor::hex("#FF8000").unwrap();
+        assert_eq!((color.r, color.g, color.b, color.a), (255, 128, 0, 255));
+        assert_eq!(RasterColor::hex("#FF800080").unwrap().to_hex(), "#FF800080");
+
+        assert!(matches!(RasterColor::hex("#FF80"), Err(RasterError::InvalidHex)));
+        assert!(matches!(RasterColor::hex("#GG8000"), Err(RasterError::HexParse(_))));
+
+        // Same length in bytes as a valid color, but not made of digits
+        assert!(matches!(RasterColor::hex("#a\u{e9}\u{2026}"), Err(RasterError::InvalidHex)));
+    }
+}
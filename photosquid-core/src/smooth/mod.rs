@@ -10,4 +10,4 @@ pub use circle_lerpable::CircleLerpable;
 pub use lerpable::Lerpable;
 pub use multi_lerp::MultiLerp;
 pub use no_lerp::NoLerp;
-pub use smooth::Smooth;
+pub use smooth::{default_smooth_duration, Smooth};
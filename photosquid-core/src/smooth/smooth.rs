@@ -55,6 +55,17 @@ where
         self.changed = Instant::now();
     }
 
+    // Whether the animated value is still on its way to the real one
+    pub fn is_animating(&self) -> bool {
+        self.changed.elapsed() < self.duration
+    }
+
+    // Skips to the end of any animation in progress
+    pub fn finish(&mut self) {
+        self.previous = self.data;
+        self.changed = Instant::now().checked_sub(self.duration).unwrap_or(self.changed);
+    }
+
     pub fn manual_get_real(&mut self) -> &mut T {
         &mut self.data
     }
@@ -63,3 +74,24 @@ where
         &mut self.previous
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Smooth;
+    use std::time::Duration;
+
+    #[test]
+    fn smooth_animating() {
+        let mut smooth = Smooth::new(0.0, Some(Duration::from_secs(60)));
+        smooth.set(10.0);
+        assert!(smooth.is_animating());
+
+        smooth.finish();
+        assert!(!smooth.is_animating());
+        assert_eq!(smooth.get_animated(), 10.0);
+
+        // Animations that are done don't need to be drawn anymore
+        let smooth = Smooth::new(0.0, Some(Duration::ZERO));
+        assert!(!smooth.is_animating());
+    }
+}
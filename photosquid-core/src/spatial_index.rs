@@ -0,0 +1,133 @@
+use crate::aabb::AABB;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+// Grid of cells that remembers which boxes overlap each cell,
+// so finding what's near a point doesn't have to look at everything.
+// Boxes that would cover lots of cells are kept aside and always considered
+#[derive(Clone)]
+pub struct SpatialIndex<K> {
+    cells: HashMap<(i32, i32), Vec<K>>,
+    boxes: HashMap<K, AABB>,
+    large: HashSet<K>,
+}
+
+impl<K> Default for SpatialIndex<K> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            boxes: HashMap::new(),
+            large: HashSet::new(),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash> SpatialIndex<K> {
+    const CELL_SIZE: f32 = 256.0;
+    const MAX_CELLS: i64 = 64;
+
+    // Adds a box for a key, replacing any box it already had
+    pub fn insert(&mut self, key: K, area: AABB) {
+        self.remove(key);
+        self.boxes.insert(key, area);
+
+        let (min, max) = get_cell_range(&area);
+
+        if (max.0 as i64 - min.0 as i64 + 1) * (max.1 as i64 - min.1 as i64 + 1) > Self::MAX_CELLS {
+            self.large.insert(key);
+            return;
+        }
+
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                self.cells.entry((x, y)).or_default().push(key);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: K) {
+        let area = match self.boxes.remove(&key) {
+            Some(area) => area,
+            None => return,
+        };
+
+        if self.large.remove(&key) {
+            return;
+        }
+
+        let (min, max) = get_cell_range(&area);
+
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                if let Some(cell) = self.cells.get_mut(&(x, y)) {
+                    cell.retain(|other| *other != key);
+
+                    if cell.is_empty() {
+                        self.cells.remove(&(x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.boxes.clear();
+        self.large.clear();
+    }
+
+    // Keys with boxes that touch an area
+    pub fn query(&self, area: &AABB) -> HashSet<K> {
+        let (min, max) = get_cell_range(area);
+        let mut found: HashSet<K> = self.large.iter().copied().filter(|key| self.boxes[key].intersecting_aabb(area)).collect();
+
+        // Large queries are cheaper to answer by checking every box
+        if (max.0 as i64 - min.0 as i64 + 1) * (max.1 as i64 - min.1 as i64 + 1) > self.cells.len() as i64 {
+            found.extend(self.boxes.iter().filter(|(_, other)| other.intersecting_aabb(area)).map(|(key, _)| *key));
+            return found;
+        }
+
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    found.extend(cell.iter().copied().filter(|key| self.boxes[key].intersecting_aabb(area)));
+                }
+            }
+        }
+
+        found
+    }
+}
+
+fn get_cell_range(area: &AABB) -> ((i32, i32), (i32, i32)) {
+    let cell = |value: f32| (value / SpatialIndex::<()>::CELL_SIZE).floor() as i32;
+    ((cell(area.min_x), cell(area.min_y)), (cell(area.max_x), cell(area.max_y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpatialIndex;
+    use crate::aabb::AABB;
+
+    #[test]
+    fn query_spatial_index() {
+        let mut index = SpatialIndex::default();
+        index.insert(1, AABB::new(0.0, 0.0, 10.0, 10.0));
+        index.insert(2, AABB::new(1000.0, 1000.0, 10.0, 10.0));
+        index.insert(3, AABB::new(-100_000.0, -100_000.0, 200_000.0, 200_000.0));
+
+        let near_origin = index.query(&AABB::new(5.0, 5.0, 1.0, 1.0));
+        assert!(near_origin.contains(&1) && near_origin.contains(&3) && !near_origin.contains(&2));
+
+        // Moving a box forgets where it was
+        index.insert(1, AABB::new(1000.0, 1000.0, 10.0, 10.0));
+        assert!(!index.query(&AABB::new(5.0, 5.0, 1.0, 1.0)).contains(&1));
+        assert!(index.query(&AABB::new(1005.0, 1005.0, 1.0, 1.0)).contains(&1));
+
+        index.remove(3);
+        assert!(index.query(&AABB::new(5.0, 5.0, 1.0, 1.0)).is_empty());
+        assert_eq!(index.query(&AABB::new(-1e6, -1e6, 2e6, 2e6)).len(), 2);
+    }
+}
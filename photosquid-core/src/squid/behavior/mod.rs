@@ -6,6 +6,6 @@ mod translate_behavior;
 
 pub use dilate_behavior::DilateBehavior;
 pub use revolve_behavior::RevolveBehavior;
-pub use rotate_behavior::get_delta_rotation;
+pub use rotate_behavior::{get_delta_rotation, get_rotation_snapping, SHIFT_ROTATION_SNAPPING};
 pub use spread_behavior::SpreadBehavior;
 pub use translate_behavior::TranslateBehavior;
@@ -30,7 +30,7 @@ impl Expression {
 
 impl RevolveBehavior {
     // Returns origin point to rotate around a certain amount
-    pub fn express(&mut self, current: &glm::Vec2, options: &InteractionOptions) -> Option<Expression> {
+    pub fn express(&mut self, current: &glm::Vec2, shift: bool, options: &InteractionOptions) -> Option<Expression> {
         use crate::math::AsAngle;
 
         if !self.revolving {
@@ -41,7 +41,10 @@ impl RevolveBehavior {
             let total_delta_mu = mu0 - mu1;
 
             let raw_delta_rotation = angle_difference(self.rotation + *self.accumulator.residue(), total_delta_mu);
-            let delta_rotation = self.accumulator.accumulate(&raw_delta_rotation, options.rotation_snapping).unwrap_or_default();
+            let delta_rotation = self
+                .accumulator
+                .accumulate(&raw_delta_rotation, super::get_rotation_snapping(options, shift))
+                .unwrap_or_default();
 
             self.rotation += delta_rotation;
 
@@ -0,0 +1,56 @@
+use crate::{accumulator::Accumulator, camera::Camera, interaction_options::InteractionOptions, math::angle_difference};
+use angular_units::Rad;
+use nalgebra_glm as glm;
+
+// Step that turns are snapped to while shift is held, no matter the rotation snapping
+pub const SHIFT_ROTATION_SNAPPING: Rad<f32> = Rad(std::f32::consts::PI / 12.0);
+
+pub fn get_delta_rotation(
+    center: &glm::Vec2,
+    existing_rotation: Rad<f32>,
+    mouse_position: &glm::Vec2,
+    rotation_accumulator: &Accumulator<Rad<f32>>,
+    camera: &Camera,
+) -> Rad<f32> {
+    let screen_center = camera.apply(center);
+
+    let old_rotation = existing_rotation + *rotation_accumulator.residue();
+    let new_rotation = camera.apply_reverse_to_rotation(Rad(-1.0 * (mouse_position.y - screen_center.y).atan2(mouse_position.x - screen_center.x)));
+
+    angle_difference(old_rotation, new_rotation)
+}
+
+pub fn get_rotation_snapping(options: &InteractionOptions, shift: bool) -> Rad<f32> {
+    if shift {
+        SHIFT_ROTATION_SNAPPING
+    } else {
+        options.get_rotation_snapping()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_rotation_snapping, SHIFT_ROTATION_SNAPPING};
+    use crate::{accumulator::Accumulator, interaction_options::InteractionOptions};
+    use angular_units::Rad;
+
+    #[test]
+    fn shift_rotation_snapping() {
+        let options = InteractionOptions {
+            rotation_snapping: Rad(0.5),
+            snapping: false,
+            ..Default::default()
+        };
+
+        // Shift snaps even when snapping is off
+        assert_eq!(get_rotation_snapping(&options, false), Rad(0.0));
+        assert_eq!(get_rotation_snapping(&options, true), SHIFT_ROTATION_SNAPPING);
+
+        let mut accumulator = Accumulator::new();
+        assert_eq!(accumulator.accumulate(&Rad(0.1), get_rotation_snapping(&options, true)), None);
+        assert_eq!(
+            accumulator.accumulate(&Rad(0.1), get_rotation_snapping(&options, true)),
+            Some(SHIFT_ROTATION_SNAPPING)
+        );
+    }
+}
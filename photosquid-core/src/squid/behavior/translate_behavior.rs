@@ -9,7 +9,7 @@ pub struct TranslateBehavior {
 impl TranslateBehavior {
     // Returns delta position
     pub fn express(&mut self, raw_delta: &glm::Vec2, options: &InteractionOptions) -> glm::Vec2 {
-        self.accumulator.accumulate(raw_delta, options.translation_snapping).unwrap_or_default()
+        self.accumulator.accumulate(raw_delta, options.get_translation_snapping()).unwrap_or_default()
     }
 }
 
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, fmt};
+
+// Identifies a squid for as long as it exists, even across saving and loading,
+// unlike squid references which are only meaningful within the ocean they came from.
+// Saved as a random (version 4) UUID
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct SquidId(u128);
+
+impl SquidId {
+    pub fn new() -> Self {
+        let random: u128 = rand::random();
+
+        // Version and variant bits
+        Self((random & !(0xF << 76) & !(0b11 << 62)) | (0x4 << 76) | (0b10 << 62))
+    }
+}
+
+impl Default for SquidId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SquidId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(f, "{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+    }
+}
+
+impl From<SquidId> for String {
+    fn from(id: SquidId) -> String {
+        id.to_string()
+    }
+}
+
+impl TryFrom<String> for SquidId {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let hex: String = text.chars().filter(|c| *c != '-').collect();
+
+        if hex.len() != 32 {
+            return Err(format!("Invalid squid id '{}'", text));
+        }
+
+        u128::from_str_radix(&hex, 16).map(Self).map_err(|_| format!("Invalid squid id '{}'", text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SquidId;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn squid_id_text() {
+        let id = SquidId::new();
+        let text = id.to_string();
+
+        assert_eq!(text.len(), 36);
+        assert_eq!(&text[14..15], "4");
+        assert_eq!(SquidId::try_from(text), Ok(id));
+        assert!(SquidId::try_from("not-an-id".to_string()).is_err());
+        assert_ne!(SquidId::new(), id);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod behavior;
+mod id;
+
+pub use id::SquidId;
+
+use slotmap::new_key_type;
+
+new_key_type! {
+    pub struct SquidRef;
+}
@@ -0,0 +1,32 @@
+use crate::{color::Color, data::rect::BorderRadii};
+use serde::{Deserialize, Serialize};
+use slotmap::new_key_type;
+
+new_key_type! { pub struct StyleRef; }
+
+// Named color that can be shared by multiple squids,
+// editing it changes the color of every squid that uses it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColorStyle {
+    pub name: String,
+    pub color: Color,
+
+    // Rest of the look of the squid the style was saved from, if it was saved from one.
+    // These are given to squids when they're linked or the style is updated
+    #[serde(default)]
+    pub radii: Option<BorderRadii>,
+
+    #[serde(default)]
+    pub blur: Option<f32>,
+}
+
+impl ColorStyle {
+    pub fn new(name: String, color: Color) -> Self {
+        Self {
+            name,
+            color,
+            radii: None,
+            blur: None,
+        }
+    }
+}
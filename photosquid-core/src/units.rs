@@ -0,0 +1,96 @@
+// Units that lengths can be typed and shown in, where pixels are the same as world units
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Unit {
+    #[default]
+    Px,
+    Pt,
+    In,
+    Cm,
+    Mm,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 5] = [Unit::Px, Unit::Pt, Unit::In, Unit::Cm, Unit::Mm];
+
+    pub fn get_suffix(self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Pt => "pt",
+            Self::In => "in",
+            Self::Cm => "cm",
+            Self::Mm => "mm",
+        }
+    }
+
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|unit| unit.get_suffix().eq_ignore_ascii_case(suffix))
+    }
+
+    pub fn get_pixels_per_unit(self, dpi: f32) -> f32 {
+        match self {
+            Self::Px => 1.0,
+            Self::Pt => dpi / 72.0,
+            Self::In => dpi,
+            Self::Cm => dpi / 2.54,
+            Self::Mm => dpi / 25.4,
+        }
+    }
+}
+
+// How lengths are shown, along with how many pixels make up an inch for converting between units
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LengthFormat {
+    pub unit: Unit,
+    pub dpi: f32,
+}
+
+impl Default for LengthFormat {
+    fn default() -> Self {
+        Self { unit: Unit::Px, dpi: 96.0 }
+    }
+}
+
+impl LengthFormat {
+    pub fn to_pixels(&self, value: f32, unit: Unit) -> f32 {
+        value * unit.get_pixels_per_unit(self.dpi)
+    }
+
+    pub fn from_pixels(&self, pixels: f32) -> f32 {
+        pixels / self.unit.get_pixels_per_unit(self.dpi)
+    }
+
+    // Converts a value typed in some unit to the unit lengths are shown in
+    pub fn convert(&self, value: f32, unit: Unit) -> f32 {
+        self.from_pixels(self.to_pixels(value, unit))
+    }
+}
+
+// Splits the unit off the end of text like '10cm', if it has one
+pub fn split_unit(text: &str) -> (&str, Option<Unit>) {
+    let trimmed = text.trim_end();
+    let start = trimmed.trim_end_matches(|c: char| c.is_ascii_alphabetic()).len();
+
+    match Unit::from_suffix(&trimmed[start..]) {
+        Some(unit) => (&trimmed[..start], Some(unit)),
+        None => (text, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_unit, LengthFormat, Unit};
+
+    #[test]
+    fn convert_units() {
+        assert_eq!(split_unit("10cm"), ("10", Some(Unit::Cm)));
+        assert_eq!(split_unit("2 IN "), ("2 ", Some(Unit::In)));
+        assert_eq!(split_unit("1920/2"), ("1920/2", None));
+        assert_eq!(split_unit("3km"), ("3km", None));
+
+        let format = LengthFormat { unit: Unit::Cm, dpi: 96.0 };
+        assert_eq!(format.to_pixels(2.0, Unit::In), 192.0);
+        assert_eq!(format.convert(1.0, Unit::In), 2.54);
+        assert_eq!(format.from_pixels(96.0), 2.54);
+        assert_eq!(LengthFormat::default().convert(72.0, Unit::Pt), 96.0);
+    }
+}
@@ -0,0 +1,8 @@
+#version 140
+
+in vec4 vertex_color;
+out vec4 out_color;
+
+void main() {
+    out_color = vertex_color;
+}
@@ -0,0 +1,13 @@
+#version 140
+
+in vec2 position;
+in vec4 color;
+out vec4 vertex_color;
+
+uniform mat3 view;
+uniform mat4 projection;
+
+void main() {
+    vertex_color = color;
+    gl_Position = projection * vec4((view * vec3(position, 1.0)).xy, 0.0, 1.0);
+}
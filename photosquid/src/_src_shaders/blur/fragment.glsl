@@ -0,0 +1,26 @@
+#version 140
+
+uniform sampler2D texture_sampler;
+
+// Distance between neighboring pixels in the direction being blurred
+uniform vec2 pixel_step;
+
+// How many pixels away the blur reaches, which covers three standard deviations
+uniform float radius;
+
+in vec2 pass_uvs;
+out vec4 out_color;
+
+void main() {
+    float sigma = max(radius / 3.0, 0.001);
+    vec4 total = texture(texture_sampler, pass_uvs);
+    float total_weight = 1.0;
+
+    for (int i = 1; i <= int(ceil(radius)); i++) {
+        float weight = exp(-float(i * i) / (2.0 * sigma * sigma));
+        total += weight * (texture(texture_sampler, pass_uvs + float(i) * pixel_step) + texture(texture_sampler, pass_uvs - float(i) * pixel_step));
+        total_weight += 2.0 * weight;
+    }
+
+    out_color = total / total_weight;
+}
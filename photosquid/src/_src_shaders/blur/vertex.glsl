@@ -0,0 +1,14 @@
+#version 140
+
+in vec2 position;
+in vec2 uvs;
+out vec2 pass_uvs;
+
+uniform mat3 transformation;
+uniform mat3 view;
+uniform mat4 projection;
+
+void main() {
+    pass_uvs = uvs;
+    gl_Position = projection * vec4((view * transformation * vec3(position, 1.0)).xy, 0.0, 1.0);
+}
@@ -2,10 +2,10 @@
 
 in vec2 position;
 
-uniform mat4 transformation;
-uniform mat4 view;
+uniform mat3 transformation;
+uniform mat3 view;
 uniform mat4 projection;
 
 void main() {
-    gl_Position = projection * view * transformation * vec4(position, 0.0, 1.0);
+    gl_Position = projection * vec4((view * transformation * vec3(position, 1.0)).xy, 0.0, 1.0);
 }
@@ -0,0 +1,49 @@
+#version 140
+
+uniform vec2 dimensions;  // Width and height of color picker box in logical pixels
+uniform vec2 point;       // Selection point in UV coords
+uniform vec3 color;       // RGB color without alpha
+
+in vec2 pass_uvs;
+out vec4 out_color;
+
+// Custom function for strength of fragment near selection point
+// where 'x' is [0..1] representing the percentage of the total radius
+float f(float x) {
+    const float bumpers = 0.2;
+    
+    if (x < bumpers) {
+        return smoothstep(0.0, 1.0, x * (1.0 / bumpers));
+    } else if (x > 1.0 - bumpers) {
+        return 1.0 - smoothstep(0.0, 1.0, (x - (1.0 - bumpers)) * (1.0 / bumpers));
+    } else {
+        return 1.0;
+    }
+}
+
+void main() {
+    vec2 uv = vec2(pass_uvs.x, 1.0 - pass_uvs.y);
+
+    // Checkerboard behind the color to show transparency
+    vec2 cell = floor(uv * dimensions / 6.0);
+    vec3 checker = mod(cell.x + cell.y, 2.0) < 1.0 ? vec3(0.4) : vec3(0.6);
+    vec3 col = mix(checker, color, uv.x);
+
+    vec2 point = vec2(point.x, 1.0 - point.y);
+    float x_diff = (uv.x - point.x) * dimensions.x;
+    float y_diff = (uv.y - point.y) * dimensions.y;
+    
+    float size = 0.5;
+    float d2 = x_diff * x_diff + y_diff * y_diff;
+    
+    if (d2 < 14.0 * 14.0 * size * size && d2 > 6.0 * 6.0 * size * size) {
+        // [0.0, 1.0]
+        float x = (d2 - 36.0 * size * size) / (196.0 * size * size - 36.0 * size * size);
+        x = smoothstep(0.0, 1.0, x);
+        
+        float v = f(x);
+        col = vec3(v) + (1.0 - v) * col;
+    }
+    
+    out_color = vec4(col, 1.0);
+}
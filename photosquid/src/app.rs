@@ -1,25 +1,56 @@
 use crate::{
+    aabb::AABB,
+    affine::Affine2,
+    arrange::{self, Axis},
+    breadcrumb::{Breadcrumb, BreadcrumbTarget},
     camera::Camera,
     capture::Capture,
+    color::Color,
     color_scheme::ColorScheme,
-    context_menu::ContextMenu,
+    command::DocumentCommand,
+    command_palette::{Command, CommandPalette, CommandPaletteResponse},
+    context_menu::{ContextAction, ContextMenu, ContextMenuResponse},
     ctrl_or_cmd::CtrlOrCmd,
+    damage::Damage,
     data::RectData,
-    dialog::{ask_open, ask_save, Filter},
+    dialog::{ask_folder, ask_open, ask_save, ask_save_one_of, Filter},
+    document::{get_document_name, Document},
+    document_tabs::{DocumentTabs, DocumentTabsTarget},
     dragging::Dragging,
-    export::export,
+    export::{export, get_profile_outputs, ExportBounds, ExportFormat, ExportOptions, ExportProfile, ExportSize, RasterExport},
+    frame_pacer::FramePacer,
     history::History,
+    inertia::Inertia,
     interaction::{Interaction, KeyInteraction},
     interaction_options::InteractionOptions,
+    keymap::{Action, Keymap},
+    macro_recorder::MacroRecorder,
     mesh::{MeshXyz, MeshXyzUv},
-    ocean::Ocean,
+    mesh_cache::MeshCache,
+    modal::{Modal, ModalResponse},
+    ocean::{Ocean, Similarity},
     operation::Operation,
+    options::color_picker::ColorTarget,
+    palette,
+    panel_cache::PanelCache,
+    plugin::PluginRegistry,
+    preferences::Preferences,
+    quick_find::{QuickFind, QuickFindResponse},
+    radial_menu::RadialMenu,
+    raster_color::RasterColor,
+    script,
+    script_console::{ScriptConsole, ScriptConsoleResponse},
     selection::{selection_contains, Selection},
     shaders::Shaders,
     smooth::Smooth,
-    squid::{Initiation, Squid, SquidRef},
-    tool::{Tool, ToolKey},
+    snapping,
+    squid::{self, Appearance, Initiation, Squid, SquidRef},
+    status_bar::StatusBar,
+    stress_test,
+    style::ColorStyle,
+    tool::{KnifeLine, PenPath, PencilStroke, ShapeDrag, Tool, ToolKey, ToolKind, ZoomBox},
     toolbox::ToolBox,
+    units::LengthFormat,
 };
 use angular_units::Rad;
 use glium::{
@@ -30,19 +61,29 @@ use glium::{
     },
     Display,
 };
-use glium_text_rusttype::{FontTexture, TextSystem};
+use glium_text_rusttype::{FontTexture, TextBatch, TextSystem};
 use nalgebra_glm as glm;
-use native_dialog::{MessageDialog, MessageType};
 use slotmap::SlotMap;
 use std::{
     collections::{btree_set::BTreeSet, HashSet},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
-pub const MULTISAMPLING_COUNT: u16 = 4;
+// How far the view turns for each press of Ctrl+[ or Ctrl+]
+const ROTATE_VIEW_STEP: f32 = std::f32::consts::PI / 12.0;
+
+const KEYMAP_FILTER: Filter = Filter {
+    description: "Keymap",
+    extensions: &["json"],
+};
+
+const PALETTE_FILTER: Filter = Filter {
+    description: "Color Palette",
+    extensions: &["gpl", "ase"],
+};
 
 pub struct App {
     pub display: Display,
@@ -55,13 +96,18 @@ pub struct App {
     pub shaders: Shaders,
     pub mouse_position: Option<LogicalPosition<f32>>,
     pub scale_factor: f64,
+
+    // Multisampling the window was created with, which can't change without recreating it
+    pub window_msaa: u16,
+
     pub ocean: Ocean,
-    pub history: History,
+    pub history: History<Ocean>,
     pub dimensions: glm::Vec2,
     pub projection: Option<glm::Mat4>,
-    pub view: Option<glm::Mat4>,
-    pub frame_start_time: Instant,
+    pub view: Option<Affine2>,
+    pub frame_pacer: FramePacer,
     pub camera: Smooth<Camera>,
+    pub pan_inertia: Inertia,
     pub dragging: Option<Dragging>,
     pub selections: Vec<Selection>,
     pub keys_held: BTreeSet<VirtualKeyCode>,
@@ -70,11 +116,44 @@ pub struct App {
     pub text_system: TextSystem,
     pub font: Rc<FontTexture>,
     pub context_menu: Option<ContextMenu>,
+    pub radial_menu: Option<RadialMenu>,
+    pub quick_find: Option<QuickFind>,
+    pub command_palette: Option<CommandPalette>,
+    pub script_console: Option<ScriptConsole>,
+    pub pen_path: Option<PenPath>,
+    pub pencil_stroke: Option<PencilStroke>,
+    pub zoom_box: Option<ZoomBox>,
+    pub knife_line: Option<KnifeLine>,
+    pub editing_point: Option<(SquidRef, usize)>,
+    pub shape_drag: Option<ShapeDrag>,
+    pub modals: Vec<Modal>,
+    pub breadcrumb: Breadcrumb,
+    pub document_tabs: DocumentTabs,
+    pub status_bar: StatusBar,
     pub interaction_options: InteractionOptions,
+    pub keymap: Keymap,
+    pub preferences: Preferences,
+    pub plugins: PluginRegistry,
+    pub export_options: ExportOptions,
+    pub pending_raster_exports: Vec<RasterExport>,
     pub wait_for_stop_drag: bool,
     pub operation: Option<Operation>,
     pub perform_next_operation_collectively: bool,
+
+    // Whether to show what each selected squid is, where it is and how big it is next to it
+    pub show_object_info: bool,
     pub filename: Option<PathBuf>,
+    pub documents: Vec<Document>,
+    pub active_document: usize,
+    pub clipboard: Vec<Squid>,
+    pub style_clipboard: Option<Appearance>,
+    pub commands: Vec<DocumentCommand>,
+    pub macro_recorder: MacroRecorder,
+    pub last_autosave: Instant,
+    pub panel_cache: PanelCache,
+    pub mesh_cache: MeshCache,
+    pub text_batch: Option<TextBatch<Rc<FontTexture>>>,
+    pub damage: Damage,
 }
 
 impl App {
@@ -94,7 +173,7 @@ impl App {
         let highest_squids: Vec<SquidRef> = self.ocean.get_squids_unordered().collect();
 
         for reference in highest_squids {
-            if selection_contains(&self.selections, reference) {
+            if selection_contains(&self.selections, reference) && self.ocean.is_interactable(reference) {
                 if let Some(squid) = self.ocean.get_mut(reference) {
                     squid.interact(interaction, &self.camera.get_animated(), &self.interaction_options)?;
                 }
@@ -104,7 +183,8 @@ impl App {
         Capture::Miss
     }
 
-    pub fn scroll(&mut self, delta: &glm::Vec2) {
+    // Zooms in or out around the mouse
+    pub fn scroll_zoom(&mut self, delta: &glm::Vec2) {
         let range = 1000.0;
 
         let zoom = match delta.y {
@@ -114,8 +194,13 @@ impl App {
         };
 
         let mouse_position = self.mouse_position.unwrap();
-        let mouse_position = glm::vec2(mouse_position.x, mouse_position.y);
-        let ratios = mouse_position.component_div(&self.camera.get_real().window);
+        self.zoom_at(zoom, &glm::vec2(mouse_position.x, mouse_position.y));
+    }
+
+    // Zooms while keeping what's under a point on the screen in place
+    pub fn zoom_at(&mut self, zoom: f32, point_on_screen: &glm::Vec2) {
+        let point_on_screen = self.camera.get_real().unrotate(point_on_screen);
+        let ratios = point_on_screen.component_div(&self.camera.get_real().window);
         let view = self.camera.get_real().view();
         let view_size = view.1 - view.0;
         let center = view.0 + ratios.component_mul(&view_size);
@@ -124,36 +209,62 @@ impl App {
         self.camera.zoom_point(zoom, &center);
     }
 
-    pub fn press_key(&mut self, key: VirtualKeyCode, tools: &mut SlotMap<ToolKey, Tool>) {
+    // Moves the camera so that the canvas follows the scroll
+    pub fn scroll_pan(&mut self, delta: &glm::Vec2) {
+        let camera = self.camera.get_real();
+        let location = camera.position - camera.apply_reverse_to_vector(delta);
+
         use crate::camera::EasySmoothCamera;
+        self.camera.set_location(location);
+    }
 
-        if self.modifiers_held.ctrl_or_cmd() {
-            let shift = self.modifiers_held.shift();
+    pub fn press_key(&mut self, key: VirtualKeyCode, tools: &mut SlotMap<ToolKey, Tool>) {
+        // Dialogs take all keyboard input while open
+        if let Some(modal) = self.modals.first_mut() {
+            let response = modal.press_key(key);
+            self.respond_to_modal(response);
+            return;
+        }
 
-            match key {
-                VirtualKeyCode::Z => {
-                    if shift {
-                        self.redo();
-                    } else {
-                        self.undo();
-                    }
-                    return;
-                }
-                VirtualKeyCode::Equals => {
-                    self.camera.increase_zoom();
-                    return;
-                }
-                VirtualKeyCode::Minus => {
-                    self.camera.decrease_zoom();
-                    return;
-                }
-                VirtualKeyCode::O => {
-                    self.load();
-                    return;
-                }
-                VirtualKeyCode::S => self.save(if shift { SaveMethod::SaveAs } else { SaveMethod::Save }),
-                _ => (),
-            }
+        // Quick find overlay takes all keyboard input while open
+        if let Some(quick_find) = &mut self.quick_find {
+            let response = quick_find.press_key(key, &self.ocean);
+            self.respond_to_quick_find(response);
+            return;
+        }
+
+        // Command palette takes all keyboard input while open
+        if let Some(command_palette) = &mut self.command_palette {
+            let response = command_palette.press_key(key);
+            self.respond_to_command_palette(response, tools);
+            return;
+        }
+
+        // Script console takes all keyboard input while open
+        if let Some(script_console) = &mut self.script_console {
+            let response = script_console.press_key(key);
+            self.respond_to_script_console(response);
+            return;
+        }
+
+        // Context menu takes all keyboard input while open
+        if let Some(context_menu) = &mut self.context_menu {
+            let response = context_menu.press_key(key);
+            self.respond_to_context_menu(response, tools);
+            return;
+        }
+
+        let action = self.keymap.get_action(key, self.modifiers_held);
+
+        // Shortcuts using ctrl work even while the tool is using the keyboard, such as when typing into an option
+        if let Some(action) = action.filter(|action| self.keymap.get_binding(*action).is_some_and(|binding| binding.ctrl)) {
+            self.perform_action(action, tools);
+            return;
+        }
+
+        if key == VirtualKeyCode::G && self.modifiers_held.ctrl_or_cmd() && self.modifiers_held.shift() && cfg!(debug_assertions) {
+            self.generate_stress_test();
+            return;
         }
 
         if let Some(tool_key) = self.toolbox.get_selected() {
@@ -168,25 +279,182 @@ impl App {
             }
         }
 
-        match key {
-            VirtualKeyCode::Key1 => self.toolbox.select_tool(1),
-            VirtualKeyCode::Key2 => self.toolbox.select_tool(2),
-            VirtualKeyCode::Key3 => self.toolbox.select_tool(3),
-            VirtualKeyCode::Key4 => self.toolbox.select_tool(4),
-            VirtualKeyCode::Key5 => self.toolbox.select_tool(5),
-            VirtualKeyCode::Key6 => self.toolbox.select_tool(6),
-            VirtualKeyCode::Key7 => self.toolbox.select_tool(7),
-            VirtualKeyCode::Key8 => self.toolbox.select_tool(8),
-            VirtualKeyCode::Key9 => self.toolbox.select_tool(9),
-            VirtualKeyCode::Key0 => self.toolbox.select_tool(0),
-            VirtualKeyCode::X => self.delete_selected(),
-            VirtualKeyCode::Escape => self.context_menu = None,
-            VirtualKeyCode::D => {
-                if self.keys_held.contains(&VirtualKeyCode::LShift) {
-                    self.duplicate_selected();
+        if let Some(action) = action {
+            self.perform_action(action, tools);
+        }
+    }
+
+    // Performs what a keyboard shortcut is bound to
+    pub fn perform_action(&mut self, action: Action, tools: &SlotMap<ToolKey, Tool>) {
+        use crate::camera::EasySmoothCamera;
+
+        if let Some(index) = action.get_tool_index() {
+            self.toolbox.select_tool(index);
+            return;
+        }
+
+        match action {
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::NewDocument => self.new_document(),
+            Action::Open => self.load(),
+            Action::Save => self.save(SaveMethod::Save),
+            Action::SaveAs => self.save(SaveMethod::SaveAs),
+            Action::CloseDocument => self.request_close_document(self.active_document),
+            Action::NextDocument => self.switch_adjacent_document(false),
+            Action::PreviousDocument => self.switch_adjacent_document(true),
+            Action::Export => self.export(),
+            Action::QuickFind => self.quick_find = Some(QuickFind::new(&self.ocean)),
+            Action::CommandPalette => self.open_command_palette(tools),
+            Action::ScriptConsole => self.script_console = Some(ScriptConsole::new()),
+            Action::RecordMacro => self.macro_recorder.toggle(),
+            Action::PlayMacro => self.play_macro(),
+            Action::KeyboardShortcuts => self.show_keymap(),
+            Action::ToggleSnapping => self.interaction_options.snapping = !self.interaction_options.snapping,
+            Action::ToggleObjectInfo => self.show_object_info = !self.show_object_info,
+            Action::ZoomIn => self.camera.increase_zoom(),
+            Action::ZoomOut => self.camera.decrease_zoom(),
+            Action::RotateViewLeft => self.camera.rotate(-ROTATE_VIEW_STEP),
+            Action::RotateViewRight => self.camera.rotate(ROTATE_VIEW_STEP),
+            Action::ResetViewRotation => {
+                let camera = self.camera.get_real();
+                self.camera.set(camera.with_rotation(0.0));
+            }
+            Action::Copy => self.copy_selected(),
+            Action::Paste => self.emit(DocumentCommand::Paste),
+            Action::CopyStyle => self.copy_selected_style(),
+            Action::PasteStyle => self.emit(DocumentCommand::PasteStyle),
+            Action::Delete => self.emit(DocumentCommand::DeleteSelected),
+            Action::Duplicate => self.emit(DocumentCommand::DuplicateSelected),
+            Action::SelectAll => self.emit(DocumentCommand::SelectAll),
+            Action::DeselectAll => self.emit(DocumentCommand::ClearSelection),
+            Action::InvertSelection => self.emit(DocumentCommand::InvertSelection),
+            Action::SelectSameFillColor => self.emit(DocumentCommand::SelectSimilar(Similarity::FillColor)),
+            Action::SelectSameKind => self.emit(DocumentCommand::SelectSimilar(Similarity::Kind)),
+            Action::SelectSameLayer => self.emit(DocumentCommand::SelectSimilar(Similarity::Layer)),
+            Action::SelectByTag => self.ask_select_by_tag(),
+            Action::EditTags => self.ask_edit_tags(),
+            Action::RenameSelected => self.ask_rename_selected(),
+            Action::BringToFront => self.emit(DocumentCommand::ReorderSelected(0)),
+            Action::BringForward => self.emit(DocumentCommand::ShiftSelectedWithinLayer(-1)),
+            Action::SendBackward => self.emit(DocumentCommand::ShiftSelectedWithinLayer(1)),
+            Action::SendToBack => self.emit(DocumentCommand::ReorderSelected(usize::MAX)),
+            Action::EqualHorizontalGaps => self.emit(DocumentCommand::EqualizeSelectedGaps(Axis::Horizontal)),
+            Action::EqualVerticalGaps => self.emit(DocumentCommand::EqualizeSelectedGaps(Axis::Vertical)),
+            Action::MatchWidth => self.emit(DocumentCommand::MatchSelectedSize(Axis::Horizontal)),
+            Action::MatchHeight => self.emit(DocumentCommand::MatchSelectedSize(Axis::Vertical)),
+            Action::ConvertToPath => self.emit(DocumentCommand::ConvertSelectedToPath),
+            // Escape on the canvas lets go of the selection once there's no menu left to close
+            Action::DismissMenu if self.radial_menu.is_some() => self.radial_menu = None,
+            Action::DismissMenu => self.emit(DocumentCommand::ClearSelection),
+            Action::PreviousTool => self.toolbox.select_adjacent_tool(true),
+            Action::NextTool => self.toolbox.select_adjacent_tool(false),
+            Action::PreviousTab => self.toolbox.select_adjacent_tab(true),
+            Action::NextTab => self.toolbox.select_adjacent_tab(false),
+
+            // Only done by the pointer tool
+            Action::Grab | Action::Rotate | Action::Scale | Action::Collectively => (),
+
+            // Selecting tools is handled above
+            Action::MainMenu
+            | Action::Tool1
+            | Action::Tool2
+            | Action::Tool3
+            | Action::Tool4
+            | Action::Tool5
+            | Action::Tool6
+            | Action::Tool7
+            | Action::Tool8
+            | Action::Tool9 => (),
+        }
+    }
+
+    // Lists every command that makes sense without the mouse, with tools listed by name
+    pub fn open_command_palette(&mut self, tools: &SlotMap<ToolKey, Tool>) {
+        let commands = Action::ALL
+            .iter()
+            .filter(|action| !action.needs_mouse() && **action != Action::CommandPalette)
+            .filter_map(|action| {
+                let name = match action.get_tool_index() {
+                    Some(index) => tools.get(self.toolbox.get_tool_at(index)?)?.get_name().to_string(),
+                    None => action.get_name().to_string(),
+                };
+
+                Some(Command {
+                    action: *action,
+                    name,
+                    shortcut: self.keymap.get_binding(*action).map_or_else(String::new, |binding| binding.to_string()),
+                })
+            })
+            .collect();
+
+        self.command_palette = Some(CommandPalette::new(commands));
+    }
+
+    pub fn respond_to_command_palette(&mut self, response: CommandPaletteResponse, tools: &SlotMap<ToolKey, Tool>) {
+        match response {
+            CommandPaletteResponse::Continue => (),
+            CommandPaletteResponse::Close => self.command_palette = None,
+            CommandPaletteResponse::Choose(action) => {
+                self.command_palette = None;
+                self.perform_action(action, tools);
+            }
+        }
+    }
+
+    pub fn respond_to_script_console(&mut self, response: ScriptConsoleResponse) {
+        match response {
+            ScriptConsoleResponse::Continue => (),
+            ScriptConsoleResponse::Close => self.script_console = None,
+            ScriptConsoleResponse::Run(source) => {
+                let result = script::run(&source, self);
+
+                if let Some(script_console) = &mut self.script_console {
+                    match result {
+                        Ok(output) => output.into_iter().for_each(|line| script_console.print(line, false)),
+                        Err(message) => script_console.print(message, true),
+                    }
                 }
             }
-            _ => (),
+        }
+    }
+
+    // Whether an overlay that takes all keyboard input is open
+    pub fn has_overlay(&self) -> bool {
+        self.quick_find.is_some() || self.command_palette.is_some() || self.script_console.is_some() || !self.modals.is_empty()
+    }
+
+    pub fn show_keymap(&mut self) {
+        self.show_modal(Modal::message("Keyboard Shortcuts", &self.keymap.describe()));
+    }
+
+    pub fn load_keymap(&mut self) {
+        let filename = match ask_open(Some(KEYMAP_FILTER)) {
+            Ok(Some(filename)) => filename,
+            _ => return,
+        };
+
+        match Keymap::load(&filename) {
+            Ok(keymap) => {
+                self.keymap = keymap;
+
+                if !self.keymap.get_conflicts().is_empty() {
+                    self.show_keymap();
+                }
+            }
+            Err(message) => self.show_error("Failed to load keymap", &message),
+        }
+    }
+
+    // Writes out the current keymap, as a starting point for customizing it
+    pub fn save_keymap(&mut self) {
+        let filename = match ask_save(Some(KEYMAP_FILTER)) {
+            Ok(Some(filename)) => filename,
+            _ => return,
+        };
+
+        if let Err(message) = self.keymap.save(&filename) {
+            self.show_error("Failed to save keymap", &message);
         }
     }
 
@@ -203,16 +471,27 @@ impl App {
             Capture::TakeFocus => (),
             Capture::Keyboard(..) => (),
             Capture::MoveSelectedSquids { delta_in_world } => {
-                for squid_id in self.get_selected_squids() {
-                    if let Some(squid) = self.ocean.get_mut(squid_id) {
+                let selected = self.get_selected_squids();
+                let previous_snap = self
+                    .dragging
+                    .as_mut()
+                    .map_or(glm::zero(), |dragging| std::mem::replace(&mut dragging.object_snap, glm::zero()));
+
+                for squid_id in &selected {
+                    if let Some(squid) = self.ocean.get_mut(*squid_id) {
+                        squid.reposition_by(-previous_snap);
                         squid.translate(&delta_in_world, &self.interaction_options);
                     }
                 }
+
+                if self.interaction_options.snaps_to_objects() && self.dragging.is_some() {
+                    self.snap_to_objects(&selected);
+                }
             }
-            Capture::RotateSelectedSquids { delta_theta } => {
+            Capture::RotateSelectedSquids { delta_theta, shift } => {
                 for squid_id in self.get_selected_squids() {
                     if let Some(squid) = self.ocean.get_mut(squid_id) {
-                        squid.rotate(delta_theta, &self.interaction_options);
+                        squid.rotate(delta_theta, shift, &self.interaction_options);
                     }
                 }
             }
@@ -230,10 +509,10 @@ impl App {
                     }
                 }
             }
-            Capture::RevolveSelectedSquids { current } => {
+            Capture::RevolveSelectedSquids { current, shift } => {
                 for squid_id in self.get_selected_squids() {
                     if let Some(squid) = self.ocean.get_mut(squid_id) {
-                        squid.revolve(&current, &self.interaction_options);
+                        squid.revolve(&current, shift, &self.interaction_options);
                     }
                 }
             }
@@ -247,6 +526,43 @@ impl App {
         }
     }
 
+    // Nudges squids being dragged so their edges or centers line up with those of other squids nearby
+    fn snap_to_objects(&mut self, moving: &[SquidRef]) {
+        let get_bounds = |reference: SquidRef| AABB::from_points(&self.ocean.get(reference)?.get_extreme_points());
+
+        let moving_bounds = moving.iter().filter_map(|reference| get_bounds(*reference)).reduce(|a, b| AABB {
+            min_x: a.min_x.min(b.min_x),
+            min_y: a.min_y.min(b.min_y),
+            max_x: a.max_x.max(b.max_x),
+            max_y: a.max_y.max(b.max_y),
+        });
+
+        let moving_bounds = match moving_bounds {
+            Some(bounds) => bounds,
+            None => return,
+        };
+
+        let others: Vec<AABB> = self
+            .ocean
+            .get_interactable_squids_highest()
+            .filter(|reference| !moving.contains(reference))
+            .filter_map(get_bounds)
+            .collect();
+
+        let reach = self.camera.get_real().apply_reverse_to_scale(snapping::OBJECT_SNAP_DISTANCE);
+        let snap = snapping::get_object_snap(&moving_bounds, &others, reach);
+
+        for reference in moving {
+            if let Some(squid) = self.ocean.get_mut(*reference) {
+                squid.reposition_by(snap);
+            }
+        }
+
+        if let Some(dragging) = &mut self.dragging {
+            dragging.object_snap = snap;
+        }
+    }
+
     pub fn clear_selection(&mut self) {
         self.selections.clear();
     }
@@ -317,6 +633,140 @@ impl App {
         }
     }
 
+    pub fn type_character(&mut self, character: char) {
+        if let Some(modal) = self.modals.first_mut() {
+            modal.type_character(character);
+        } else if let Some(quick_find) = &mut self.quick_find {
+            quick_find.type_character(character, &self.ocean);
+        } else if let Some(command_palette) = &mut self.command_palette {
+            command_palette.type_character(character);
+        } else if let Some(script_console) = &mut self.script_console {
+            script_console.type_character(character);
+        }
+    }
+
+    pub fn respond_to_quick_find(&mut self, response: QuickFindResponse) {
+        match response {
+            QuickFindResponse::Continue => (),
+            QuickFindResponse::Close => self.quick_find = None,
+            QuickFindResponse::Choose(reference) => {
+                self.quick_find = None;
+                self.jump_to(reference);
+            }
+        }
+    }
+
+    pub fn respond_to_context_menu(&mut self, response: ContextMenuResponse, tools: &SlotMap<ToolKey, Tool>) {
+        match response {
+            ContextMenuResponse::Continue => (),
+            ContextMenuResponse::Close => self.context_menu = None,
+            ContextMenuResponse::Choose(action) => {
+                self.context_menu = None;
+                self.perform_context_action(action, tools);
+            }
+        }
+    }
+
+    // Selects a squid and moves the camera to frame it
+    pub fn jump_to(&mut self, reference: SquidRef) {
+        if let Some(squid) = self.ocean.get(reference) {
+            let camera = self.camera.get_real().framing(&squid.get_center(), squid.get_bounding_radius());
+            self.camera.set(camera);
+
+            self.selections = vec![Selection {
+                squid_id: reference,
+                limb_id: None,
+            }];
+        }
+    }
+
+    pub fn select_breadcrumb_target(&mut self, target: BreadcrumbTarget) {
+        let squids: Vec<SquidRef> = match target {
+            BreadcrumbTarget::Document => self.ocean.get_interactable_squids_highest().collect(),
+            BreadcrumbTarget::Layer(index) => match self.ocean.get_layers().get(index) {
+                Some(layer) if layer.is_interactable() => layer.get_highest().collect(),
+                _ => vec![],
+            },
+            BreadcrumbTarget::Squid(reference) => vec![reference],
+            BreadcrumbTarget::Selection => return,
+        };
+
+        self.selections = squids.into_iter().map(|reference| Selection::new(reference, None)).collect();
+    }
+
+    pub fn set_selected_color(&mut self, color: Color) {
+        let mut applied = false;
+
+        for selection in self.selections.iter().filter(|selection| selection.limb_id.is_none()) {
+            if let Some(squid) = self.ocean.get_mut(selection.squid_id) {
+                squid.set_color(color);
+                applied = true;
+
+                // Recoloring a squid that uses a style recolors the style for everyone
+                if let Some(style) = squid.get_style() {
+                    self.ocean.set_style_color(style, color);
+                }
+            }
+        }
+
+        if applied {
+            self.toolbox.color_picker.remember_color(color);
+        }
+    }
+
+    // Whether the color picker can keep editing something, a style is only edited while something using it is selected
+    pub fn is_color_target_selected(&self, target: ColorTarget) -> bool {
+        match target {
+            ColorTarget::Selection => true,
+            ColorTarget::Style(style) => self
+                .selections
+                .iter()
+                .any(|selection| self.ocean.get(selection.squid_id).is_some_and(|squid| squid.get_style() == Some(style))),
+        }
+    }
+
+    // Raises (negative) or lowers (positive) selected squids within their layers
+    pub fn shift_selected_within_layer(&mut self, offset: isize) {
+        for selection in &self.selections {
+            self.ocean.shift_within_layer(selection.squid_id, offset);
+        }
+    }
+
+    // Moves selected squids to a position within their layers, where 0 is the highest
+    pub fn reorder_selected(&mut self, index: usize) {
+        for selection in &self.selections {
+            self.ocean.reorder(selection.squid_id, index);
+        }
+    }
+
+    pub fn perform_context_action(&mut self, action: ContextAction, tools: &SlotMap<ToolKey, Tool>) {
+        use ContextAction::*;
+
+        match action {
+            DeleteSelected => self.emit(DocumentCommand::DeleteSelected),
+            DuplicateSelected => self.emit(DocumentCommand::DuplicateSelected),
+            GrabSelected => self.grab_selected(),
+            RotateSelected => self.rotate_selected(),
+            ScaleSelected => self.scale_selected(),
+            Collectively => self.toggle_next_operation_collectively(),
+            Undo => self.undo(),
+            Redo => self.redo(),
+            EditPoints => self.toolbox.select_tool_of_kind(tools, ToolKind::EditPoints),
+            SetAsViewport => self.emit(DocumentCommand::SetSelectedAsViewport),
+            ConvertToPath => self.emit(DocumentCommand::ConvertSelectedToPath),
+            SelectSimilar(similarity) => self.emit(DocumentCommand::SelectSimilar(similarity)),
+            SelectByTag => self.ask_select_by_tag(),
+            EditTags => self.ask_edit_tags(),
+            RenameSelected => self.ask_rename_selected(),
+            BringToFront => self.emit(DocumentCommand::ReorderSelected(0)),
+            BringForward => self.emit(DocumentCommand::ShiftSelectedWithinLayer(-1)),
+            SendBackward => self.emit(DocumentCommand::ShiftSelectedWithinLayer(1)),
+            SendToBack => self.emit(DocumentCommand::ReorderSelected(usize::MAX)),
+            EqualGaps(axis) => self.emit(DocumentCommand::EqualizeSelectedGaps(axis)),
+            MatchSize(axis) => self.emit(DocumentCommand::MatchSelectedSize(axis)),
+        }
+    }
+
     pub fn toggle_next_operation_collectively(&mut self) {
         self.perform_next_operation_collectively = !self.perform_next_operation_collectively;
     }
@@ -335,9 +785,15 @@ impl App {
                 let position = self.get_mouse_in_world_space();
 
                 if let Some(rotate_point) = self.get_closest_selection_center(&position) {
+                    // Measured on screen, the same as the angles while dragging
                     let point = self.camera.get_animated().apply(&rotate_point);
-                    let rotation = Rad((rotate_point.y - position.y).atan2(position.x - rotate_point.x)) - Rad::pi_over_2();
-                    self.operation = Some(Operation::Rotate { point, rotation });
+                    let mouse = self.mouse_position.unwrap_or_default();
+                    let rotation = Rad((point.y - mouse.y).atan2(mouse.x - point.x)) - Rad::pi_over_2();
+                    self.operation = Some(Operation::Rotate {
+                        point,
+                        rotation,
+                        total: Rad(0.0),
+                    });
                 }
             }
             Initiation::Scale => {
@@ -351,7 +807,11 @@ impl App {
                 self.operation = Some(Operation::Spread { point, origin: center });
             }
             Initiation::Revolve { point, center } => {
-                self.operation = Some(Operation::Revolve { point, origin: center });
+                self.operation = Some(Operation::Revolve {
+                    point,
+                    origin: center,
+                    total: Rad(0.0),
+                });
             }
             Initiation::Dilate { point, center } => {
                 self.operation = Some(Operation::Dilate { point, origin: center });
@@ -400,31 +860,232 @@ impl App {
         Some(average / selected_squids.len() as f32)
     }
 
+    // Queues a change to the document, which is applied along with the rest of the frame's changes
+    pub fn emit(&mut self, command: DocumentCommand) {
+        self.commands.push(command);
+    }
+
+    // Applies queued changes, recording a single history marker for all of them.
+    // Changes made while the mouse is held down, like drawing out a new shape,
+    // are recorded once it's let go instead
+    pub fn apply_commands(&mut self) {
+        if !self.commands.is_empty() {
+            self.damage.mark();
+        }
+
+        let mut records_history = false;
+
+        for command in std::mem::take(&mut self.commands) {
+            records_history |= command.records_history();
+            self.macro_recorder.record(&command);
+            self.apply(command);
+        }
+
+        if records_history && self.dragging.is_none() {
+            self.add_history_marker();
+        }
+    }
+
+    // Emits the recorded macro's commands again, which are applied to whatever is selected now
+    pub fn play_macro(&mut self) {
+        self.commands.extend_from_slice(self.macro_recorder.get_recorded());
+    }
+
+    // Changes every selected squid in the same way
+    fn change_selected(&mut self, mut change: impl FnMut(&mut Squid)) {
+        for squid_id in self.get_selected_squids() {
+            if let Some(squid) = self.ocean.get_mut(squid_id) {
+                change(squid);
+            }
+        }
+    }
+
+    fn apply(&mut self, command: DocumentCommand) {
+        match command {
+            DocumentCommand::Insert(squid) => {
+                self.insert(*squid);
+            }
+            DocumentCommand::InsertAndSelect(squid) => {
+                let reference = self.insert(*squid);
+                self.selections = vec![Selection::new(reference, None)];
+            }
+            DocumentCommand::Replace(id, replacements) => {
+                if let Some(reference) = self.ocean.find_by_id(id) {
+                    let pieces = self.ocean.replace(reference, replacements);
+                    self.prune_selection();
+                    self.selections.extend(pieces.into_iter().map(|piece| Selection::new(piece, None)));
+                }
+            }
+            DocumentCommand::FitToBox(..)
+            | DocumentCommand::MovePoint(..)
+            | DocumentCommand::InsertPoint(..)
+            | DocumentCommand::RemovePoint(..)
+            | DocumentCommand::SetViewportSize(..) => {
+                if let Some(squid) = command.get_target().and_then(|id| self.ocean.get_mut_by_id(id)) {
+                    command.apply_to_squid(squid);
+                }
+            }
+            DocumentCommand::SetDpi(dpi) => self.ocean.set_dpi(dpi),
+            DocumentCommand::SelectAll => {
+                self.selections = self
+                    .ocean
+                    .get_interactable_squids_highest()
+                    .map(|reference| Selection::new(reference, None))
+                    .collect();
+            }
+            DocumentCommand::InvertSelection => {
+                let selected = self.get_selected_squids();
+
+                self.selections = self
+                    .ocean
+                    .get_interactable_squids_highest()
+                    .filter(|reference| !selected.contains(reference))
+                    .map(|reference| Selection::new(reference, None))
+                    .collect();
+            }
+            DocumentCommand::SelectSimilar(similarity) => {
+                let similar = self.ocean.get_similar(&self.get_selected_squids(), similarity);
+                self.selections = similar.into_iter().map(|reference| Selection::new(reference, None)).collect();
+            }
+            DocumentCommand::SelectByTag(tag) => {
+                let tagged = self.ocean.get_tagged(&tag);
+                self.selections = tagged.into_iter().map(|reference| Selection::new(reference, None)).collect();
+            }
+            DocumentCommand::ClearSelection => self.clear_selection(),
+            DocumentCommand::DeleteSelected => self.delete_selected(),
+            DocumentCommand::DuplicateSelected => self.duplicate_selected(),
+            DocumentCommand::Paste => self.paste(),
+            DocumentCommand::PasteStyle => self.paste_style(),
+            DocumentCommand::SaveSelectedStyle => self.save_selected_style(),
+            DocumentCommand::UpdateSelectedStyle => self.update_selected_style(),
+            DocumentCommand::ReorderSelected(index) => self.reorder_selected(index),
+            DocumentCommand::ShiftSelectedWithinLayer(offset) => self.shift_selected_within_layer(offset),
+            DocumentCommand::EqualizeSelectedGaps(axis) => {
+                let selected = self.get_selected_squids();
+                arrange::equalize_gaps(&mut self.ocean, &selected, axis);
+            }
+            DocumentCommand::MatchSelectedSize(axis) => {
+                let selected = self.get_selected_squids();
+                arrange::match_size(&mut self.ocean, &selected, axis);
+            }
+            DocumentCommand::SetSelectedAsViewport => self.change_selected(Squid::set_as_viewport),
+            DocumentCommand::ConvertSelectedToPath => self.change_selected(|squid| {
+                squid.convert_to_path();
+            }),
+            DocumentCommand::SetSelectedColor(color) => self.set_selected_color(color),
+            DocumentCommand::RenameSelected(pattern) => {
+                for (i, squid_id) in self.get_selected_squids().into_iter().enumerate() {
+                    if let Some(squid) = self.ocean.get_mut_in_place(squid_id) {
+                        let name = squid::apply_name_pattern(&pattern, squid.get_name(), i + 1);
+                        squid.set_name(name);
+                    }
+                }
+            }
+            DocumentCommand::SetSelectedTags(tags) => {
+                for squid_id in self.get_selected_squids() {
+                    if let Some(squid) = self.ocean.get_mut_in_place(squid_id) {
+                        squid.set_tags(tags.clone());
+                    }
+                }
+            }
+            DocumentCommand::SetStyleColor(style, color) => {
+                self.ocean.set_style_color(style, color);
+                self.toolbox.color_picker.remember_color(color);
+            }
+            DocumentCommand::TranslateSelected(delta) => self.change_selected(|squid| squid.reposition_by(delta)),
+            DocumentCommand::RotateSelected(delta_theta) => self.change_selected(|squid| squid.rotate_by(delta_theta)),
+        }
+    }
+
     pub fn add_history_marker(&mut self) {
         self.history.push(self.ocean.clone());
     }
 
     pub fn undo(&mut self) {
         if let Some(previous) = self.history.undo() {
-            self.ocean = previous;
+            self.restore(previous);
         }
     }
 
     pub fn redo(&mut self) {
-        if let Some(previous) = self.history.redo() {
-            self.ocean = previous;
+        if let Some(next) = self.history.redo() {
+            self.restore(next);
         }
     }
 
+    // Squids keep their references across history, since cloning the ocean keeps the keys of its squids.
+    // So the selection stays the same, apart from squids that don't exist at that point in history
+    fn restore(&mut self, ocean: Ocean) {
+        self.ocean = ocean;
+
+        let ocean = &self.ocean;
+        self.selections.retain(|selection| ocean.is_interactable(selection.squid_id));
+    }
+
+    // Debug command that adds another thousand procedurally generated squids,
+    // repeat it to build up larger documents
+    pub fn generate_stress_test(&mut self) {
+        let start = Instant::now();
+        stress_test::generate(&mut self.ocean, 1000);
+        self.add_history_marker();
+
+        println!(
+            "generated stress test, now {} squids ({:?})",
+            self.ocean.get_squids_unordered().count(),
+            start.elapsed()
+        );
+    }
+
     pub fn insert(&mut self, value: Squid) -> SquidRef {
         self.prune_selection();
         self.ocean.insert(value)
     }
 
+    // Turns the path drawn with the pen tool into a squid
+    pub fn finish_pen_path(&mut self, closed: bool) {
+        let anchors = match self.pen_path.take() {
+            Some(pen_path) => pen_path.anchors,
+            None => return,
+        };
+
+        if anchors.len() >= if closed { 3 } else { 2 } {
+            let color = self.toolbox.color_picker.calculate_color();
+            self.emit(DocumentCommand::Insert(Box::new(Squid::path(anchors, closed, color))));
+        }
+    }
+
     pub fn prune_selection(&mut self) {
         self.selections = self.selections.iter().filter(|x| self.ocean.get(x.squid_id).is_some()).copied().collect();
     }
 
+    pub fn is_over_panels(&self, point: &glm::Vec2) -> bool {
+        self.toolbox
+            .get_panel_areas(&self.dimensions)
+            .iter()
+            .any(|area| area.intersecting_point(point.x, point.y))
+    }
+
+    // Reads the color of the pixel under a point on the screen,
+    // from what was last displayed
+    pub fn sample_color(&self, position: &glm::Vec2) -> Option<Color> {
+        let pixels: Vec<Vec<(u8, u8, u8, u8)>> = self.display.read_front_buffer().ok()?;
+
+        // Rows go from bottom to top
+        let x = (position.x as f64 * self.scale_factor) as usize;
+        let y = (position.y as f64 * self.scale_factor) as usize;
+        let row = pixels.len().checked_sub(y + 1)?;
+        let (r, g, b, _) = *pixels.get(row)?.get(x)?;
+
+        Some(RasterColor::new(r, g, b, 255).into())
+    }
+
+    // Applies the color under a point on the screen to the color picker (and so the selection)
+    pub fn pick_color_at(&mut self, position: &glm::Vec2) {
+        if let Some(color) = self.sample_color(position) {
+            self.toolbox.color_picker.set_selected_color(color);
+        }
+    }
+
     pub fn get_mouse_in_world_space(&self) -> glm::Vec2 {
         let mouse = self.mouse_position.unwrap();
         let camera = self.camera.get_animated();
@@ -440,53 +1101,509 @@ impl App {
         }
     }
 
+    // Opens into a new document, unless the current one is still blank
     pub fn load(&mut self) {
-        if let Ok(Some(filename)) = ask_open() {
+        if let Ok(Some(filename)) = ask_open(None) {
+            if self.filename.is_some() || self.ocean.get_squids_lowest().next().is_some() {
+                self.new_document();
+            }
+
             self.load_from_file(filename);
         }
     }
 
-    pub fn export(&mut self) {
-        let viewport = if let Some(viewport) = self.get_selected_viewport() {
-            viewport
+    pub fn get_document_names(&self) -> Vec<String> {
+        self.documents
+            .iter()
+            .enumerate()
+            .map(|(i, document)| {
+                let filename = if i == self.active_document { &self.filename } else { &document.filename };
+                get_document_name(filename.as_ref())
+            })
+            .collect()
+    }
+
+    // Exchanges the app's working state with the active document's slot
+    fn swap_active_document(&mut self) {
+        let document = &mut self.documents[self.active_document];
+        std::mem::swap(&mut self.ocean, &mut document.ocean);
+        std::mem::swap(&mut self.history, &mut document.history);
+        std::mem::swap(&mut self.camera, &mut document.camera);
+        std::mem::swap(&mut self.selections, &mut document.selections);
+        std::mem::swap(&mut self.filename, &mut document.filename);
+    }
+
+    pub fn switch_document(&mut self, index: usize) {
+        if index == self.active_document || index >= self.documents.len() {
+            return;
+        }
+
+        self.swap_active_document();
+        self.active_document = index;
+        self.swap_active_document();
+        self.clear_document_state();
+    }
+
+    pub fn switch_adjacent_document(&mut self, backwards: bool) {
+        let count = self.documents.len();
+        let step = if backwards { count - 1 } else { 1 };
+        self.switch_document((self.active_document + step) % count);
+    }
+
+    pub fn new_document(&mut self) {
+        self.documents.push(Document::new(self.dimensions));
+        self.switch_document(self.documents.len() - 1);
+    }
+
+    // Asks before closing a document with changes that haven't been saved, since closing can't be undone
+    pub fn request_close_document(&mut self, index: usize) {
+        let history = if index == self.active_document {
+            &self.history
         } else {
-            _ = MessageDialog::new()
-                .set_title("No viewport selected!")
-                .set_text("Must have a viewport selected to export")
-                .set_type(MessageType::Error)
-                .show_alert();
+            &self.documents[index].history
+        };
+
+        if !history.is_changed() {
+            self.close_document(index);
+            return;
+        }
+
+        let name = self.get_document_names().swap_remove(index);
+
+        self.show_modal(Modal::confirm(
+            "Close Document",
+            &format!("'{}' has unsaved changes. Close it anyway?", name),
+            Box::new(move |app| app.close_document(index)),
+        ));
+    }
+
+    // Closing the last document leaves a blank one in its place
+    pub fn close_document(&mut self, index: usize) {
+        if self.documents.len() == 1 {
+            self.documents[0] = Document::new(self.dimensions);
+            self.swap_active_document();
+            self.clear_document_state();
+            return;
+        }
+
+        let is_active = index == self.active_document;
+
+        // The working state gets closed along with its placeholder
+        if is_active {
+            self.swap_active_document();
+        }
+
+        self.documents.remove(index);
+
+        if index < self.active_document || self.active_document == self.documents.len() {
+            self.active_document -= 1;
+        }
+
+        if is_active {
+            self.swap_active_document();
+            self.clear_document_state();
+        }
+    }
+
+    // Forgets anything that was in progress in the previous document
+    fn clear_document_state(&mut self) {
+        self.operation = None;
+        self.dragging = None;
+        self.pen_path = None;
+        self.pencil_stroke = None;
+        self.zoom_box = None;
+        self.knife_line = None;
+        self.editing_point = None;
+        self.shape_drag = None;
+        self.context_menu = None;
+        self.radial_menu = None;
+        self.panel_cache.invalidate();
+        self.update_title();
+    }
+
+    pub fn respond_to_document_tab(&mut self, target: DocumentTabsTarget) {
+        match target {
+            DocumentTabsTarget::Switch(index) => self.switch_document(index),
+            DocumentTabsTarget::Close(index) => self.request_close_document(index),
+            DocumentTabsTarget::New => self.new_document(),
+        }
+    }
+
+    // Copied squids don't keep their color styles, since they can be pasted into other documents
+    pub fn copy_selected(&mut self) {
+        self.clipboard = self
+            .get_selected_squids()
+            .iter()
+            .filter_map(|reference| self.ocean.get(*reference))
+            .map(|squid| {
+                let mut copy = squid.clone();
+                copy.set_style(None);
+                copy
+            })
+            .collect();
+    }
+
+    pub fn paste(&mut self) {
+        if self.clipboard.is_empty() {
             return;
+        }
+
+        let pasted: Vec<SquidRef> = self.clipboard.clone().into_iter().map(|squid| self.insert(squid)).collect();
+        self.selections = pasted.into_iter().map(|reference| Selection::new(reference, None)).collect();
+    }
+
+    // Remembers how the first selected squid looks, so it can be given to others
+    pub fn copy_selected_style(&mut self) {
+        if let Some(squid) = self.get_selected_squids().first().and_then(|reference| self.ocean.get(*reference)) {
+            self.style_clipboard = Some(squid.get_appearance());
+        }
+    }
+
+    pub fn paste_style(&mut self) {
+        let appearance = match self.style_clipboard {
+            Some(appearance) => appearance,
+            None => return,
+        };
+
+        for squid_id in self.get_selected_squids() {
+            // Links to styles aren't kept, since the style may be from another document
+            if let Some(squid) = self.ocean.get_mut(squid_id) {
+                squid.set_style(None);
+                squid.set_appearance(&appearance);
+            }
+        }
+    }
+
+    // Saves how the first selected squid looks as a new style, which it's then linked to
+    pub fn save_selected_style(&mut self) {
+        let reference = match self.get_selected_squids().first() {
+            Some(reference) => *reference,
+            None => return,
         };
 
-        if let Some(filename) = ask_save(Some(Filter {
+        if let Some(squid) = self.ocean.get(reference) {
+            let name = format!("Style {}", self.ocean.get_styles().count() + 1);
+            let style = self.ocean.add_style(squid.get_appearance().to_style(name));
+            self.ocean.set_squid_style(reference, Some(style));
+        }
+    }
+
+    // Changes the style the first selected squid is linked to so it looks like that squid
+    pub fn update_selected_style(&mut self) {
+        let squid = self.get_selected_squids().first().and_then(|reference| self.ocean.get(*reference));
+
+        if let Some((style, appearance)) = squid.and_then(|squid| Some((squid.get_style()?, squid.get_appearance()))) {
+            self.ocean.update_style(style, &appearance);
+        }
+    }
+
+    // Asks for new tags for the selected squids, starting from the tags of the first one
+    pub fn ask_edit_tags(&mut self) {
+        let tags = match self.get_selected_squids().first().and_then(|reference| self.ocean.get(*reference)) {
+            Some(squid) => squid.get_tags().join(", "),
+            None => return,
+        };
+
+        self.show_modal(Modal::input(
+            "Edit Tags",
+            "Tags separated by commas",
+            &tags,
+            Box::new(|app, text| app.emit(DocumentCommand::SetSelectedTags(squid::parse_tags(&text)))),
+        ));
+    }
+
+    // Renames the selected squids in the order they were selected, using a pattern such as "Icon_{n}"
+    pub fn ask_rename_selected(&mut self) {
+        let selected = self.get_selected_squids();
+
+        let default_pattern = match selected.as_slice() {
+            [] => return,
+            [only] => self.ocean.get(*only).map_or("{name}", |squid| squid.get_name()).to_string(),
+            _ => "{name} {n}".to_string(),
+        };
+
+        self.show_modal(Modal::input(
+            "Rename Selected",
+            "{n} counts up from 1 and {name} is the current name",
+            &default_pattern,
+            Box::new(|app, pattern| {
+                if !pattern.is_empty() {
+                    app.emit(DocumentCommand::RenameSelected(pattern));
+                }
+            }),
+        ));
+    }
+
+    pub fn ask_select_by_tag(&mut self) {
+        self.show_modal(Modal::input(
+            "Select by Tag",
+            "Select everything with the tag",
+            "",
+            Box::new(|app, tag| {
+                if !tag.trim().is_empty() {
+                    app.emit(DocumentCommand::SelectByTag(tag));
+                }
+            }),
+        ));
+    }
+
+    pub fn export(&mut self) {
+        let bounds = match self.export_options.bounds {
+            ExportBounds::Viewport => match self.get_selected_viewport() {
+                Some(viewport) => {
+                    let position = viewport.position.reveal();
+                    let size = viewport.size.abs();
+                    AABB::new(position.x - size.x * 0.5, position.y - size.y * 0.5, size.x, size.y)
+                }
+                None => {
+                    self.show_error("No viewport selected!", "Must have a viewport selected to export");
+                    return;
+                }
+            },
+            ExportBounds::Content => match self.ocean.get_content_bounds() {
+                Some(bounds) => bounds,
+                None => {
+                    self.show_error("Nothing to export!", "Must have something visible to export");
+                    return;
+                }
+            },
+        };
+
+        let view_box = bounds.padded(self.export_options.margin);
+
+        // Formats from plugins are offered alongside SVG
+        let plugin_extensions: Vec<[&str; 1]> = self.plugins.exporters.iter().map(|plugin| [plugin.get_extension()]).collect();
+
+        let mut filters = vec![Filter {
             description: "Scalable Vector Graphic",
-            extension: "svg",
-        }))
-        .unwrap_or(None)
-        {
-            self.export_to_file(filename, viewport);
+            extensions: &["svg"],
+        }];
+
+        filters.extend(self.plugins.exporters.iter().zip(&plugin_extensions).map(|(plugin, extensions)| Filter {
+            description: plugin.get_name(),
+            extensions,
+        }));
+
+        if let Some(filename) = ask_save_one_of(&filters).unwrap_or(None) {
+            self.export_to_file(filename, view_box);
+        }
+    }
+
+    // Adds an export profile for the selected viewport (or all viewports if none is selected),
+    // writing into a folder chosen by the user
+    pub fn add_export_profile(&mut self, format: ExportFormat, size: ExportSize) {
+        let folder = match ask_folder() {
+            Ok(Some(folder)) => folder,
+            _ => return,
+        };
+
+        let viewport = self
+            .selections
+            .iter()
+            .map(|selection| selection.squid_id)
+            .find(|reference| self.ocean.get(*reference).is_some_and(|squid| squid.as_viewport().is_some()));
+
+        self.ocean.add_export_profile(ExportProfile {
+            format,
+            size,
+            folder,
+            viewport,
+        });
+        self.add_history_marker();
+    }
+
+    // Produces the output of every profile for every viewport,
+    // PNG outputs are rendered over the next few frames
+    pub fn export_all_profiles(&mut self) {
+        let outputs = get_profile_outputs(&self.ocean);
+
+        if outputs.is_empty() {
+            self.show_error("Nothing to export!", "Must have an export profile and a viewport to export all profiles");
+            return;
+        }
+
+        self.ocean.resolve_styles();
+
+        let raster_count = outputs.iter().filter(|output| output.format == ExportFormat::Png).count();
+
+        if raster_count > 0 {
+            self.show_modal(Modal::progress("Exporting", "Rendering PNG images...", raster_count));
+        }
+
+        for output in outputs {
+            match output.format {
+                ExportFormat::Svg => {
+                    println!("exporting to {}", output.path.to_string_lossy());
+
+                    if export(&output.path, &output.view_box, &output.size, &self.ocean).is_err() {
+                        self.show_export_error(&output.path);
+                    }
+                }
+                ExportFormat::Png => {
+                    self.pending_raster_exports.push(RasterExport::new(output.path, output.view_box, &output.size));
+                }
+            }
+        }
+    }
+
+    pub fn load_color_scheme(&mut self) {
+        let filename = match ask_open(Some(Filter {
+            description: "Color Scheme",
+            extensions: &["json"],
+        })) {
+            Ok(Some(filename)) => filename,
+            _ => return,
+        };
+
+        match ColorScheme::load(&filename) {
+            Ok(color_scheme) => self.color_scheme = color_scheme,
+            Err(message) => self.show_error("Failed to load color scheme", &message),
+        }
+    }
+
+    // Adds the colors from a palette file as shared color styles
+    pub fn import_palette(&mut self) {
+        let filename = match ask_open(Some(PALETTE_FILTER)) {
+            Ok(Some(filename)) => filename,
+            _ => return,
+        };
+
+        match palette::load(&filename) {
+            Ok(styles) => {
+                for style in styles {
+                    self.ocean.add_style(style);
+                }
+                self.add_history_marker();
+            }
+            Err(message) => self.show_error("Failed to import palette", &message),
+        }
+    }
+
+    // Writes the shared color styles out as a palette file
+    pub fn export_palette(&mut self) {
+        let filename = match ask_save(Some(PALETTE_FILTER)) {
+            Ok(Some(filename)) => filename,
+            _ => return,
+        };
+
+        let styles: Vec<ColorStyle> = self.ocean.get_styles().map(|(_, style)| style.clone()).collect();
+
+        if let Err(message) = palette::save(&filename, &styles) {
+            self.show_error("Failed to export palette", &message);
+        }
+    }
+
+    // Whether the window has to be redrawn, either because something changed since the last frame,
+    // something is still animating, or there's work in progress that will show up on its own
+    pub fn needs_redraw(&self) -> bool {
+        self.damage.is_damaged() || !self.commands.is_empty() || self.is_animating() || !self.pending_raster_exports.is_empty() || self.mesh_cache.is_busy()
+    }
+
+    fn is_animating(&self) -> bool {
+        self.camera.is_animating() || self.ocean.is_animating() || self.toolbox.is_animating()
+    }
+
+    // When the next autosave is due, if autosaving is turned on
+    pub fn get_next_autosave(&self) -> Option<Instant> {
+        let interval = self.preferences.autosave_minutes * 60.0;
+
+        if interval <= 0.0 || self.filename.is_none() {
+            return None;
+        }
+
+        Duration::try_from_secs_f32(interval)
+            .ok()
+            .and_then(|interval| self.last_autosave.checked_add(interval))
+    }
+
+    // Saves over the current file every so often, when autosaving is turned on
+    pub fn autosave(&mut self) {
+        let interval = self.preferences.autosave_minutes * 60.0;
+
+        if interval <= 0.0 || self.last_autosave.elapsed().as_secs_f32() < interval {
+            return;
+        }
+
+        self.last_autosave = Instant::now();
+
+        if let Some(filename) = self.filename.clone() {
+            self.save_to_file(filename);
+        }
+    }
+
+    pub fn can_draw_directly(&self) -> bool {
+        self.preferences.get_render_quality().can_draw_directly(self.scale_factor, self.window_msaa)
+    }
+
+    // The interface is drawn at the window's scale factor multiplied by the preferred UI scale,
+    // while the camera shrinks the view back down so the document keeps its size on screen
+    pub fn update_scale_factor(&mut self) {
+        let window_scale_factor = self.display.gl_window().window().scale_factor();
+        self.scale_factor = window_scale_factor * self.preferences.ui_scale as f64;
+        self.panel_cache.invalidate();
+    }
+
+    // Lengths are shown in the preferred unit, converted using the document's DPI
+    pub fn get_length_format(&self) -> LengthFormat {
+        LengthFormat {
+            unit: self.preferences.unit,
+            dpi: self.ocean.get_dpi(),
         }
     }
 
     pub fn save_to_file(&mut self, filename: PathBuf) {
         let contents = serde_json::to_string(&self.ocean).expect("Failed to serialize project");
-        fs::write(&filename, contents).expect("Failed to write project file to disk");
+
+        if fs::write(&filename, contents).is_err() {
+            self.show_error("Failed to save", &format!("Could not write to '{}'", filename.to_string_lossy()));
+            return;
+        }
+
         self.filename = Some(filename);
+        self.history.mark_saved();
         self.update_title();
     }
 
     pub fn load_from_file(&mut self, filename: PathBuf) {
-        let contents = fs::read_to_string(&filename).expect("Failed to read project file from disk");
-        self.ocean = serde_json::from_str(&contents).expect("Bad project format");
+        let contents = match fs::read_to_string(&filename) {
+            Ok(contents) => contents,
+            Err(_) => {
+                self.show_error("Failed to open", &format!("Could not read '{}'", filename.to_string_lossy()));
+                return;
+            }
+        };
+
+        self.ocean = match Ocean::from_json(&contents) {
+            Ok(ocean) => ocean,
+            Err(error) => {
+                self.show_error("Failed to open", &format!("Not a valid project: {}", error));
+                return;
+            }
+        };
+
         self.filename = Some(filename);
+        self.history.mark_saved();
         self.reset_camera();
         self.clear_selection();
         self.update_title();
     }
 
-    pub fn export_to_file(&mut self, filename: PathBuf, viewport: RectData) {
+    pub fn export_to_file(&mut self, filename: PathBuf, view_box: AABB) {
         println!("exporting to {}", filename.to_string_lossy());
-        _ = export(filename, &viewport, &self.ocean);
+        self.ocean.resolve_styles();
+
+        let result = match self.plugins.get_exporter_for(&filename) {
+            Some(plugin) => plugin.export(&filename, &view_box, &self.ocean),
+            None => export(&filename, &view_box, &self.export_options.size, &self.ocean),
+        };
+
+        if result.is_err() {
+            self.show_export_error(&filename);
+        }
+    }
+
+    pub fn show_export_error(&mut self, filename: &Path) {
+        self.show_error("Failed to export", &format!("Could not write to '{}'", filename.to_string_lossy()));
     }
 
     pub fn reset_camera(&mut self) {
@@ -516,12 +1633,36 @@ impl App {
         None
     }
 
-    pub fn about(&self) {
-        _ = MessageDialog::new()
-            .set_title("Photosquid")
-            .set_text("(c) 2021-2022 - Isaac Shelton")
-            .set_type(MessageType::Info)
-            .show_alert();
+    pub fn about(&mut self) {
+        self.show_modal(Modal::message("Photosquid", "(c) 2021-2022 - Isaac Shelton"));
+    }
+
+    // Dialogs are shown one at a time, in the order they were requested
+    pub fn show_modal(&mut self, modal: Modal) {
+        self.modals.push(modal);
+    }
+
+    pub fn show_error(&mut self, title: &str, text: &str) {
+        self.show_modal(Modal::message(title, text));
+    }
+
+    pub fn respond_to_modal(&mut self, response: ModalResponse) {
+        match response {
+            ModalResponse::Continue => (),
+            ModalResponse::Close => {
+                self.modals.remove(0);
+            }
+            ModalResponse::Accept => self.modals.remove(0).accept(self),
+        }
+    }
+
+    // Records that a step tracked by the current progress dialog finished, closing it once all are done
+    pub fn advance_progress(&mut self) {
+        if let Some(modal) = self.modals.first_mut().filter(|modal| modal.is_progress()) {
+            if modal.advance() {
+                self.modals.remove(0);
+            }
+        }
     }
 }
 
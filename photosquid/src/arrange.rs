@@ -0,0 +1,130 @@
+use crate::{aabb::AABB, ocean::Ocean, squid::SquidRef};
+use nalgebra_glm as glm;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn get_range(self, bounds: &AABB) -> (f32, f32) {
+        match self {
+            Self::Horizontal => (bounds.min_x, bounds.max_x),
+            Self::Vertical => (bounds.min_y, bounds.max_y),
+        }
+    }
+
+    fn vector(self, amount: f32) -> glm::Vec2 {
+        match self {
+            Self::Horizontal => glm::vec2(amount, 0.0),
+            Self::Vertical => glm::vec2(0.0, amount),
+        }
+    }
+}
+
+fn get_bounds(ocean: &Ocean, references: &[SquidRef]) -> Vec<(SquidRef, AABB)> {
+    references
+        .iter()
+        .filter_map(|reference| Some((*reference, AABB::from_points(&ocean.get(*reference)?.get_extreme_points())?)))
+        .collect()
+}
+
+// Moves squids so that the space between each one and the next is the same along an axis,
+// where the squids furthest on either side stay where they are
+pub fn equalize_gaps(ocean: &mut Ocean, references: &[SquidRef], axis: Axis) {
+    let mut bounds = get_bounds(ocean, references);
+
+    if bounds.len() < 3 {
+        return;
+    }
+
+    bounds.sort_by(|(_, a), (_, b)| axis.get_range(a).0.total_cmp(&axis.get_range(b).0));
+
+    let (start, _) = axis.get_range(&bounds[0].1);
+    let end = bounds.iter().map(|(_, bounds)| axis.get_range(bounds).1).fold(f32::MIN, f32::max);
+    let total_size: f32 = bounds.iter().map(|(_, bounds)| axis.get_range(bounds)).map(|(min, max)| max - min).sum();
+    let gap = (end - start - total_size) / (bounds.len() - 1) as f32;
+
+    let mut next = start;
+
+    for (reference, bounds) in bounds {
+        let (min, max) = axis.get_range(&bounds);
+
+        if let Some(squid) = ocean.get_mut(reference) {
+            squid.reposition_by(axis.vector(next - min));
+        }
+
+        next += max - min + gap;
+    }
+}
+
+// Stretches squids to be as wide or tall as the first one, keeping each centered where it was
+pub fn match_size(ocean: &mut Ocean, references: &[SquidRef], axis: Axis) {
+    let bounds = get_bounds(ocean, references);
+
+    let target = match bounds.first() {
+        Some((_, first)) => axis.get_range(first),
+        None => return,
+    };
+
+    for (reference, bounds) in bounds.iter().skip(1) {
+        let (min, max) = axis.get_range(bounds);
+
+        if max - min <= f32::EPSILON {
+            continue;
+        }
+
+        let factor = glm::vec2(1.0, 1.0) + axis.vector((target.1 - target.0) / (max - min) - 1.0);
+        let center = glm::vec2(bounds.center_x(), bounds.center_y());
+
+        if let Some(squid) = ocean.get_mut(*reference) {
+            squid.stretch(&factor, &center);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{equalize_gaps, get_bounds, match_size, Axis};
+    use crate::{color::Color, ocean::Ocean, squid::Squid};
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    fn rect(x: f32, width: f32) -> Squid {
+        Squid::rect(glm::vec2(x, 0.0), glm::vec2(width, 20.0), Rad(0.0), Color::white(), 0.0, false)
+    }
+
+    #[test]
+    fn equal_gaps() {
+        let mut ocean = Ocean::default();
+        let squids = vec![ocean.insert(rect(0.0, 20.0)), ocean.insert(rect(100.0, 20.0)), ocean.insert(rect(30.0, 40.0))];
+
+        // The middle squid ends up halfway between, and ones on the ends don't move
+        equalize_gaps(&mut ocean, &squids, Axis::Horizontal);
+        let min_x: Vec<f32> = get_bounds(&ocean, &squids).iter().map(|(_, bounds)| bounds.min_x).collect();
+        assert_eq!(min_x, vec![-10.0, 90.0, 30.0]);
+    }
+
+    #[test]
+    fn match_width_and_height() {
+        let mut ocean = Ocean::default();
+        let first = ocean.insert(rect(0.0, 60.0));
+        let tri = ocean.insert(Squid::tri([glm::zero(), glm::vec2(10.0, 0.0), glm::vec2(0.0, 10.0)], Rad(0.3), Color::white()));
+        let circle = ocean.insert(Squid::circle(glm::vec2(100.0, 0.0), 5.0, Color::white()));
+        let squids = vec![first, tri, circle];
+
+        match_size(&mut ocean, &squids, Axis::Horizontal);
+
+        for (_, bounds) in get_bounds(&ocean, &squids) {
+            assert!((bounds.width() - 60.0).abs() < 0.01);
+        }
+
+        // Circles stay round and where they were
+        assert_eq!(ocean.get(circle).unwrap().get_center(), glm::vec2(100.0, 0.0));
+
+        match_size(&mut ocean, &squids, Axis::Vertical);
+        let (_, bounds) = get_bounds(&ocean, &[tri])[0];
+        assert!((bounds.height() - 20.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    affine::Affine2,
+    as_values::AsValues,
+    clearable::Clearable,
+    color::Color,
+    mesh::{MeshXyz, MeshXyzUv},
+    render_ctx::RenderCtx,
+    renderer::Renderer,
+    thumbnail_cache::premultiplied,
+    vertex::VertexXYRGBA,
+};
+use glium::{
+    framebuffer::SimpleFrameBuffer,
+    texture::SrgbTexture2d,
+    uniforms::{SamplerWrapFunction, Uniforms},
+    Display, LinearBlendingFactor, Surface, VertexBuffer,
+};
+use nalgebra_glm as glm;
+
+// Furthest a blur reaches in pixels, since each pixel samples every pixel within reach
+const MAX_RADIUS: f32 = 128.0;
+
+// Squids are blurred by drawing them into a texture the size of the canvas first.
+// The texture is then blurred across into another texture, and that is blurred down onto
+// whatever is underneath, which is the same as blurring in both directions at once but much cheaper.
+// Textures have premultiplied alpha, so colors don't darken as they spread into empty space
+pub struct BlurLayer<'a> {
+    texture: SrgbTexture2d,
+    display: &'a Display,
+    color_shader: &'a glium::Program,
+    batch_shader: &'a glium::Program,
+    blur_shader: &'a glium::Program,
+    square: &'a MeshXyzUv,
+    view: &'a Affine2,
+    projection: &'a glm::Mat4,
+}
+
+impl<'a> BlurLayer<'a> {
+    pub fn new(ctx: &RenderCtx<'a, '_>) -> Self {
+        let (width, height) = ctx.get_dimensions();
+        let texture = SrgbTexture2d::empty(ctx.display, width, height).unwrap();
+        SimpleFrameBuffer::new(ctx.display, &texture).unwrap().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        Self {
+            texture,
+            display: ctx.display,
+            color_shader: ctx.color_shader,
+            batch_shader: ctx.batch_shader,
+            blur_shader: ctx.blur_shader,
+            square: ctx.square_xyzuv,
+            view: ctx.view,
+            projection: ctx.projection,
+        }
+    }
+
+    // Blurs what was drawn and blends it over the canvas, where the radius is in world units
+    pub fn finish(self, ctx: &mut RenderCtx, radius: f32) {
+        let radius = get_pixel_radius(ctx, radius);
+        let across = self.blur_across(radius);
+        let uniforms = get_uniforms(&across, glm::vec2(0.0, 1.0 / across.height() as f32), radius);
+        let mesh = ctx.square_xyzuv;
+
+        ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.blur_shader, &uniforms, &get_blend()).unwrap();
+    }
+
+    // Blurs what was drawn and blends it over another layer, so that squids can be blurred within a blurred layer
+    pub fn finish_onto(self, other: &mut BlurLayer, ctx: &RenderCtx, radius: f32) {
+        let radius = get_pixel_radius(ctx, radius);
+        let across = self.blur_across(radius);
+        let uniforms = get_uniforms(&across, glm::vec2(0.0, 1.0 / across.height() as f32), radius);
+
+        SimpleFrameBuffer::new(self.display, &other.texture)
+            .unwrap()
+            .draw(&self.square.vertex_buffer, self.square.indices, self.blur_shader, &uniforms, &get_blend())
+            .unwrap();
+    }
+
+    fn blur_across(&self, radius: f32) -> SrgbTexture2d {
+        let (width, height) = self.texture.dimensions();
+        let across = SrgbTexture2d::empty(self.display, width, height).unwrap();
+        let uniforms = get_uniforms(&self.texture, glm::vec2(1.0 / width as f32, 0.0), radius);
+
+        SimpleFrameBuffer::new(self.display, &across)
+            .unwrap()
+            .draw(
+                &self.square.vertex_buffer,
+                self.square.indices,
+                self.blur_shader,
+                &uniforms,
+                &Default::default(),
+            )
+            .unwrap();
+
+        across
+    }
+
+    fn draw_mesh_with_view(&mut self, mesh: &MeshXyz, transformation: &Affine2, view: &Affine2, color: &Color) {
+        let uniforms = glium::uniform! {
+            transformation: transformation.as_values(),
+            view: view.as_values(),
+            projection: self.projection.as_values(),
+            color: color.as_values()
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: premultiplied(LinearBlendingFactor::SourceAlpha),
+            ..Default::default()
+        };
+
+        SimpleFrameBuffer::new(self.display, &self.texture)
+            .unwrap()
+            .draw(&mesh.vertex_buffer, &mesh.indices, self.color_shader, &uniforms, &draw_parameters)
+            .unwrap();
+    }
+}
+
+impl Renderer for BlurLayer<'_> {
+    type Mesh = MeshXyz;
+
+    fn clear_color(&mut self, color: &Color) {
+        color.clear_framebuffer_with(&mut SimpleFrameBuffer::new(self.display, &self.texture).unwrap());
+    }
+
+    fn draw_colored_triangles(&mut self, vertices: &[VertexXYRGBA]) {
+        if vertices.is_empty() {
+            return;
+        }
+
+        let vertex_buffer = VertexBuffer::new(self.display, vertices).unwrap();
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+
+        let uniforms = glium::uniform! {
+            view: self.view.as_values(),
+            projection: self.projection.as_values(),
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: premultiplied(LinearBlendingFactor::SourceAlpha),
+            ..Default::default()
+        };
+
+        SimpleFrameBuffer::new(self.display, &self.texture)
+            .unwrap()
+            .draw(&vertex_buffer, indices, self.batch_shader, &uniforms, &draw_parameters)
+            .unwrap();
+    }
+
+    fn draw_mesh(&mut self, mesh: &MeshXyz, transformation: &Affine2, color: &Color) {
+        self.draw_mesh_with_view(mesh, transformation, &Affine2::identity(), color);
+    }
+
+    fn draw_mesh_in_world(&mut self, mesh: &MeshXyz, transformation: &Affine2, color: &Color) {
+        let view = *self.view;
+        self.draw_mesh_with_view(mesh, transformation, &view, color);
+    }
+}
+
+// Converts a radius in world units into pixels of what's being drawn to
+fn get_pixel_radius(ctx: &RenderCtx, radius: f32) -> f32 {
+    let (width, _) = ctx.get_dimensions();
+    let pixels_per_unit = width as f32 / ctx.camera.window.x.max(1.0);
+    (ctx.camera.apply_to_scale(radius) * pixels_per_unit).min(MAX_RADIUS)
+}
+
+// Draws a texture over everything using the blur shader, where the step is the distance between pixels
+// in the direction being blurred. Nothing beyond the edges of the texture spreads inwards
+fn get_uniforms(texture: &SrgbTexture2d, pixel_step: glm::Vec2, radius: f32) -> impl Uniforms + '_ {
+    let identity = Affine2::identity();
+
+    glium::uniform! {
+        transformation: identity.as_values(),
+        view: identity.as_values(),
+        projection: glm::Mat4::identity().as_values(),
+        texture_sampler: texture.sampled().wrap_function(SamplerWrapFunction::BorderClamp),
+        pixel_step: <[f32; 2]>::from(pixel_step),
+        radius: radius
+    }
+}
+
+fn get_blend() -> glium::DrawParameters<'static> {
+    glium::DrawParameters {
+        blend: premultiplied(LinearBlendingFactor::One),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    aabb::AABB,
+    document_tabs::DocumentTabs,
+    draw_text::{draw_text, get_text_width},
+    ocean::Ocean,
+    render_ctx::RenderCtx,
+    selection::Selection,
+    squid::SquidRef,
+};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use itertools::Itertools;
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Bar that shows the ancestry of the current selection,
+// where each ancestor can be clicked to select it
+pub struct Breadcrumb {
+    segments: Vec<Segment>,
+}
+
+struct Segment {
+    label: String,
+    target: BreadcrumbTarget,
+    area: Option<AABB>,
+}
+
+#[derive(Copy, Clone)]
+pub enum BreadcrumbTarget {
+    Document,
+    Layer(usize),
+    Squid(SquidRef),
+    Selection,
+}
+
+impl Breadcrumb {
+    const MARGIN: f32 = 16.0;
+    const BASELINE: f32 = DocumentTabs::HEIGHT + 28.0;
+    const SEPARATOR: &'static str = " > ";
+
+    pub fn new() -> Self {
+        Self { segments: vec![] }
+    }
+
+    pub fn update(&mut self, ocean: &Ocean, selections: &[Selection]) {
+        self.segments.clear();
+
+        if selections.is_empty() {
+            return;
+        }
+
+        self.push("Document", BreadcrumbTarget::Document);
+
+        let layer_indices: Vec<usize> = selections
+            .iter()
+            .filter_map(|selection| ocean.get_layer_index_of(selection.squid_id))
+            .unique()
+            .collect();
+
+        if let [layer_index] = layer_indices[..] {
+            if let Some(layer) = ocean.get_layers().get(layer_index) {
+                self.push(layer.get_name(), BreadcrumbTarget::Layer(layer_index));
+            }
+        }
+
+        match selections {
+            [selection] => {
+                if let Some(squid) = ocean.get(selection.squid_id) {
+                    self.push(squid.get_name(), BreadcrumbTarget::Squid(selection.squid_id));
+                }
+            }
+            _ => self.push(&format!("{} squids", selections.len()), BreadcrumbTarget::Selection),
+        }
+    }
+
+    fn push(&mut self, label: &str, target: BreadcrumbTarget) {
+        self.segments.push(Segment {
+            label: label.into(),
+            target,
+            area: None,
+        });
+    }
+
+    pub fn click(&self, position: &glm::Vec2) -> Option<BreadcrumbTarget> {
+        self.segments
+            .iter()
+            .find(|segment| segment.area.is_some_and(|area| area.intersecting_point(position.x, position.y)))
+            .map(|segment| segment.target)
+    }
+
+    // Draws the segments starting from 'left', which is where the canvas begins
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, left: f32) {
+        let mut x = left + Self::MARGIN;
+        let last = self.segments.len().saturating_sub(1);
+
+        for (i, segment) in self.segments.iter_mut().enumerate() {
+            let width = get_text_width(&font, &segment.label);
+            segment.area = Some(AABB::new(x, Self::BASELINE - 18.0, width, 24.0));
+
+            let color = if i == last {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+
+            draw_text(text_system, font.clone(), &segment.label, &glm::vec2(x, Self::BASELINE), ctx, color);
+            x += width;
+
+            if i != last {
+                let separator_width = get_text_width(&font, Self::SEPARATOR);
+                draw_text(
+                    text_system,
+                    font.clone(),
+                    Self::SEPARATOR,
+                    &glm::vec2(x, Self::BASELINE),
+                    ctx,
+                    ctx.color_scheme.muted,
+                );
+                x += separator_width;
+            }
+        }
+    }
+}
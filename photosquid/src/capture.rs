@@ -10,10 +10,10 @@ pub enum Capture {
     TakeFocus,
     Keyboard(KeyCapture),
     MoveSelectedSquids { delta_in_world: glm::Vec2 },
-    RotateSelectedSquids { delta_theta: Rad<f32> },
+    RotateSelectedSquids { delta_theta: Rad<f32>, shift: bool },
     ScaleSelectedSquids { total_scale_factor: f32 },
     SpreadSelectedSquids { current: glm::Vec2 },
-    RevolveSelectedSquids { current: glm::Vec2 },
+    RevolveSelectedSquids { current: glm::Vec2, shift: bool },
     DilateSelectedSquids { current: glm::Vec2 },
 }
 
@@ -1,14 +1,68 @@
-use crate::color::Color;
+use crate::{color::Color, raster_color::RasterColor};
+use serde::{de::Error, Deserialize, Deserializer};
+use std::{fs, path::Path};
 
+// Colors used to draw the user interface.
+// Custom schemes can be loaded from JSON files mapping each field to a hex color,
+// any fields left out are taken from the default (dark) scheme
+#[derive(Deserialize)]
+#[serde(default)]
 pub struct ColorScheme {
+    #[serde(deserialize_with = "hex")]
     pub background: Color,
+    #[serde(deserialize_with = "hex")]
     pub light_ribbon: Color,
+    #[serde(deserialize_with = "hex")]
     pub dark_ribbon: Color,
+    #[serde(deserialize_with = "hex")]
     pub foreground: Color,
+    #[serde(deserialize_with = "hex")]
     pub dark_foreground: Color,
+    #[serde(deserialize_with = "hex")]
     pub really_dark_foreground: Color,
+    #[serde(deserialize_with = "hex")]
     pub input: Color,
+    #[serde(deserialize_with = "hex")]
     pub error: Color,
+    #[serde(deserialize_with = "hex")]
+    pub text: Color,
+    #[serde(deserialize_with = "hex")]
+    pub secondary_text: Color,
+    #[serde(deserialize_with = "hex")]
+    pub label: Color,
+    #[serde(deserialize_with = "hex")]
+    pub muted: Color,
+    #[serde(deserialize_with = "hex")]
+    pub shade: Color,
+}
+
+impl ColorScheme {
+    pub fn light() -> Self {
+        Self {
+            background: Color::from_hex("#F2F3F5"),
+            light_ribbon: Color::from_hex("#EBEDEF"),
+            dark_ribbon: Color::from_hex("#E3E5E8"),
+            foreground: Color::from_hex("#5865F2"),
+            dark_foreground: Color::from_hex("#4752C4"),
+            really_dark_foreground: Color::from_hex("#3C45A5"),
+            input: Color::from_hex("#D4D7DC"),
+            error: Color::from_hex("#D83C3E"),
+            text: Color::from_hex("#2E3338"),
+            secondary_text: Color::from_hex("#4F5660"),
+            label: Color::from_hex("#747F8D"),
+            muted: Color::from_hex("#A3A6AA"),
+            shade: Color::from_hex("#00000040"),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|_| "Failed to read color scheme file")?;
+        Self::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        serde_json::from_str(contents).map_err(|error| format!("Invalid color scheme: {}", error))
+    }
 }
 
 impl Default for ColorScheme {
@@ -22,6 +76,37 @@ impl Default for ColorScheme {
             really_dark_foreground: Color::from_hex("#3D5CCC"),
             input: Color::from_hex("#40444B"),
             error: Color::from_hex("#ed2326"),
+            text: Color::from_hex("#FFFFFF"),
+            secondary_text: Color::from_hex("#AAAAAA"),
+            label: Color::from_hex("#777777"),
+            muted: Color::from_hex("#555555"),
+            shade: Color::from_hex("#00000080"),
         }
     }
 }
+
+fn hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+
+    RasterColor::hex(&hex)
+        .map(Color::from)
+        .map_err(|_| D::Error::custom(format!("'{}' is not a hex color", hex)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorScheme;
+    use crate::color::Color;
+
+    #[test]
+    fn color_scheme_parse() {
+        let scheme = ColorScheme::parse(r##"{ "background": "#FFFFFF", "text": "#000000" }"##).unwrap();
+
+        assert_eq!(scheme.background, Color::from_hex("#FFFFFF"));
+        assert_eq!(scheme.text, Color::from_hex("#000000"));
+        assert_eq!(scheme.foreground, ColorScheme::default().foreground);
+
+        assert!(ColorScheme::parse(r##"{ "background": "white" }"##).is_err());
+        assert!(ColorScheme::parse(r##"{ "background": "#aé…" }"##).is_err());
+    }
+}
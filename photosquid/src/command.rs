@@ -0,0 +1,101 @@
+use crate::{
+    arrange::Axis,
+    color::Color,
+    ocean::Similarity,
+    squid::{Squid, SquidId},
+    style::StyleRef,
+};
+use angular_units::Rad;
+use nalgebra_glm as glm;
+
+// Change to the document that tools and actions ask for instead of making themselves.
+// Commands are queued with App::emit and applied together once per frame,
+// so every change to the document goes through a single place
+#[derive(Clone)]
+pub enum DocumentCommand {
+    Insert(Box<Squid>),
+    InsertAndSelect(Box<Squid>),
+
+    // Swaps a squid out for others, which are added to the selection
+    Replace(SquidId, Vec<Squid>),
+
+    // Changes to a single squid, like the shape being drawn out or the point being edited.
+    // Squids are referred to by id, since squids inserted by earlier commands don't have references yet
+    FitToBox(SquidId, glm::Vec2, glm::Vec2),
+    MovePoint(SquidId, usize, glm::Vec2),
+    InsertPoint(SquidId, glm::Vec2, f32),
+    RemovePoint(SquidId, usize),
+    SetViewportSize(SquidId, glm::Vec2),
+    SetDpi(f32),
+
+    SelectAll,
+    InvertSelection,
+    SelectSimilar(Similarity),
+    SelectByTag(String),
+    ClearSelection,
+    DeleteSelected,
+    DuplicateSelected,
+    Paste,
+    PasteStyle,
+    ReorderSelected(usize),
+    ShiftSelectedWithinLayer(isize),
+    EqualizeSelectedGaps(Axis),
+    MatchSelectedSize(Axis),
+    SetSelectedAsViewport,
+    ConvertSelectedToPath,
+    SetSelectedColor(Color),
+    SetSelectedTags(Vec<String>),
+    RenameSelected(String),
+    SetStyleColor(StyleRef, Color),
+    SaveSelectedStyle,
+    UpdateSelectedStyle,
+    TranslateSelected(glm::Vec2),
+    RotateSelected(Rad<f32>),
+}
+
+impl DocumentCommand {
+    // Continuous changes, like recoloring while dragging around the color picker,
+    // are recorded once the mouse is let go instead of after every step
+    pub fn records_history(&self) -> bool {
+        !matches!(self, Self::SetSelectedColor(_) | Self::SetStyleColor(..))
+    }
+
+    // Squid changed by commands that only change a single squid
+    pub fn get_target(&self) -> Option<SquidId> {
+        match self {
+            Self::FitToBox(id, ..) | Self::MovePoint(id, ..) | Self::InsertPoint(id, ..) | Self::RemovePoint(id, _) | Self::SetViewportSize(id, _) => Some(*id),
+            _ => None,
+        }
+    }
+
+    // Makes the change for commands that only change a single squid
+    pub fn apply_to_squid(&self, squid: &mut Squid) {
+        match self {
+            Self::FitToBox(_, min, max) => squid.fit_to_box(min, max),
+            Self::MovePoint(_, index, world_position) => squid.move_editable_point(*index, world_position),
+            Self::InsertPoint(_, world_position, tolerance) => {
+                squid.insert_editable_point(world_position, *tolerance);
+            }
+            Self::RemovePoint(_, index) => {
+                squid.remove_editable_point(*index);
+            }
+            Self::SetViewportSize(_, size) => squid.set_viewport_size(*size),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DocumentCommand;
+    use crate::{color::Color, style::StyleRef};
+    use slotmap::Key;
+
+    #[test]
+    fn records_history() {
+        assert!(DocumentCommand::DeleteSelected.records_history());
+        assert!(DocumentCommand::ReorderSelected(0).records_history());
+        assert!(!DocumentCommand::SetSelectedColor(Color::white()).records_history());
+        assert!(!DocumentCommand::SetStyleColor(StyleRef::null(), Color::white()).records_history());
+    }
+}
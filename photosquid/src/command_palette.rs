@@ -0,0 +1,233 @@
+use crate::{
+    aabb::AABB,
+    affine::Affine2,
+    as_values::AsValues,
+    draw_text::{draw_text, get_text_width},
+    keymap::Action,
+    render_ctx::RenderCtx,
+};
+use glium::glutin::event::VirtualKeyCode;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Overlay for searching through every command and performing one
+pub struct CommandPalette {
+    query: String,
+    commands: Vec<Command>,
+    results: Vec<usize>,
+    highlighted: usize,
+}
+
+pub struct Command {
+    pub action: Action,
+    pub name: String,
+    pub shortcut: String,
+}
+
+pub enum CommandPaletteResponse {
+    Continue,
+    Close,
+    Choose(Action),
+}
+
+impl CommandPalette {
+    const WIDTH: f32 = 400.0;
+    const QUERY_HEIGHT: f32 = 40.0;
+    const RESULT_HEIGHT: f32 = 30.0;
+    const MAX_RESULTS: usize = 10;
+    const TOP: f32 = 16.0;
+
+    pub fn new(commands: Vec<Command>) -> Self {
+        let mut command_palette = Self {
+            query: String::new(),
+            commands,
+            results: vec![],
+            highlighted: 0,
+        };
+        command_palette.update_results();
+        command_palette
+    }
+
+    fn update_results(&mut self) {
+        let mut scored: Vec<(usize, i32)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| Some((i, get_fuzzy_score(&self.query, &command.name)?)))
+            .collect();
+
+        // Sorting is stable, so equally good matches stay in their usual order
+        scored.sort_by_key(|(_, score)| -score);
+
+        self.results = scored.into_iter().map(|(i, _)| i).take(Self::MAX_RESULTS).collect();
+        self.highlighted = 0;
+    }
+
+    pub fn type_character(&mut self, character: char) {
+        if !character.is_control() {
+            self.query.push(character);
+            self.update_results();
+        }
+    }
+
+    pub fn press_key(&mut self, key: VirtualKeyCode) -> CommandPaletteResponse {
+        match key {
+            VirtualKeyCode::Escape => return CommandPaletteResponse::Close,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                return match self.results.get(self.highlighted) {
+                    Some(index) => CommandPaletteResponse::Choose(self.commands[*index].action),
+                    None => CommandPaletteResponse::Close,
+                }
+            }
+            VirtualKeyCode::Back => {
+                self.query.pop();
+                self.update_results();
+            }
+            VirtualKeyCode::Up => self.highlighted = self.highlighted.saturating_sub(1),
+            VirtualKeyCode::Down if self.highlighted + 1 < self.results.len() => self.highlighted += 1,
+            _ => (),
+        }
+
+        CommandPaletteResponse::Continue
+    }
+
+    pub fn click(&self, position: &glm::Vec2, window_width: f32) -> CommandPaletteResponse {
+        let area = Self::get_area(window_width, self.results.len());
+
+        if !area.intersecting_point(position.x, position.y) {
+            return CommandPaletteResponse::Close;
+        }
+
+        let results_top = area.min_y + Self::QUERY_HEIGHT;
+
+        if position.y < results_top {
+            return CommandPaletteResponse::Continue;
+        }
+
+        let index = ((position.y - results_top) / Self::RESULT_HEIGHT) as usize;
+
+        match self.results.get(index) {
+            Some(index) => CommandPaletteResponse::Choose(self.commands[*index].action),
+            None => CommandPaletteResponse::Continue,
+        }
+    }
+
+    fn get_area(window_width: f32, result_count: usize) -> AABB {
+        let height = Self::QUERY_HEIGHT + Self::RESULT_HEIGHT * result_count as f32;
+        AABB::new(0.5 * (window_width - Self::WIDTH), Self::TOP, Self::WIDTH, height)
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+        let area = Self::get_area(ctx.width, self.results.len());
+
+        // Render background
+        {
+            let mesh = ctx.square_xyzuv;
+            let identity = Affine2::identity();
+            let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
+            let transformation = Affine2::translation(&glm::vec2(area.center_x(), area.center_y()));
+            let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
+
+            let uniforms = glium::uniform! {
+                transformation: transformation.as_values(),
+                view: identity.as_values(),
+                projection: ctx.projection.as_values(),
+                rectangle_color: ctx.color_scheme.dark_ribbon.as_values(),
+                dimensions: [quad_dimensions.x, quad_dimensions.y],
+                height_scale: 1.0f32,
+                do_shadow: 1
+            };
+
+            let draw_parameters = glium::DrawParameters {
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            };
+
+            ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.rounded_rectangle_shader, &uniforms, &draw_parameters)
+                .unwrap();
+        }
+
+        let left = area.min_x + 16.0;
+
+        // Draw query
+        let (query, query_color) = if self.query.is_empty() {
+            ("Type a command...", ctx.color_scheme.label)
+        } else {
+            (self.query.as_str(), ctx.color_scheme.text)
+        };
+
+        draw_text(
+            text_system,
+            font.clone(),
+            query,
+            &glm::vec2(left, area.min_y + 0.5 * Self::QUERY_HEIGHT + 6.0),
+            ctx,
+            query_color,
+        );
+
+        // Draw results along with their shortcuts
+        for (i, index) in self.results.iter().enumerate() {
+            let command = &self.commands[*index];
+            let y = area.min_y + Self::QUERY_HEIGHT + (i as f32 + 0.5) * Self::RESULT_HEIGHT + 6.0;
+
+            let color = if i == self.highlighted {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+
+            draw_text(text_system, font.clone(), &command.name, &glm::vec2(left, y), ctx, color);
+
+            let shortcut_width = get_text_width(&font, &command.shortcut);
+            let shortcut_position = glm::vec2(area.max_x - 16.0 - shortcut_width, y);
+            draw_text(text_system, font.clone(), &command.shortcut, &shortcut_position, ctx, ctx.color_scheme.label);
+        }
+    }
+}
+
+// How well a query matches a name, where every character of the query has to appear in order.
+// Matches at the start of words and runs of consecutive characters score higher
+pub fn get_fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for character in query.to_lowercase().chars().filter(|character| !character.is_whitespace()) {
+        let index = next + name[next..].iter().position(|c| *c == character)?;
+
+        if index == 0 || name[index - 1] == ' ' {
+            score += 8;
+        }
+
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 6;
+        }
+
+        // Skipping over characters is penalized
+        score -= (index - next) as i32;
+
+        previous_match = Some(index);
+        next = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_fuzzy_score;
+
+    #[test]
+    fn fuzzy_score() {
+        assert!(get_fuzzy_score("sva", "Save As").is_some());
+        assert!(get_fuzzy_score("", "Undo").is_some());
+        assert!(get_fuzzy_score("xyz", "Undo").is_none());
+        assert!(get_fuzzy_score("odnu", "Undo").is_none());
+
+        // Starts of words and consecutive characters are better matches
+        assert!(get_fuzzy_score("bf", "Bring Forward") > get_fuzzy_score("bf", "Tab Fix"));
+        assert!(get_fuzzy_score("save", "Save") > get_fuzzy_score("save", "Send Away Via Email"));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{aabb::AABB, as_values::AsValues, color::Color, draw_text, render_ctx::RenderCtx};
-use glium::glutin::event::MouseButton;
-use glium_text_rusttype::{FontTexture, TextDisplay, TextSystem};
+use crate::{aabb::AABB, affine::Affine2, arrange::Axis, as_values::AsValues, color::Color, draw_text, ocean::Similarity, render_ctx::RenderCtx};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
 use std::rc::Rc;
 
@@ -8,14 +8,27 @@ pub struct ContextMenu {
     position: glm::Vec2,
     options: Vec<ContextMenuOption>,
     background_color: Color,
+    open_submenu: Option<Box<ContextMenu>>,
+    highlighted: Option<usize>,
 }
 
+#[derive(Clone)]
 pub struct ContextMenuOption {
     friendly_name: String,
     friendly_shortcut: String,
-    action: ContextAction,
-    text_display: Option<TextDisplay<Rc<FontTexture>>>,
-    shortcut_display: Option<TextDisplay<Rc<FontTexture>>>,
+    kind: ContextMenuOptionKind,
+}
+
+#[derive(Clone)]
+enum ContextMenuOptionKind {
+    Action(ContextAction),
+    Submenu(Vec<ContextMenuOption>),
+}
+
+pub enum ContextMenuResponse {
+    Continue,
+    Close,
+    Choose(ContextAction),
 }
 
 #[derive(Copy, Clone)]
@@ -26,6 +39,21 @@ pub enum ContextAction {
     RotateSelected,
     ScaleSelected,
     Collectively,
+    Undo,
+    Redo,
+    EditPoints,
+    SetAsViewport,
+    ConvertToPath,
+    SelectSimilar(Similarity),
+    SelectByTag,
+    EditTags,
+    RenameSelected,
+    BringToFront,
+    BringForward,
+    SendBackward,
+    SendToBack,
+    EqualGaps(Axis),
+    MatchSize(Axis),
 }
 
 impl ContextMenu {
@@ -34,20 +62,88 @@ impl ContextMenu {
             position,
             options,
             background_color,
+            open_submenu: None,
+            highlighted: None,
         }
     }
 
-    pub fn click(&self, button: MouseButton, position: &glm::Vec2) -> Option<ContextAction> {
-        let area = self.get_area();
+    const HEIGHT_PER_ENTRY: f32 = 30.0;
+
+    fn get_option_index_at(&self, position: &glm::Vec2) -> Option<usize> {
+        if !self.get_area().intersecting_point(position.x, position.y) {
+            return None;
+        }
+
+        let y_offset = 8.0f32 * 0.8;
+        let option_index = ((position.y - self.position.y + y_offset) / Self::HEIGHT_PER_ENTRY) as usize;
+        Some(option_index.clamp(0, self.options.len() - 1))
+    }
+
+    // Chooses an option, where options with a submenu open it next to themselves
+    // and only one submenu is open at each level
+    fn activate(&mut self, option_index: usize) -> ContextMenuResponse {
+        match &self.options[option_index].kind {
+            ContextMenuOptionKind::Action(action) => ContextMenuResponse::Choose(*action),
+            ContextMenuOptionKind::Submenu(options) => {
+                let submenu_position = self.position + glm::vec2(self.get_area().width(), Self::HEIGHT_PER_ENTRY * option_index as f32);
+                self.open_submenu = Some(Box::new(ContextMenu::new(submenu_position, options.clone(), self.background_color)));
+                self.highlighted = Some(option_index);
+                ContextMenuResponse::Continue
+            }
+        }
+    }
+
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2) -> ContextMenuResponse {
+        if let Some(submenu) = &mut self.open_submenu {
+            match submenu.click(button, position) {
+                ContextMenuResponse::Close => (),
+                response => return response,
+            }
+        }
+
+        match self.get_option_index_at(position) {
+            Some(option_index) if button == MouseButton::Left => self.activate(option_index),
+            _ => ContextMenuResponse::Close,
+        }
+    }
+
+    // Arrow keys move between options and in and out of submenus,
+    // the deepest open submenu is the one that's navigated
+    pub fn press_key(&mut self, key: VirtualKeyCode) -> ContextMenuResponse {
+        if let Some(submenu) = &mut self.open_submenu {
+            if key == VirtualKeyCode::Left && submenu.open_submenu.is_none() {
+                self.open_submenu = None;
+                return ContextMenuResponse::Continue;
+            }
+
+            return submenu.press_key(key);
+        }
 
-        if button == MouseButton::Left && area.intersecting_point(position.x, position.y) {
-            let y_offset = 8.0f32 * 0.8;
-            let height_per_entry = 30.0f32;
-            let option_index = ((position.y - self.position.y + y_offset) / height_per_entry) as usize;
-            let option_index = option_index.clamp(0, self.options.len() - 1);
-            Some(self.options[option_index].action)
-        } else {
-            None
+        let last = self.options.len() - 1;
+
+        match key {
+            VirtualKeyCode::Escape => ContextMenuResponse::Close,
+            VirtualKeyCode::Up => {
+                self.highlighted = Some(self.highlighted.map_or(last, |index| index.saturating_sub(1)));
+                ContextMenuResponse::Continue
+            }
+            VirtualKeyCode::Down => {
+                self.highlighted = Some(self.highlighted.map_or(0, |index| (index + 1).min(last)));
+                ContextMenuResponse::Continue
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Right => match self.highlighted {
+                Some(index) if key != VirtualKeyCode::Right || matches!(self.options[index].kind, ContextMenuOptionKind::Submenu(_)) => {
+                    let response = self.activate(index);
+
+                    if let Some(submenu) = &mut self.open_submenu {
+                        submenu.highlighted = Some(0);
+                    }
+
+                    response
+                }
+                _ => ContextMenuResponse::Continue,
+            },
+            _ => ContextMenuResponse::Continue,
         }
     }
 
@@ -57,21 +153,30 @@ impl ContextMenu {
         AABB::new(self.position.x, self.position.y - 12.0, width, height)
     }
 
-    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, mouse: Option<glm::Vec2>) {
         let area = self.get_area();
 
+        // Hovering over an option highlights it, unless it's covered by a submenu
+        let over_submenu = self
+            .open_submenu
+            .as_ref()
+            .is_some_and(|submenu| mouse.is_some_and(|mouse| submenu.get_area().intersecting_point(mouse.x, mouse.y)));
+
+        if let Some(option_index) = mouse.filter(|_| !over_submenu).and_then(|mouse| self.get_option_index_at(&mouse)) {
+            self.highlighted = Some(option_index);
+        }
+
         // Render context menu background
         {
             let mesh = ctx.square_xyzuv;
-            let identity = glm::identity::<f32, 4>();
+            let identity = Affine2::identity();
             let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
             let dead_space = quad_dimensions - glm::vec2(area.width(), area.height());
-            let transformation = glm::translation(&glm::vec3(
+            let transformation = Affine2::translation(&glm::vec2(
                 area.min_x + quad_dimensions.x * 0.5 - dead_space.x * 0.5,
                 area.min_y + quad_dimensions.y * 0.5 - dead_space.y * 0.5,
-                0.0,
             ));
-            let transformation = glm::scale(&transformation, &glm::vec3(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5, 0.0));
+            let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
 
             let uniforms = glium::uniform! {
                 transformation: transformation.as_values(),
@@ -92,25 +197,37 @@ impl ContextMenu {
                 .unwrap();
         }
 
-        for (i, option) in self.options.iter_mut().enumerate() {
+        if let Some(i) = self.highlighted {
+            let position = self.position + glm::vec2(4.0, Self::HEIGHT_PER_ENTRY * i as f32 - 8.0 * 0.8);
+            ctx.ribbon_mesh.render(
+                ctx,
+                position,
+                glm::vec2(area.width() - 8.0, Self::HEIGHT_PER_ENTRY),
+                &ctx.color_scheme.light_ribbon,
+            );
+        }
+
+        for (i, option) in self.options.iter().enumerate() {
             // Draw friendly name
-            let text_display = option.get_text_display(text_system, font.clone());
-            let transformation = glm::translation(&glm::vec2_to_vec3(&self.position));
-            let transformation = glm::translate(&transformation, &glm::vec3(16.0, (16.0 * 0.8) + 30.0 * i as f32, 0.0));
-            let transformation = glm::scale(&transformation, &glm::vec3(16.0, -16.0, 0.0));
-            let matrix = ctx.projection * transformation;
-            ctx.draw_text(text_display, text_system, matrix, (1.0, 1.0, 1.0, 1.0)).unwrap();
+            let transformation = Affine2::translation(&self.position);
+            let transformation = transformation.translate(&glm::vec2(16.0, (16.0 * 0.8) + 30.0 * i as f32));
+            let transformation = transformation.scale(&glm::vec2(16.0, -16.0));
+            let matrix = ctx.projection * transformation.to_mat4();
+            ctx.draw_text(text_system, font.clone(), &option.friendly_name, matrix, ctx.color_scheme.text.into());
 
             // Draw friendly shortcut
-            let text_display = option.get_shortcut_display(text_system, font.clone());
-            let transformation = glm::translation(&glm::vec2_to_vec3(&self.position));
-            let transformation = glm::translate(
-                &transformation,
-                &glm::vec3(area.width() - 14.0 - text_display.get_width() * 16.0, (16.0 * 0.8) + 30.0 * i as f32, 0.0),
-            );
-            let transformation = glm::scale(&transformation, &glm::vec3(16.0, -16.0, 0.0));
-            let matrix = ctx.projection * transformation;
-            ctx.draw_text(text_display, text_system, matrix, (0.5, 0.5, 0.5, 1.0)).unwrap();
+            let transformation = Affine2::translation(&self.position);
+            let transformation = transformation.translate(&glm::vec2(
+                area.width() - 14.0 - draw_text::get_text_width(&font, &option.friendly_shortcut),
+                (16.0 * 0.8) + 30.0 * i as f32,
+            ));
+            let transformation = transformation.scale(&glm::vec2(16.0, -16.0));
+            let matrix = ctx.projection * transformation.to_mat4();
+            ctx.draw_text(text_system, font.clone(), &option.friendly_shortcut, matrix, ctx.color_scheme.label.into());
+        }
+
+        if let Some(submenu) = &mut self.open_submenu {
+            submenu.render(ctx, text_system, font, mouse);
         }
     }
 }
@@ -120,17 +237,53 @@ impl ContextMenuOption {
         Self {
             friendly_name: friendly_name.into(),
             friendly_shortcut: friendly_shortcut.into(),
-            action,
-            text_display: None,
-            shortcut_display: None,
+            kind: ContextMenuOptionKind::Action(action),
         }
     }
 
-    pub fn get_text_display(&mut self, text_system: &TextSystem, font: Rc<FontTexture>) -> &TextDisplay<Rc<FontTexture>> {
-        draw_text::get_or_make_display(&mut self.text_display, text_system, font, &self.friendly_name)
+    pub fn submenu(friendly_name: impl Into<String>, options: Vec<ContextMenuOption>) -> Self {
+        Self {
+            friendly_name: friendly_name.into(),
+            friendly_shortcut: ">".into(),
+            kind: ContextMenuOptionKind::Submenu(options),
+        }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextAction, ContextMenu, ContextMenuOption, ContextMenuResponse};
+    use crate::color::Color;
+    use glium::glutin::event::VirtualKeyCode::*;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn context_menu_keyboard_navigation() {
+        let options = vec![
+            ContextMenuOption::new("Delete", "X", ContextAction::DeleteSelected),
+            ContextMenuOption::submenu(
+                "Arrange",
+                vec![
+                    ContextMenuOption::new("Bring to Front", "", ContextAction::BringToFront),
+                    ContextMenuOption::new("Send to Back", "", ContextAction::SendToBack),
+                ],
+            ),
+        ];
+        let mut context_menu = ContextMenu::new(glm::zero(), options, Color::white());
+
+        assert!(matches!(context_menu.press_key(Down), ContextMenuResponse::Continue));
+        assert!(matches!(context_menu.press_key(Down), ContextMenuResponse::Continue));
+        assert!(matches!(context_menu.press_key(Right), ContextMenuResponse::Continue));
+        assert!(matches!(context_menu.press_key(Down), ContextMenuResponse::Continue));
+        assert!(matches!(context_menu.press_key(Return), ContextMenuResponse::Choose(ContextAction::SendToBack)));
 
-    pub fn get_shortcut_display(&mut self, text_system: &TextSystem, font: Rc<FontTexture>) -> &TextDisplay<Rc<FontTexture>> {
-        draw_text::get_or_make_display(&mut self.shortcut_display, text_system, font, &self.friendly_shortcut)
+        // Leaving the submenu goes back to navigating the top level
+        context_menu.press_key(Left);
+        context_menu.press_key(Up);
+        assert!(matches!(
+            context_menu.press_key(Return),
+            ContextMenuResponse::Choose(ContextAction::DeleteSelected)
+        ));
+        assert!(matches!(context_menu.press_key(Escape), ContextMenuResponse::Close));
     }
 }
@@ -0,0 +1,41 @@
+// Tracks whether the window has to be redrawn because of something that happened since the last frame,
+// so that nothing is drawn while the app sits idle. Animations are checked separately while they play
+pub struct Damage {
+    damaged: bool,
+}
+
+impl Damage {
+    pub fn new() -> Self {
+        Self { damaged: true }
+    }
+
+    pub fn mark(&mut self) {
+        self.damaged = true;
+    }
+
+    // Called once a frame has been drawn
+    pub fn clear(&mut self) {
+        self.damaged = false;
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.damaged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Damage;
+
+    #[test]
+    fn clear_damage() {
+        let mut damage = Damage::new();
+        assert!(damage.is_damaged());
+
+        damage.clear();
+        assert!(!damage.is_damaged());
+
+        damage.mark();
+        assert!(damage.is_damaged());
+    }
+}
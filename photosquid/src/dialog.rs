@@ -1,26 +1,49 @@
 use native_dialog::{self, FileDialog};
 use std::path::PathBuf;
 
-pub fn ask_open() -> Result<Option<PathBuf>, String> {
-    match FileDialog::new().add_filter("Photosquid Project", &["photosquid"]).show_open_single_file() {
+pub struct Filter<'a> {
+    pub description: &'a str,
+    pub extensions: &'a [&'a str],
+}
+
+const PROJECT_FILTER: Filter = Filter {
+    description: "Photosquid Project",
+    extensions: &["photosquid"],
+};
+
+pub fn ask_open(filter: Option<Filter>) -> Result<Option<PathBuf>, String> {
+    let filter = filter.unwrap_or(PROJECT_FILTER);
+
+    match FileDialog::new().add_filter(filter.description, filter.extensions).show_open_single_file() {
         Ok(selection) => Ok(selection),
         Err(_) => Err("Failed to ask user to open a file".into()),
     }
 }
 
-pub struct Filter<'a> {
-    pub description: &'a str,
-    pub extension: &'a str,
+pub fn ask_save(filter: Option<Filter>) -> Result<Option<PathBuf>, String> {
+    let filter = filter.unwrap_or(PROJECT_FILTER);
+
+    match FileDialog::new().add_filter(filter.description, filter.extensions).show_save_single_file() {
+        Ok(selection) => Ok(selection),
+        Err(_) => Err("Failed to ask user to save a file".into()),
+    }
 }
 
-pub fn ask_save(filter: Option<Filter>) -> Result<Option<PathBuf>, String> {
-    let filter = filter.unwrap_or(Filter {
-        description: "Photosquid Project",
-        extension: "photosquid",
-    });
+// Lets the user choose between several formats to save as
+pub fn ask_save_one_of(filters: &[Filter]) -> Result<Option<PathBuf>, String> {
+    let dialog = filters
+        .iter()
+        .fold(FileDialog::new(), |dialog, filter| dialog.add_filter(filter.description, filter.extensions));
 
-    match FileDialog::new().add_filter(filter.description, &[filter.extension]).show_save_single_file() {
+    match dialog.show_save_single_file() {
         Ok(selection) => Ok(selection),
         Err(_) => Err("Failed to ask user to save a file".into()),
     }
 }
+
+pub fn ask_folder() -> Result<Option<PathBuf>, String> {
+    match FileDialog::new().show_open_single_dir() {
+        Ok(selection) => Ok(selection),
+        Err(_) => Err("Failed to ask user to choose a folder".into()),
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{camera::Camera, history::History, ocean::Ocean, selection::Selection, smooth::Smooth};
+use nalgebra_glm as glm;
+use std::path::PathBuf;
+
+// Everything that belongs to a single open project.
+// The app works on the active document's state directly, so the active entry
+// in the list of documents is only a placeholder until it's switched away from
+pub struct Document {
+    pub ocean: Ocean,
+    pub history: History<Ocean>,
+    pub camera: Smooth<Camera>,
+    pub selections: Vec<Selection>,
+    pub filename: Option<PathBuf>,
+}
+
+impl Document {
+    pub fn new(dimensions: glm::Vec2) -> Self {
+        Self {
+            ocean: Default::default(),
+            history: Default::default(),
+            camera: Smooth::new(Camera::identity(dimensions), None),
+            selections: vec![],
+            filename: None,
+        }
+    }
+}
+
+pub fn get_document_name(filename: Option<&PathBuf>) -> String {
+    filename
+        .and_then(|filename| filename.file_stem())
+        .map_or("Untitled".into(), |name| name.to_string_lossy().into())
+}
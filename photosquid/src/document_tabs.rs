@@ -0,0 +1,139 @@
+use crate::{
+    aabb::AABB,
+    draw_text::{draw_text, get_text_width},
+    render_ctx::RenderCtx,
+};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Bar along the top of the canvas with a tab for each open document,
+// followed by a button for starting a new one
+pub struct DocumentTabs {
+    tabs: Vec<DocumentTab>,
+    new_area: Option<AABB>,
+}
+
+struct DocumentTab {
+    name: String,
+    area: Option<AABB>,
+    close_area: Option<AABB>,
+}
+
+#[derive(Copy, Clone)]
+pub enum DocumentTabsTarget {
+    Switch(usize),
+    Close(usize),
+    New,
+}
+
+impl DocumentTabs {
+    pub const HEIGHT: f32 = 32.0;
+    const PADDING: f32 = 12.0;
+    const CLOSE: &'static str = "x";
+    const NEW: &'static str = "+";
+
+    pub fn new() -> Self {
+        Self { tabs: vec![], new_area: None }
+    }
+
+    pub fn update(&mut self, names: Vec<String>) {
+        if self.tabs.iter().map(|tab| &tab.name).eq(names.iter()) {
+            return;
+        }
+
+        self.tabs = names
+            .into_iter()
+            .map(|name| DocumentTab {
+                name,
+                area: None,
+                close_area: None,
+            })
+            .collect();
+    }
+
+    // Spans the canvas between the side panels
+    pub fn get_area(panel_areas: &[AABB; 2]) -> AABB {
+        let left = panel_areas[0].max_x;
+        let right = panel_areas[1].min_x;
+        AABB::new(left, 0.0, (right - left).max(0.0), Self::HEIGHT)
+    }
+
+    pub fn click(&self, position: &glm::Vec2) -> Option<DocumentTabsTarget> {
+        let is_over = |area: &Option<AABB>| area.is_some_and(|area| area.intersecting_point(position.x, position.y));
+
+        if is_over(&self.new_area) {
+            return Some(DocumentTabsTarget::New);
+        }
+
+        self.tabs.iter().enumerate().find_map(|(i, tab)| {
+            if is_over(&tab.close_area) {
+                Some(DocumentTabsTarget::Close(i))
+            } else if is_over(&tab.area) {
+                Some(DocumentTabsTarget::Switch(i))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB, active: usize) {
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x, area.min_y),
+            glm::vec2(area.width(), area.height()),
+            &ctx.color_scheme.dark_ribbon,
+        );
+
+        let baseline = area.center_y() + 6.0;
+        let close_width = get_text_width(&font, Self::CLOSE);
+        let mut x = area.min_x;
+
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            let name_width = get_text_width(&font, &tab.name);
+            let width = Self::PADDING + name_width + Self::PADDING + close_width + Self::PADDING;
+            let tab_area = AABB::new(x, area.min_y, width, area.height());
+
+            if i == active {
+                ctx.ribbon_mesh.render(
+                    ctx,
+                    glm::vec2(tab_area.min_x, tab_area.min_y),
+                    glm::vec2(tab_area.width(), tab_area.height()),
+                    &ctx.color_scheme.light_ribbon,
+                );
+            }
+
+            let color = if i == active {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+
+            let close_x = x + Self::PADDING + name_width + Self::PADDING;
+            draw_text(text_system, font.clone(), &tab.name, &glm::vec2(x + Self::PADDING, baseline), ctx, color);
+            draw_text(
+                text_system,
+                font.clone(),
+                Self::CLOSE,
+                &glm::vec2(close_x, baseline),
+                ctx,
+                ctx.color_scheme.muted,
+            );
+
+            tab.area = Some(tab_area);
+            tab.close_area = Some(AABB::new(close_x - 4.0, area.min_y, close_width + 8.0, area.height()));
+            x += width;
+        }
+
+        let new_width = Self::PADDING + get_text_width(&font, Self::NEW) + Self::PADDING;
+        draw_text(
+            text_system,
+            font,
+            Self::NEW,
+            &glm::vec2(x + Self::PADDING, baseline),
+            ctx,
+            ctx.color_scheme.label,
+        );
+        self.new_area = Some(AABB::new(x, area.min_y, new_width, area.height()));
+    }
+}
@@ -6,6 +6,10 @@ pub struct Dragging {
     pub down: glm::Vec2,
     pub current: glm::Vec2,
     pub last: glm::Vec2,
+
+    // How far squids being moved were nudged to line up with others,
+    // which is taken back before each move so that they don't drift away from the mouse
+    pub object_snap: glm::Vec2,
 }
 
 impl Dragging {
@@ -16,6 +20,7 @@ impl Dragging {
             down: position,
             current: position,
             last: position,
+            object_snap: glm::zero(),
         }
     }
 
@@ -1,53 +1,44 @@
-use crate::{color::Color, render_ctx::RenderCtx};
-use glium_text_rusttype::{FontTexture, TextDisplay, TextSystem};
+use crate::{affine::Affine2, color::Color, render_ctx::RenderCtx};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
 use std::rc::Rc;
 
-pub fn get_or_make_display<'a>(
-    persistent: &'a mut Option<TextDisplay<Rc<FontTexture>>>,
-    text_system: &TextSystem,
-    font: Rc<FontTexture>,
-    text: &str,
-) -> &'a TextDisplay<Rc<FontTexture>> {
-    if persistent.is_none() {
-        let text_display = TextDisplay::new(text_system, font, text);
-        *persistent = Some(text_display);
+// Width of a line of text in pixels
+pub fn get_text_width(font: &FontTexture, text: &str) -> f32 {
+    font.measure(text) * 16.0
+}
+
+// Splits text into lines no wider than 'max_width', breaking between words
+pub fn wrap_text(font: &FontTexture, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = vec![];
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let extended = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+
+            if !line.is_empty() && get_text_width(font, &extended) > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = extended;
+            }
+        }
+
+        lines.push(line);
     }
-    return persistent.as_ref().unwrap();
+
+    lines
 }
 
-pub fn draw_text<'a>(
-    persistent: &'a mut Option<TextDisplay<Rc<FontTexture>>>,
-    text_system: &TextSystem,
-    font: Rc<FontTexture>,
-    text: &str,
-    location: &glm::Vec2,
-    ctx: &mut RenderCtx,
-    color: Color,
-) {
-    get_or_make_display(persistent, text_system, font, text);
-
-    let text_display = persistent.as_ref().unwrap();
-    let transformation = glm::translation(&glm::vec3(location.x, location.y, 0.0));
-    let transformation = glm::scale(&transformation, &glm::vec3(16.0, -16.0, 0.0));
-    let matrix = ctx.projection * transformation;
-    ctx.draw_text(text_display, text_system, matrix, color.into()).unwrap();
+pub fn draw_text(text_system: &TextSystem, font: Rc<FontTexture>, text: &str, location: &glm::Vec2, ctx: &mut RenderCtx, color: Color) {
+    let transformation = Affine2::translation(location);
+    let transformation = transformation.scale(&glm::vec2(16.0, -16.0));
+    let matrix = ctx.projection * transformation.to_mat4();
+    ctx.draw_text(text_system, font, text, matrix, color.into());
 }
 
-pub fn draw_text_centered<'a>(
-    persistent: &'a mut Option<TextDisplay<Rc<FontTexture>>>,
-    text_system: &TextSystem,
-    font: Rc<FontTexture>,
-    text: &str,
-    location: &glm::Vec2,
-    ctx: &mut RenderCtx,
-    color: Color,
-) {
-    get_or_make_display(persistent, text_system, font, text);
-
-    let text_display = persistent.as_ref().unwrap();
-    let transformation = glm::translation(&glm::vec3(location.x - 0.5 * text_display.get_width() * 16.0, location.y, 0.0));
-    let transformation = glm::scale(&transformation, &glm::vec3(16.0, -16.0, 0.0));
-    let matrix = ctx.projection * transformation;
-    ctx.draw_text(text_display, text_system, matrix, color.into()).unwrap();
+pub fn draw_text_centered(text_system: &TextSystem, font: Rc<FontTexture>, text: &str, location: &glm::Vec2, ctx: &mut RenderCtx, color: Color) {
+    let width = get_text_width(&font, text);
+    draw_text(text_system, font, text, &glm::vec2(location.x - 0.5 * width, location.y), ctx, color);
 }
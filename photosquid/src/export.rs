@@ -1,7 +1,14 @@
-use std::path::PathBuf;
+use glium::texture::SrgbTexture2d;
+use nalgebra_glm as glm;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 use svg::Document;
 
-use crate::{data::RectData, ocean::Ocean};
+use crate::{aabb::AABB, camera::Camera, ocean::Ocean, png, squid::SquidRef};
 
 // Let's use our own custom vertex type instead of the default one.
 #[derive(Copy, Clone, Debug)]
@@ -9,20 +16,301 @@ struct Vertex {
     position: [f32; 2],
 }
 
-pub fn export(filename: PathBuf, viewport: &RectData, ocean: &Ocean) -> std::io::Result<()> {
-    let position = viewport.position.reveal();
-    let size = viewport.size;
+// How the area of the exported document is chosen
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ExportBounds {
+    // The selected viewport
+    Viewport,
 
-    let mut document = Document::new().set("viewBox", (position.x - size.x * 0.5, position.y - size.y * 0.5, size.x, size.y));
+    // Tightly around the visible content
+    Content,
+}
+
+// Output size of the exported document
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ExportSize {
+    // Same as the bounds in world units
+    Native,
+
+    // Multiple of the bounds
+    Scale(f32),
+
+    // Explicit width and height, content is fit inside
+    Dimensions(f32, f32),
+}
+
+impl ExportSize {
+    // Parses sizes like "512x512" or scales like "2x", anything else (e.g. "auto") is left at the native size
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim().to_lowercase();
+
+        if let Some((width, height)) = text.split_once('x') {
+            if let (Ok(width), Ok(height)) = (width.trim().parse::<f32>(), height.trim().parse::<f32>()) {
+                if width > 0.0 && height > 0.0 {
+                    return Self::Dimensions(width, height);
+                }
+            }
+        }
+
+        match text.trim_end_matches('x').trim().parse::<f32>() {
+            Ok(scale) if scale > 0.0 => Self::Scale(scale),
+            _ => Self::Native,
+        }
+    }
 
-    for squid_ref in ocean.get_squids_lowest() {
+    pub fn get_dimensions(&self, view_box: &AABB) -> Option<(f32, f32)> {
+        match *self {
+            Self::Native => None,
+            Self::Scale(scale) => Some((view_box.width() * scale, view_box.height() * scale)),
+            Self::Dimensions(width, height) => Some((width, height)),
+        }
+    }
+}
+
+pub struct ExportOptions {
+    pub bounds: ExportBounds,
+
+    // Extra space added around each side of the bounds
+    pub margin: f32,
+
+    pub size: ExportSize,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            bounds: ExportBounds::Viewport,
+            margin: 0.0,
+            size: ExportSize::Native,
+        }
+    }
+}
+
+pub fn export(filename: &Path, view_box: &AABB, size: &ExportSize, ocean: &Ocean) -> std::io::Result<()> {
+    let mut document = Document::new().set("viewBox", (view_box.min_x, view_box.min_y, view_box.width(), view_box.height()));
+
+    if let Some((width, height)) = size.get_dimensions(view_box) {
+        document = document.set("width", width).set("height", height);
+    }
+
+    for (squid_ref, opacity) in ocean.get_visible_squids_lowest() {
         if let Some(squid) = ocean.get(squid_ref) {
-            squid.build(&mut document);
+            squid.build(&mut document, opacity);
         }
     }
 
     // Save svg file
-    svg::save(&filename, &document)
+    svg::save(filename, &document)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Svg,
+    Png,
+}
+
+impl ExportFormat {
+    pub fn get_extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+// Output that is produced when exporting all profiles
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExportProfile {
+    pub format: ExportFormat,
+    pub size: ExportSize,
+    pub folder: PathBuf,
+
+    // Viewport the profile belongs to, or None for every viewport
+    pub viewport: Option<SquidRef>,
+}
+
+impl ExportProfile {
+    // Short description, e.g. "PNG @2x"
+    pub fn get_name(&self) -> String {
+        let format = self.format.get_extension().to_uppercase();
+
+        match self.size {
+            ExportSize::Native => format,
+            ExportSize::Scale(scale) => format!("{} @{}x", format, scale),
+            ExportSize::Dimensions(width, height) => format!("{} {}x{}", format, width, height),
+        }
+    }
+
+    // Distinguishes outputs of different profiles for the same viewport
+    fn get_suffix(&self) -> String {
+        match self.size {
+            ExportSize::Native => "".into(),
+            ExportSize::Scale(1.0) => "".into(),
+            ExportSize::Scale(scale) => format!("@{}x", scale),
+            ExportSize::Dimensions(width, height) => format!("-{}x{}", width, height),
+        }
+    }
+}
+
+pub struct ProfileOutput {
+    pub path: PathBuf,
+    pub format: ExportFormat,
+    pub size: ExportSize,
+    pub view_box: AABB,
+}
+
+// Every file that should be written to export all profiles, named after their viewports
+pub fn get_profile_outputs(ocean: &Ocean) -> Vec<ProfileOutput> {
+    let mut outputs = vec![];
+    let mut name_uses: HashMap<String, usize> = HashMap::new();
+
+    for reference in ocean.get_squids_lowest() {
+        let squid = match ocean.get(reference) {
+            Some(squid) => squid,
+            None => continue,
+        };
+
+        let viewport = match squid.as_viewport() {
+            Some(viewport) => viewport,
+            None => continue,
+        };
+
+        let position = viewport.position.reveal();
+        let size = viewport.size.abs();
+        let view_box = AABB::new(position.x - size.x * 0.5, position.y - size.y * 0.5, size.x, size.y);
+
+        // Viewports that share a name get numbered
+        let mut name: String = squid
+            .get_name()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || " -_".contains(c) { c } else { '_' })
+            .collect();
+        let uses = name_uses.entry(name.clone()).or_insert(0);
+        *uses += 1;
+
+        if *uses > 1 {
+            name = format!("{} {}", name, uses);
+        }
+
+        for profile in ocean.get_export_profiles() {
+            if profile.viewport.is_none_or(|only| only == reference) {
+                let filename = format!("{}{}.{}", name, profile.get_suffix(), profile.format.get_extension());
+
+                outputs.push(ProfileOutput {
+                    path: profile.folder.join(filename),
+                    format: profile.format,
+                    size: profile.size,
+                    view_box,
+                });
+            }
+        }
+    }
+
+    outputs
+}
+
+// PNG export that is waiting to be rendered
+pub struct RasterExport {
+    pub path: PathBuf,
+    pub view_box: AABB,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RasterExport {
+    const SUPERSAMPLING: u32 = 2;
+    const MAX_TEXTURE_SIZE: u32 = 8192;
+
+    pub fn new(path: PathBuf, view_box: AABB, size: &ExportSize) -> Self {
+        let (width, height) = size.get_dimensions(&view_box).unwrap_or((view_box.width(), view_box.height()));
+        let max_size = (Self::MAX_TEXTURE_SIZE / Self::SUPERSAMPLING) as f32;
+        let width = width.round().clamp(1.0, max_size);
+        let height = height.round().clamp(1.0, max_size);
+
+        // Grow the view box to the aspect ratio of the image, like SVG viewers do
+        let scale = (width / view_box.width()).min(height / view_box.height());
+        let extra = glm::vec2(width / scale - view_box.width(), height / scale - view_box.height()) * 0.5;
+
+        Self {
+            path,
+            view_box: AABB {
+                min_x: view_box.min_x - extra.x,
+                min_y: view_box.min_y - extra.y,
+                max_x: view_box.max_x + extra.x,
+                max_y: view_box.max_y + extra.y,
+            },
+            width: width as u32,
+            height: height as u32,
+        }
+    }
+
+    // Camera and projection that map the view box onto the whole texture
+    pub fn get_camera(&self) -> Camera {
+        Camera {
+            position: glm::vec2(self.view_box.min_x, self.view_box.min_y),
+            zoom: 1.0,
+            window: glm::vec2(self.view_box.width(), self.view_box.height()),
+            rotation: 0.0,
+            ui_scale: 1.0,
+        }
+    }
+
+    pub fn get_projection(&self) -> glm::Mat4 {
+        glm::ortho(0.0, self.view_box.width(), self.view_box.height(), 0.0, 100.0, -100.0)
+    }
+
+    // Rendered larger than the output, since drawing into a texture doesn't get MSAA
+    pub fn get_texture_dimensions(&self) -> (u32, u32) {
+        (self.width * Self::SUPERSAMPLING, self.height * Self::SUPERSAMPLING)
+    }
+
+    // Downsamples the rendered texture and writes it out
+    pub fn finish(&self, texture: &SrgbTexture2d) -> std::io::Result<()> {
+        println!("exporting to {}", self.path.to_string_lossy());
+
+        // Rows are read bottom to top
+        let rendered: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        let samples = Self::SUPERSAMPLING as usize;
+        let mut pixels = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+
+        for y in 0..self.height as usize {
+            for x in 0..self.width as usize {
+                let mut total = [0u32; 4];
+
+                for row in rendered.iter().rev().skip(y * samples).take(samples) {
+                    for (r, g, b, a) in row.iter().skip(x * samples).take(samples) {
+                        total[0] += *r as u32;
+                        total[1] += *g as u32;
+                        total[2] += *b as u32;
+                        total[3] += *a as u32;
+                    }
+                }
+
+                pixels.extend(total.map(|channel| (channel / (samples * samples) as u32) as u8));
+            }
+        }
+
+        fs::write(&self.path, png::encode(self.width, self.height, &pixels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportSize;
+    use crate::aabb::AABB;
+
+    #[test]
+    fn export_size_parse() {
+        assert_eq!(ExportSize::parse("512x256"), ExportSize::Dimensions(512.0, 256.0));
+        assert_eq!(ExportSize::parse(" 2x "), ExportSize::Scale(2.0));
+        assert_eq!(ExportSize::parse("0.5"), ExportSize::Scale(0.5));
+        assert_eq!(ExportSize::parse("auto"), ExportSize::Native);
+        assert_eq!(ExportSize::parse("-3x"), ExportSize::Native);
+
+        let view_box = AABB::new(0.0, 0.0, 100.0, 50.0);
+        assert_eq!(ExportSize::Scale(2.0).get_dimensions(&view_box), Some((200.0, 100.0)));
+        assert_eq!(ExportSize::Native.get_dimensions(&view_box), None);
+    }
 }
 
 /*
@@ -0,0 +1,100 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+// Font that's built into the executable, so there's always something to draw text with
+pub const BUNDLED_FONT: &[u8] = include_bytes!("../../Roboto-Regular.ttf");
+
+// How many folders deep to look inside of each font directory
+const MAX_SEARCH_DEPTH: usize = 4;
+
+// Reads a font, which can either be a path to a font file or the name of an installed font file
+// (without its extension). Nothing is found for an empty name, which means the bundled font
+pub fn find_font(name: &str) -> Option<Vec<u8>> {
+    if name.is_empty() {
+        return None;
+    }
+
+    let path = match Path::new(name).is_file() {
+        true => PathBuf::from(name),
+        false => find_system_font(name)?,
+    };
+
+    fs::read(path).ok()
+}
+
+pub fn find_system_font(name: &str) -> Option<PathBuf> {
+    get_font_directories().iter().find_map(|directory| search(directory, name, MAX_SEARCH_DEPTH))
+}
+
+fn get_font_directories() -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = dirs_next::font_dir().into_iter().collect();
+
+    if let Some(home) = dirs_next::home_dir() {
+        directories.push(home.join(".fonts"));
+    }
+
+    if let Some(windows) = std::env::var_os("WINDIR") {
+        directories.push(Path::new(&windows).join("Fonts"));
+    }
+
+    directories.extend(["/usr/share/fonts", "/usr/local/share/fonts", "/Library/Fonts", "/System/Library/Fonts"].map(PathBuf::from));
+    directories
+}
+
+// Looks through a directory, and then the directories inside of it, for a font file with a name
+fn search(directory: &Path, name: &str, depth: usize) -> Option<PathBuf> {
+    let mut subdirectories = vec![];
+
+    for path in fs::read_dir(directory).ok()?.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            subdirectories.push(path);
+        } else if is_font_named(&path, name) {
+            return Some(path);
+        }
+    }
+
+    if depth == 0 {
+        return None;
+    }
+
+    subdirectories.iter().find_map(|subdirectory| search(subdirectory, name, depth - 1))
+}
+
+fn is_font_named(path: &Path, name: &str) -> bool {
+    let is_font = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf"));
+
+    is_font
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_font, search, BUNDLED_FONT};
+    use std::fs;
+
+    #[test]
+    fn find_fonts() {
+        assert!(!BUNDLED_FONT.is_empty());
+        assert!(find_font("").is_none());
+
+        let directory = std::env::temp_dir().join("photosquid-find-fonts");
+        fs::create_dir_all(directory.join("nested")).unwrap();
+        fs::write(directory.join("nested").join("Example-Regular.TTF"), [1, 2, 3]).unwrap();
+        fs::write(directory.join("Example-Regular.txt"), []).unwrap();
+
+        let found = search(&directory, "example-regular", 1).unwrap();
+        assert_eq!(found.file_name().unwrap(), "Example-Regular.TTF");
+        assert!(search(&directory, "example-regular", 0).is_none());
+        assert_eq!(find_font(found.to_str().unwrap()), Some(vec![1, 2, 3]));
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
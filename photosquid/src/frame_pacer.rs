@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+// Decides when frames should be drawn, so that they're evenly spaced out at the target frame rate
+pub struct FramePacer {
+    // Frames per second, where zero means as fast as possible
+    target_fps: f32,
+
+    next_frame: Instant,
+}
+
+impl FramePacer {
+    // Slowest frame rate other than zero, since a frame rate that's too small has no frame duration that fits in a Duration
+    pub const MIN_TARGET_FPS: f32 = 1.0;
+
+    pub fn clamp_target_fps(target_fps: f32) -> f32 {
+        if target_fps > 0.0 {
+            target_fps.max(Self::MIN_TARGET_FPS)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn new(target_fps: f32) -> Self {
+        Self {
+            target_fps: Self::clamp_target_fps(target_fps),
+            next_frame: Instant::now(),
+        }
+    }
+
+    // Starts over with the new frame rate, rather than waiting out a frame at the old one
+    pub fn set_target_fps(&mut self, target_fps: f32) {
+        self.target_fps = Self::clamp_target_fps(target_fps);
+        self.next_frame = Instant::now();
+    }
+
+    pub fn get_frame_duration(&self) -> Duration {
+        if self.target_fps > 0.0 {
+            Duration::try_from_secs_f32(1.0 / self.target_fps).unwrap_or(Duration::ZERO)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    pub fn get_next_frame(&self) -> Instant {
+        self.next_frame
+    }
+
+    pub fn is_frame_due(&self, now: Instant) -> bool {
+        now >= self.next_frame
+    }
+
+    // Schedules the next frame to be one frame after this one was due,
+    // so a frame that's drawn a little late doesn't push back the ones after it.
+    // Starting over is better after falling behind though, like after sitting idle
+    pub fn frame_drawn(&mut self, now: Instant) {
+        let frame_duration = self.get_frame_duration();
+        let next_frame = self.next_frame + frame_duration;
+
+        self.next_frame = if next_frame < now { now + frame_duration } else { next_frame };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FramePacer;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn pace_frames() {
+        let mut pacer = FramePacer::new(50.0);
+        let start = pacer.get_next_frame();
+        assert!(pacer.is_frame_due(start));
+
+        // Being a little late keeps the same schedule
+        pacer.frame_drawn(start + Duration::from_millis(5));
+        assert_eq!(pacer.get_next_frame(), start + Duration::from_millis(20));
+        assert!(!pacer.is_frame_due(start + Duration::from_millis(19)));
+
+        // Falling far behind starts over
+        let later = start + Duration::from_secs(10);
+        pacer.frame_drawn(later);
+        assert_eq!(pacer.get_next_frame(), later + Duration::from_millis(20));
+
+        // Tiny frame rates are slowed down to the minimum instead of having no frame duration
+        pacer.set_target_fps(1e-39);
+        assert_eq!(pacer.get_frame_duration(), Duration::from_secs(1));
+
+        // Uncapped frames are always due
+        pacer.set_target_fps(0.0);
+        pacer.frame_drawn(Instant::now());
+        assert!(pacer.is_frame_due(Instant::now()));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{as_values::AsValues, color::Color, mesh::MeshXyz, press_animation::PressAnimation, render_ctx::RenderCtx, smooth::Smooth};
+use crate::{affine::Affine2, color::Color, mesh::MeshXyz, press_animation::PressAnimation, render_ctx::RenderCtx, renderer::Renderer, smooth::Smooth};
 use angular_units::Angle;
 use glium::Display;
 use nalgebra_glm as glm;
@@ -48,6 +48,10 @@ impl<T> IconButton<T> {
         }
     }
 
+    pub fn is_animating(&self) -> bool {
+        self.instant.is_some_and(|instant| instant.elapsed() < self.duration) || self.color.as_ref().is_some_and(Smooth::is_animating)
+    }
+
     pub fn render(&mut self, ctx: &mut RenderCtx, color: &Color) {
         let animation_moment = if let Some(instant) = self.instant {
             let since_instant = Instant::now() - instant;
@@ -63,21 +67,14 @@ impl<T> IconButton<T> {
             self.color = Some(Smooth::new(*color, None));
         }
 
-        let identity = glm::identity::<f32, 4>();
         let real_scale = animation_moment.relative_scale * self.relative_scale * 24.0 * 0.5; // (times 0.5 since icons are 2x2 meters)
-        let transformation = glm::translation(&self.position);
-        let transformation = glm::rotate(&transformation, animation_moment.backwards_rotation.scalar(), &glm::vec3(1.0, 0.0, 0.0));
-        let transformation = glm::rotate(&transformation, animation_moment.rotation.scalar(), &glm::vec3(0.0, 0.0, -1.0));
-        let transformation = glm::scale(&transformation, &glm::vec3(real_scale, real_scale, 0.0));
+        let transformation = Affine2::translation(&self.position.xy());
 
-        let uniforms = glium::uniform! {
-            transformation: transformation.as_values(),
-            view: identity.as_values(),
-            projection: ctx.projection.as_values(),
-            color: color.as_values()
-        };
+        // Flipping backwards around the x axis only foreshortens vertically when viewed head-on
+        let transformation = transformation.scale(&glm::vec2(1.0, animation_moment.backwards_rotation.scalar().cos()));
+        let transformation = transformation.rotate(-animation_moment.rotation.scalar());
+        let transformation = transformation.scale(&glm::vec2(real_scale, real_scale));
 
-        ctx.draw(&self.mesh.vertex_buffer, &self.mesh.indices, ctx.color_shader, &uniforms, &Default::default())
-            .unwrap();
+        ctx.draw_mesh(&self.mesh, &transformation, color);
     }
 }
@@ -0,0 +1,104 @@
+use nalgebra_glm as glm;
+use std::time::{Duration, Instant};
+
+// Keeps the canvas moving after a pan drag is let go, slowing down over time
+#[derive(Default)]
+pub struct Inertia {
+    // Screen pixels per second
+    velocity: glm::Vec2,
+
+    last_record: Option<Instant>,
+    last_step: Option<Instant>,
+    coasting: bool,
+}
+
+impl Inertia {
+    // Fraction of velocity that remains after one second
+    const FRICTION: f32 = 0.02;
+
+    // Slowest velocity that keeps coasting
+    const MIN_SPEED: f32 = 20.0;
+
+    // Drags that stopped moving for longer than this before release don't coast
+    const RELEASE_WINDOW: Duration = Duration::from_millis(60);
+
+    // Records movement of an ongoing drag
+    pub fn record(&mut self, delta: &glm::Vec2, now: Instant) {
+        if let Some(last_record) = self.last_record {
+            let elapsed = now.duration_since(last_record).as_secs_f32().max(0.001);
+
+            // Blend with the previous velocity to smooth out uneven mouse events
+            self.velocity = glm::lerp(&self.velocity, &(delta / elapsed), 0.6);
+        }
+
+        self.last_record = Some(now);
+        self.coasting = false;
+    }
+
+    // Starts coasting if the drag was still moving when it was let go
+    pub fn release(&mut self, now: Instant) {
+        let recent = self
+            .last_record
+            .is_some_and(|last_record| now.duration_since(last_record) <= Self::RELEASE_WINDOW);
+
+        self.coasting = recent && glm::length(&self.velocity) >= Self::MIN_SPEED;
+        self.last_record = None;
+        self.last_step = Some(now);
+
+        if !self.coasting {
+            self.velocity = glm::zero();
+        }
+    }
+
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    // Returns how far to move since the last step, if still coasting
+    pub fn step(&mut self, now: Instant) -> Option<glm::Vec2> {
+        if !self.coasting {
+            return None;
+        }
+
+        let elapsed = self.last_step.map_or(0.0, |last_step| now.duration_since(last_step).as_secs_f32());
+        let decay = Self::FRICTION.powf(elapsed);
+
+        // Distance travelled while slowing down exponentially over the elapsed time
+        let distance = self.velocity * (1.0 - decay) / -Self::FRICTION.ln();
+
+        self.velocity *= decay;
+        self.last_step = Some(now);
+
+        if glm::length(&self.velocity) < Self::MIN_SPEED {
+            self.stop();
+        }
+
+        Some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Inertia;
+    use nalgebra_glm as glm;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn inertia_coasts_then_stops() {
+        let start = Instant::now();
+        let mut inertia = Inertia::default();
+
+        inertia.record(&glm::zero(), start);
+        inertia.record(&glm::vec2(10.0, 0.0), start + Duration::from_millis(10));
+        inertia.release(start + Duration::from_millis(20));
+
+        let first = inertia.step(start + Duration::from_millis(36)).unwrap();
+        assert!(first.x > 0.0 && first.y == 0.0);
+
+        let second = inertia.step(start + Duration::from_millis(52)).unwrap();
+        assert!(second.x < first.x);
+
+        assert!(inertia.step(start + Duration::from_secs(5)).is_some());
+        assert!(inertia.step(start + Duration::from_secs(6)).is_none());
+    }
+}
@@ -9,6 +9,8 @@ pub enum Interaction {
     MouseRelease(MouseReleaseInteraction),
     Drag(DragInteraction),
     Key(KeyInteraction),
+    Scroll(ScrollInteraction),
+    Character(CharacterInteraction),
 }
 
 #[derive(Copy, Clone)]
@@ -32,7 +34,18 @@ pub struct DragInteraction {
     pub modifiers: ModifiersState,
 }
 
+#[derive(Copy, Clone)]
+pub struct ScrollInteraction {
+    pub delta: glm::Vec2,
+    pub position: glm::Vec2,
+}
+
 #[derive(Copy, Clone)]
 pub struct KeyInteraction {
     pub virtual_keycode: VirtualKeyCode,
 }
+
+#[derive(Copy, Clone)]
+pub struct CharacterInteraction {
+    pub character: char,
+}
@@ -0,0 +1,621 @@
+use crate::ctrl_or_cmd::CtrlOrCmd;
+use glium::glutin::event::{ModifiersState, VirtualKeyCode};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+// Everything that can be done with a keyboard shortcut, in the order they're listed to the user
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    Undo,
+    Redo,
+    NewDocument,
+    Open,
+    Save,
+    SaveAs,
+    CloseDocument,
+    NextDocument,
+    PreviousDocument,
+    Export,
+    QuickFind,
+    CommandPalette,
+    ScriptConsole,
+    RecordMacro,
+    PlayMacro,
+    KeyboardShortcuts,
+    ToggleObjectInfo,
+    ToggleSnapping,
+    ZoomIn,
+    ZoomOut,
+    RotateViewLeft,
+    RotateViewRight,
+    ResetViewRotation,
+    Copy,
+    Paste,
+    CopyStyle,
+    PasteStyle,
+    Delete,
+    Duplicate,
+    SelectAll,
+    DeselectAll,
+    InvertSelection,
+    SelectSameFillColor,
+    SelectSameKind,
+    SelectSameLayer,
+    SelectByTag,
+    EditTags,
+    RenameSelected,
+    BringToFront,
+    BringForward,
+    SendBackward,
+    SendToBack,
+    EqualHorizontalGaps,
+    EqualVerticalGaps,
+    MatchWidth,
+    MatchHeight,
+    ConvertToPath,
+    Grab,
+    Rotate,
+    Scale,
+    Collectively,
+    DismissMenu,
+    PreviousTool,
+    NextTool,
+    PreviousTab,
+    NextTab,
+    MainMenu,
+    Tool1,
+    Tool2,
+    Tool3,
+    Tool4,
+    Tool5,
+    Tool6,
+    Tool7,
+    Tool8,
+    Tool9,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = {
+        use Action::*;
+
+        &[
+            Undo,
+            Redo,
+            NewDocument,
+            Open,
+            Save,
+            SaveAs,
+            CloseDocument,
+            NextDocument,
+            PreviousDocument,
+            Export,
+            QuickFind,
+            CommandPalette,
+            ScriptConsole,
+            RecordMacro,
+            PlayMacro,
+            KeyboardShortcuts,
+            ToggleObjectInfo,
+            ToggleSnapping,
+            ZoomIn,
+            ZoomOut,
+            RotateViewLeft,
+            RotateViewRight,
+            ResetViewRotation,
+            Copy,
+            Paste,
+            CopyStyle,
+            PasteStyle,
+            Delete,
+            Duplicate,
+            SelectAll,
+            DeselectAll,
+            InvertSelection,
+            SelectSameFillColor,
+            SelectSameKind,
+            SelectSameLayer,
+            SelectByTag,
+            EditTags,
+            RenameSelected,
+            BringToFront,
+            BringForward,
+            SendBackward,
+            SendToBack,
+            EqualHorizontalGaps,
+            EqualVerticalGaps,
+            MatchWidth,
+            MatchHeight,
+            ConvertToPath,
+            Grab,
+            Rotate,
+            Scale,
+            Collectively,
+            DismissMenu,
+            PreviousTool,
+            NextTool,
+            PreviousTab,
+            NextTab,
+            MainMenu,
+            Tool1,
+            Tool2,
+            Tool3,
+            Tool4,
+            Tool5,
+            Tool6,
+            Tool7,
+            Tool8,
+            Tool9,
+        ]
+    };
+
+    pub fn get_name(&self) -> &'static str {
+        use Action::*;
+
+        match self {
+            Undo => "Undo",
+            Redo => "Redo",
+            NewDocument => "New Document",
+            Open => "Open",
+            Save => "Save",
+            SaveAs => "Save As",
+            CloseDocument => "Close Document",
+            NextDocument => "Next Document",
+            PreviousDocument => "Previous Document",
+            Export => "Export",
+            QuickFind => "Find by Name",
+            CommandPalette => "Command Palette",
+            ScriptConsole => "Script Console",
+            RecordMacro => "Start/Stop Recording Macro",
+            PlayMacro => "Play Macro",
+            KeyboardShortcuts => "Keyboard Shortcuts",
+            ToggleObjectInfo => "Toggle Object Info",
+            ToggleSnapping => "Toggle Snapping",
+            ZoomIn => "Zoom In",
+            ZoomOut => "Zoom Out",
+            RotateViewLeft => "Rotate View Left",
+            RotateViewRight => "Rotate View Right",
+            ResetViewRotation => "Reset View Rotation",
+            Copy => "Copy",
+            Paste => "Paste",
+            CopyStyle => "Copy Style",
+            PasteStyle => "Paste Style",
+            Delete => "Delete",
+            Duplicate => "Duplicate",
+            SelectAll => "Select All",
+            DeselectAll => "Deselect All",
+            InvertSelection => "Invert Selection",
+            SelectSameFillColor => "Select Same Fill Color",
+            SelectSameKind => "Select Same Kind",
+            SelectSameLayer => "Select Same Layer",
+            SelectByTag => "Select by Tag",
+            EditTags => "Edit Tags",
+            RenameSelected => "Rename Selected",
+            BringToFront => "Bring to Front",
+            BringForward => "Bring Forward",
+            SendBackward => "Send Backward",
+            SendToBack => "Send to Back",
+            EqualHorizontalGaps => "Equal Horizontal Gaps",
+            EqualVerticalGaps => "Equal Vertical Gaps",
+            MatchWidth => "Match Width",
+            MatchHeight => "Match Height",
+            ConvertToPath => "Convert to Path",
+            Grab => "Grab",
+            Rotate => "Rotate",
+            Scale => "Scale",
+            Collectively => "Collectively",
+            DismissMenu => "Dismiss Menu",
+            PreviousTool => "Previous Tool",
+            NextTool => "Next Tool",
+            PreviousTab => "Previous Tab",
+            NextTab => "Next Tab",
+            MainMenu => "Main Menu",
+            Tool1 => "Tool 1",
+            Tool2 => "Tool 2",
+            Tool3 => "Tool 3",
+            Tool4 => "Tool 4",
+            Tool5 => "Tool 5",
+            Tool6 => "Tool 6",
+            Tool7 => "Tool 7",
+            Tool8 => "Tool 8",
+            Tool9 => "Tool 9",
+        }
+    }
+
+    // Actions that depend on where the mouse is, which can't be done from the command palette
+    pub fn needs_mouse(&self) -> bool {
+        matches!(self, Action::Grab | Action::Rotate | Action::Scale | Action::DismissMenu)
+    }
+
+    // Index of the toolbox button that the action selects, if it selects one
+    pub fn get_tool_index(&self) -> Option<usize> {
+        use Action::*;
+
+        let index = match self {
+            MainMenu => 0,
+            Tool1 => 1,
+            Tool2 => 2,
+            Tool3 => 3,
+            Tool4 => 4,
+            Tool5 => 5,
+            Tool6 => 6,
+            Tool7 => 7,
+            Tool8 => 8,
+            Tool9 => 9,
+            _ => return None,
+        };
+
+        Some(index)
+    }
+}
+
+// Key along with the modifiers that have to be held for it,
+// where ctrl is the command key on macOS
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub key: VirtualKeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+// Names of keys as they're written in keymap files
+const KEY_NAMES: &[(VirtualKeyCode, &str)] = {
+    use VirtualKeyCode::*;
+
+    &[
+        (A, "A"),
+        (B, "B"),
+        (C, "C"),
+        (D, "D"),
+        (E, "E"),
+        (F, "F"),
+        (G, "G"),
+        (H, "H"),
+        (I, "I"),
+        (J, "J"),
+        (K, "K"),
+        (L, "L"),
+        (M, "M"),
+        (N, "N"),
+        (O, "O"),
+        (P, "P"),
+        (Q, "Q"),
+        (R, "R"),
+        (S, "S"),
+        (T, "T"),
+        (U, "U"),
+        (V, "V"),
+        (W, "W"),
+        (X, "X"),
+        (Y, "Y"),
+        (Z, "Z"),
+        (Key0, "0"),
+        (Key1, "1"),
+        (Key2, "2"),
+        (Key3, "3"),
+        (Key4, "4"),
+        (Key5, "5"),
+        (Key6, "6"),
+        (Key7, "7"),
+        (Key8, "8"),
+        (Key9, "9"),
+        (F1, "F1"),
+        (F2, "F2"),
+        (F3, "F3"),
+        (F4, "F4"),
+        (F5, "F5"),
+        (F6, "F6"),
+        (F7, "F7"),
+        (F8, "F8"),
+        (F9, "F9"),
+        (F10, "F10"),
+        (F11, "F11"),
+        (F12, "F12"),
+        (Equals, "="),
+        (Minus, "-"),
+        (LBracket, "["),
+        (RBracket, "]"),
+        (Comma, ","),
+        (Period, "."),
+        (Slash, "/"),
+        (Semicolon, ";"),
+        (Apostrophe, "'"),
+        (Backslash, "\\"),
+        (Grave, "`"),
+        (Space, "Space"),
+        (Tab, "Tab"),
+        (Return, "Enter"),
+        (Escape, "Escape"),
+        (Back, "Backspace"),
+        (Delete, "Delete"),
+        (Insert, "Insert"),
+        (Home, "Home"),
+        (End, "End"),
+        (PageUp, "PageUp"),
+        (PageDown, "PageDown"),
+        (Up, "Up"),
+        (Down, "Down"),
+        (Left, "Left"),
+        (Right, "Right"),
+    ]
+};
+
+impl KeyBinding {
+    pub fn new(key: VirtualKeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub fn ctrl(self) -> Self {
+        Self { ctrl: true, ..self }
+    }
+
+    pub fn shift(self) -> Self {
+        Self { shift: true, ..self }
+    }
+
+    pub fn alt(self) -> Self {
+        Self { alt: true, ..self }
+    }
+
+    // Parses bindings written like "Ctrl+Shift+Z"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key_name = parts.pop().unwrap_or_default();
+
+        let key = KEY_NAMES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(key_name))
+            .map(|(key, _)| *key)
+            .ok_or_else(|| format!("Unknown key '{}'", key_name))?;
+
+        let mut binding = Self::new(key);
+
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                "alt" => binding.alt = true,
+                _ => return Err(format!("Unknown modifier '{}'", modifier)),
+            }
+        }
+
+        Ok(binding)
+    }
+
+    pub fn matches(&self, key: VirtualKeyCode, modifiers: ModifiersState) -> bool {
+        self.key == key && self.ctrl == modifiers.ctrl_or_cmd() && self.shift == modifiers.shift() && self.alt == modifiers.alt()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "{}+", if cfg!(target_os = "macos") { "Cmd" } else { "Ctrl" })?;
+        }
+
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+
+        let name = KEY_NAMES.iter().find(|(key, _)| *key == self.key).map_or("?", |(_, name)| name);
+        write!(f, "{}", name)
+    }
+}
+
+// Which keyboard shortcut performs each action, actions without one are unbound
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Action::*;
+        use VirtualKeyCode as Key;
+
+        let bindings = [
+            (Undo, KeyBinding::new(Key::Z).ctrl()),
+            (Redo, KeyBinding::new(Key::Z).ctrl().shift()),
+            (NewDocument, KeyBinding::new(Key::N).ctrl()),
+            (Open, KeyBinding::new(Key::O).ctrl()),
+            (Save, KeyBinding::new(Key::S).ctrl()),
+            (SaveAs, KeyBinding::new(Key::S).ctrl().shift()),
+            (CloseDocument, KeyBinding::new(Key::W).ctrl()),
+            (NextDocument, KeyBinding::new(Key::Tab).ctrl()),
+            (PreviousDocument, KeyBinding::new(Key::Tab).ctrl().shift()),
+            (QuickFind, KeyBinding::new(Key::F).ctrl()),
+            (CommandPalette, KeyBinding::new(Key::K).ctrl()),
+            (ScriptConsole, KeyBinding::new(Key::Grave).ctrl()),
+            (RecordMacro, KeyBinding::new(Key::R).ctrl().shift()),
+            (PlayMacro, KeyBinding::new(Key::R).ctrl()),
+            (ToggleObjectInfo, KeyBinding::new(Key::I).ctrl()),
+            (ToggleSnapping, KeyBinding::new(Key::Semicolon).ctrl()),
+            (ZoomIn, KeyBinding::new(Key::Equals).ctrl()),
+            (ZoomOut, KeyBinding::new(Key::Minus).ctrl()),
+            (RotateViewLeft, KeyBinding::new(Key::LBracket).ctrl()),
+            (RotateViewRight, KeyBinding::new(Key::RBracket).ctrl()),
+            (ResetViewRotation, KeyBinding::new(Key::Key0).ctrl()),
+            (Copy, KeyBinding::new(Key::C).ctrl()),
+            (Paste, KeyBinding::new(Key::V).ctrl()),
+            (CopyStyle, KeyBinding::new(Key::C).ctrl().alt()),
+            (PasteStyle, KeyBinding::new(Key::V).ctrl().alt()),
+            (Delete, KeyBinding::new(Key::X)),
+            (Duplicate, KeyBinding::new(Key::D).shift()),
+            (SelectAll, KeyBinding::new(Key::A).ctrl()),
+            (InvertSelection, KeyBinding::new(Key::I).ctrl().shift()),
+            (RenameSelected, KeyBinding::new(Key::F2)),
+            (BringForward, KeyBinding::new(Key::PageUp)),
+            (SendBackward, KeyBinding::new(Key::PageDown)),
+            (Grab, KeyBinding::new(Key::G)),
+            (Rotate, KeyBinding::new(Key::R)),
+            (Scale, KeyBinding::new(Key::S)),
+            (Collectively, KeyBinding::new(Key::C)),
+            (DismissMenu, KeyBinding::new(Key::Escape)),
+            (PreviousTool, KeyBinding::new(Key::Up)),
+            (NextTool, KeyBinding::new(Key::Down)),
+            (PreviousTab, KeyBinding::new(Key::Left)),
+            (NextTab, KeyBinding::new(Key::Right)),
+            (MainMenu, KeyBinding::new(Key::Key0)),
+            (Tool1, KeyBinding::new(Key::Key1)),
+            (Tool2, KeyBinding::new(Key::Key2)),
+            (Tool3, KeyBinding::new(Key::Key3)),
+            (Tool4, KeyBinding::new(Key::Key4)),
+            (Tool5, KeyBinding::new(Key::Key5)),
+            (Tool6, KeyBinding::new(Key::Key6)),
+            (Tool7, KeyBinding::new(Key::Key7)),
+            (Tool8, KeyBinding::new(Key::Key8)),
+            (Tool9, KeyBinding::new(Key::Key9)),
+        ];
+
+        Self {
+            bindings: bindings.iter().copied().collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|_| "Failed to read keymap file")?;
+        Self::parse(&contents)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json()).map_err(|_| "Failed to write keymap file to disk".into())
+    }
+
+    // Keymap files map action names to bindings, where an empty binding unbinds the action.
+    // Actions that aren't mentioned keep their default binding
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let entries: BTreeMap<Action, String> = serde_json::from_str(contents).map_err(|error| format!("Invalid keymap: {}", error))?;
+        let mut keymap = Self::default();
+
+        for (action, text) in entries {
+            if text.trim().is_empty() {
+                keymap.bindings.remove(&action);
+            } else {
+                let binding = KeyBinding::parse(&text).map_err(|error| format!("Invalid binding for {:?}: {}", action, error))?;
+                keymap.bindings.insert(action, binding);
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries: BTreeMap<Action, String> = self.bindings.iter().map(|(action, binding)| (*action, binding.to_string())).collect();
+        serde_json::to_string_pretty(&entries).expect("Failed to serialize keymap")
+    }
+
+    pub fn get_binding(&self, action: Action) -> Option<KeyBinding> {
+        self.bindings.get(&action).copied()
+    }
+
+    pub fn get_action(&self, key: VirtualKeyCode, modifiers: ModifiersState) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, binding)| binding.matches(key, modifiers))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn get_bindings(&self) -> impl Iterator<Item = (Action, KeyBinding)> + '_ {
+        self.bindings.iter().map(|(action, binding)| (*action, *binding))
+    }
+
+    // Pairs of actions that are bound to the same shortcut, only the first of which can be used
+    pub fn get_conflicts(&self) -> Vec<(Action, Action)> {
+        let bindings: Vec<(Action, KeyBinding)> = self.get_bindings().collect();
+        let mut conflicts = vec![];
+
+        for (i, (action, binding)) in bindings.iter().enumerate() {
+            for (other_action, other_binding) in &bindings[i + 1..] {
+                if binding == other_binding {
+                    conflicts.push((*action, *other_action));
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    // Listing of every binding, with any conflicts first
+    pub fn describe(&self) -> String {
+        let mut lines: Vec<String> = self
+            .get_conflicts()
+            .iter()
+            .map(|(action, other_action)| format!("Conflict: {} and {}", action.get_name(), other_action.get_name()))
+            .collect();
+
+        lines.extend(self.get_bindings().map(|(action, binding)| format!("{}: {}", action.get_name(), binding)));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, KeyBinding, Keymap};
+    use glium::glutin::event::{ModifiersState, VirtualKeyCode};
+
+    #[test]
+    fn key_binding_parse() {
+        assert_eq!(KeyBinding::parse("Ctrl+Shift+Z"), Ok(KeyBinding::new(VirtualKeyCode::Z).ctrl().shift()));
+        assert_eq!(KeyBinding::parse("pageup"), Ok(KeyBinding::new(VirtualKeyCode::PageUp)));
+        assert_eq!(KeyBinding::parse("Ctrl+["), Ok(KeyBinding::new(VirtualKeyCode::LBracket).ctrl()));
+        assert!(KeyBinding::parse("Hyper+Z").is_err());
+        assert!(KeyBinding::parse("Ctrl+Banana").is_err());
+
+        let binding = KeyBinding::new(VirtualKeyCode::D).shift();
+        assert_eq!(KeyBinding::parse(&binding.to_string()), Ok(binding));
+    }
+
+    #[test]
+    fn keymap_parse() {
+        let keymap = Keymap::parse(r#"{ "Delete": "Backspace", "Grab": "" }"#).unwrap();
+
+        assert_eq!(keymap.get_action(VirtualKeyCode::Back, ModifiersState::empty()), Some(Action::Delete));
+        assert_eq!(keymap.get_action(VirtualKeyCode::X, ModifiersState::empty()), None);
+        assert_eq!(keymap.get_binding(Action::Grab), None);
+        assert_eq!(keymap.get_binding(Action::Undo), Keymap::default().get_binding(Action::Undo));
+
+        // Modifiers have to match exactly
+        assert_eq!(keymap.get_action(VirtualKeyCode::D, ModifiersState::SHIFT), Some(Action::Duplicate));
+        assert_eq!(keymap.get_action(VirtualKeyCode::D, ModifiersState::empty()), None);
+
+        assert!(Keymap::parse(r#"{ "Fly": "F" }"#).is_err());
+        assert!(Keymap::parse(&Keymap::default().to_json()).is_ok());
+    }
+
+    #[test]
+    fn selection_shortcuts() {
+        let keymap = Keymap::default();
+
+        assert_eq!(keymap.get_action(VirtualKeyCode::A, ModifiersState::CTRL), Some(Action::SelectAll));
+        assert_eq!(
+            keymap.get_action(VirtualKeyCode::I, ModifiersState::CTRL | ModifiersState::SHIFT),
+            Some(Action::InvertSelection)
+        );
+        assert_eq!(keymap.get_binding(Action::DeselectAll), None);
+    }
+
+    #[test]
+    fn action_all_in_order() {
+        assert!(Action::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Action::ALL.last(), Some(&Action::Tool9));
+    }
+
+    #[test]
+    fn keymap_conflicts() {
+        assert!(Keymap::default().get_conflicts().is_empty());
+
+        let keymap = Keymap::parse(r#"{ "Duplicate": "X" }"#).unwrap();
+        assert_eq!(keymap.get_conflicts(), vec![(Action::Delete, Action::Duplicate)]);
+    }
+}
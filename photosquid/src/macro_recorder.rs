@@ -0,0 +1,98 @@
+use crate::command::DocumentCommand;
+
+// Keeps the document commands that are applied while recording,
+// so the same changes can be played back later
+#[derive(Default)]
+pub struct MacroRecorder {
+    recording: Option<Vec<DocumentCommand>>,
+    recorded: Vec<DocumentCommand>,
+}
+
+impl MacroRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    // Starts recording, or stops and keeps what was recorded.
+    // Stopping without recording anything keeps the previous macro
+    pub fn toggle(&mut self) {
+        match self.recording.take() {
+            Some(commands) if !commands.is_empty() => self.recorded = commands,
+            Some(_) => (),
+            None => self.recording = Some(vec![]),
+        }
+    }
+
+    // Changes to squids inserted while recording, like the size of a shape drawn out with a tool,
+    // are made to the recorded copies instead. Playing back inserts them as new squids with ids of their own,
+    // so the changes would otherwise go to the original squids
+    pub fn record(&mut self, command: &DocumentCommand) {
+        let commands = match &mut self.recording {
+            Some(commands) => commands,
+            None => return,
+        };
+
+        let inserted = command.get_target().and_then(|id| {
+            commands.iter_mut().rev().find_map(|recorded| match recorded {
+                DocumentCommand::Insert(squid) | DocumentCommand::InsertAndSelect(squid) if squid.get_id() == id => Some(squid),
+                _ => None,
+            })
+        });
+
+        match inserted {
+            Some(squid) => command.apply_to_squid(squid),
+            None => commands.push(command.clone()),
+        }
+    }
+
+    pub fn get_recorded(&self) -> &[DocumentCommand] {
+        &self.recorded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MacroRecorder;
+    use crate::{color::Color, command::DocumentCommand, squid::Squid};
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn record_macro() {
+        let mut recorder = MacroRecorder::default();
+        recorder.record(&DocumentCommand::DeleteSelected);
+        assert!(recorder.get_recorded().is_empty());
+
+        recorder.toggle();
+        assert!(recorder.is_recording());
+        recorder.record(&DocumentCommand::DuplicateSelected);
+        recorder.record(&DocumentCommand::ReorderSelected(0));
+        recorder.toggle();
+        assert!(!recorder.is_recording());
+        assert_eq!(recorder.get_recorded().len(), 2);
+
+        // An empty recording doesn't replace the macro
+        recorder.toggle();
+        recorder.toggle();
+        assert_eq!(recorder.get_recorded().len(), 2);
+    }
+
+    #[test]
+    fn record_tool_created_shape() {
+        let mut recorder = MacroRecorder::default();
+        recorder.toggle();
+
+        // Same as the rect tool, which inserts a squid and then resizes it while it's dragged out
+        let squid = Squid::rect(glm::vec2(0.0, 0.0), glm::vec2(10.0, 10.0), Rad(0.0), Color::white(), 0.0, false);
+        let id = squid.get_id();
+        recorder.record(&DocumentCommand::Insert(Box::new(squid)));
+        recorder.record(&DocumentCommand::FitToBox(id, glm::vec2(10.0, 10.0), glm::vec2(30.0, 50.0)));
+        recorder.record(&DocumentCommand::FitToBox(id, glm::vec2(20.0, 20.0), glm::vec2(40.0, 60.0)));
+        recorder.toggle();
+
+        match recorder.get_recorded() {
+            [DocumentCommand::Insert(squid)] => assert_eq!(squid.get_center(), glm::vec2(30.0, 40.0)),
+            _ => panic!("expected a single recorded insert"),
+        }
+    }
+}
@@ -8,61 +8,95 @@
 #![feature(try_trait_v2)]
 #![feature(array_methods)]
 
-mod aabb;
-mod accumulator;
-mod algorithm;
 mod app;
-mod approx_instant;
-mod as_values;
+mod arrange;
+mod blur;
 mod bool_poll;
-mod camera;
+mod breadcrumb;
 mod capture;
 mod clearable;
-mod color;
 mod color_scheme;
+mod command;
+mod command_palette;
 mod components;
 mod context_menu;
 mod ctrl_or_cmd;
-mod data;
+mod damage;
 mod dialog;
+mod document;
+mod document_tabs;
 mod dragging;
 mod draw_text;
 mod export;
-mod history;
+mod font;
+mod frame_pacer;
 mod icon_button;
+mod inertia;
 mod interaction;
-mod interaction_options;
-mod layer;
-mod math;
+mod keymap;
+mod macro_recorder;
 mod mesh;
+mod mesh_cache;
+mod modal;
 mod mouse;
 mod obj;
 mod ocean;
 mod operation;
 mod options;
+mod palette;
+mod panel_cache;
+mod plugin;
+mod preferences;
 mod press_animation;
-mod raster_color;
+mod quick_find;
+mod radial_menu;
 mod render_ctx;
+mod render_quality;
+mod renderer;
+mod script;
+mod script_console;
 mod selection;
 mod shader;
 mod shaders;
-mod smooth;
+mod snapping;
 mod squid;
+mod squid_batch;
+mod status_bar;
+mod stress_test;
+mod tessellator;
+mod thumbnail_cache;
 mod tool;
 mod tool_button;
 mod toolbox;
 mod user_input;
 mod vertex;
 
-const TARGET_FPS: u64 = 60;
+use photosquid_core::{
+    aabb, accumulator, affine, algorithm, approx_instant, as_values, camera, color, data, history, interaction_options, layer, math, png, raster_color, smooth,
+    spatial_index, style, units,
+};
+
+const PIXELS_PER_SCROLL_LINE: f32 = 100.0;
 
-use app::{App, MULTISAMPLING_COUNT};
+use aabb::AABB;
+use angular_units::Angle;
+use app::App;
 use as_values::AsValues;
+use blur::BlurLayer;
+use breadcrumb::Breadcrumb;
 use camera::Camera;
 use capture::Capture;
+use color::Color;
 use color_scheme::ColorScheme;
-use context_menu::ContextAction;
+use context_menu::ContextMenuResponse;
+use ctrl_or_cmd::CtrlOrCmd;
+use damage::Damage;
+use document::Document;
+use document_tabs::DocumentTabs;
 use dragging::Dragging;
+use draw_text::{draw_text, get_text_width};
+use export::RasterExport;
+use frame_pacer::FramePacer;
 use glium::{
     glutin::{
         event::{ElementState, Event, ModifiersState, MouseButton, MouseScrollDelta, WindowEvent},
@@ -74,34 +108,53 @@ use glium::{
 };
 use glium_text::{FontTexture, TextSystem};
 use glium_text_rusttype as glium_text;
-use interaction::{Interaction, MouseReleaseInteraction};
+use inertia::Inertia;
+use interaction::{CharacterInteraction, Interaction, KeyInteraction, MouseReleaseInteraction, ScrollInteraction};
 use mesh::{MeshXyz, MeshXyzUv};
+use mesh_cache::MeshCache;
 use mouse::OnScreen;
 use nalgebra_glm as glm;
-use options::tab::{Tab, TabRef};
+use ocean::Ocean;
+use options::{
+    color_picker::ColorTarget,
+    tab::{Tab, TabRef},
+};
+use panel_cache::PanelCache;
+use plugin::PluginRegistry;
+use preferences::Preferences;
 use render_ctx::RenderCtx;
-use selection::selection_contains;
+use renderer::Renderer;
+use selection::{selection_contains, Selection};
 use shaders::Shaders;
 use slotmap::SlotMap;
 use smooth::Smooth;
 use squid::SquidRef;
+use squid_batch::SquidBatch;
+use status_bar::StatusBar;
 use std::{
     collections::{btree_set::BTreeSet, HashSet},
-    fs::File,
-    path::Path,
     rc::Rc,
-    time::{Duration, Instant},
+    time::Instant,
 };
 use tool::{Tool, ToolKey, ToolKind};
 use toolbox::ToolBox;
 
-use crate::{interaction::ClickInteraction, toolbox::find_tool};
+use crate::{affine::Affine2, command::DocumentCommand, interaction::ClickInteraction, toolbox::find_tool};
 
-fn main() {
-    // <コ:彡
+// Loads the preferred UI font, falling back to the bundled font if it can't be found or read
+fn load_font(display: &Display, preferences: &Preferences) -> FontTexture {
+    let load = |bytes: &[u8]| FontTexture::new(display, bytes, 20, FontTexture::ascii_character_list());
 
-    // Build window
-    let event_loop = EventLoop::new();
+    font::find_font(&preferences.ui_font).and_then(|bytes| load(&bytes).ok()).unwrap_or_else(|| {
+        if !preferences.ui_font.is_empty() {
+            eprintln!("Failed to load font '{}', using bundled font", preferences.ui_font);
+        }
+
+        load(font::BUNDLED_FONT).unwrap()
+    })
+}
+
+fn create_display(event_loop: &EventLoop<()>, preferences: &Preferences) -> Display {
     let window_builder = WindowBuilder::new()
         .with_title("Photosquid :)")
         .with_inner_size(glium::glutin::dpi::LogicalSize::new(1280, 720));
@@ -109,11 +162,27 @@ fn main() {
         .with_srgb(true)
         .with_gl_profile(GlProfile::Core)
         .with_gl(GlRequest::Specific(glium::glutin::Api::OpenGl, (4, 0)))
-        .with_multisampling(MULTISAMPLING_COUNT)
         .with_double_buffer(Some(true))
-        .with_vsync(true)
+        .with_vsync(preferences.vsync)
         .with_depth_buffer(8);
-    let display = Display::new(window_builder, context_builder, &event_loop).unwrap();
+
+    let context_builder = match preferences.get_render_quality().msaa {
+        0 => context_builder,
+        msaa => context_builder.with_multisampling(msaa),
+    };
+
+    Display::new(window_builder, context_builder, event_loop).unwrap()
+}
+
+fn main() {
+    // <コ:彡
+
+    let preferences = Preferences::load();
+
+    // Build window
+    let event_loop = EventLoop::new();
+    let display = create_display(&event_loop, &preferences);
+    let mut plugins = PluginRegistry::with_builtin();
 
     // Build toolbox
     let mut toolbox = ToolBox::new(&display);
@@ -121,8 +190,10 @@ fn main() {
     let mut options_tabs: SlotMap<options::tab::TabRef, Box<dyn options::tab::Tab>> = SlotMap::with_key();
 
     // Create standard tool set
-    toolbox.create_standard_tools(&mut tools, &display);
-    toolbox.create_standard_options_tabs(&mut options_tabs, &display);
+    toolbox.create_standard_tools(&mut tools, &display, &preferences);
+    toolbox.create_plugin_tools(&mut tools, &display, std::mem::take(&mut plugins.tools));
+    toolbox.create_standard_options_tabs(&mut options_tabs, &display, &preferences);
+    toolbox.set_panel_widths([preferences.tool_panel_width, preferences.options_panel_width]);
 
     let ribbon_mesh = MeshXyz::new_ui_rect(&display);
     let ring_mesh = MeshXyz::new_ui_ring(&display);
@@ -132,27 +203,15 @@ fn main() {
     let shaders = Shaders::new(&display);
     let text_system = TextSystem::new(&display);
 
-    let font = FontTexture::new(
-        &display,
-        File::open(&Path::new("Roboto-Regular.ttf")).unwrap(),
-        20,
-        glium_text::FontTexture::ascii_character_list(),
-    )
-    .unwrap();
-
-    fn view_size_from_framebuffer_dimensions(framebuffer_dimensions: (u32, u32), scale_factor: f32) -> glm::Vec2 {
-        let view_width = framebuffer_dimensions.0 as f32 / scale_factor;
-        let view_height = framebuffer_dimensions.1 as f32 / scale_factor;
-        glm::vec2(view_width, view_height)
-    }
+    let font = load_font(&display, &preferences);
 
-    let scale_factor = display.gl_window().window().scale_factor();
+    let scale_factor = display.gl_window().window().scale_factor() * preferences.ui_scale as f64;
     let framebuffer_dimensions = display.get_framebuffer_dimensions();
     let initial_dimensions = view_size_from_framebuffer_dimensions(framebuffer_dimensions, scale_factor as f32);
 
     let mut app = App {
         display,
-        color_scheme: Default::default(),
+        color_scheme: preferences.get_color_scheme(),
         toolbox,
         ribbon_mesh,
         ring_mesh,
@@ -161,13 +220,15 @@ fn main() {
         shaders,
         mouse_position: None,
         scale_factor,
+        window_msaa: preferences.get_render_quality().msaa,
         ocean: Default::default(),
         history: Default::default(),
         dimensions: initial_dimensions,
         projection: None,
         view: None,
-        frame_start_time: Instant::now(),
+        frame_pacer: FramePacer::new(preferences.target_fps),
         camera: Smooth::new(Camera::identity(initial_dimensions), None),
+        pan_inertia: Inertia::default(),
         dragging: None,
         selections: vec![],
         keys_held: BTreeSet::new(),
@@ -176,19 +237,50 @@ fn main() {
         text_system,
         font: Rc::new(font),
         context_menu: None,
-        interaction_options: Default::default(),
+        radial_menu: None,
+        quick_find: None,
+        command_palette: None,
+        script_console: None,
+        pen_path: None,
+        pencil_stroke: None,
+        zoom_box: None,
+        knife_line: None,
+        editing_point: None,
+        shape_drag: None,
+        modals: vec![],
+        breadcrumb: Breadcrumb::new(),
+        document_tabs: DocumentTabs::new(),
+        status_bar: StatusBar::new(),
+        interaction_options: preferences.get_interaction_options(),
+        keymap: Default::default(),
+        preferences,
+        plugins,
+        export_options: Default::default(),
+        pending_raster_exports: vec![],
         wait_for_stop_drag: false,
         operation: None,
         perform_next_operation_collectively: false,
+        show_object_info: false,
         filename: None,
+        documents: vec![Document::new(initial_dimensions)],
+        active_document: 0,
+        clipboard: vec![],
+        style_clipboard: None,
+        commands: vec![],
+        macro_recorder: Default::default(),
+        last_autosave: Instant::now(),
+        panel_cache: PanelCache::new(),
+        mesh_cache: MeshCache::new(),
+        text_batch: None,
+        damage: Damage::new(),
     };
 
     event_loop.run(move |abstract_event, _, control_flow| {
         let framebuffer_dimensions = app.display.get_framebuffer_dimensions();
 
-        app.frame_start_time = Instant::now();
-        app.dimensions = view_size_from_framebuffer_dimensions(framebuffer_dimensions, scale_factor as f32);
+        app.dimensions = view_size_from_framebuffer_dimensions(framebuffer_dimensions, app.scale_factor as f32);
         app.camera.manual_get_real().window = app.dimensions;
+        app.camera.manual_get_real().ui_scale = app.preferences.ui_scale;
 
         // Handle user input
         if let Some(new_control_flow) = on_event(abstract_event, &mut app, &mut tools, &mut options_tabs) {
@@ -197,28 +289,43 @@ fn main() {
         }
 
         // Update components
-        update_components(&mut app);
+        update_components(&mut app, &mut tools);
 
         // Handle control flow
         if !matches!(*control_flow, ControlFlow::Exit) {
-            let elapsed_time = Instant::now().duration_since(app.frame_start_time).as_millis() as u64;
-            app.display.gl_window().window().request_redraw();
-
-            let wait_millis = match 100 / TARGET_FPS >= elapsed_time {
-                true => 1000 / TARGET_FPS - elapsed_time,
-                false => 0,
-            };
-
-            *control_flow = ControlFlow::WaitUntil(app.frame_start_time + Duration::from_millis(wait_millis));
+            *control_flow = get_control_flow(&app, &tools, &options_tabs);
         }
     });
 }
 
-fn on_modifiers_changed(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, value: ModifiersState) {
+// Keeps redrawing at the target frame rate while anything is changing,
+// otherwise sleeps until woken up by an event
+fn get_control_flow(app: &App, tools: &SlotMap<ToolKey, Tool>, options_tabs: &SlotMap<TabRef, Box<dyn Tab>>) -> ControlFlow {
+    if !app.needs_redraw() && !is_panel_animating(app, tools, options_tabs) {
+        // Wake up in time for the next autosave though
+        return app.get_next_autosave().map_or(ControlFlow::Wait, ControlFlow::WaitUntil);
+    }
+
+    if app.frame_pacer.is_frame_due(Instant::now()) {
+        app.display.gl_window().window().request_redraw();
+    }
+
+    ControlFlow::WaitUntil(app.frame_pacer.get_next_frame())
+}
+
+// Whether anything in the side panels is still animating
+fn is_panel_animating(app: &App, tools: &SlotMap<ToolKey, Tool>, options_tabs: &SlotMap<TabRef, Box<dyn Tab>>) -> bool {
+    app.toolbox.is_animating() || tools.values().any(Tool::is_animating) || options_tabs.values().any(|tab| tab.is_animating())
+}
+
+fn on_modifiers_changed(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>, value: ModifiersState) {
     app.modifiers_held = value;
 
+    // Holding ctrl while dragging moves and turns freely
+    app.interaction_options.snapping_suspended = value.ctrl_or_cmd();
+
     if app.dragging.is_some() {
-        let capture = do_drag(app, tools);
+        let capture = do_drag(app, tools, options_tabs);
         app.do_capture(capture);
     }
 }
@@ -227,46 +334,134 @@ fn on_event(event: Event<()>, app: &mut App, tools: &mut SlotMap<ToolKey, Tool>,
     use WindowEvent::*;
 
     match event {
-        Event::WindowEvent { event, .. } => match event {
-            CloseRequested => return Some(ControlFlow::Exit),
-            KeyboardInput { input, .. } => on_keyboard_input(app, tools, input),
-            ModifiersChanged(value) => on_modifiers_changed(app, tools, value),
-            MouseInput { state, button, .. } => on_mouse_input(app, tools, options_tabs, state, button),
-            CursorMoved { position, .. } => on_mouse_move(app, tools, position),
-            ScaleFactorChanged { scale_factor, .. } => app.scale_factor = scale_factor,
-            MouseWheel { delta, .. } => on_scroll(app, delta),
-            _ => (),
-        },
+        Event::WindowEvent { event, .. } => {
+            app.damage.mark();
+
+            // Side panels only have to be redrawn when an event could have changed them
+            if !is_canvas_only_event(app, &event) {
+                app.panel_cache.invalidate();
+            }
+
+            match event {
+                CloseRequested => {
+                    if let Err(message) = app.preferences.save() {
+                        eprintln!("{}", message);
+                    }
+
+                    return Some(ControlFlow::Exit);
+                }
+                KeyboardInput { input, .. } => on_keyboard_input(app, tools, options_tabs, input),
+                ReceivedCharacter(character) => on_character(app, tools, options_tabs, character),
+                ModifiersChanged(value) => on_modifiers_changed(app, tools, options_tabs, value),
+                MouseInput { state, button, .. } => on_mouse_input(app, tools, options_tabs, state, button),
+                CursorMoved { position, .. } => on_mouse_move(app, tools, options_tabs, position),
+                ScaleFactorChanged { .. } => app.update_scale_factor(),
+                MouseWheel { delta, .. } => on_scroll(app, tools, options_tabs, delta),
+                _ => (),
+            }
+        }
         Event::RedrawRequested(..) => redraw(app, tools, options_tabs),
         _ => (),
     }
     None
 }
 
-fn update_components(app: &mut App) {
+fn is_canvas_only_event(app: &App, event: &WindowEvent) -> bool {
+    let mouse_position = app.mouse_position.map(OnScreen::on_screen).unwrap_or_default();
+    let is_dragging_from_panels = app.dragging.as_ref().is_some_and(|dragging| app.is_over_panels(&dragging.down));
+
+    match event {
+        WindowEvent::CursorMoved { position, .. } => {
+            let new_mouse_position = position.to_logical(app.scale_factor).on_screen();
+            !app.is_over_panels(&mouse_position) && !app.is_over_panels(&new_mouse_position) && !is_dragging_from_panels
+        }
+        WindowEvent::MouseWheel { .. } => !app.is_over_panels(&mouse_position),
+        _ => false,
+    }
+}
+
+fn view_size_from_framebuffer_dimensions(framebuffer_dimensions: (u32, u32), scale_factor: f32) -> glm::Vec2 {
+    let view_width = framebuffer_dimensions.0 as f32 / scale_factor;
+    let view_height = framebuffer_dimensions.1 as f32 / scale_factor;
+    glm::vec2(view_width, view_height)
+}
+
+fn update_components(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>) {
     let [width, height]: [f32; 2] = app.dimensions.into();
 
     app.toolbox.update(width, height);
 
+    if let Some(tool) = app.toolbox.get_selected().and_then(|tool_key| tools.get_mut(tool_key)) {
+        tool.update(app);
+    }
+
+    let mouse_in_world = app.mouse_position.map(|_| app.get_mouse_in_world_space());
+    app.status_bar.update(
+        mouse_in_world,
+        app.camera.get_real().zoom,
+        &app.ocean,
+        &app.selections,
+        app.macro_recorder.is_recording(),
+        app.interaction_options.is_snapping(),
+    );
+
+    app.autosave();
+
+    // Keep panning after a pan drag is let go
+    if let Some(delta) = app.pan_inertia.step(Instant::now()) {
+        app.scroll_pan(&delta);
+        app.damage.mark();
+    }
+
+    // The color picker goes back to editing the selection once what it was routed to isn't selected anymore
+    if !app.is_color_target_selected(app.toolbox.color_picker.get_target()) {
+        app.toolbox.color_picker.set_target(ColorTarget::Selection);
+    }
+
     if let Some(new_color) = app.toolbox.color_picker.poll() {
-        for selection in app.selections.iter().filter(|selection| selection.limb_id.is_none()) {
-            if let Some(squid) = app.ocean.get_mut(selection.squid_id) {
-                squid.set_color(new_color);
-            }
+        match app.toolbox.color_picker.get_target() {
+            ColorTarget::Selection => app.emit(DocumentCommand::SetSelectedColor(new_color)),
+            ColorTarget::Style(style) => app.emit(DocumentCommand::SetStyleColor(style, new_color)),
         }
     }
+
+    app.apply_commands();
 }
 
 fn redraw(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<options::tab::TabRef, Box<dyn options::tab::Tab>>) {
+    app.frame_pacer.frame_drawn(Instant::now());
+    app.damage.clear();
+
     // Get dimensions of window
     let [width, height]: [f32; 2] = app.dimensions.into();
-    let (width_u32, height_u32) = app.display.get_framebuffer_dimensions();
 
-    // Create texture to hold render output (if we aren't going to render directly)
+    // Create textures to hold render output (if we aren't going to render directly),
+    // where drawing is multisampled first and then resolved if needed
+    let quality = app.preferences.get_render_quality();
+    let draw_directly = app.can_draw_directly();
+    let (width_u32, height_u32) = quality.get_texture_dimensions(app.display.get_framebuffer_dimensions());
     let rendered = glium::texture::SrgbTexture2d::empty(&app.display, width_u32, height_u32).unwrap();
+    let multisampled = (!draw_directly && quality.msaa > 0)
+        .then(|| glium::texture::SrgbTexture2dMultisample::empty(&app.display, width_u32, height_u32, quality.msaa.into()).ok())
+        .flatten();
 
-    // Create framebuffer (in case we aren't going to render directly)
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&app.display, &rendered).unwrap();
+    // Take texture for side panels if they need to be redrawn
+    if is_panel_animating(app, tools, options_tabs) {
+        app.panel_cache.invalidate();
+    }
+
+    app.panel_cache.track_camera(app.camera.get_real());
+    let panel_scale = PanelCache::get_scale(app.scale_factor);
+    let panel_texture = app
+        .panel_cache
+        .begin_refresh(&app.display, (width as f64 * panel_scale) as u32, (height as f64 * panel_scale) as u32);
+
+    // Take the next PNG export waiting to be rendered, one is done per frame
+    let raster_export = (!app.pending_raster_exports.is_empty()).then(|| app.pending_raster_exports.remove(0));
+    let raster_texture = raster_export.as_ref().map(|job| {
+        let (texture_width, texture_height) = job.get_texture_dimensions();
+        glium::texture::SrgbTexture2d::empty(&app.display, texture_width, texture_height).unwrap()
+    });
 
     // Setup matrices
     app.projection = Some(glm::ortho(0.0, width, height, 0.0, 100.0, -100.0));
@@ -276,15 +471,55 @@ fn redraw(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut
     let mut target = app.display.draw();
 
     // Render main application
-    render_app(app, tools, options_tabs, &mut target, &mut framebuffer);
+    {
+        let mut framebuffer = match &multisampled {
+            Some(texture) => glium::framebuffer::SimpleFrameBuffer::new(&app.display, texture),
+            None => glium::framebuffer::SimpleFrameBuffer::new(&app.display, &rendered),
+        }
+        .unwrap();
+
+        let mut panel_framebuffer = panel_texture
+            .as_ref()
+            .map(|texture| glium::framebuffer::SimpleFrameBuffer::new(&app.display, texture).unwrap());
+
+        let mut raster_framebuffer = raster_texture
+            .as_ref()
+            .map(|texture| glium::framebuffer::SimpleFrameBuffer::new(&app.display, texture).unwrap());
+
+        render_app(
+            app,
+            tools,
+            options_tabs,
+            &mut target,
+            &mut framebuffer,
+            panel_framebuffer.as_mut(),
+            raster_export.as_ref().zip(raster_framebuffer.as_mut()),
+        );
+    }
+
+    if let Some(panel_texture) = panel_texture {
+        app.panel_cache.finish_refresh(panel_texture);
+    }
 
     // If we rendered indirectly, then render the final output to screen now
-    if app.scale_factor != 1.0 {
+    if !draw_directly {
+        if let Some(multisampled) = &multisampled {
+            resolve_multisampled(&app.display, multisampled, &rendered);
+        }
+
         render_television(&mut target, &rendered, &app.square_xyzuv, &app.shaders.television_shader);
     }
 
     // Finalize render
     target.finish().unwrap();
+
+    if let (Some(job), Some(texture)) = (raster_export, raster_texture) {
+        if job.finish(&texture).is_err() {
+            app.show_export_error(&job.path);
+        }
+
+        app.advance_progress();
+    }
 }
 
 fn render_app<'f>(
@@ -293,20 +528,29 @@ fn render_app<'f>(
     options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>,
     target: &'f mut glium::Frame,
     framebuffer: &'f mut glium::framebuffer::SimpleFrameBuffer<'f>,
+    panel_framebuffer: Option<&'f mut glium::framebuffer::SimpleFrameBuffer<'f>>,
+    raster_export: Option<(&RasterExport, &'f mut glium::framebuffer::SimpleFrameBuffer<'f>)>,
 ) {
     let [width, height]: [f32; 2] = app.dimensions.into();
+    let draw_directly = app.can_draw_directly();
+
+    // Matrices for rendering the PNG export that's in progress (if any)
+    let raster_matrices = raster_export.as_ref().map(|(job, _)| {
+        let camera = job.get_camera();
+        let view = camera.mat();
+        (camera, job.get_projection(), view)
+    });
 
     // TLDR: Don't use 'target' or 'framebuffer' directly unless you really know
     // what you're doing. Instead use methods of 'RenderCtx' that will automatically
     // use the appropriate target.
 
     // NOTE: We won't use 'target' and 'framebuffer' directly most of the time,
-    // since which we render to is dependent on the display's scale factor.
-    // We do this because on macOS, for some reason, to only way to enable MSAA,
-    // is by rendering directly to the buffer. So for retina (and other non-1-factor displays),
-    // we will render to a framebuffer first (since more pixels will be sampled anyways),
-    // but for displays that have a 1-factor ratio, we will render directly and utilize
-    // the built in MSAA for the window render target (this is the only portable way apparently)
+    // since which we render to is dependent on the display's scale factor and the render quality.
+    // When the window was created with the chosen multisampling and doesn't need scaling,
+    // we will render directly and utilize the built in MSAA for the window render target.
+    // Otherwise, we will render to a framebuffer first (which is multisampled and/or supersampled
+    // as needed) and then draw that onto the window
     // Render context is a subset of App that only
     // contains information related to rendering
 
@@ -314,14 +558,19 @@ fn render_app<'f>(
         target,
         framebuffer,
         color_shader: &app.shaders.color_shader,
+        batch_shader: &app.shaders.batch_shader,
         hue_value_picker_shader: &app.shaders.hue_value_picker_shader,
         saturation_picker_shader: &app.shaders.saturation_picker_shader,
+        alpha_picker_shader: &app.shaders.alpha_picker_shader,
         rounded_rectangle_shader: &app.shaders.rounded_rectangle_shader,
+        television_shader: &app.shaders.television_shader,
+        blur_shader: &app.shaders.blur_shader,
         projection: &app.projection.unwrap(),
         view: &app.view.unwrap(),
         width,
         height,
-        scale_factor: app.scale_factor,
+        scale_factor: app.scale_factor * app.preferences.get_render_quality().supersampling as f64,
+        draw_directly,
         ribbon_mesh: &app.ribbon_mesh,
         ring_mesh: &app.ring_mesh,
         check_mesh: &app.check_mesh,
@@ -330,58 +579,351 @@ fn render_app<'f>(
         camera: &app.camera.get_animated(),
         real_camera: app.camera.get_real(),
         display: &app.display,
+        clip: None,
+        text_batch: app.text_batch.take(),
+        text_clip: None,
+        length_format: app.get_length_format(),
     };
 
     ctx.clear_color(&app.color_scheme.background);
 
     // Render squids and their selection points
-    {
-        let ctx = &mut ctx;
-        let mut all_selection_points: Vec<glm::Vec2> = vec![];
+    app.ocean.prepare(ctx.display, &mut app.mesh_cache);
+    render_squids(&mut ctx, &app.ocean, &app.selections);
+    render_canvas_overlays(&mut ctx, app, tools);
+
+    if let (Some((_, raster_framebuffer)), Some((camera, projection, view))) = (raster_export, &raster_matrices) {
+        render_raster_export(&mut ctx, &app.ocean, raster_framebuffer, camera, projection, view);
+    }
+
+    // Redraw side panels into the panel cache if needed
+    if let Some(panel_framebuffer) = panel_framebuffer {
+        // Temporarily redirect drawing into the panel framebuffer
+        let canvas_framebuffer = std::mem::replace(&mut ctx.framebuffer, panel_framebuffer);
+        let canvas_scale_factor = std::mem::replace(&mut ctx.scale_factor, PanelCache::get_scale(app.scale_factor));
+        ctx.draw_directly = false;
+        ctx.clear_color(&app.color_scheme.background);
+
+        app.toolbox.render(
+            &mut ctx,
+            tools,
+            options_tabs,
+            &app.color_scheme,
+            &app.text_system,
+            app.font.clone(),
+            &app.ocean,
+            &app.selections,
+        );
+
+        ctx.flush_text(&app.text_system);
+        ctx.framebuffer = canvas_framebuffer;
+        ctx.scale_factor = canvas_scale_factor;
+        ctx.draw_directly = draw_directly;
+    }
+
+    app.panel_cache.render(&mut ctx, &app.toolbox.get_panel_areas(&app.dimensions));
+
+    let document_tabs_area = DocumentTabs::get_area(&app.toolbox.get_panel_areas(&app.dimensions));
+    app.document_tabs.update(app.get_document_names());
+    app.document_tabs
+        .render(&mut ctx, &app.text_system, app.font.clone(), &document_tabs_area, app.active_document);
+
+    app.breadcrumb.update(&app.ocean, &app.selections);
+    app.breadcrumb.render(&mut ctx, &app.text_system, app.font.clone(), document_tabs_area.min_x);
+
+    let status_bar_area = StatusBar::get_area(&app.toolbox.get_panel_areas(&app.dimensions));
+    app.status_bar.render(&mut ctx, &app.text_system, app.font.clone(), &status_bar_area);
+
+    // Text is batched per panel, so each overlay is finished before the next is drawn on top
+    ctx.flush_text(&app.text_system);
+
+    if let Some(context_menu) = &mut app.context_menu {
+        let mouse_position = app.mouse_position.map(|position| glm::vec2(position.x, position.y));
+        context_menu.render(&mut ctx, &app.text_system, app.font.clone(), mouse_position);
+        ctx.flush_text(&app.text_system);
+    }
+
+    if let Some(radial_menu) = &mut app.radial_menu {
+        let mouse_position = app.mouse_position.map(|position| glm::vec2(position.x, position.y));
+        radial_menu.render(&mut ctx, &app.text_system, app.font.clone(), mouse_position);
+        ctx.flush_text(&app.text_system);
+    }
+
+    render_overlays(&mut ctx, app);
+    app.text_batch = ctx.text_batch.take();
+}
+
+fn render_squids(ctx: &mut RenderCtx, ocean: &Ocean, selections: &[Selection]) {
+    let mut all_selection_points: Vec<glm::Vec2> = vec![];
+
+    // Skip drawing squids that are entirely off screen
+    let window = ctx.camera.window;
+    let corners = [glm::zero(), glm::vec2(window.x, 0.0), glm::vec2(0.0, window.y), window].map(|corner| ctx.camera.apply_reverse(&corner));
+    let view = AABB::from_points(&corners).unwrap();
+
+    draw_ocean(ctx, ocean, Some(&view));
+
+    for (reference, _) in ocean.get_visible_squids_lowest() {
+        if let Some(squid) = ocean.get(reference).filter(|_| selection_contains(selections, reference)) {
+            squid.get_selection_points(ctx.camera, &mut all_selection_points);
+        }
+    }
+
+    for point in all_selection_points {
+        ctx.ring_mesh.render(ctx, point, *squid::HANDLE_SIZE, &ctx.color_scheme.foreground);
+    }
+}
+
+// Draws the squids of visible layers from lowest to highest, skipping ones outside of the view (if any).
+// Squids and layers that are blurred are drawn on their own, so they can be blurred before being blended in
+fn draw_ocean(ctx: &mut RenderCtx, ocean: &Ocean, view: Option<&AABB>) {
+    let mut batch = SquidBatch::default();
+
+    for layer in ocean.get_layers().iter().filter(|layer| !layer.is_hidden()) {
+        let opacity = layer.get_opacity();
+
+        let mut blurred_layer = (layer.get_blur() > 0.0).then(|| {
+            batch.flush(ctx);
+            BlurLayer::new(ctx)
+        });
+
+        for squid in layer.get_lowest().filter_map(|reference| ocean.get(reference)) {
+            let blur = squid.get_blur();
+            let is_in_view = |view: &AABB| squid.get_bounding_box().padded(blur + layer.get_blur()).intersecting_aabb(view);
 
-        for reference in &app.ocean.get_squids_lowest().collect::<Vec<_>>() {
-            if let Some(squid) = app.ocean.get_mut(*reference) {
-                squid.render(ctx, None);
+            let drawable = match squid.get_drawable(None).filter(|_| view.is_none_or(is_in_view)) {
+                Some(drawable) => drawable,
+                None => continue,
+            };
+
+            if blur <= 0.0 {
+                batch.push(&drawable, opacity);
+                continue;
+            }
+
+            let mut blurred_squid = BlurLayer::new(ctx);
+            let mut alone = SquidBatch::default();
+            alone.push(&drawable, opacity);
+            alone.flush(&mut blurred_squid);
 
-                if selection_contains(&app.selections, *reference) {
-                    squid.get_selection_points(ctx.camera, &mut all_selection_points);
+            match &mut blurred_layer {
+                Some(blurred_layer) => {
+                    batch.flush(blurred_layer);
+                    blurred_squid.finish_onto(blurred_layer, ctx, blur);
+                }
+                None => {
+                    batch.flush(ctx);
+                    blurred_squid.finish(ctx, blur);
                 }
             }
         }
 
-        for point in all_selection_points {
-            ctx.ring_mesh.render(ctx, point, *squid::HANDLE_SIZE, &ctx.color_scheme.foreground);
+        if let Some(mut blurred_layer) = blurred_layer {
+            batch.flush(&mut blurred_layer);
+            blurred_layer.finish(ctx, layer.get_blur());
+        }
+    }
+
+    batch.flush(ctx);
+}
+
+// Draws overlays that take all input while open
+fn render_overlays(ctx: &mut RenderCtx, app: &App) {
+    if let Some(quick_find) = &app.quick_find {
+        quick_find.render(ctx, &app.text_system, app.font.clone());
+        ctx.flush_text(&app.text_system);
+    }
+
+    if let Some(command_palette) = &app.command_palette {
+        command_palette.render(ctx, &app.text_system, app.font.clone());
+        ctx.flush_text(&app.text_system);
+    }
+
+    if let Some(script_console) = &app.script_console {
+        script_console.render(ctx, &app.text_system, app.font.clone());
+        ctx.flush_text(&app.text_system);
+    }
+
+    if let Some(modal) = app.modals.first() {
+        modal.render(ctx, &app.text_system, app.font.clone());
+        ctx.flush_text(&app.text_system);
+    }
+}
+
+// Draws what sits on top of the squids, such as paths being drawn and readouts
+fn render_canvas_overlays(ctx: &mut RenderCtx, app: &App, tools: &SlotMap<ToolKey, Tool>) {
+    if let Some(tool) = app.toolbox.get_selected().and_then(|tool_key| tools.get(tool_key)) {
+        tool.render_canvas(ctx, app);
+    }
+
+    if let Some(pen_path) = &app.pen_path {
+        // Only show where the next anchor would go while not dragging out handles
+        let mouse_position = app.mouse_position.filter(|_| app.dragging.is_none());
+        pen_path.render(ctx, mouse_position.map(|position| glm::vec2(position.x, position.y)));
+    }
+
+    if let Some(pencil_stroke) = &app.pencil_stroke {
+        pencil_stroke.render(ctx);
+    }
+
+    if let Some(zoom_box) = &app.zoom_box {
+        zoom_box.render(ctx);
+    }
+
+    if let Some(knife_line) = &app.knife_line {
+        knife_line.render(ctx);
+    }
+
+    render_size_readout(ctx, app);
+    render_rotation_readout(ctx, app);
+
+    if app.show_object_info {
+        render_object_info(ctx, app);
+    }
+}
+
+// Describes each selected squid next to its top right corner
+fn render_object_info(ctx: &mut RenderCtx, app: &App) {
+    const LINE_HEIGHT: f32 = 20.0;
+
+    for reference in app.get_selected_squids() {
+        let squid = match app.ocean.get(reference) {
+            Some(squid) => squid,
+            None => continue,
+        };
+
+        let on_screen: Vec<glm::Vec2> = squid.get_extreme_points().iter().map(|point| ctx.camera.apply(point)).collect();
+
+        let bounds = match AABB::from_points(&on_screen) {
+            Some(bounds) => bounds,
+            None => continue,
+        };
+
+        let lines = squid.get_info();
+        let width = lines.iter().map(|line| get_text_width(&app.font, line)).fold(0.0, f32::max);
+        let position = glm::vec2(bounds.max_x + 12.0, bounds.min_y);
+
+        ctx.ribbon_mesh.render(
+            ctx,
+            position,
+            glm::vec2(width + 16.0, LINE_HEIGHT * lines.len() as f32 + 8.0),
+            &ctx.color_scheme.dark_ribbon,
+        );
+
+        for (i, line) in lines.iter().enumerate() {
+            let color = if i == 0 {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+            draw_text(
+                &app.text_system,
+                app.font.clone(),
+                line,
+                &(position + glm::vec2(8.0, 18.0 + i as f32 * LINE_HEIGHT)),
+                ctx,
+                color,
+            );
         }
     }
+}
+
+// Shows the size of a squid being resized next to the cursor
+fn render_size_readout(ctx: &mut RenderCtx, app: &App) {
+    let readout = app
+        .selections
+        .iter()
+        .find_map(|selection| app.ocean.get(selection.squid_id)?.get_size_readout());
+
+    if let (Some(readout), Some(mouse)) = (readout, app.mouse_position) {
+        render_readout(ctx, app, &readout, &glm::vec2(mouse.x + 16.0, mouse.y + 16.0));
+    }
+}
+
+// Shows how far a rotate or revolve has turned next to its pivot,
+// along with the angle it actually snapped to when rotation snapping is on or shift is held
+fn render_rotation_readout(ctx: &mut RenderCtx, app: &App) {
+    let (pivot, total) = match app.operation.as_ref().and_then(|operation| operation.get_rotation_readout(ctx.camera)) {
+        Some(readout) => readout,
+        None => return,
+    };
+
+    let degrees = total.scalar().to_degrees();
+    let snapping = squid::behavior::get_rotation_snapping(&app.interaction_options, app.modifiers_held.shift())
+        .scalar()
+        .to_degrees();
+
+    let readout = if snapping > 0.0 {
+        format!("{:.1} deg ({:.0} deg)", degrees, (degrees / snapping).round() * snapping)
+    } else {
+        format!("{:.1} deg", degrees)
+    };
+
+    render_readout(ctx, app, &readout, &(pivot + glm::vec2(16.0, 16.0)));
+}
 
-    app.toolbox.render(
-        &mut ctx,
-        tools,
-        options_tabs,
-        &app.color_scheme,
+// Small label drawn over the canvas
+fn render_readout(ctx: &mut RenderCtx, app: &App, readout: &str, position: &glm::Vec2) {
+    let size = glm::vec2(get_text_width(&app.font, readout) + 16.0, 24.0);
+
+    ctx.ribbon_mesh.render(ctx, *position, size, &ctx.color_scheme.dark_ribbon);
+    draw_text(
         &app.text_system,
         app.font.clone(),
-        &mut app.ocean,
-        &app.selections,
+        readout,
+        &(position + glm::vec2(8.0, 18.0)),
+        ctx,
+        ctx.color_scheme.foreground,
     );
+}
 
-    if let Some(context_menu) = &mut app.context_menu {
-        context_menu.render(&mut ctx, &app.text_system, app.font.clone());
-    }
+// Renders the ocean as it should appear in a PNG export, without any viewports or editing aids
+fn render_raster_export<'a, 'f>(
+    ctx: &mut RenderCtx<'a, 'f>,
+    ocean: &Ocean,
+    raster_framebuffer: &'f mut glium::framebuffer::SimpleFrameBuffer<'f>,
+    camera: &'a Camera,
+    projection: &'a glm::Mat4,
+    view: &'a Affine2,
+) {
+    // Temporarily redirect drawing into the export framebuffer
+    let canvas_framebuffer = std::mem::replace(&mut ctx.framebuffer, raster_framebuffer);
+    let canvas_camera = std::mem::replace(&mut ctx.camera, camera);
+    let canvas_projection = std::mem::replace(&mut ctx.projection, projection);
+    let canvas_view = std::mem::replace(&mut ctx.view, view);
+    let canvas_draw_directly = std::mem::replace(&mut ctx.draw_directly, false);
+
+    ctx.clear_color(&Color::new(0.0, 0.0, 0.0, 0.0));
+    draw_ocean(ctx, ocean, None);
+
+    ctx.framebuffer = canvas_framebuffer;
+    ctx.camera = canvas_camera;
+    ctx.projection = canvas_projection;
+    ctx.view = canvas_view;
+    ctx.draw_directly = canvas_draw_directly;
+}
+
+// Averages the samples of each pixel, so the result can be drawn like any other texture
+fn resolve_multisampled(display: &Display, multisampled: &glium::texture::SrgbTexture2dMultisample, resolved: &glium::texture::SrgbTexture2d) {
+    use glium::Surface;
+
+    let source = glium::framebuffer::SimpleFrameBuffer::new(display, multisampled).unwrap();
+    let destination = glium::framebuffer::SimpleFrameBuffer::new(display, resolved).unwrap();
+    source.fill(&destination, glium::uniforms::MagnifySamplerFilter::Nearest);
 }
 
 fn render_television(target: &mut glium::Frame, rendered: &glium::texture::SrgbTexture2d, television: &MeshXyzUv, television_shader_program: &glium::Program) {
-    // If we're not doing MSAA, render a framebuffer instead of having just rendered directly.
+    // If we didn't draw directly, render the framebuffer's texture instead.
     // Draw render to window
 
     use glium::Surface;
 
-    let identity = glm::identity::<f32, 4>();
+    let identity = Affine2::identity();
 
     let uniforms = glium::uniform! {
         transformation: identity.as_values(),
         view: identity.as_values(),
-        projection: identity.as_values(),
+        projection: glm::Mat4::identity().as_values(),
         texture_sampler: rendered
     };
 
@@ -396,24 +938,38 @@ fn render_television(target: &mut glium::Frame, rendered: &glium::texture::SrgbT
         .unwrap();
 }
 
-fn do_click_context_menu(app: &mut App, button: MouseButton, mouse_position: &glm::Vec2) -> Capture {
-    use ContextAction::*;
+fn do_click_context_menu(app: &mut App, tools: &SlotMap<ToolKey, Tool>, button: MouseButton, mouse_position: &glm::Vec2) -> Capture {
+    if let Some(context_menu) = &mut app.context_menu {
+        let response = context_menu.click(button, mouse_position);
+
+        // Clicking outside of the context menu dismisses it and continues on to whatever was clicked
+        if let ContextMenuResponse::Close = response {
+            app.context_menu = None;
+            return Capture::Miss;
+        }
 
-    if let Some(context_menu) = &app.context_menu {
-        // Get context menu action
-        let action = context_menu.click(button, mouse_position);
+        app.respond_to_context_menu(response, tools);
+        Capture::NoDrag
+    } else {
+        Capture::Miss
+    }
+}
 
-        // Destroy context menu
-        app.context_menu = None;
+// Status bar only displays information, so clicks on it go nowhere
+fn do_click_status_bar(app: &App, mouse_position: &glm::Vec2) -> Capture {
+    if StatusBar::get_area(&app.toolbox.get_panel_areas(&app.dimensions)).intersecting_point(mouse_position.x, mouse_position.y) {
+        Capture::NoDrag
+    } else {
+        Capture::Miss
+    }
+}
 
-        match action {
-            Some(DeleteSelected) => app.delete_selected(),
-            Some(DuplicateSelected) => app.duplicate_selected(),
-            Some(GrabSelected) => app.grab_selected(),
-            Some(RotateSelected) => app.rotate_selected(),
-            Some(ScaleSelected) => app.scale_selected(),
-            Some(Collectively) => app.toggle_next_operation_collectively(),
-            None => return Capture::Miss,
+fn do_click_radial_menu(app: &mut App, tools: &SlotMap<ToolKey, Tool>, mouse_position: &glm::Vec2) -> Capture {
+    // Radial menu was left open after a right-click without a direction,
+    // so the next click either chooses an option or dismisses it
+    if let Some(radial_menu) = app.radial_menu.take() {
+        if let Some(action) = radial_menu.select(mouse_position) {
+            app.perform_context_action(action, tools);
         }
 
         Capture::NoDrag
@@ -426,6 +982,7 @@ fn do_click(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mu
     // Returns whether a drag is allowed to start
 
     app.mouse_buttons_held.insert(button);
+    app.pan_inertia.stop();
 
     use bool_poll::BoolPoll;
 
@@ -439,8 +996,38 @@ fn do_click(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mu
     let position = glm::vec2(position.x, position.y);
     let [width, height]: [f32; 2] = app.dimensions.into();
 
+    // Dialogs block everything underneath them
+    if let Some(modal) = app.modals.first_mut() {
+        let response = modal.click(&position, &app.dimensions, &app.font);
+        app.respond_to_modal(response);
+        return Capture::NoDrag;
+    }
+
+    // Quick find overlay
+    if let Some(quick_find) = &app.quick_find {
+        let response = quick_find.click(&position, width);
+        app.respond_to_quick_find(response);
+        return Capture::NoDrag;
+    }
+
+    // Command palette overlay
+    if let Some(command_palette) = &app.command_palette {
+        let response = command_palette.click(&position, width);
+        app.respond_to_command_palette(response, tools);
+        return Capture::NoDrag;
+    }
+
+    // Script console overlay
+    if let Some(script_console) = &app.script_console {
+        let response = script_console.click(&position, width, app.dimensions.y);
+        app.respond_to_script_console(response);
+        return Capture::NoDrag;
+    }
+
     // Context Menu
-    do_click_context_menu(app, button, &position)?;
+    do_click_context_menu(app, tools, button, &position)?;
+    do_click_radial_menu(app, tools, &position)?;
+    do_click_status_bar(app, &position)?;
 
     let interaction = Interaction::Click(ClickInteraction {
         button,
@@ -448,6 +1035,19 @@ fn do_click(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mu
         modifiers: app.modifiers_held,
     });
 
+    // Breadcrumb
+    if button == MouseButton::Left {
+        if let Some(target) = app.breadcrumb.click(&position) {
+            app.select_breadcrumb_target(target);
+            return Capture::NoDrag;
+        }
+
+        if let Some(target) = app.document_tabs.click(&position) {
+            app.respond_to_document_tab(target);
+            return Capture::NoDrag;
+        }
+    }
+
     // Tool options ribbon
     if let Some(tool_key) = app.toolbox.get_selected() {
         tools[tool_key].interact_options(interaction, app)?;
@@ -456,12 +1056,21 @@ fn do_click(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mu
     // Tool ribbon
     app.toolbox.click(interaction, width, height)?;
 
-    if button == MouseButton::Left && position.x > width - 256.0 {
+    if button == MouseButton::Left && position.x > app.toolbox.get_options_area(&app.dimensions).min_x {
         if let Some(current_tab) = options_tabs.get_mut(app.toolbox.get_current_options_tab_key()) {
             return current_tab.interact(interaction, app);
         }
     }
 
+    // Holding alt while the color picker is shown samples colors from the canvas,
+    // unless zooming, where alt-clicking zooms out
+    let zooming = app.toolbox.get_selected().is_some_and(|tool_key| tools[tool_key].kind() == ToolKind::Zoom);
+
+    if button == MouseButton::Left && app.modifiers_held.alt() && app.toolbox.is_showing_color_picker() && !zooming {
+        app.pick_color_at(&position);
+        return Capture::NoDrag;
+    }
+
     if let Some(tool_key) = app.toolbox.get_selected() {
         tools[tool_key].interact(interaction, app)?;
     }
@@ -469,16 +1078,17 @@ fn do_click(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mu
     Capture::Miss
 }
 
-fn do_mouse_release(app: &mut App, button: MouseButton) {
+fn do_mouse_release(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>, button: MouseButton) {
     let position = app.mouse_position.unwrap();
     let position = glm::vec2(position.x, position.y);
     let animated_camera = app.camera.get_animated();
     let unordered_squids: Vec<SquidRef> = app.ocean.get_squids_unordered().collect();
 
     app.mouse_buttons_held.remove(&button);
+    app.pan_inertia.release(Instant::now());
 
     for reference in unordered_squids {
-        if let Some(squid) = app.ocean.get_mut(reference) {
+        if let Some(squid) = app.ocean.get_mut_in_place(reference) {
             squid.interact(
                 &Interaction::MouseRelease(MouseReleaseInteraction { position, button }),
                 &animated_camera,
@@ -487,13 +1097,33 @@ fn do_mouse_release(app: &mut App, button: MouseButton) {
         }
     }
 
-    app.toolbox.mouse_release(button);
+    // Remember resized panels for next time
+    if app.toolbox.mouse_release(button) {
+        [app.preferences.tool_panel_width, app.preferences.options_panel_width] = app.toolbox.get_panel_widths();
+    }
+
+    // Lets go of anything being dragged in the current options tab
+    do_options_tab_interaction(app, options_tabs, Interaction::MouseRelease(MouseReleaseInteraction { position, button }));
+
+    if let Some(tool_key) = app.toolbox.get_selected() {
+        tools[tool_key].interact_options(Interaction::MouseRelease(MouseReleaseInteraction { position, button }), app);
+        tools[tool_key].interact(Interaction::MouseRelease(MouseReleaseInteraction { position, button }), app);
+    }
+
+    // Releasing right-click in a direction chooses from the radial menu
+    if button == MouseButton::Right {
+        if let Some(action) = app.radial_menu.as_ref().and_then(|radial_menu| radial_menu.select(&position)) {
+            app.radial_menu = None;
+            app.perform_context_action(action, tools);
+        }
+    }
 
-    // Primitive history
+    // Primitive history, which includes whatever was changed during the drag
+    app.apply_commands();
     app.add_history_marker();
 }
 
-fn do_drag(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>) -> Capture {
+fn do_drag(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>) -> Capture {
     use MouseButton::*;
 
     let drag = app.dragging.as_ref().unwrap().to_interaction(app.modifiers_held);
@@ -501,6 +1131,18 @@ fn do_drag(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>) -> Capture {
 
     app.toolbox.drag(Left, &drag, width)?;
 
+    // Drags that started in the options panel belong to the current tab
+    if drag.as_drag().unwrap().start.x > app.toolbox.get_options_area(&app.dimensions).min_x {
+        if let Some(current_tab) = options_tabs.get_mut(app.toolbox.get_current_options_tab_key()) {
+            return current_tab.interact(drag, app);
+        }
+    }
+
+    // Sliders in the tool options keep following the mouse once grabbed
+    if let Some(tool_key) = app.toolbox.get_selected() {
+        tools[tool_key].interact_options(drag, app)?;
+    }
+
     // Redirect middle mouse button to pan tool
     if app.mouse_buttons_held.contains(&Middle) {
         if let Some(pan_tool) = find_tool(tools, ToolKind::Pan) {
@@ -515,7 +1157,12 @@ fn do_drag(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>) -> Capture {
     Capture::Miss
 }
 
-pub fn on_keyboard_input(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, input: glium::glutin::event::KeyboardInput) {
+pub fn on_keyboard_input(
+    app: &mut App,
+    tools: &mut SlotMap<ToolKey, Tool>,
+    options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>,
+    input: glium::glutin::event::KeyboardInput,
+) {
     use ElementState::*;
 
     if let Some(virtual_keycode) = input.virtual_keycode {
@@ -525,7 +1172,12 @@ pub fn on_keyboard_input(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, inpu
             Pressed => {
                 if keys_held.insert(virtual_keycode) {
                     // Press first time
-                    app.press_key(virtual_keycode, tools);
+                    if do_options_tab_interaction(app, options_tabs, Interaction::Key(KeyInteraction { virtual_keycode })) == Capture::Miss {
+                        app.press_key(virtual_keycode, tools);
+                    } else if let Some(tool_key) = app.toolbox.get_selected() {
+                        // Keyboard focus is in the options tab, so it can't be in the tool options too
+                        tools[tool_key].unfocus_options();
+                    }
                 }
             }
             Released => {
@@ -535,6 +1187,35 @@ pub fn on_keyboard_input(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, inpu
     }
 }
 
+fn on_character(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>, character: char) {
+    if app.has_overlay() {
+        app.type_character(character);
+        return;
+    }
+
+    let interaction = Interaction::Character(CharacterInteraction { character });
+
+    if do_options_tab_interaction(app, options_tabs, interaction) != Capture::Miss {
+        return;
+    }
+
+    if let Some(tool_key) = app.toolbox.get_selected() {
+        tools[tool_key].interact_options(interaction, app);
+    }
+}
+
+// Gives the current options tab a chance at input before anything else
+fn do_options_tab_interaction(app: &mut App, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>, interaction: Interaction) -> Capture {
+    if app.has_overlay() {
+        return Capture::Miss;
+    }
+
+    match options_tabs.get_mut(app.toolbox.get_current_options_tab_key()) {
+        Some(current_tab) => current_tab.interact(interaction, app),
+        None => Capture::Miss,
+    }
+}
+
 fn on_mouse_input(
     app: &mut App,
     tools: &mut SlotMap<ToolKey, Tool>,
@@ -551,7 +1232,7 @@ fn on_mouse_input(
             }
         }
     } else {
-        do_mouse_release(app, button);
+        do_mouse_release(app, tools, options_tabs, button);
 
         if !app.wait_for_stop_drag {
             app.dragging = None;
@@ -559,19 +1240,70 @@ fn on_mouse_input(
     }
 }
 
-fn on_mouse_move(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, position: glium::glutin::dpi::PhysicalPosition<f64>) {
+fn on_mouse_move(
+    app: &mut App,
+    tools: &mut SlotMap<ToolKey, Tool>,
+    options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>,
+    position: glium::glutin::dpi::PhysicalPosition<f64>,
+) {
     app.mouse_position = Some(position.to_logical(app.scale_factor));
 
     if let Some(dragging) = app.dragging.as_mut() {
         dragging.update(app.mouse_position.unwrap().on_screen());
 
-        let capture = do_drag(app, tools);
+        let capture = do_drag(app, tools, options_tabs);
         app.do_capture(capture);
     }
 }
 
-fn on_scroll(app: &mut App, scroll: MouseScrollDelta) {
-    if let MouseScrollDelta::PixelDelta(logical_pixel_delta) = scroll {
-        app.scroll(&glm::vec2(logical_pixel_delta.x as f32, logical_pixel_delta.y as f32));
+fn on_scroll(app: &mut App, tools: &mut SlotMap<ToolKey, Tool>, options_tabs: &mut SlotMap<TabRef, Box<dyn Tab>>, scroll: MouseScrollDelta) {
+    if !app.modals.is_empty() {
+        return;
+    }
+
+    let position = app.mouse_position.unwrap_or_default();
+    let position = glm::vec2(position.x, position.y);
+
+    let is_over_tools = position.x < app.toolbox.get_tools_area(&app.dimensions).max_x;
+    let is_over_options = position.x > app.toolbox.get_options_area(&app.dimensions).min_x;
+
+    // Scrolling over a side panel scrolls its contents
+    if is_over_tools || is_over_options {
+        let delta = match scroll {
+            MouseScrollDelta::LineDelta(x, y) => glm::vec2(x, y) * 30.0,
+            MouseScrollDelta::PixelDelta(logical_pixel_delta) => glm::vec2(logical_pixel_delta.x as f32, logical_pixel_delta.y as f32),
+        };
+        let interaction = Interaction::Scroll(ScrollInteraction { delta, position });
+
+        if is_over_options {
+            if let Some(current_tab) = options_tabs.get_mut(app.toolbox.get_current_options_tab_key()) {
+                current_tab.interact(interaction, app);
+            }
+        } else if let Some(tool_key) = app.toolbox.get_selected() {
+            tools[tool_key].interact_options(interaction, app);
+        }
+        return;
+    }
+
+    // Mouse wheels report notches as lines instead of pixels
+    let delta = match scroll {
+        MouseScrollDelta::LineDelta(x, y) => glm::vec2(x, y) * PIXELS_PER_SCROLL_LINE,
+        MouseScrollDelta::PixelDelta(logical_pixel_delta) => glm::vec2(logical_pixel_delta.x as f32, logical_pixel_delta.y as f32),
+    };
+
+    // Ctrl+wheel zooms, otherwise the canvas is panned (horizontally when holding shift).
+    // The windowing library doesn't report pinch gestures yet, so on touchpads Ctrl+two-finger scroll zooms
+    if app.modifiers_held.ctrl_or_cmd() {
+        app.scroll_zoom(&delta);
+    } else if app.modifiers_held.shift() {
+        app.scroll_pan(&glm::vec2(delta.y, delta.x));
+    } else {
+        app.scroll_pan(&delta);
+    }
+
+    // Touchpads send lots of small precise deltas, which should be followed
+    // directly instead of each one being eased into
+    if let MouseScrollDelta::PixelDelta(..) = scroll {
+        app.camera.finish();
     }
 }
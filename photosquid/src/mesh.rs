@@ -1,14 +1,14 @@
 use crate::{
-    as_values::AsValues,
+    affine::Affine2,
     color::Color,
-    data::rect::BorderRadii,
     obj,
     render_ctx::RenderCtx,
+    renderer::Renderer,
+    tessellator::Tessellation,
     vertex::{Vertex, VertexXYUV},
 };
 use glium::{index::PrimitiveType, Display, VertexBuffer};
 use itertools::Itertools;
-use lyon::geom::Box2D;
 use nalgebra_glm as glm;
 
 pub enum MeshIndices {
@@ -28,6 +28,9 @@ impl<'a> From<&'a MeshIndices> for glium::index::IndicesSource<'a> {
 pub struct MeshXyz {
     pub vertex_buffer: VertexBuffer<Vertex>,
     pub indices: MeshIndices,
+
+    // Copy of the triangles kept around, so they can be combined with other meshes
+    pub triangles: Vec<Vertex>,
 }
 
 impl MeshXyz {
@@ -43,16 +46,19 @@ impl MeshXyz {
         Self {
             vertex_buffer,
             indices: MeshIndices::None(indices),
+            triangles: vertices.to_vec(),
         }
     }
 
     pub fn from_vertices_and_indices(vertices: &[Vertex], indices: &[u16], display: &Display) -> Self {
         let vertex_buffer = VertexBuffer::new(display, vertices).unwrap();
+        let triangles = indices.iter().map(|index| vertices[*index as usize]).collect();
         let indices = glium::IndexBuffer::new(display, PrimitiveType::TrianglesList, indices).unwrap();
 
         Self {
             vertex_buffer,
             indices: MeshIndices::TrianglesU16(Box::new(indices)),
+            triangles,
         }
     }
 
@@ -95,55 +101,14 @@ impl MeshXyz {
         Self::new(include_str!("_src_objs/shape/circle.obj"), display)
     }
 
-    pub fn new_rect(display: &Display, size: glm::Vec2, radii: BorderRadii) -> Self {
-        use lyon::{
-            path::{math::point, Winding},
-            tessellation::{BuffersBuilder, FillOptions, FillTessellator, FillVertex, VertexBuffers},
-        };
-
-        let width = size.x.abs();
-        let height = size.y.abs();
-
-        let mut builder = lyon::path::Path::builder();
-        builder.add_rounded_rectangle(
-            &Box2D::new(point(-width / 2.0, -height / 2.0), point(width / 2.0, height / 2.0)),
-            &radii.into(),
-            Winding::Positive,
-        );
-        let lyon_path = builder.build();
-
-        // Will contain the result of the tessellation.
-        let mut geometry: VertexBuffers<Vertex, u16> = VertexBuffers::new();
-        let mut tessellator = FillTessellator::new();
-
-        // Create tessellated geometry for fill
-        tessellator
-            .tessellate_path(
-                &lyon_path,
-                &FillOptions::default(),
-                &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| Vertex {
-                    position: vertex.position().to_array(),
-                }),
-            )
-            .unwrap();
-
-        Self::from_vertices_and_indices(&geometry.vertices, &geometry.indices, display)
+    pub fn from_tessellation(tessellation: &Tessellation, display: &Display) -> Self {
+        Self::from_vertices_and_indices(&tessellation.vertices, &tessellation.indices, display)
     }
 
     pub fn render(&self, ctx: &mut RenderCtx, position: glm::Vec2, scale: glm::Vec2, color: &Color) {
-        let identity = glm::identity::<f32, 4>();
-        let transformation = glm::translation(&glm::vec2_to_vec3(&position));
-        let transformation = glm::scale(&transformation, &glm::vec2_to_vec3(&scale));
-
-        let uniforms = glium::uniform! {
-            transformation: transformation.as_values(),
-            view: identity.as_values(),
-            projection: ctx.projection.as_values(),
-            color: color.as_values()
-        };
-
-        ctx.draw(&self.vertex_buffer, &self.indices, ctx.color_shader, &uniforms, &Default::default())
-            .unwrap();
+        let transformation = Affine2::translation(&position);
+        let transformation = transformation.scale(&scale);
+        ctx.draw_mesh(self, &transformation, color);
     }
 }
 
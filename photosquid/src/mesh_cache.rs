@@ -0,0 +1,198 @@
+use crate::{
+    data::rect::BorderRadii,
+    mesh::MeshXyz,
+    tessellator::{Shape, Tessellation, TessellationTicket, Tessellator},
+};
+use glium::Display;
+use nalgebra_glm as glm;
+use std::{collections::HashMap, rc::Rc};
+
+// Which mesh to use for a shape.
+// Meshes are made at a standard size and stretched to fit each squid,
+// so squids can share them and resizing rarely needs a new one
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MeshKey {
+    Circle,
+    Triangle,
+
+    // Aspect ratio and corner radii are rounded, so similar rectangles share a mesh
+    RoundedRect { aspect: i32, radii: [i32; 4] },
+}
+
+impl MeshKey {
+    // Longest side of rounded rectangle meshes
+    const UNIT_SIZE: f32 = 1024.0;
+
+    // Each step in aspect ratio is this many times taller than the last
+    const ASPECT_STEP: f32 = 1.02;
+
+    // Fraction of the shortest side that each step in corner radius adds
+    const RADIUS_STEP: f32 = 1.0 / 256.0;
+
+    pub fn rounded_rect(size: glm::Vec2, radii: BorderRadii) -> Self {
+        let width = size.x.abs().max(0.001);
+        let height = size.y.abs().max(0.001);
+        let shortest = width.min(height);
+        let step = |radius: f32| (radius.abs() / shortest / Self::RADIUS_STEP).round() as i32;
+
+        let radii = [step(radii.top_left), step(radii.top_right), step(radii.bottom_left), step(radii.bottom_right)];
+
+        // Without rounded corners, every rectangle is a stretched square
+        let aspect = if radii == [0; 4] {
+            0
+        } else {
+            ((height / width).ln() / Self::ASPECT_STEP.ln()).round() as i32
+        };
+
+        Self::RoundedRect { aspect, radii }
+    }
+
+    // Size of the area that the mesh fills
+    pub fn get_size(&self) -> glm::Vec2 {
+        match self {
+            Self::Circle | Self::Triangle => glm::vec2(1.0, 1.0),
+            Self::RoundedRect { aspect, .. } => {
+                let ratio = Self::ASPECT_STEP.powi(*aspect);
+
+                if ratio >= 1.0 {
+                    glm::vec2(Self::UNIT_SIZE / ratio, Self::UNIT_SIZE)
+                } else {
+                    glm::vec2(Self::UNIT_SIZE, Self::UNIT_SIZE * ratio)
+                }
+            }
+        }
+    }
+
+    // How much the mesh has to be stretched to fill an area
+    pub fn get_fit(&self, size: &glm::Vec2) -> glm::Vec2 {
+        size.abs().component_div(&self.get_size())
+    }
+
+    fn get_shape(&self) -> Option<Shape> {
+        match self {
+            Self::Circle | Self::Triangle => None,
+            Self::RoundedRect { radii, .. } => {
+                let size = self.get_size();
+                let radius = |step: i32| step as f32 * Self::RADIUS_STEP * size.x.min(size.y);
+
+                Some(Shape::RoundedRect {
+                    size,
+                    radii: BorderRadii {
+                        top_left: radius(radii[0]),
+                        top_right: radius(radii[1]),
+                        bottom_left: radius(radii[2]),
+                        bottom_right: radius(radii[3]),
+                    },
+                })
+            }
+        }
+    }
+}
+
+// Meshes shared between squids, along with the tessellator that makes the harder ones
+pub struct MeshCache {
+    tessellator: Tessellator,
+    meshes: HashMap<MeshKey, Rc<MeshXyz>>,
+    pending: HashMap<MeshKey, TessellationTicket>,
+}
+
+impl MeshCache {
+    // Meshes that nothing uses are let go once there are more than this many
+    const MAX_MESHES: usize = 256;
+
+    pub fn new() -> Self {
+        Self {
+            tessellator: Tessellator::new(),
+            meshes: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    // Gets a mesh right away, making it on the spot if it doesn't exist yet
+    pub fn get(&mut self, key: MeshKey, display: &Display) -> Rc<MeshXyz> {
+        self.meshes
+            .entry(key)
+            .or_insert_with(|| {
+                Rc::new(match key {
+                    MeshKey::Circle => MeshXyz::new_shape_circle(display),
+                    MeshKey::Triangle => MeshXyz::new_shape_triangle(display, [glm::vec2(0.0, 0.0), glm::vec2(1.0, 0.0), glm::vec2(0.0, 1.0)]),
+                    MeshKey::RoundedRect { .. } => MeshXyz::from_tessellation(&key.get_shape().unwrap().tessellate().unwrap_or_default(), display),
+                })
+            })
+            .clone()
+    }
+
+    // Gets a mesh if it exists, otherwise starts making it in the background
+    pub fn get_eventually(&mut self, key: MeshKey, display: &Display) -> Option<Rc<MeshXyz>> {
+        if let Some(mesh) = self.meshes.get(&key) {
+            return Some(mesh.clone());
+        }
+
+        match key.get_shape() {
+            Some(shape) => {
+                if !self.pending.contains_key(&key) {
+                    self.pending.insert(key, self.tessellator.submit(shape));
+                }
+                None
+            }
+            None => Some(self.get(key, display)),
+        }
+    }
+
+    // Uploads meshes that finished being tessellated, and lets go of old ones
+    pub fn update(&mut self, display: &Display) {
+        self.tessellator.receive();
+
+        let tessellator = &mut self.tessellator;
+        let meshes = &mut self.meshes;
+
+        self.pending.retain(|key, ticket| match tessellator.take(*ticket) {
+            Some(tessellation) => {
+                meshes.insert(*key, Rc::new(MeshXyz::from_tessellation(&tessellation, display)));
+                false
+            }
+            None => true,
+        });
+
+        if self.meshes.len() > Self::MAX_MESHES {
+            self.meshes.retain(|_, mesh| Rc::strong_count(mesh) > 1);
+        }
+    }
+
+    // Starts tessellating a shape that won't be shared, see 'take'
+    pub fn tessellate(&mut self, shape: Shape) -> TessellationTicket {
+        self.tessellator.submit(shape)
+    }
+
+    // Claims a tessellation started with 'tessellate' if it's done
+    pub fn take(&mut self, ticket: TessellationTicket) -> Option<Tessellation> {
+        self.tessellator.take(ticket)
+    }
+
+    // Whether any meshes are still being made
+    pub fn is_busy(&self) -> bool {
+        self.tessellator.is_busy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeshKey;
+    use crate::data::rect::BorderRadii;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn share_mesh_keys() {
+        // Resizing a little keeps the same mesh
+        let key = MeshKey::rounded_rect(glm::vec2(200.0, 100.0), BorderRadii::new(10.0));
+        assert_eq!(key, MeshKey::rounded_rect(glm::vec2(201.0, 100.5), BorderRadii::new(10.05)));
+        assert_ne!(key, MeshKey::rounded_rect(glm::vec2(200.0, 200.0), BorderRadii::new(10.0)));
+
+        // Rectangles with sharp corners all share the same mesh
+        let sharp = MeshKey::rounded_rect(glm::vec2(30.0, 30.0), BorderRadii::new(0.0));
+        assert_eq!(sharp, MeshKey::rounded_rect(glm::vec2(-500.0, 80.0), BorderRadii::new(0.0)));
+
+        let fit = key.get_fit(&glm::vec2(200.0, 100.0)).component_mul(&key.get_size());
+        assert!(glm::distance(&fit, &glm::vec2(200.0, 100.0)) < 0.001);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    aabb::AABB,
+    affine::Affine2,
+    app::App,
+    as_values::AsValues,
+    draw_text::{draw_text, draw_text_centered, wrap_text},
+    render_ctx::RenderCtx,
+};
+use glium::glutin::event::VirtualKeyCode;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Dialog drawn over the rest of the app, which takes all input until it's dismissed
+pub struct Modal {
+    title: String,
+    text: String,
+    kind: ModalKind,
+    highlighted: usize,
+}
+
+pub type ConfirmAction = Box<dyn FnOnce(&mut App)>;
+pub type SubmitAction = Box<dyn FnOnce(&mut App, String)>;
+
+pub enum ModalKind {
+    Message,
+    Confirm(ConfirmAction),
+    Input { text: String, on_submit: SubmitAction },
+    Progress { done: usize, total: usize },
+}
+
+pub enum ModalResponse {
+    Continue,
+    Close,
+    Accept,
+}
+
+impl Modal {
+    const WIDTH: f32 = 360.0;
+    const PADDING: f32 = 24.0;
+    const TITLE_HEIGHT: f32 = 32.0;
+    const LINE_HEIGHT: f32 = 22.0;
+    const FIELD_HEIGHT: f32 = 32.0;
+    const BUTTON_WIDTH: f32 = 96.0;
+    const BUTTON_SPACING: f32 = 12.0;
+
+    pub fn message(title: &str, text: &str) -> Self {
+        Self::new(title, text, ModalKind::Message)
+    }
+
+    pub fn confirm(title: &str, text: &str, on_confirm: ConfirmAction) -> Self {
+        Self::new(title, text, ModalKind::Confirm(on_confirm))
+    }
+
+    pub fn input(title: &str, text: &str, default_text: &str, on_submit: SubmitAction) -> Self {
+        Self::new(
+            title,
+            text,
+            ModalKind::Input {
+                text: default_text.into(),
+                on_submit,
+            },
+        )
+    }
+
+    pub fn progress(title: &str, text: &str, total: usize) -> Self {
+        Self::new(title, text, ModalKind::Progress { done: 0, total })
+    }
+
+    fn new(title: &str, text: &str, kind: ModalKind) -> Self {
+        let mut modal = Self {
+            title: title.into(),
+            text: text.into(),
+            kind,
+            highlighted: 0,
+        };
+
+        // The accepting button is the default
+        modal.highlighted = modal.get_buttons().len().saturating_sub(1);
+        modal
+    }
+
+    // The last button accepts the dialog, any others dismiss it
+    fn get_buttons(&self) -> &'static [&'static str] {
+        match self.kind {
+            ModalKind::Message => &["OK"],
+            ModalKind::Confirm(..) | ModalKind::Input { .. } => &["Cancel", "OK"],
+            ModalKind::Progress { .. } => &[],
+        }
+    }
+
+    fn respond_to_button(&self, index: usize) -> ModalResponse {
+        if index + 1 == self.get_buttons().len() {
+            ModalResponse::Accept
+        } else {
+            ModalResponse::Close
+        }
+    }
+
+    pub fn is_progress(&self) -> bool {
+        matches!(self.kind, ModalKind::Progress { .. })
+    }
+
+    // Records one more finished step, returns whether everything is done
+    pub fn advance(&mut self) -> bool {
+        match &mut self.kind {
+            ModalKind::Progress { done, total } => {
+                *done += 1;
+                done >= total
+            }
+            _ => false,
+        }
+    }
+
+    pub fn type_character(&mut self, character: char) {
+        if let ModalKind::Input { text, .. } = &mut self.kind {
+            if !character.is_control() {
+                text.push(character);
+            }
+        }
+    }
+
+    pub fn press_key(&mut self, key: VirtualKeyCode) -> ModalResponse {
+        let button_count = self.get_buttons().len();
+
+        match key {
+            VirtualKeyCode::Escape if !self.is_progress() => return ModalResponse::Close,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter if button_count > 0 => return self.respond_to_button(self.highlighted),
+            VirtualKeyCode::Tab | VirtualKeyCode::Right if button_count > 0 => self.highlighted = (self.highlighted + 1) % button_count,
+            VirtualKeyCode::Left if button_count > 0 => self.highlighted = (self.highlighted + button_count - 1) % button_count,
+            VirtualKeyCode::Back => {
+                if let ModalKind::Input { text, .. } = &mut self.kind {
+                    text.pop();
+                }
+            }
+            _ => (),
+        }
+
+        ModalResponse::Continue
+    }
+
+    pub fn click(&mut self, position: &glm::Vec2, window: &glm::Vec2, font: &FontTexture) -> ModalResponse {
+        let area = self.get_area(window, font);
+
+        for i in 0..self.get_buttons().len() {
+            if self.get_button_area(&area, i).intersecting_point(position.x, position.y) {
+                return self.respond_to_button(i);
+            }
+        }
+
+        ModalResponse::Continue
+    }
+
+    // Runs the action of a dialog that was accepted
+    pub fn accept(self, app: &mut App) {
+        match self.kind {
+            ModalKind::Confirm(on_confirm) => on_confirm(app),
+            ModalKind::Input { text, on_submit } => on_submit(app, text),
+            ModalKind::Message | ModalKind::Progress { .. } => (),
+        }
+    }
+
+    fn get_lines(&self, font: &FontTexture) -> Vec<String> {
+        wrap_text(font, &self.text, Self::WIDTH - 2.0 * Self::PADDING)
+    }
+
+    // Input field, progress bar, or nothing, which goes between the text and the buttons
+    fn get_field_height(&self) -> f32 {
+        match self.kind {
+            ModalKind::Message | ModalKind::Confirm(..) => 0.0,
+            ModalKind::Input { .. } | ModalKind::Progress { .. } => Self::FIELD_HEIGHT + Self::PADDING,
+        }
+    }
+
+    fn get_area(&self, window: &glm::Vec2, font: &FontTexture) -> AABB {
+        let buttons_height = if self.get_buttons().is_empty() {
+            0.0
+        } else {
+            Self::FIELD_HEIGHT + Self::PADDING
+        };
+
+        let height =
+            2.0 * Self::PADDING + Self::TITLE_HEIGHT + self.get_lines(font).len() as f32 * Self::LINE_HEIGHT + self.get_field_height() + buttons_height;
+
+        AABB::new(0.5 * (window.x - Self::WIDTH), 0.5 * (window.y - height), Self::WIDTH, height)
+    }
+
+    // Buttons are right-aligned along the bottom
+    fn get_button_area(&self, area: &AABB, index: usize) -> AABB {
+        let from_right = (self.get_buttons().len() - index) as f32;
+        let x = area.max_x - Self::PADDING - from_right * Self::BUTTON_WIDTH - (from_right - 1.0) * Self::BUTTON_SPACING;
+        let y = area.max_y - Self::PADDING - Self::FIELD_HEIGHT;
+        AABB::new(x, y, Self::BUTTON_WIDTH, Self::FIELD_HEIGHT)
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+        let window = glm::vec2(ctx.width, ctx.height);
+        let area = self.get_area(&window, &font);
+        let left = area.min_x + Self::PADDING;
+
+        // Shade everything underneath
+        ctx.ribbon_mesh.render(ctx, glm::zero(), window, &ctx.color_scheme.shade);
+
+        // Render background
+        {
+            let mesh = ctx.square_xyzuv;
+            let identity = Affine2::identity();
+            let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
+            let transformation = Affine2::translation(&glm::vec2(area.center_x(), area.center_y()));
+            let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
+
+            let uniforms = glium::uniform! {
+                transformation: transformation.as_values(),
+                view: identity.as_values(),
+                projection: ctx.projection.as_values(),
+                rectangle_color: ctx.color_scheme.dark_ribbon.as_values(),
+                dimensions: [quad_dimensions.x, quad_dimensions.y],
+                height_scale: 1.0f32,
+                do_shadow: 1
+            };
+
+            let draw_parameters = glium::DrawParameters {
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            };
+
+            ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.rounded_rectangle_shader, &uniforms, &draw_parameters)
+                .unwrap();
+        }
+
+        let mut y = area.min_y + Self::PADDING + 0.5 * Self::TITLE_HEIGHT;
+        draw_text(text_system, font.clone(), &self.title, &glm::vec2(left, y), ctx, ctx.color_scheme.foreground);
+        y += 0.5 * Self::TITLE_HEIGHT + 0.5 * Self::LINE_HEIGHT;
+
+        for line in self.get_lines(&font) {
+            draw_text(text_system, font.clone(), &line, &glm::vec2(left, y + 6.0), ctx, ctx.color_scheme.text);
+            y += Self::LINE_HEIGHT;
+        }
+
+        let field_position = glm::vec2(left, y - 0.5 * Self::LINE_HEIGHT + 0.5 * Self::PADDING);
+        let field_size = glm::vec2(Self::WIDTH - 2.0 * Self::PADDING, Self::FIELD_HEIGHT);
+
+        match &self.kind {
+            ModalKind::Input { text, .. } => {
+                ctx.ribbon_mesh.render(ctx, field_position, field_size, &ctx.color_scheme.input);
+
+                draw_text(
+                    text_system,
+                    font.clone(),
+                    &format!("{}|", text),
+                    &(field_position + glm::vec2(8.0, 0.5 * Self::FIELD_HEIGHT + 6.0)),
+                    ctx,
+                    ctx.color_scheme.text,
+                );
+            }
+            ModalKind::Progress { done, total } => {
+                let fraction = if *total == 0 { 1.0 } else { *done as f32 / *total as f32 };
+                let bar_size = glm::vec2(field_size.x, 8.0);
+                let bar_position = field_position + glm::vec2(0.0, 0.5 * (Self::FIELD_HEIGHT - bar_size.y));
+
+                ctx.ribbon_mesh.render(ctx, bar_position, bar_size, &ctx.color_scheme.input);
+                ctx.ribbon_mesh
+                    .render(ctx, bar_position, glm::vec2(bar_size.x * fraction, bar_size.y), &ctx.color_scheme.foreground);
+            }
+            ModalKind::Message | ModalKind::Confirm(..) => (),
+        }
+
+        for (i, label) in self.get_buttons().iter().enumerate() {
+            let button = self.get_button_area(&area, i);
+
+            let (background, color) = if i == self.highlighted {
+                (ctx.color_scheme.foreground, ctx.color_scheme.text)
+            } else {
+                (ctx.color_scheme.input, ctx.color_scheme.secondary_text)
+            };
+
+            ctx.ribbon_mesh.render(
+                ctx,
+                glm::vec2(button.min_x, button.min_y),
+                glm::vec2(button.width(), button.height()),
+                &background,
+            );
+
+            draw_text_centered(
+                text_system,
+                font.clone(),
+                label,
+                &glm::vec2(button.center_x(), button.center_y() + 6.0),
+                ctx,
+                color,
+            );
+        }
+    }
+}
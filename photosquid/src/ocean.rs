@@ -1,14 +1,26 @@
 use crate::{
+    aabb::AABB,
     camera::Camera,
+    color::Color,
     color_scheme::ColorScheme,
     context_menu::ContextMenu,
-    layer::Layer,
+    export::ExportProfile,
+    layer::{Layer, LayerTag},
+    mesh_cache::MeshCache,
     selection::{selection_contains, Selection, TrySelectResult},
-    squid::{self, Squid, SquidRef},
+    smooth::default_smooth_duration,
+    spatial_index::SpatialIndex,
+    squid::{self, Appearance, Squid, SquidId, SquidRef},
+    style::{ColorStyle, StyleRef},
 };
+use glium::Display;
 use nalgebra_glm as glm;
 use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
 
 // A world that objects (aka squids) live in
 #[derive(Clone, Serialize, Deserialize)]
@@ -16,6 +28,32 @@ pub struct Ocean {
     current_layer: usize,
     layers: Vec<Layer>,
     squids: SlotMap<SquidRef, Squid>,
+
+    #[serde(default)]
+    styles: SlotMap<StyleRef, ColorStyle>,
+
+    #[serde(default)]
+    export_profiles: Vec<ExportProfile>,
+
+    // Pixels per inch, used when lengths are entered in physical units
+    #[serde(default = "default_dpi")]
+    dpi: f32,
+
+    // Where squids are, for finding what's under the mouse without checking every squid.
+    // Squids that were changed recently may still be animating, so they're left out
+    // until they settle and are always checked instead
+    #[serde(skip)]
+    index: SpatialIndex<SquidRef>,
+
+    #[serde(skip)]
+    unsettled: HashMap<SquidRef, Instant>,
+
+    #[serde(skip)]
+    is_indexed: bool,
+
+    // Which squid has each id, since squid references aren't kept when saving
+    #[serde(skip)]
+    ids: HashMap<SquidId, SquidRef>,
 }
 
 impl Default for Ocean {
@@ -24,13 +62,31 @@ impl Default for Ocean {
             current_layer: 0,
             layers: vec![Default::default()],
             squids: SlotMap::with_key(),
+            styles: SlotMap::with_key(),
+            export_profiles: vec![],
+            dpi: default_dpi(),
+            index: Default::default(),
+            unsettled: HashMap::new(),
+            is_indexed: false,
+            ids: HashMap::new(),
         }
     }
 }
 
 impl Ocean {
+    // Most squids that can get new meshes within a single frame
+    const MAX_IMMEDIATE_MESHES: usize = 16;
+
+    // Loads a saved ocean
+    pub fn from_json(contents: &str) -> serde_json::Result<Self> {
+        let mut ocean: Ocean = serde_json::from_str(contents)?;
+        ocean.index_ids();
+        Ok(ocean)
+    }
+
     pub fn insert(&mut self, value: Squid) -> SquidRef {
-        let reference = self.squids.insert(value);
+        let reference = self.insert_squid(value);
+        self.touch(reference);
 
         self.force_valid_layer();
 
@@ -38,6 +94,35 @@ impl Ocean {
         reference
     }
 
+    // Adds a squid without putting it in a layer. Squids that already exist in the ocean,
+    // such as ones being pasted, get a new id
+    fn insert_squid(&mut self, mut value: Squid) -> SquidRef {
+        if self.ids.contains_key(&value.get_id()) {
+            value.renew_id();
+        }
+
+        let id = value.get_id();
+        let reference = self.squids.insert(value);
+        self.ids.insert(id, reference);
+        reference
+    }
+
+    fn index_ids(&mut self) {
+        self.ids.clear();
+
+        let references: Vec<SquidRef> = self.squids.keys().collect();
+
+        for reference in references {
+            let squid = &mut self.squids[reference];
+
+            if self.ids.contains_key(&squid.get_id()) {
+                squid.renew_id();
+            }
+
+            self.ids.insert(squid.get_id(), reference);
+        }
+    }
+
     fn force_valid_layer(&mut self) {
         if self.layers.is_empty() {
             self.layers.push(Default::default());
@@ -53,32 +138,315 @@ impl Ocean {
             layer.remove_mention(reference);
         }
 
-        self.squids.remove(reference);
+        if let Some(squid) = self.squids.remove(reference) {
+            self.ids.remove(&squid.get_id());
+        }
+
+        self.index.remove(reference);
+        self.unsettled.remove(&reference);
+    }
+
+    // Swaps a squid out for others, which take its place within its layer
+    pub fn replace(&mut self, reference: SquidRef, replacements: Vec<Squid>) -> Vec<SquidRef> {
+        let (layer_index, index) = match self.get_layer_index_of(reference) {
+            Some(layer_index) => (layer_index, self.layers[layer_index].get_index_of(reference).unwrap()),
+            None => return vec![],
+        };
+
+        let references = replacements
+            .into_iter()
+            .map(|replacement| {
+                let new_reference = self.insert_squid(replacement);
+                self.touch(new_reference);
+                self.layers[layer_index].add(new_reference);
+                self.layers[layer_index].reorder(new_reference, index + 1);
+                new_reference
+            })
+            .collect();
+
+        self.remove(reference);
+        references
     }
 
     pub fn get(&self, reference: SquidRef) -> Option<&Squid> {
         self.squids.get(reference)
     }
 
+    pub fn find_by_id(&self, id: SquidId) -> Option<SquidRef> {
+        self.ids.get(&id).copied()
+    }
+
+    pub fn get_mut_by_id(&mut self, id: SquidId) -> Option<&mut Squid> {
+        let reference = self.find_by_id(id)?;
+        self.get_mut(reference)
+    }
+
+    // Squids can be changed in any way through a mutable reference, so they're indexed again afterwards
     pub fn get_mut(&mut self, reference: SquidRef) -> Option<&mut Squid> {
+        self.touch(reference);
         self.squids.get_mut(reference)
     }
 
-    pub fn get_squids_and_layers(&mut self) -> (&mut SlotMap<SquidRef, Squid>, &mut Vec<Layer>) {
-        (&mut self.squids, &mut self.layers)
+    // For changes that don't move the squid, which keeps it indexed
+    pub fn get_mut_in_place(&mut self, reference: SquidRef) -> Option<&mut Squid> {
+        self.squids.get_mut(reference)
+    }
+
+    fn touch(&mut self, reference: SquidRef) {
+        if self.is_indexed {
+            self.index.remove(reference);
+            self.unsettled.insert(reference, Instant::now());
+        }
+    }
+
+    // Indexes squids that have finished animating, or everything if there isn't an index yet
+    fn update_index(&mut self) {
+        if !self.is_indexed {
+            self.index.clear();
+            self.unsettled.clear();
+
+            for (reference, squid) in &self.squids {
+                self.index.insert(reference, squid.get_bounding_box());
+            }
+
+            self.is_indexed = true;
+            return;
+        }
+
+        let settle_time = default_smooth_duration();
+        let squids = &self.squids;
+        let index = &mut self.index;
+
+        self.unsettled.retain(|reference, changed| {
+            if changed.elapsed() < settle_time {
+                return true;
+            }
+
+            if let Some(squid) = squids.get(*reference) {
+                index.insert(*reference, squid.get_bounding_box());
+            }
+
+            false
+        });
+    }
+
+    // Squids that might be within an area, which can include some that aren't
+    pub fn get_squids_near(&mut self, area: &AABB) -> HashSet<SquidRef> {
+        self.update_index();
+
+        let mut found = self.index.query(area);
+        found.extend(self.unsettled.keys().copied());
+        found
     }
 
     pub fn get_layers(&self) -> &[Layer] {
         &self.layers
     }
 
+    pub fn get_layer(&self, index: usize) -> Option<&Layer> {
+        self.layers.get(index)
+    }
+
+    pub fn set_layer_name(&mut self, index: usize, name: String) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_name(name);
+        }
+    }
+
+    pub fn set_layer_hidden(&mut self, index: usize, hidden: bool) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_hidden(hidden);
+        }
+    }
+
+    pub fn set_layer_locked(&mut self, index: usize, locked: bool) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_locked(locked);
+        }
+    }
+
+    pub fn set_layer_opacity(&mut self, index: usize, opacity: f32) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_opacity(opacity);
+        }
+    }
+
+    pub fn set_layer_blur(&mut self, index: usize, blur: f32) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_blur(blur);
+        }
+    }
+
+    pub fn set_layer_tag(&mut self, index: usize, tag: LayerTag) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.set_tag(tag);
+        }
+    }
+
+    // Squids in a layer, ordered from highest to lowest
+    pub fn get_layer_squids(&self, index: usize) -> impl Iterator<Item = SquidRef> + '_ {
+        self.layers.get(index).into_iter().flat_map(|layer| layer.get_highest())
+    }
+
+    // Moves a squid to the top of another layer
+    pub fn move_to_layer(&mut self, reference: SquidRef, layer_index: usize) -> bool {
+        if layer_index >= self.layers.len() || !self.squids.contains_key(reference) {
+            return false;
+        }
+
+        for layer in &mut self.layers {
+            layer.remove_mention(reference);
+        }
+
+        self.layers[layer_index].add(reference);
+        true
+    }
+
+    // Moves a squid to a position within its layer, where 0 is the highest
+    pub fn reorder(&mut self, reference: SquidRef, new_index: usize) -> bool {
+        self.layers.iter_mut().any(|layer| layer.reorder(reference, new_index))
+    }
+
+    // Moves a squid up (negative) or down (positive) within its layer
+    pub fn shift_within_layer(&mut self, reference: SquidRef, offset: isize) -> bool {
+        let index = self.layers.iter().find_map(|layer| layer.get_index_of(reference));
+
+        match index {
+            Some(index) => self.reorder(reference, index.saturating_add_signed(offset)),
+            None => false,
+        }
+    }
+
+    // Layer that new squids are inserted into
+    pub fn get_current_layer_index(&self) -> usize {
+        self.current_layer.min(self.layers.len().saturating_sub(1))
+    }
+
+    pub fn set_current_layer(&mut self, index: usize) {
+        if index < self.layers.len() {
+            self.current_layer = index;
+        }
+    }
+
+    // Adds a new layer and makes it the current layer
+    pub fn add_layer(&mut self, layer: Layer) -> usize {
+        self.layers.push(layer);
+        self.current_layer = self.layers.len() - 1;
+        self.current_layer
+    }
+
+    pub fn get_layer_index_of(&self, reference: SquidRef) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.contains(reference))
+    }
+
+    // Whether a squid belongs to a layer that is neither hidden nor locked
+    pub fn add_style(&mut self, style: ColorStyle) -> StyleRef {
+        self.styles.insert(style)
+    }
+
+    pub fn get_style(&self, reference: StyleRef) -> Option<&ColorStyle> {
+        self.styles.get(reference)
+    }
+
+    pub fn get_styles(&self) -> impl Iterator<Item = (StyleRef, &ColorStyle)> + '_ {
+        self.styles.iter()
+    }
+
+    pub fn set_style_color(&mut self, reference: StyleRef, color: Color) {
+        if let Some(style) = self.styles.get_mut(reference) {
+            style.color = color;
+            self.resolve_styles();
+        }
+    }
+
+    // Links a squid to a style (or unlinks it), taking on the style's look
+    pub fn set_squid_style(&mut self, reference: SquidRef, style: Option<StyleRef>) {
+        let style = style.filter(|style| self.styles.contains_key(*style));
+        let styles = &self.styles;
+
+        if let Some(squid) = self.squids.get_mut(reference) {
+            squid.set_style(style);
+
+            if let Some(style) = style.and_then(|style| styles.get(style)) {
+                squid.set_appearance(&squid.get_appearance().with_style(style));
+            }
+        }
+
+        self.resolve_styles();
+    }
+
+    // Changes a style to look like the given appearance, along with every squid that uses it
+    pub fn update_style(&mut self, reference: StyleRef, appearance: &Appearance) {
+        let style = match self.styles.get_mut(reference) {
+            Some(style) => style,
+            None => return,
+        };
+
+        *style = appearance.to_style(std::mem::take(&mut style.name));
+
+        for squid in self.squids.values_mut().filter(|squid| squid.get_style() == Some(reference)) {
+            squid.set_appearance(&squid.get_appearance().with_style(style));
+        }
+    }
+
+    // Brings the color of every squid that uses a style in line with it,
+    // squids referencing styles that no longer exist are unlinked
+    pub fn resolve_styles(&mut self) {
+        for squid in self.squids.values_mut() {
+            let style = match squid.get_style() {
+                Some(style) => style,
+                None => continue,
+            };
+
+            match self.styles.get(style) {
+                Some(style) if squid.get_color() != style.color => squid.set_color(style.color),
+                Some(_) => (),
+                None => squid.set_style(None),
+            }
+        }
+    }
+
+    pub fn add_export_profile(&mut self, profile: ExportProfile) {
+        self.export_profiles.push(profile);
+    }
+
+    pub fn remove_export_profile(&mut self, index: usize) {
+        if index < self.export_profiles.len() {
+            self.export_profiles.remove(index);
+        }
+    }
+
+    pub fn get_dpi(&self) -> f32 {
+        self.dpi
+    }
+
+    pub fn set_dpi(&mut self, dpi: f32) {
+        if dpi > 0.0 {
+            self.dpi = dpi;
+        }
+    }
+
+    pub fn get_export_profiles(&self) -> &[ExportProfile] {
+        &self.export_profiles
+    }
+
+    pub fn is_interactable(&self, reference: SquidRef) -> bool {
+        self.layers.iter().any(|layer| layer.is_interactable() && layer.contains(reference))
+    }
+
     // Tries to find a squid/squid-limb underneath a point to select
     pub fn try_select(&mut self, underneath: glm::Vec2, camera: &Camera, existing_selections: &[Selection]) -> TrySelectResult {
-        let highest_squids: Vec<SquidRef> = self.get_squids_highest().collect();
         let world_mouse = camera.apply_reverse(&underneath);
 
+        // Selected squids are always considered, since their handles can reach outside of them
+        let margin = 2.0 * squid::HANDLE_RADIUS / camera.get_scale().max(0.001);
+        let mut near = self.get_squids_near(&AABB::new(world_mouse.x, world_mouse.y, 0.0, 0.0).padded(margin));
+        near.extend(existing_selections.iter().map(|selection| selection.squid_id));
+
+        let highest_squids: Vec<SquidRef> = self.get_interactable_squids_highest().filter(|reference| near.contains(reference)).collect();
+
         for self_reference in highest_squids {
-            if let Some(squid) = self.get_mut(self_reference) {
+            if let Some(squid) = self.get(self_reference) {
                 let already_selected = selection_contains(existing_selections, self_reference);
 
                 // If the squid is already selected, and we are trying to select over on-top of one
@@ -119,9 +487,80 @@ impl Ocean {
         self.layers.iter().flat_map(|layer| layer.get_lowest())
     }
 
+    // Interactable squids that are similar to any of the given squids, highest first
+    pub fn get_similar(&self, references: &[SquidRef], similarity: Similarity) -> Vec<SquidRef> {
+        let originals: Vec<&Squid> = references.iter().filter_map(|reference| self.get(*reference)).collect();
+        let layers: Vec<usize> = references.iter().filter_map(|reference| self.get_layer_index_of(*reference)).collect();
+
+        let is_similar = |layer_index: usize, squid: &Squid| match similarity {
+            Similarity::FillColor => originals.iter().any(|original| original.get_color() == squid.get_color()),
+            Similarity::Kind => originals.iter().any(|original| original.is_same_kind(squid)),
+            Similarity::Layer => layers.contains(&layer_index),
+        };
+
+        self.layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| layer.is_interactable())
+            .flat_map(|(layer_index, layer)| layer.get_highest().map(move |reference| (layer_index, reference)))
+            .filter(|(layer_index, reference)| self.get(*reference).is_some_and(|squid| is_similar(*layer_index, squid)))
+            .map(|(_, reference)| reference)
+            .collect()
+    }
+
+    // Squids that can be interacted with and have a tag, highest first
+    pub fn get_tagged(&self, tag: &str) -> Vec<SquidRef> {
+        self.get_interactable_squids_highest()
+            .filter(|reference| self.get(*reference).is_some_and(|squid| squid.has_tag(tag)))
+            .collect()
+    }
+
+    pub fn get_interactable_squids_highest(&self) -> impl Iterator<Item = SquidRef> + '_ {
+        self.layers.iter().filter(|layer| layer.is_interactable()).flat_map(|layer| layer.get_highest())
+    }
+
+    // Squids in visible layers along with the opacity of their layer
+    pub fn get_visible_squids_lowest(&self) -> impl Iterator<Item = (SquidRef, f32)> + '_ {
+        self.layers
+            .iter()
+            .filter(|layer| !layer.is_hidden())
+            .flat_map(|layer| layer.get_lowest().map(move |reference| (reference, layer.get_opacity())))
+    }
+
+    // Tight bounds around everything visible, not including viewports
+    pub fn get_content_bounds(&self) -> Option<AABB> {
+        let points: Vec<glm::Vec2> = self
+            .get_visible_squids_lowest()
+            .filter_map(|(reference, _)| self.get(reference))
+            .filter(|squid| squid.as_viewport().is_none())
+            .flat_map(|squid| squid.get_extreme_points())
+            .collect();
+
+        AABB::from_points(&points)
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.squids.values().any(Squid::is_animating)
+    }
+
+    // Refreshes cached meshes of every squid, so that they
+    // can be rendered without mutable access afterwards
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache) {
+        mesh_cache.update(display);
+        self.resolve_styles();
+
+        // When lots of squids need new meshes at once (like after opening a file),
+        // tessellate them in parallel instead of holding up the frame
+        let immediately = self.squids.values().filter(|squid| squid.needs_mesh()).count() <= Self::MAX_IMMEDIATE_MESHES;
+
+        for squid in self.squids.values_mut() {
+            squid.prepare(display, mesh_cache, immediately);
+        }
+    }
+
     // Tries to get a context menu for a squid underneath a point
     pub fn try_context_menu(&self, underneath: glm::Vec2, camera: &Camera, color_scheme: &ColorScheme) -> Option<ContextMenu> {
-        for self_reference in self.get_squids_highest() {
+        for self_reference in self.get_interactable_squids_highest() {
             if let Some(value) = self.get(self_reference) {
                 if let Some(new_context_menu) = value.try_context_menu(underneath, camera, self_reference, color_scheme) {
                     return Some(new_context_menu);
@@ -132,3 +571,274 @@ impl Ocean {
         None
     }
 }
+
+// What squids have to have in common with the selection to be selected along with it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Similarity {
+    FillColor,
+    Kind,
+    Layer,
+}
+
+fn default_dpi() -> f32 {
+    96.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ocean, Similarity};
+    use crate::{
+        aabb::AABB,
+        color::Color,
+        data::rect::BorderRadii,
+        layer::Layer,
+        squid::{self, Squid, SquidProperty},
+        style::ColorStyle,
+    };
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    fn circle() -> Squid {
+        Squid::circle(glm::zero(), 10.0, Color::white())
+    }
+
+    #[test]
+    fn ocean_move_to_layer() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(circle());
+
+        let second = ocean.add_layer(Layer::new("Second".into()));
+        assert_eq!(ocean.get_current_layer_index(), second);
+
+        assert!(ocean.move_to_layer(a, second));
+        assert_eq!(ocean.get_layer_squids(0).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(ocean.get_layer_squids(second).collect::<Vec<_>>(), vec![a]);
+        assert_eq!(ocean.get_layer_index_of(a), Some(second));
+
+        assert!(!ocean.move_to_layer(a, 5));
+    }
+
+    #[test]
+    fn ocean_reorder() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(circle());
+        let c = ocean.insert(circle());
+
+        // Newest squids are highest
+        assert_eq!(ocean.get_layer_squids(0).collect::<Vec<_>>(), vec![c, b, a]);
+
+        assert!(ocean.shift_within_layer(a, -1));
+        assert_eq!(ocean.get_layer_squids(0).collect::<Vec<_>>(), vec![c, a, b]);
+
+        assert!(ocean.shift_within_layer(c, 10));
+        assert_eq!(ocean.get_layer_squids(0).collect::<Vec<_>>(), vec![a, b, c]);
+
+        assert!(ocean.reorder(c, 0));
+        assert_eq!(ocean.get_squids_highest().collect::<Vec<_>>(), vec![c, a, b]);
+    }
+
+    #[test]
+    fn ocean_replace() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(circle());
+        let second = ocean.add_layer(Layer::new("Second".into()));
+        let c = ocean.insert(circle());
+
+        let replacements = ocean.replace(a, vec![circle(), circle()]);
+        assert_eq!(replacements.len(), 2);
+        assert!(ocean.get(a).is_none());
+
+        // Replacements stay in the same layer, at the same depth
+        assert_eq!(ocean.get_layer_squids(0).collect::<Vec<_>>(), vec![b, replacements[1], replacements[0]]);
+        assert_eq!(ocean.get_layer_squids(second).collect::<Vec<_>>(), vec![c]);
+    }
+
+    #[test]
+    fn ocean_styles() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(circle());
+        let red = Color::from_hex("#FF0000");
+        let style = ocean.add_style(ColorStyle::new("Accent".into(), red));
+
+        ocean.set_squid_style(a, Some(style));
+        assert_eq!(ocean.get(a).unwrap().get_color(), red);
+        assert_eq!(ocean.get(b).unwrap().get_color(), Color::white());
+
+        let blue = Color::from_hex("#0000FF");
+        ocean.set_style_color(style, blue);
+        assert_eq!(ocean.get(a).unwrap().get_color(), blue);
+
+        // Duplicates share the style too
+        let c = ocean.insert(ocean.get(a).unwrap().duplicate(&glm::zero()));
+        assert_eq!(ocean.get(c).unwrap().get_style(), Some(style));
+    }
+
+    #[test]
+    fn ocean_saved_styles() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(Squid::rect(glm::zero(), glm::vec2(100.0, 60.0), Rad(0.0), Color::white(), 8.0, false));
+        let b = ocean.insert(circle());
+
+        // Squids take the whole look of a style saved from another squid
+        let mut appearance = ocean.get(a).unwrap().get_appearance();
+        appearance.blur = 2.0;
+        let style = ocean.add_style(appearance.to_style("Card".into()));
+        ocean.set_squid_style(a, Some(style));
+        ocean.set_squid_style(b, Some(style));
+        assert_eq!(ocean.get(a).unwrap().get_blur(), 2.0);
+        assert_eq!(ocean.get(b).unwrap().get_blur(), 2.0);
+
+        // Updating the style changes everything linked to it
+        let red = Color::from_hex("#FF0000");
+        appearance.color = red;
+        appearance.radii = Some(BorderRadii::new(4.0));
+        ocean.update_style(style, &appearance);
+        assert_eq!(ocean.get_style(style).unwrap().name, "Card");
+        assert_eq!(ocean.get(a).unwrap().get_appearance(), appearance);
+        assert_eq!(ocean.get(b).unwrap().get_color(), red);
+        assert_eq!(ocean.get(b).unwrap().get_appearance().radii, None);
+    }
+
+    #[test]
+    fn ocean_similar() {
+        let mut ocean = Ocean::default();
+        let red = Color::from_hex("#FF0000");
+        let a = ocean.insert(circle());
+        let b = ocean.insert(Squid::rect(glm::zero(), glm::vec2(10.0, 10.0), Rad(0.0), red, 0.0, false));
+        let second = ocean.add_layer(Layer::new("Second".into()));
+        let c = ocean.insert(Squid::circle(glm::zero(), 5.0, red));
+        let d = ocean.insert(circle());
+
+        assert_eq!(ocean.get_similar(&[a], Similarity::FillColor), vec![a, d]);
+        assert_eq!(ocean.get_similar(&[b], Similarity::FillColor), vec![b, c]);
+        assert_eq!(ocean.get_similar(&[a], Similarity::Kind), vec![a, d, c]);
+        assert_eq!(ocean.get_similar(&[b, d], Similarity::Layer), vec![b, a, d, c]);
+
+        // Squids that can't be interacted with are left out
+        ocean.set_layer_locked(second, true);
+        assert_eq!(ocean.get_similar(&[a], Similarity::Kind), vec![a]);
+    }
+
+    #[test]
+    fn ocean_clone_keeps_references() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(Squid::circle(glm::vec2(40.0, 0.0), 5.0, Color::white()));
+        let snapshot = ocean.clone();
+
+        ocean.remove(a);
+        let c = ocean.insert(circle());
+
+        // Restoring a snapshot brings back the same references, and ones made since don't mix it up
+        assert_eq!(snapshot.get_squids_highest().collect::<Vec<_>>(), vec![b, a]);
+        assert_eq!(snapshot.get(b).unwrap().get_extreme_points(), ocean.get(b).unwrap().get_extreme_points());
+        assert!(snapshot.get(c).is_none());
+    }
+
+    #[test]
+    fn ocean_tags() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(circle());
+        let c = ocean.insert(circle());
+
+        let tags = squid::parse_tags(" Background, trees,, background ");
+        assert_eq!(tags, vec!["Background".to_string(), "trees".to_string()]);
+        ocean.get_mut(a).unwrap().set_tags(tags);
+        ocean.get_mut(c).unwrap().set_tags(vec!["background".into()]);
+
+        // Tags are found ignoring case, and copies are tagged the same
+        assert_eq!(ocean.get_tagged("BACKGROUND"), vec![c, a]);
+        assert_eq!(ocean.get_tagged("tree"), vec![]);
+        assert!(ocean.get(a).unwrap().clone().has_tag("trees"));
+
+        // Filters match part of a tag or name
+        assert!(ocean.get(a).unwrap().matches_filter("tre"));
+        assert!(!ocean.get(b).unwrap().matches_filter("tre"));
+        assert!(ocean.get(b).unwrap().matches_filter(" "));
+        ocean.get_mut(b).unwrap().set_name("Tree Trunk".into());
+        assert!(ocean.get(b).unwrap().matches_filter("tre"));
+    }
+
+    #[test]
+    fn name_pattern() {
+        assert_eq!(squid::apply_name_pattern("Icon_{n}", "Unnamed Rect", 3), "Icon_3");
+        assert_eq!(squid::apply_name_pattern("{name} {n}", "Leaf", 12), "Leaf 12");
+
+        // Names aren't treated as patterns themselves
+        assert_eq!(squid::apply_name_pattern("{name}", "Step {n}", 1), "Step {n}");
+    }
+
+    #[test]
+    fn ocean_ids() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+        let b = ocean.insert(ocean.get(a).unwrap().clone());
+        let (a_id, b_id) = (ocean.get(a).unwrap().get_id(), ocean.get(b).unwrap().get_id());
+
+        // Pasted copies get their own id, while snapshots keep them
+        assert_ne!(a_id, b_id);
+        assert_eq!(ocean.clone().get(a).unwrap().get_id(), a_id);
+        assert_eq!(ocean.find_by_id(b_id), Some(b));
+
+        // Ids are saved, and lead to the same squids after loading
+        let loaded = Ocean::from_json(&serde_json::to_string(&ocean).unwrap()).unwrap();
+        assert_eq!(loaded.get(loaded.find_by_id(a_id).unwrap()).unwrap().get_id(), a_id);
+        assert_eq!(loaded.get(loaded.find_by_id(b_id).unwrap()).unwrap().get_id(), b_id);
+
+        ocean.remove(a);
+        assert_eq!(ocean.find_by_id(a_id), None);
+    }
+
+    #[test]
+    fn ocean_blur() {
+        let mut ocean = Ocean::default();
+        let a = ocean.insert(circle());
+
+        // Blurs can't be negative, and duplicates are blurred the same
+        let squid = ocean.get_mut(a).unwrap();
+        squid.set_property(SquidProperty::Blur, -4.0);
+        assert_eq!(squid.get_property(SquidProperty::Blur), Some(0.0));
+        squid.set_property(SquidProperty::Blur, 6.0);
+        assert_eq!(squid.duplicate(&glm::zero()).get_blur(), 6.0);
+
+        ocean.set_layer_blur(0, 8.0);
+        assert_eq!(ocean.get_layer(0).map(Layer::get_blur), Some(8.0));
+    }
+
+    #[test]
+    fn ocean_content_bounds() {
+        let mut ocean = Ocean::default();
+        assert!(ocean.get_content_bounds().is_none());
+
+        ocean.insert(Squid::circle(glm::vec2(10.0, 20.0), 5.0, Color::white()));
+        ocean.insert(Squid::circle(glm::vec2(-30.0, 0.0), 10.0, Color::white()));
+
+        // Viewports only frame content
+        ocean.insert(Squid::rect(glm::zero(), glm::vec2(1000.0, 1000.0), Rad(0.0), Color::white(), 0.0, true));
+
+        let bounds = ocean.get_content_bounds().unwrap().padded(2.0);
+        assert_eq!((bounds.min_x, bounds.min_y, bounds.max_x, bounds.max_y), (-42.0, -12.0, 17.0, 27.0));
+    }
+
+    #[test]
+    fn ocean_squids_near() {
+        let mut ocean = Ocean::default();
+        let near = ocean.insert(circle());
+        let far = ocean.insert(Squid::circle(glm::vec2(5000.0, 0.0), 5.0, Color::white()));
+
+        let found = ocean.get_squids_near(&AABB::new(0.0, 0.0, 1.0, 1.0));
+        assert!(found.contains(&near) && !found.contains(&far));
+
+        // Changed squids are found anywhere until they've settled
+        ocean.get_mut(far);
+        assert!(ocean.get_squids_near(&AABB::new(0.0, 0.0, 1.0, 1.0)).contains(&far));
+
+        ocean.remove(near);
+        assert!(!ocean.get_squids_near(&AABB::new(0.0, 0.0, 1.0, 1.0)).contains(&near));
+    }
+}
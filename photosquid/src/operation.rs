@@ -1,10 +1,32 @@
+use crate::{camera::Camera, math::angle_difference};
 use angular_units::Rad;
 use nalgebra_glm as glm;
 
 pub enum Operation {
-    Rotate { point: glm::Vec2, rotation: Rad<f32> },
+    Rotate { point: glm::Vec2, rotation: Rad<f32>, total: Rad<f32> },
     Scale { point: glm::Vec2, origin: glm::Vec2 },
     Spread { point: glm::Vec2, origin: glm::Vec2 },
-    Revolve { point: glm::Vec2, origin: glm::Vec2 },
+    Revolve { point: glm::Vec2, origin: glm::Vec2, total: Rad<f32> },
     Dilate { point: glm::Vec2, origin: glm::Vec2 },
 }
+
+impl Operation {
+    // Records how far a revolve has turned, given the current mouse position in world space
+    pub fn track_revolve(&mut self, current: &glm::Vec2) {
+        use crate::math::AsAngle;
+
+        if let Self::Revolve { point, origin, total } = self {
+            let swept = (*point - *origin).as_angle() - (current - *origin).as_angle();
+            *total += angle_difference(*total, swept);
+        }
+    }
+
+    // Pivot on screen and how far the selection has been turned, for rotating operations
+    pub fn get_rotation_readout(&self, camera: &Camera) -> Option<(glm::Vec2, Rad<f32>)> {
+        match self {
+            Self::Rotate { point, total, .. } => Some((*point, *total)),
+            Self::Revolve { origin, total, .. } => Some((camera.apply(origin), *total)),
+            _ => None,
+        }
+    }
+}
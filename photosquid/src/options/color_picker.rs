@@ -1,23 +1,49 @@
 use crate::{
     aabb::AABB,
+    affine::Affine2,
     as_values::AsValues,
-    capture::Capture,
+    capture::{Capture, KeyCapture},
     color::Color,
     interaction::{DragInteraction, Interaction},
+    options::section::Section,
+    raster_color::RasterColor,
     render_ctx::RenderCtx,
     smooth::Smooth,
+    style::StyleRef,
+    user_input::{step_focus, TextInput},
 };
-use glium::glutin::event::MouseButton;
+use glium::glutin::event::{ModifiersState, MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
+
+// What changes to the color picker are applied to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ColorTarget {
+    // Fill of the selected squids
+    #[default]
+    Selection,
+
+    Style(StyleRef),
+}
 
 pub struct ColorPicker {
     is_selecting_hue_value: bool,
     is_selecting_saturation: bool,
+    is_selecting_alpha: bool,
     hue_value_point: Smooth<glm::Vec2>,
     saturation_point: Smooth<f32>,
+    alpha_point: Smooth<f32>,
     color_changed_to: Option<Color>,
     y: f32,
+    hex_input: TextInput,
+    channel_inputs: [TextInput; 4],
+    recent_colors: Vec<Color>,
+    is_continuing_recent: bool,
+    target: ColorTarget,
+
+    // Whether the section of the object tab containing the color picker is expanded
+    shown: bool,
 }
 
 impl Default for ColorPicker {
@@ -25,15 +51,27 @@ impl Default for ColorPicker {
         Self {
             is_selecting_hue_value: false,
             is_selecting_saturation: false,
+            is_selecting_alpha: false,
             hue_value_point: Smooth::new(glm::vec2(0.0, 0.0), Some(Duration::from_millis(200))),
             saturation_point: Smooth::new(1.0, Some(Duration::from_millis(200))),
+            alpha_point: Smooth::new(1.0, Some(Duration::from_millis(200))),
             color_changed_to: None,
-            y: 64.0,
+            y: 64.0 + Section::HEIGHT,
+            hex_input: TextInput::hex("000000".into(), "Hex".into()),
+            channel_inputs: ["R", "G", "B", "A"].map(|label| TextInput::new("0".into(), label.into(), "".into())),
+            recent_colors: vec![],
+            is_continuing_recent: false,
+            target: ColorTarget::Selection,
+            shown: true,
         }
     }
 }
 
 impl ColorPicker {
+    const MAX_RECENT_COLORS: usize = 12;
+    const CHIP_SIZE: f32 = 16.0;
+    const CHIP_SPACING: f32 = 19.0;
+
     // Sets the selected color in the color picker without triggering a color change notification
     pub fn set_selected_color_no_notif(&mut self, color: Color) {
         let (h, s, v) = color.to_hsv();
@@ -41,35 +79,203 @@ impl ColorPicker {
 
         self.hue_value_point.set(glm::vec2(h, v));
         self.saturation_point.set(s);
+        self.alpha_point.set(color.a);
+    }
+
+    // Sets the selected color in the color picker and notifies that it changed
+    pub fn set_selected_color(&mut self, color: Color) {
+        self.set_selected_color_no_notif(color);
+        self.color_changed_to = Some(color);
+    }
+
+    // Starts editing something else, showing its current color
+    pub fn edit(&mut self, target: ColorTarget, color: Color) {
+        self.target = target;
+        self.set_selected_color_no_notif(color);
+    }
+
+    pub fn get_target(&self) -> ColorTarget {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: ColorTarget) {
+        self.target = target;
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.shown
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.hue_value_point.is_animating() || self.saturation_point.is_animating() || self.alpha_point.is_animating()
+    }
+
+    // Hiding the color picker stops editing anything in it
+    pub fn set_shown(&mut self, shown: bool) {
+        self.shown = shown;
+
+        if !shown {
+            std::iter::once(&mut self.hex_input)
+                .chain(self.channel_inputs.iter_mut())
+                .for_each(TextInput::unfocus);
+            self.apply_inputs();
+        }
     }
 
     pub fn poll(&mut self) -> Option<Color> {
         self.color_changed_to.take()
     }
 
-    pub fn click(&mut self, button: MouseButton, mouse: glm::Vec2, screen_width: f32) -> bool {
-        if button == MouseButton::Left && self.is_over_hue_value(mouse, screen_width) {
+    // Remembers a color that was applied to squids,
+    // all colors applied during a single drag only take up one spot
+    pub fn remember_color(&mut self, color: Color) {
+        if self.is_continuing_recent && !self.recent_colors.is_empty() {
+            self.recent_colors.remove(0);
+        }
+
+        self.recent_colors.retain(|recent| *recent != color);
+        self.recent_colors.insert(0, color);
+        self.recent_colors.truncate(Self::MAX_RECENT_COLORS);
+        self.is_continuing_recent = self.is_selecting_color();
+    }
+
+    pub fn click(&mut self, button: MouseButton, mouse: glm::Vec2, panel: &AABB) -> bool {
+        if self.click_inputs(button, mouse, panel) {
+            return true;
+        }
+
+        if button == MouseButton::Left {
+            if let Some(color) = self.get_clicked_recent_color(mouse, panel) {
+                self.set_selected_color(color);
+                return true;
+            }
+        }
+
+        if button == MouseButton::Left && self.is_over_hue_value(mouse, panel) {
             self.is_selecting_hue_value = true;
-            self.set_hue_value_with_mouse(mouse, screen_width);
+            self.set_hue_value_with_mouse(mouse, panel);
             return true;
         }
 
-        if button == MouseButton::Left && self.is_over_saturation(mouse, screen_width) {
+        if button == MouseButton::Left && self.is_over_saturation(mouse, panel) {
             self.is_selecting_saturation = true;
-            self.set_saturation_with_mouse(mouse, screen_width);
+            self.set_saturation_with_mouse(mouse, panel);
+            return true;
+        }
+
+        if button == MouseButton::Left && self.is_over_alpha(mouse, panel) {
+            self.is_selecting_alpha = true;
+            self.set_alpha_with_mouse(mouse, panel);
             return true;
         }
 
         false
     }
 
-    pub fn drag(&mut self, interaction: &Interaction, screen_width: f32) -> Capture {
+    fn click_inputs(&mut self, button: MouseButton, mouse: glm::Vec2, panel: &AABB) -> bool {
+        let mut took_focus = self.hex_input.click(button, &mouse, &self.get_hex_area(panel)) == Capture::TakeFocus;
+
+        for (i, input) in self.channel_inputs.iter_mut().enumerate() {
+            let area = Self::get_channel_area(self.y, i, panel);
+            took_focus |= input.click(button, &mouse, &area) == Capture::TakeFocus;
+        }
+
+        self.apply_inputs();
+        took_focus
+    }
+
+    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode, modifiers: ModifiersState) -> KeyCapture {
+        if virtual_keycode == VirtualKeyCode::Tab && self.focus_next_input(modifiers.shift()) {
+            self.apply_inputs();
+            return KeyCapture::Capture;
+        }
+
+        let capture = std::iter::once(&mut self.hex_input)
+            .chain(self.channel_inputs.iter_mut())
+            .find_map(|input| input.key_press(virtual_keycode, modifiers).to_option())
+            .unwrap_or(KeyCapture::Miss);
+
+        self.apply_inputs();
+        capture
+    }
+
+    pub fn receive_character(&mut self, character: char) -> KeyCapture {
+        let capture = std::iter::once(&mut self.hex_input)
+            .chain(self.channel_inputs.iter_mut())
+            .find_map(|input| input.receive_character(character).to_option())
+            .unwrap_or(KeyCapture::Miss);
+
+        self.apply_inputs();
+        capture
+    }
+
+    // Commits the input being edited and moves on to the next (or previous) one,
+    // returns whether there was an input being edited
+    fn focus_next_input(&mut self, backwards: bool) -> bool {
+        let mut inputs: Vec<&mut TextInput> = std::iter::once(&mut self.hex_input).chain(self.channel_inputs.iter_mut()).collect();
+
+        let focused = match inputs.iter().position(|input| input.is_focused()) {
+            Some(focused) => focused,
+            None => return false,
+        };
+
+        inputs[focused].unfocus();
+
+        if let Some(next) = step_focus(Some(focused), inputs.len(), backwards) {
+            inputs[next].focus();
+        }
+
+        true
+    }
+
+    // Applies newly typed hex or RGBA values to the picker.
+    // Hex is either #RRGGBB, which keeps the current alpha, or #RRGGBBAA
+    fn apply_inputs(&mut self) {
+        let mut new_color = None;
+        let current_color = self.calculate_color();
+
+        if let Some(content) = self.hex_input.poll() {
+            let digits = content.trim_start_matches('#');
+
+            new_color = RasterColor::hex(&format!("#{}", digits)).ok().map(|mut raster| {
+                if digits.len() == 6 {
+                    raster.a = RasterColor::from(current_color).a;
+                }
+                Color::from(raster)
+            });
+        }
+
+        for (i, input) in self.channel_inputs.iter_mut().enumerate() {
+            if let Some(value) = input.poll().and_then(|content| content.parse::<f32>().ok()) {
+                let mut raster = RasterColor::from(new_color.unwrap_or(current_color));
+                let value = value.round().clamp(0.0, 255.0) as u8;
+
+                match i {
+                    0 => raster.r = value,
+                    1 => raster.g = value,
+                    2 => raster.b = value,
+                    _ => raster.a = value,
+                }
+
+                new_color = Some(raster.into());
+            }
+        }
+
+        if let Some(new_color) = new_color {
+            self.set_selected_color_no_notif(new_color);
+            self.color_changed_to = Some(self.calculate_color());
+        }
+    }
+
+    pub fn drag(&mut self, interaction: &Interaction, panel: &AABB) -> Capture {
         match interaction {
             Interaction::Drag(DragInteraction { current, .. }) => {
                 if self.is_selecting_hue_value {
-                    self.set_hue_value_with_mouse(*current, screen_width);
+                    self.set_hue_value_with_mouse(*current, panel);
                 } else if self.is_selecting_saturation {
-                    self.set_saturation_with_mouse(*current, screen_width);
+                    self.set_saturation_with_mouse(*current, panel);
+                } else if self.is_selecting_alpha {
+                    self.set_alpha_with_mouse(*current, panel);
                 }
                 Capture::AllowDrag
             }
@@ -81,43 +287,76 @@ impl ColorPicker {
         if button == MouseButton::Left {
             self.is_selecting_hue_value = false;
             self.is_selecting_saturation = false;
+            self.is_selecting_alpha = false;
+            self.is_continuing_recent = false;
         }
     }
 
-    pub fn is_over_hue_value(&self, mouse: glm::Vec2, screen_width: f32) -> bool {
-        if let Some(area) = self.get_hue_value_area(screen_width) {
+    pub fn is_over_hue_value(&self, mouse: glm::Vec2, panel: &AABB) -> bool {
+        if let Some(area) = self.get_hue_value_area(panel) {
             return area.intersecting_point(mouse.x, mouse.y);
         }
 
         false
     }
 
-    pub fn is_over_saturation(&self, mouse: glm::Vec2, screen_width: f32) -> bool {
-        if let Some(area) = self.get_saturation_area(screen_width) {
+    pub fn is_over_saturation(&self, mouse: glm::Vec2, panel: &AABB) -> bool {
+        if let Some(area) = self.get_saturation_area(panel) {
             return area.intersecting_point(mouse.x, mouse.y);
         }
 
         false
     }
 
-    pub fn get_hue_value_area(&self, screen_width: f32) -> Option<AABB> {
-        Some(AABB::new(screen_width - 256.0, self.y, 256.0, 192.0))
+    pub fn is_over_alpha(&self, mouse: glm::Vec2, panel: &AABB) -> bool {
+        self.get_alpha_area(panel).intersecting_point(mouse.x, mouse.y)
+    }
+
+    pub fn get_hue_value_area(&self, panel: &AABB) -> Option<AABB> {
+        Some(AABB::new(panel.min_x, self.y, panel.width(), 192.0))
     }
 
     pub fn get_hue_value_point(&self) -> &Smooth<glm::Vec2> {
         &self.hue_value_point
     }
 
-    pub fn get_saturation_area(&self, screen_width: f32) -> Option<AABB> {
-        Some(AABB::new(screen_width - 256.0, self.y + 196.0, 256.0, 24.0))
+    pub fn get_saturation_area(&self, panel: &AABB) -> Option<AABB> {
+        Some(AABB::new(panel.min_x, self.y + 196.0, panel.width(), 24.0))
+    }
+
+    pub fn get_alpha_area(&self, panel: &AABB) -> AABB {
+        AABB::new(panel.min_x, self.y + 224.0, panel.width(), 24.0)
+    }
+
+    pub fn get_hex_area(&self, panel: &AABB) -> AABB {
+        let standard = TextInput::standard_area(&glm::vec2(panel.min_x + 16.0, self.y + 276.0));
+        AABB::new(standard.min_x, standard.min_y, 104.0, standard.height())
+    }
+
+    fn get_channel_area(y: f32, i: usize, panel: &AABB) -> AABB {
+        let standard = TextInput::standard_area(&glm::vec2(panel.min_x + 16.0 + i as f32 * 60.0, y + 348.0));
+        AABB::new(standard.min_x, standard.min_y, 48.0, standard.height())
+    }
+
+    fn get_recent_color_area(&self, i: usize, panel: &AABB) -> AABB {
+        let x = panel.min_x + 16.0 + i as f32 * Self::CHIP_SPACING;
+        AABB::new(x, self.y + 396.0, Self::CHIP_SIZE, Self::CHIP_SIZE)
+    }
+
+    fn get_clicked_recent_color(&self, mouse: glm::Vec2, panel: &AABB) -> Option<Color> {
+        self.recent_colors
+            .iter()
+            .enumerate()
+            .find(|(i, _)| self.get_recent_color_area(*i, panel).intersecting_point(mouse.x, mouse.y))
+            .map(|(_, color)| *color)
     }
 
     pub fn get_saturation_point(&self) -> &Smooth<f32> {
         &self.saturation_point
     }
 
-    pub fn set_hue_value_with_mouse(&mut self, mouse: glm::Vec2, screen_width: f32) {
-        if let Some(area) = self.get_hue_value_area(screen_width) {
+    pub fn set_hue_value_with_mouse(&mut self, mouse: glm::Vec2, panel: &AABB) {
+        if let Some(area) = self.get_hue_value_area(panel) {
             let u = (mouse.x - area.min_x) / area.width();
             let v = (mouse.y - area.min_y) / area.height();
             let u = u.clamp(0.0, 1.0);
@@ -127,44 +366,86 @@ impl ColorPicker {
         }
     }
 
-    pub fn set_saturation_with_mouse(&mut self, mouse: glm::Vec2, screen_width: f32) {
-        if let Some(area) = self.get_saturation_area(screen_width) {
+    pub fn set_saturation_with_mouse(&mut self, mouse: glm::Vec2, panel: &AABB) {
+        if let Some(area) = self.get_saturation_area(panel) {
             let u = (mouse.x - area.min_x) / area.width();
             self.saturation_point.set(u.clamp(0.0, 1.0));
             self.color_changed_to = Some(self.calculate_color());
         }
     }
 
+    pub fn set_alpha_with_mouse(&mut self, mouse: glm::Vec2, panel: &AABB) {
+        let area = self.get_alpha_area(panel);
+        let u = (mouse.x - area.min_x) / area.width();
+        self.alpha_point.set(u.clamp(0.0, 1.0));
+        self.color_changed_to = Some(self.calculate_color());
+    }
+
     pub fn calculate_color(&self) -> Color {
         let real_hv = self.get_hue_value_point().get_real();
         let real_s = self.get_saturation_point().get_real();
         let h = real_hv.x;
         let s = *real_s;
         let v = 1.0 - real_hv.y;
-        Color::from_hsv(h, s, v)
+        Color::from_hsv(h, s, v).with_opacity(*self.alpha_point.get_real())
     }
 
     pub fn is_selecting_color(&self) -> bool {
-        self.is_selecting_hue_value || self.is_selecting_saturation
+        self.is_selecting_hue_value || self.is_selecting_saturation || self.is_selecting_alpha
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, panel: &AABB) {
+        self.render_hue_value_picker(ctx, panel);
+        self.render_saturation_picker(ctx, panel);
+        self.render_alpha_picker(ctx, panel);
+        self.render_inputs(ctx, text_system, font, panel);
+        self.render_recent_colors(ctx, panel);
     }
 
-    pub fn render(&self, ctx: &mut RenderCtx) {
-        self.render_hue_value_picker(ctx);
-        self.render_saturation_picker(ctx);
+    fn render_recent_colors(&self, ctx: &mut RenderCtx, panel: &AABB) {
+        for (i, color) in self.recent_colors.iter().enumerate() {
+            let area = self.get_recent_color_area(i, panel);
+            ctx.ribbon_mesh
+                .render(ctx, glm::vec2(area.min_x, area.min_y), glm::vec2(area.width(), area.height()), color);
+        }
+    }
+
+    fn render_inputs(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, panel: &AABB) {
+        // Keep inputs in sync with the picker while they aren't being edited
+        let raster = RasterColor::from(self.calculate_color());
+        let hex = raster.to_hex();
+
+        if !self.hex_input.is_focused() && self.hex_input.text() != &hex[1..] {
+            self.hex_input.set(&hex[1..]);
+        }
+
+        for (input, value) in self.channel_inputs.iter_mut().zip([raster.r, raster.g, raster.b, raster.a]) {
+            let value = value.to_string();
+
+            if !input.is_focused() && input.text() != value {
+                input.set(&value);
+            }
+        }
+
+        self.hex_input.render(ctx, text_system, font.clone(), &self.get_hex_area(panel));
+
+        for (i, input) in self.channel_inputs.iter_mut().enumerate() {
+            input.render(ctx, text_system, font.clone(), &Self::get_channel_area(self.y, i, panel));
+        }
     }
 
-    pub fn render_hue_value_picker(&self, ctx: &mut RenderCtx) {
+    pub fn render_hue_value_picker(&self, ctx: &mut RenderCtx, panel: &AABB) {
         let color_picker_mesh = ctx.square_xyzuv;
 
-        let area = self.get_hue_value_area(ctx.width).unwrap();
+        let area = self.get_hue_value_area(panel).unwrap();
         let x = area.min_x;
         let y = area.min_y;
         let dimensions = glm::vec2(area.width(), area.height());
         let hue_value_point_animated = self.hue_value_point.get_animated();
 
-        let identity = glm::identity::<f32, 4>();
-        let transformation = glm::translation(&glm::vec3(dimensions.x / 2.0 + x, dimensions.y / 2.0 + y, 0.0));
-        let transformation = glm::scale(&transformation, &glm::vec3(dimensions.x / 2.0, dimensions.y / 2.0, 0.0));
+        let identity = Affine2::identity();
+        let transformation = Affine2::translation(&glm::vec2(dimensions.x / 2.0 + x, dimensions.y / 2.0 + y));
+        let transformation = transformation.scale(&glm::vec2(dimensions.x / 2.0, dimensions.y / 2.0));
 
         let uniforms = glium::uniform! {
             transformation: transformation.as_values(),
@@ -185,10 +466,10 @@ impl ColorPicker {
         .unwrap();
     }
 
-    pub fn render_saturation_picker(&self, ctx: &mut RenderCtx) {
+    pub fn render_saturation_picker(&self, ctx: &mut RenderCtx, panel: &AABB) {
         let color_picker_mesh = ctx.square_xyzuv;
 
-        let area = self.get_saturation_area(ctx.width).unwrap();
+        let area = self.get_saturation_area(panel).unwrap();
         let x = area.min_x;
         let y = area.min_y;
         let dimensions = glm::vec2(area.width(), area.height());
@@ -197,9 +478,9 @@ impl ColorPicker {
         let hue = hue_value_point.x;
         let value = 1.0 - hue_value_point.y;
 
-        let identity = glm::identity::<f32, 4>();
-        let transformation = glm::translation(&glm::vec3(dimensions.x / 2.0 + x, dimensions.y / 2.0 + y, 0.0));
-        let transformation = glm::scale(&transformation, &glm::vec3(dimensions.x / 2.0, dimensions.y / 2.0, 0.0));
+        let identity = Affine2::identity();
+        let transformation = Affine2::translation(&glm::vec2(dimensions.x / 2.0 + x, dimensions.y / 2.0 + y));
+        let transformation = transformation.scale(&glm::vec2(dimensions.x / 2.0, dimensions.y / 2.0));
         let uniforms = glium::uniform! {
             transformation: transformation.as_values(),
             view: identity.as_values(),
@@ -219,4 +500,93 @@ impl ColorPicker {
         )
         .unwrap();
     }
+
+    pub fn render_alpha_picker(&self, ctx: &mut RenderCtx, panel: &AABB) {
+        let color_picker_mesh = ctx.square_xyzuv;
+
+        let area = self.get_alpha_area(panel);
+        let dimensions = glm::vec2(area.width(), area.height());
+        let Color { r, g, b, .. } = Color::from_hsv(
+            self.hue_value_point.get_animated().x,
+            self.saturation_point.get_animated(),
+            1.0 - self.hue_value_point.get_animated().y,
+        );
+
+        let identity = Affine2::identity();
+        let transformation = Affine2::translation(&glm::vec2(dimensions.x / 2.0 + area.min_x, dimensions.y / 2.0 + area.min_y));
+        let transformation = transformation.scale(&glm::vec2(dimensions.x / 2.0, dimensions.y / 2.0));
+        let uniforms = glium::uniform! {
+            transformation: transformation.as_values(),
+            view: identity.as_values(),
+            projection: ctx.projection.as_values(),
+            color: [r, g, b],
+            point: [self.alpha_point.get_animated(), 0.5],
+            dimensions: [dimensions.x, dimensions.y],
+        };
+
+        ctx.draw(
+            &color_picker_mesh.vertex_buffer,
+            color_picker_mesh.indices,
+            ctx.alpha_picker_shader,
+            &uniforms,
+            &Default::default(),
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorPicker, ColorTarget};
+    use crate::{color::Color, raster_color::RasterColor, style::StyleRef};
+    use glium::glutin::event::{ModifiersState, VirtualKeyCode};
+    use slotmap::Key;
+
+    #[test]
+    fn tab_between_inputs() {
+        let mut color_picker = ColorPicker::default();
+        color_picker.channel_inputs[3].focus();
+
+        color_picker.key_press(VirtualKeyCode::Tab, ModifiersState::empty());
+        assert!(color_picker.hex_input.is_focused());
+        assert!(!color_picker.channel_inputs[3].is_focused());
+
+        color_picker.key_press(VirtualKeyCode::Tab, ModifiersState::SHIFT);
+        assert!(color_picker.channel_inputs[3].is_focused());
+    }
+
+    #[test]
+    fn hex_keeps_alpha() {
+        let mut color_picker = ColorPicker::default();
+        let translucent = RasterColor { r: 255, g: 0, b: 0, a: 128 };
+        color_picker.edit(ColorTarget::Selection, translucent.into());
+
+        let mut type_hex = |hex: &str| {
+            color_picker.hex_input.focus();
+            color_picker.hex_input.set("");
+            hex.chars().for_each(|character| {
+                color_picker.receive_character(character);
+            });
+            color_picker.key_press(VirtualKeyCode::Tab, ModifiersState::empty());
+            RasterColor::from(color_picker.calculate_color()).to_hex()
+        };
+
+        assert_eq!(type_hex("00FF00"), "#00FF0080");
+        assert_eq!(type_hex("0000FF40"), "#0000FF40");
+    }
+
+    #[test]
+    fn edit_other_targets() {
+        let mut color_picker = ColorPicker::default();
+        let target = ColorTarget::Style(StyleRef::null());
+
+        // Switching targets shows their color without changing anything
+        color_picker.edit(target, Color::white());
+        assert_eq!(color_picker.get_target(), target);
+        assert_eq!(color_picker.calculate_color(), Color::white());
+        assert_eq!(color_picker.poll(), None);
+
+        color_picker.set_target(ColorTarget::Selection);
+        assert_eq!(color_picker.get_target(), ColorTarget::default());
+    }
 }
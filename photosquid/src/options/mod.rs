@@ -1,4 +1,5 @@
 pub mod color_picker;
+pub mod section;
 pub mod tab;
 pub mod tab_button;
 
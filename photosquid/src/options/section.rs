@@ -0,0 +1,100 @@
+use crate::{aabb::AABB, draw_text::draw_text, render_ctx::RenderCtx};
+use glium::glutin::event::MouseButton;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Labeled header for a group of options, which collapses the group when clicked
+pub struct Section {
+    name: &'static str,
+    expanded: bool,
+}
+
+impl Section {
+    pub const HEIGHT: f32 = 28.0;
+
+    pub fn new(name: &'static str, expanded: bool) -> Self {
+        Self { name, expanded }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn get_area(top: f32, panel: &AABB) -> AABB {
+        AABB::new(panel.min_x, top, panel.width(), Self::HEIGHT)
+    }
+
+    // Returns whether the header was clicked, which expands or collapses the section
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2, top: f32, panel: &AABB) -> bool {
+        let clicked = button == MouseButton::Left && Self::get_area(top, panel).intersecting_point(position.x, position.y);
+
+        if clicked {
+            self.expanded = !self.expanded;
+        }
+
+        clicked
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, top: f32, panel: &AABB) {
+        let area = Self::get_area(top, panel);
+        let marker = if self.expanded { "v" } else { ">" };
+
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x, area.min_y + 2.0),
+            glm::vec2(area.width(), area.height() - 4.0),
+            &ctx.color_scheme.light_ribbon,
+        );
+
+        draw_text(
+            text_system,
+            font,
+            &format!("{}  {}", marker, self.name),
+            &glm::vec2(area.min_x + 16.0, area.max_y - 9.0),
+            ctx,
+            ctx.color_scheme.foreground,
+        );
+    }
+}
+
+// Gets the top of each section's header when stacked downwards from 'top',
+// where collapsed sections only take up room for their header
+pub fn stack(sections: &[Section], content_heights: &[f32], top: f32) -> Vec<f32> {
+    let mut y = top;
+
+    sections
+        .iter()
+        .zip(content_heights)
+        .map(|(section, content_height)| {
+            let section_top = y;
+            y += Section::HEIGHT + if section.expanded { *content_height } else { 0.0 };
+            section_top
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stack, Section};
+    use crate::aabb::AABB;
+    use glium::glutin::event::MouseButton;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn collapse_sections() {
+        let panel = AABB::new(0.0, 0.0, 256.0, 800.0);
+        let mut sections = [Section::new("Fill", true), Section::new("Transform", true), Section::new("Arrange", true)];
+        assert_eq!(stack(&sections, &[100.0, 50.0, 10.0], 64.0), vec![64.0, 192.0, 270.0]);
+
+        // Clicking the header of the second section collapses it
+        assert!(!sections[1].click(MouseButton::Left, &glm::vec2(100.0, 100.0), 192.0, &panel));
+        assert!(sections[1].click(MouseButton::Left, &glm::vec2(100.0, 200.0), 192.0, &panel));
+        assert!(!sections[1].is_expanded());
+        assert_eq!(stack(&sections, &[100.0, 50.0, 10.0], 64.0), vec![64.0, 192.0, 220.0]);
+    }
+}
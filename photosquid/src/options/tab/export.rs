@@ -0,0 +1,287 @@
+use super::Tab;
+use crate::{
+    aabb::AABB,
+    app::App,
+    capture::{Capture, KeyCapture},
+    draw_text::draw_text,
+    export::{ExportFormat, ExportSize},
+    interaction::{CharacterInteraction, ClickInteraction, Interaction, KeyInteraction},
+    modal::Modal,
+    ocean::Ocean,
+    render_ctx::RenderCtx,
+    selection::Selection,
+    user_input::{render_focus_marker, step_focus, Button, Dropdown, TextInput},
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+pub struct Export {
+    scale: TextInput,
+    format: Dropdown,
+    add_profile: Button,
+    export_all: Button,
+    keyboard_focus: Option<usize>,
+}
+
+#[derive(Copy, Clone)]
+enum ExportButton {
+    AddProfile,
+    ExportAll,
+}
+
+impl ExportButton {
+    const ALL: [ExportButton; 2] = [ExportButton::AddProfile, ExportButton::ExportAll];
+}
+
+impl Export {
+    const LEFT_MARGIN: f32 = 16.0;
+    const SCALE_TOP: f32 = 128.0;
+    const FORMAT_TOP: f32 = 192.0;
+    const BUTTONS_TOP: f32 = 244.0;
+    const BUTTON_SPACING: f32 = 44.0;
+    const PROFILES_TOP: f32 = 372.0;
+    const FORMATS: [ExportFormat; 2] = [ExportFormat::Svg, ExportFormat::Png];
+    const STRIP_HEIGHT: f32 = 30.0;
+    const REMOVE_HIT_WIDTH: f32 = 32.0;
+
+    pub fn new() -> Self {
+        Self {
+            scale: TextInput::plain("1x".into(), "Profile Size".into()),
+            format: Dropdown::new("Format".into(), vec!["SVG".into(), "PNG".into()], 0),
+            add_profile: Button::new("Add Profile".into(), Box::new(|_| ())),
+            export_all: Button::new("Export All Profiles".into(), Box::new(|_| ())),
+            keyboard_focus: None,
+        }
+    }
+
+    fn get_scale_area(panel: &AABB) -> AABB {
+        TextInput::standard_area(&glm::vec2(panel.min_x + Self::LEFT_MARGIN, Self::SCALE_TOP))
+    }
+
+    fn get_format_area(panel: &AABB) -> AABB {
+        TextInput::standard_area(&glm::vec2(panel.min_x + Self::LEFT_MARGIN, Self::FORMAT_TOP))
+    }
+
+    fn get_button_area(i: usize, panel: &AABB) -> AABB {
+        let y = Self::BUTTONS_TOP + i as f32 * Self::BUTTON_SPACING;
+        TextInput::standard_area(&glm::vec2(panel.min_x + Self::LEFT_MARGIN, y))
+    }
+
+    fn get_profile_y(i: usize) -> f32 {
+        Self::PROFILES_TOP + i as f32 * Self::STRIP_HEIGHT
+    }
+
+    fn get_button(&mut self, button: ExportButton) -> &mut Button {
+        match button {
+            ExportButton::AddProfile => &mut self.add_profile,
+            ExportButton::ExportAll => &mut self.export_all,
+        }
+    }
+
+    // The scale input and format come first for keyboard focus, followed by the buttons
+    fn get_focus_area(index: usize, panel: &AABB) -> AABB {
+        match index {
+            0 => Self::get_scale_area(panel),
+            1 => Self::get_format_area(panel),
+            _ => Self::get_button_area(index - 2, panel),
+        }
+    }
+
+    fn get_size(&self) -> ExportSize {
+        ExportSize::parse(self.scale.text())
+    }
+
+    // Clicking the cross at the end of a profile's row asks to remove it
+    fn click_profiles(position: &glm::Vec2, app: &mut App) -> Capture {
+        if position.x < app.toolbox.get_options_area(&app.dimensions).max_x - Self::REMOVE_HIT_WIDTH {
+            return Capture::Miss;
+        }
+
+        let count = app.ocean.get_export_profiles().len();
+
+        let clicked = (0..count).find(|i| {
+            let y = Self::get_profile_y(*i);
+            position.y >= y - 0.5 * Self::STRIP_HEIGHT && position.y < y + 0.5 * Self::STRIP_HEIGHT
+        });
+
+        let (index, name) = match clicked.and_then(|i| Some((i, app.ocean.get_export_profiles().get(i)?.get_name()))) {
+            Some(clicked) => clicked,
+            None => return Capture::Miss,
+        };
+
+        app.show_modal(Modal::confirm(
+            "Remove Profile",
+            &format!("Remove the '{}' export profile?", name),
+            Box::new(move |app| {
+                app.ocean.remove_export_profile(index);
+                app.add_history_marker();
+            }),
+        ));
+
+        Capture::NoDrag
+    }
+
+    fn press(&self, export_button: ExportButton, app: &mut App) {
+        match export_button {
+            ExportButton::AddProfile => app.add_export_profile(Self::FORMATS[self.format.selected()], self.get_size()),
+            ExportButton::ExportAll => app.export_all_profiles(),
+        }
+    }
+
+    fn click(&mut self, button: MouseButton, position: &glm::Vec2, app: &mut App) -> Capture {
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+
+        // The format's options can cover the buttons, so they get the first chance at the click
+        if self.format.click(button, position, &Self::get_format_area(&panel)) == Capture::TakeFocus {
+            self.scale.unfocus();
+            self.keyboard_focus = Some(1);
+            return Capture::TakeFocus;
+        }
+
+        if self.scale.click(button, position, &Self::get_scale_area(&panel)) == Capture::TakeFocus {
+            self.keyboard_focus = Some(0);
+            return Capture::TakeFocus;
+        }
+
+        self.scale.unfocus();
+        self.keyboard_focus = None;
+
+        for (i, export_button) in ExportButton::ALL.iter().enumerate() {
+            let area = Self::get_button_area(i, &panel);
+
+            if self.get_button(*export_button).click(button, position, &area, app) == Capture::TakeFocus {
+                self.keyboard_focus = Some(i + 2);
+                self.press(*export_button, app);
+                return Capture::NoDrag;
+            }
+        }
+
+        Self::click_profiles(position, app)
+    }
+
+    // Tab/Shift+Tab moves between controls once keyboard focus is in the tab (which F6 toggles),
+    // and Enter/Space presses the focused button
+    fn navigate(&mut self, virtual_keycode: VirtualKeyCode, shift: bool, app: &mut App) -> Option<Capture> {
+        let count = ExportButton::ALL.len() + 2;
+        let is_editing = self.scale.is_focused() || self.format.is_expanded();
+
+        let next = match virtual_keycode {
+            VirtualKeyCode::Tab if self.keyboard_focus.is_some() => step_focus(self.keyboard_focus, count, shift),
+            VirtualKeyCode::F6 if self.keyboard_focus.is_none() => Some(0),
+            VirtualKeyCode::F6 => None,
+            VirtualKeyCode::Return | VirtualKeyCode::Space if !is_editing && self.keyboard_focus.is_some() => {
+                match self.keyboard_focus? {
+                    0 => self.scale.focus(),
+                    1 => self.format.toggle(),
+                    index => self.press(ExportButton::ALL[index - 2], app),
+                }
+                return Some(Capture::Keyboard(KeyCapture::Capture));
+            }
+            VirtualKeyCode::Escape if !is_editing && self.keyboard_focus.is_some() => {
+                self.keyboard_focus = None;
+                return Some(Capture::Keyboard(KeyCapture::Capture));
+            }
+            _ => return None,
+        };
+
+        self.scale.unfocus();
+        self.format.unfocus();
+        self.keyboard_focus = next;
+
+        if next == Some(0) {
+            self.scale.focus();
+        }
+
+        // Leaving the tab lets F6 carry on to the tool options
+        next.map(|_| Capture::Keyboard(KeyCapture::Capture))
+    }
+}
+
+impl Tab for Export {
+    fn interact(&mut self, interaction: Interaction, app: &mut App) -> Capture {
+        match interaction {
+            Interaction::Click(ClickInteraction { button, position, .. }) => self.click(button, &position, app),
+            Interaction::Key(KeyInteraction { virtual_keycode }) => {
+                let shift = app.keys_held.contains(&VirtualKeyCode::LShift);
+
+                if let Some(capture) = self.navigate(virtual_keycode, shift, app) {
+                    return capture;
+                }
+
+                self.format
+                    .key_press(virtual_keycode)
+                    .to_option()
+                    .or_else(|| self.scale.key_press(virtual_keycode, app.modifiers_held).to_option())
+                    .map_or(Capture::Miss, Capture::Keyboard)
+            }
+            Interaction::Character(CharacterInteraction { character }) => {
+                self.scale.receive_character(character).to_option().map_or(Capture::Miss, Capture::Keyboard)
+            }
+            _ => Capture::Miss,
+        }
+    }
+
+    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &Ocean, _selections: &[Selection], panel: &AABB) {
+        let left = panel.min_x + Self::LEFT_MARGIN;
+
+        self.scale.render(ctx, text_system, font.clone(), &Self::get_scale_area(panel));
+        self.format.render(ctx, text_system, font.clone(), &Self::get_format_area(panel));
+
+        for (i, export_button) in ExportButton::ALL.iter().enumerate() {
+            let area = Self::get_button_area(i, panel);
+            self.get_button(*export_button).render(ctx, text_system, font.clone(), &area);
+        }
+
+        if let Some(index) = self.keyboard_focus {
+            render_focus_marker(ctx, &Self::get_focus_area(index, panel));
+        }
+
+        // Options only reach down over the buttons, so they're drawn before the profiles
+        self.format.render_overlay(ctx, text_system, font.clone(), &Self::get_format_area(panel));
+
+        draw_text(
+            text_system,
+            font.clone(),
+            "Profiles",
+            &glm::vec2(left, Self::PROFILES_TOP - Self::STRIP_HEIGHT),
+            ctx,
+            ctx.color_scheme.label,
+        );
+
+        let profiles = ocean.get_export_profiles();
+
+        if profiles.is_empty() {
+            draw_text(text_system, font, "None", &glm::vec2(left, Self::PROFILES_TOP), ctx, ctx.color_scheme.muted);
+            return;
+        }
+
+        for (i, profile) in profiles.iter().enumerate() {
+            let y = Self::get_profile_y(i);
+
+            let target = profile
+                .viewport
+                .and_then(|reference| ocean.get(reference))
+                .map_or("All viewports", |squid| squid.get_name());
+
+            draw_text(
+                text_system,
+                font.clone(),
+                &format!("{} - {}", profile.get_name(), target),
+                &glm::vec2(left, y),
+                ctx,
+                ctx.color_scheme.foreground,
+            );
+
+            draw_text(
+                text_system,
+                font.clone(),
+                "x",
+                &glm::vec2(panel.max_x - Self::REMOVE_HIT_WIDTH + 8.0, y),
+                ctx,
+                ctx.color_scheme.muted,
+            );
+        }
+    }
+}
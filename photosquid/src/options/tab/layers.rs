@@ -1,77 +1,246 @@
 use super::Tab;
 use crate::{
+    aabb::AABB,
     app::App,
-    capture::Capture,
-    color::Color,
+    capture::{Capture, KeyCapture},
     draw_text::draw_text,
-    interaction::{ClickInteraction, Interaction},
-    layer::Layer,
+    interaction::{CharacterInteraction, ClickInteraction, DragInteraction, Interaction, KeyInteraction, ScrollInteraction},
+    layer::{Layer, LayerTag},
+    modal::Modal,
     ocean::Ocean,
     render_ctx::RenderCtx,
     selection::{selection_contains, Selection},
-    squid::{PreviewParams, SquidRef},
+    squid::SquidRef,
+    thumbnail_cache::ThumbnailCache,
 };
-use glium::glutin::event::MouseButton;
+use glium::glutin::event::{ModifiersState, MouseButton, VirtualKeyCode};
 use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
-use std::rc::Rc;
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 enum Entry {
+    Filter(Filter),
     LayerName(LayerName),
+    LayerControls(LayerControls),
     Child(Child),
+    NewLayer(NewLayer),
 }
 
 struct LayerName {
+    layer_index: usize,
     name: String,
+    tag: LayerTag,
+    is_current: bool,
+    y: f32,
+}
+
+struct NewLayer {
+    y: f32,
+}
+
+// Box for only showing squids with a matching name or tag
+struct Filter {
+    y: f32,
+}
+
+struct LayerControls {
+    layer_index: usize,
+    hidden: bool,
+    locked: bool,
+    opacity: f32,
+    blur: f32,
     y: f32,
 }
 
 struct Child {
     squid: SquidRef,
+    interactable: bool,
+    opacity: f32,
     y: f32,
 }
 
+#[derive(Copy, Clone)]
+enum LayerControl {
+    Visibility,
+    Lock,
+    Opacity,
+    Blur,
+}
+
+impl LayerControl {
+    const ALL: [LayerControl; 4] = [LayerControl::Visibility, LayerControl::Lock, LayerControl::Opacity, LayerControl::Blur];
+}
+
+// Something in the list that can be double-clicked
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ClickTarget {
+    Squid(SquidRef),
+    Layer(usize),
+}
+
+struct Renaming {
+    squid: SquidRef,
+    text: String,
+}
+
 pub struct Layers {
     entries: Vec<Entry>,
+    scroll: f32,
+    content_height: f32,
+    dragging_scrollbar: bool,
+    renaming: Option<Renaming>,
+    filter: String,
+    editing_filter: bool,
+    last_click: Option<(ClickTarget, Instant)>,
+    thumbnails: ThumbnailCache,
 }
 
 impl Layers {
     const SMALL_STRIP_HEIGHT: f32 = 30.0;
-    const TAB_WIDTH: f32 = 256.0;
+    const LEFT_MARGIN: f32 = 16.0;
+    const CONTROL_WIDTH: f32 = 64.0;
+    const OPACITY_STEPS: [f32; 4] = [1.0, 0.75, 0.5, 0.25];
+    const BLUR_STEPS: [f32; 4] = [0.0, 2.0, 4.0, 8.0];
+    const CONTENT_TOP: f32 = 64.0;
+    const CONTENT_PADDING: f32 = 36.0;
+    const SCROLLBAR_WIDTH: f32 = 4.0;
+    const SCROLLBAR_HIT_WIDTH: f32 = 16.0;
+    const SCROLLBAR_MIN_LENGTH: f32 = 24.0;
+    const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+    const TAG_SIZE: f32 = 10.0;
+    const TAG_HIT_WIDTH: f32 = 20.0;
 
     pub fn new() -> Self {
-        Self { entries: vec![] }
+        Self {
+            entries: vec![],
+            scroll: 0.0,
+            content_height: 0.0,
+            dragging_scrollbar: false,
+            renaming: None,
+            filter: String::new(),
+            editing_filter: false,
+            last_click: None,
+            thumbnails: ThumbnailCache::default(),
+        }
+    }
+
+    fn finish_renaming(&mut self, app: &mut App) {
+        if let Some(Renaming { squid, text }) = self.renaming.take() {
+            if let Some(squid) = app.ocean.get_mut(squid) {
+                if !text.is_empty() {
+                    squid.set_name(text);
+                }
+            }
+        }
+    }
+
+    // Whether this click on a row completes a double-click
+    fn is_double_click(&mut self, target: ClickTarget) -> bool {
+        let now = Instant::now();
+
+        let is_double_click = matches!(self.last_click, Some((last_target, time)) if last_target == target && now - time < Self::DOUBLE_CLICK_TIME);
+
+        self.last_click = if is_double_click { None } else { Some((target, now)) };
+        is_double_click
     }
 
-    fn update(&mut self, layers: &[Layer]) {
+    fn update(&mut self, ocean: &Ocean) {
         let mut entries: Vec<Entry> = Vec::new();
-        let mut y = 100.0;
+        let mut y = Self::CONTENT_TOP + Self::CONTENT_PADDING - self.scroll;
+        let current_layer = ocean.get_current_layer_index();
+
+        entries.push(Entry::Filter(Filter { y }));
+        y += Self::SMALL_STRIP_HEIGHT;
 
-        for layer in layers.iter() {
+        for (layer_index, layer) in ocean.get_layers().iter().enumerate() {
             entries.push(Entry::LayerName(LayerName {
+                layer_index,
                 name: layer.get_name().into(),
+                tag: layer.get_tag(),
+                is_current: layer_index == current_layer,
                 y,
             }));
 
             y += Self::SMALL_STRIP_HEIGHT;
 
-            for squid_ref in &layer.squids {
-                entries.push(Entry::Child(Child { squid: *squid_ref, y }));
+            entries.push(Entry::LayerControls(LayerControls {
+                layer_index,
+                hidden: layer.is_hidden(),
+                locked: layer.is_locked(),
+                opacity: layer.get_opacity(),
+                blur: layer.get_blur(),
+                y,
+            }));
+
+            y += Self::SMALL_STRIP_HEIGHT;
+
+            let matching = layer
+                .get_highest()
+                .filter(|squid_ref| ocean.get(*squid_ref).is_some_and(|squid| squid.matches_filter(&self.filter)));
+
+            for squid_ref in matching {
+                entries.push(Entry::Child(Child {
+                    squid: squid_ref,
+                    interactable: layer.is_interactable(),
+                    opacity: if layer.is_hidden() { 0.25 } else { layer.get_opacity() },
+                    y,
+                }));
                 y += Self::SMALL_STRIP_HEIGHT;
             }
         }
 
+        entries.push(Entry::NewLayer(NewLayer { y }));
+        y += Self::SMALL_STRIP_HEIGHT;
+
         self.entries = entries;
+        self.content_height = y + self.scroll - Self::CONTENT_TOP;
+    }
+
+    fn get_max_scroll(&self, window_height: f32) -> f32 {
+        (self.content_height - (window_height - Self::CONTENT_TOP)).max(0.0)
+    }
+
+    fn set_scroll(&mut self, scroll: f32, window_height: f32) {
+        self.scroll = scroll.clamp(0.0, self.get_max_scroll(window_height));
+    }
+
+    // Gets the top and length of the scrollbar thumb, if there's anything to scroll
+    fn get_scrollbar(&self, window_height: f32) -> Option<(f32, f32)> {
+        let max_scroll = self.get_max_scroll(window_height);
+
+        if max_scroll <= 0.0 {
+            return None;
+        }
+
+        let track_length = window_height - Self::CONTENT_TOP;
+        let length = (track_length * track_length / self.content_height).max(Self::SCROLLBAR_MIN_LENGTH);
+        let top = Self::CONTENT_TOP + (self.scroll / max_scroll) * (track_length - length);
+        Some((top, length))
+    }
+
+    fn scroll_to_scrollbar_position(&mut self, mouse_y: f32, window_height: f32) {
+        if let Some((_, length)) = self.get_scrollbar(window_height) {
+            let track_length = window_height - Self::CONTENT_TOP;
+            let ratio = ((mouse_y - Self::CONTENT_TOP - 0.5 * length) / (track_length - length)).clamp(0.0, 1.0);
+            self.set_scroll(ratio * self.get_max_scroll(window_height), window_height);
+        }
     }
 
-    fn get_clicked_entry(&self, mouse: &glm::Vec2, window_dimensions: &glm::Vec2) -> Option<&Entry> {
-        if mouse.x < window_dimensions.x - Layers::TAB_WIDTH {
+    fn get_clicked_entry(&self, mouse: &glm::Vec2, panel: &AABB) -> Option<&Entry> {
+        if mouse.x < panel.min_x || mouse.y < Self::CONTENT_TOP {
             return None;
         }
 
         for entry in &self.entries {
             match entry {
-                Entry::Child(Child { y, .. }) | Entry::LayerName(LayerName { y, .. }) => {
+                Entry::Child(Child { y, .. })
+                | Entry::LayerName(LayerName { y, .. })
+                | Entry::LayerControls(LayerControls { y, .. })
+                | Entry::NewLayer(NewLayer { y })
+                | Entry::Filter(Filter { y }) => {
                     if mouse.y >= *y - 0.5 * Self::SMALL_STRIP_HEIGHT && mouse.y < y - 0.5 * Self::SMALL_STRIP_HEIGHT + Self::SMALL_STRIP_HEIGHT {
                         return Some(entry);
                     }
@@ -81,6 +250,217 @@ impl Layers {
 
         None
     }
+
+    fn click_entry(&mut self, app: &mut App, position: &glm::Vec2, modifiers: ModifiersState) {
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+        let clicked: Option<&Entry> = self.get_clicked_entry(position, &panel);
+
+        match clicked {
+            Some(Entry::Child(Child { squid, interactable: true, .. })) => {
+                let squid = *squid;
+
+                if !modifiers.shift() {
+                    app.selections.clear();
+                }
+
+                app.selections.push(Selection {
+                    squid_id: squid,
+                    limb_id: None,
+                });
+
+                if self.is_double_click(ClickTarget::Squid(squid)) {
+                    if let Some(existing) = app.ocean.get(squid) {
+                        self.renaming = Some(Renaming {
+                            squid,
+                            text: existing.get_name().into(),
+                        });
+                    }
+                }
+            }
+            Some(Entry::LayerControls(LayerControls { layer_index, .. })) => {
+                if let Some(control) = Self::get_clicked_control(position, &panel) {
+                    Self::toggle_control(app, *layer_index, control);
+                }
+            }
+            Some(Entry::LayerName(LayerName { layer_index, .. })) => {
+                let layer_index = *layer_index;
+
+                if position.x < panel.min_x + Self::LEFT_MARGIN + Self::TAG_HIT_WIDTH {
+                    if let Some(layer) = app.ocean.get_layer(layer_index) {
+                        app.ocean.set_layer_tag(layer_index, layer.get_tag().next());
+                    }
+                } else if modifiers.shift() {
+                    // Move selected squids into the layer
+                    for selection in &app.selections {
+                        app.ocean.move_to_layer(selection.squid_id, layer_index);
+                    }
+                } else {
+                    app.ocean.set_current_layer(layer_index);
+
+                    if self.is_double_click(ClickTarget::Layer(layer_index)) {
+                        Self::ask_rename_layer(app, layer_index);
+                    }
+                }
+            }
+            Some(Entry::NewLayer(_)) => {
+                let name = format!("Layer {}", app.ocean.get_layers().len() + 1);
+                app.ocean.add_layer(Layer::new(name));
+            }
+            Some(Entry::Child(_)) => (),
+            Some(Entry::Filter(_)) => self.editing_filter = true,
+            None => (),
+        }
+    }
+
+    fn ask_rename_layer(app: &mut App, layer_index: usize) {
+        let name = match app.ocean.get_layer(layer_index) {
+            Some(layer) => layer.get_name().to_string(),
+            None => return,
+        };
+
+        app.show_modal(Modal::input(
+            "Rename Layer",
+            &format!("New name for '{}'", name),
+            &name,
+            Box::new(move |app, name| {
+                if !name.is_empty() {
+                    app.ocean.set_layer_name(layer_index, name);
+                    app.add_history_marker();
+                }
+            }),
+        ));
+    }
+
+    fn render_layer_name(ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, layer_name: &LayerName, panel: &AABB) {
+        let left = panel.min_x + Self::LEFT_MARGIN;
+
+        // Highlight the layer that new squids will be inserted into
+        if layer_name.is_current {
+            ctx.ribbon_mesh.render(
+                ctx,
+                glm::vec2(panel.min_x, layer_name.y - 0.5 * Self::SMALL_STRIP_HEIGHT - 4.0),
+                glm::vec2(panel.width(), Self::SMALL_STRIP_HEIGHT),
+                &ctx.color_scheme.light_ribbon,
+            );
+        }
+
+        // Draw color tag
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(left, layer_name.y - Self::TAG_SIZE - 1.0),
+            glm::vec2(Self::TAG_SIZE, Self::TAG_SIZE),
+            &layer_name.tag.get_color(),
+        );
+
+        // Draw layer name
+        draw_text(
+            text_system,
+            font,
+            &layer_name.name,
+            &glm::vec2(left + Self::TAG_HIT_WIDTH, layer_name.y),
+            ctx,
+            if layer_name.is_current {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.muted
+            },
+        );
+    }
+
+    // Selects and frames the highest squid that matches the filter
+    fn jump_to_first_match(&self, app: &mut App) {
+        let first = self.entries.iter().find_map(|entry| match entry {
+            Entry::Child(Child { squid, interactable: true, .. }) => Some(*squid),
+            _ => None,
+        });
+
+        if let Some(squid) = first.filter(|_| !self.filter.trim().is_empty()) {
+            app.jump_to(squid);
+        }
+    }
+
+    fn render_filter(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, filter: &Filter, left: f32) {
+        if self.editing_filter || !self.filter.is_empty() {
+            ctx.ribbon_mesh.render(
+                ctx,
+                glm::vec2(left - 4.0, filter.y - 0.5 * Self::SMALL_STRIP_HEIGHT - 4.0),
+                glm::vec2(ctx.width - left - Self::LEFT_MARGIN, Self::SMALL_STRIP_HEIGHT - 2.0),
+                &ctx.color_scheme.input,
+            );
+        }
+
+        let (text, color) = if self.editing_filter {
+            (format!("{}|", self.filter), ctx.color_scheme.text)
+        } else if self.filter.is_empty() {
+            ("Find by name or tag".to_string(), ctx.color_scheme.muted)
+        } else {
+            (self.filter.clone(), ctx.color_scheme.text)
+        };
+
+        draw_text(text_system, font, &text, &glm::vec2(left, filter.y), ctx, color);
+    }
+
+    fn render_layer_controls(ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, controls: &LayerControls, left: f32) {
+        for (i, control) in LayerControl::ALL.iter().enumerate() {
+            let (label, active) = match control {
+                LayerControl::Visibility => (if controls.hidden { "Hidden" } else { "Visible" }.to_string(), controls.hidden),
+                LayerControl::Lock => (if controls.locked { "Locked" } else { "Unlocked" }.to_string(), controls.locked),
+                LayerControl::Opacity => (format!("{:.0}%", controls.opacity * 100.0), controls.opacity < 1.0),
+                LayerControl::Blur if controls.blur > 0.0 => (format!("Blur {}", controls.blur), true),
+                LayerControl::Blur => ("Sharp".to_string(), false),
+            };
+
+            let color = if active { ctx.color_scheme.foreground } else { ctx.color_scheme.label };
+
+            draw_text(
+                text_system,
+                font.clone(),
+                &label,
+                &glm::vec2(left + i as f32 * Self::CONTROL_WIDTH, controls.y),
+                ctx,
+                color,
+            );
+        }
+    }
+
+    fn get_clicked_control(mouse: &glm::Vec2, panel: &AABB) -> Option<LayerControl> {
+        let left = panel.min_x + Self::LEFT_MARGIN;
+        let index = ((mouse.x - left) / Self::CONTROL_WIDTH).floor();
+
+        if index < 0.0 {
+            return None;
+        }
+
+        LayerControl::ALL.get(index as usize).copied()
+    }
+
+    fn next_opacity(opacity: f32) -> f32 {
+        Self::OPACITY_STEPS
+            .iter()
+            .position(|step| *step < opacity - 0.01)
+            .map(|index| Self::OPACITY_STEPS[index])
+            .unwrap_or(Self::OPACITY_STEPS[0])
+    }
+
+    // Steps up through the blur sizes, going back to no blur after the largest
+    fn next_blur(blur: f32) -> f32 {
+        Self::BLUR_STEPS.iter().copied().find(|step| *step > blur + 0.01).unwrap_or(Self::BLUR_STEPS[0])
+    }
+
+    fn toggle_control(app: &mut App, layer_index: usize, control: LayerControl) {
+        if let Some(layer) = app.ocean.get_layer(layer_index) {
+            match control {
+                LayerControl::Visibility => app.ocean.set_layer_hidden(layer_index, !layer.is_hidden()),
+                LayerControl::Lock => app.ocean.set_layer_locked(layer_index, !layer.is_locked()),
+                LayerControl::Opacity => app.ocean.set_layer_opacity(layer_index, Self::next_opacity(layer.get_opacity())),
+                LayerControl::Blur => app.ocean.set_layer_blur(layer_index, Self::next_blur(layer.get_blur())),
+            }
+        }
+
+        // Squids that can no longer be interacted with shouldn't stay selected
+        let ocean = &app.ocean;
+        app.selections.retain(|selection| ocean.is_interactable(selection.squid_id));
+    }
 }
 
 impl Tab for Layers {
@@ -92,23 +472,75 @@ impl Tab for Layers {
                 position,
                 ..
             }) => {
-                if position.x >= app.dimensions.x - Layers::TAB_WIDTH {
-                    let clicked: Option<&Entry> = self.get_clicked_entry(&position, &app.dimensions);
+                self.dragging_scrollbar = false;
+                self.editing_filter = false;
+                self.finish_renaming(app);
 
-                    match clicked {
-                        Some(Entry::Child(Child { squid, .. })) => {
-                            if !modifiers.shift() {
-                                app.selections.clear();
-                            }
+                if position.x >= app.dimensions.x - Self::SCROLLBAR_HIT_WIDTH && self.get_scrollbar(app.dimensions.y).is_some() {
+                    self.dragging_scrollbar = true;
+                    self.scroll_to_scrollbar_position(position.y, app.dimensions.y);
+                    return Capture::AllowDrag;
+                }
+
+                if position.x >= app.toolbox.get_options_area(&app.dimensions).min_x {
+                    self.click_entry(app, &position, modifiers);
+                }
+            }
+            Interaction::Drag(DragInteraction { current, .. }) => {
+                if self.dragging_scrollbar {
+                    self.scroll_to_scrollbar_position(current.y, app.dimensions.y);
+                }
+            }
+            Interaction::Scroll(ScrollInteraction { delta, .. }) => {
+                self.set_scroll(self.scroll - delta.y, app.dimensions.y);
+            }
+            Interaction::Character(CharacterInteraction { character }) => {
+                if self.editing_filter {
+                    if !character.is_control() {
+                        self.filter.push(character);
+                    }
+                    return Capture::Keyboard(KeyCapture::Capture);
+                }
 
-                            app.selections.push(Selection {
-                                squid_id: *squid,
-                                limb_id: None,
-                            });
+                if let Some(renaming) = &mut self.renaming {
+                    if !character.is_control() {
+                        renaming.text.push(character);
+                    }
+                    return Capture::Keyboard(KeyCapture::Capture);
+                }
+            }
+            Interaction::Key(KeyInteraction { virtual_keycode }) => {
+                if self.editing_filter {
+                    match virtual_keycode {
+                        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                            self.editing_filter = false;
+                            self.jump_to_first_match(app);
+                        }
+                        VirtualKeyCode::Escape => {
+                            self.filter.clear();
+                            self.editing_filter = false;
                         }
-                        Some(Entry::LayerName(_)) => (),
-                        None => (),
+                        VirtualKeyCode::Back => {
+                            self.filter.pop();
+                        }
+                        _ => (),
                     }
+                    return Capture::Keyboard(KeyCapture::Capture);
+                }
+
+                if let Some(renaming) = &mut self.renaming {
+                    match virtual_keycode {
+                        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                            self.finish_renaming(app);
+                            app.add_history_marker();
+                        }
+                        VirtualKeyCode::Escape => self.renaming = None,
+                        VirtualKeyCode::Back => {
+                            renaming.text.pop();
+                        }
+                        _ => (),
+                    }
+                    return Capture::Keyboard(KeyCapture::Capture);
                 }
             }
             _ => (),
@@ -117,62 +549,98 @@ impl Tab for Layers {
         Capture::Miss
     }
 
-    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &mut Ocean, selections: &[Selection]) {
-        self.update(ocean.get_layers());
+    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &Ocean, selections: &[Selection], panel: &AABB) {
+        // Keep scroll in range as the window or the document changes size
+        self.set_scroll(self.scroll, ctx.height);
+        self.update(ocean);
 
-        const LEFT_MARGIN: f32 = 16.0;
+        ctx.clip = Some(AABB::new(panel.min_x, Self::CONTENT_TOP, panel.width(), ctx.height - Self::CONTENT_TOP));
 
-        let left = ctx.width - Self::TAB_WIDTH + LEFT_MARGIN;
+        let left = panel.min_x + Self::LEFT_MARGIN;
 
         for entry in &self.entries {
             match entry {
-                Entry::LayerName(layer_name) => {
-                    // Draw layer name
+                Entry::Filter(filter) => self.render_filter(ctx, text_system, font.clone(), filter, left),
+                Entry::LayerName(layer_name) => Self::render_layer_name(ctx, text_system, font.clone(), layer_name, panel),
+                Entry::NewLayer(new_layer) => {
                     draw_text(
-                        &mut None,
                         text_system,
                         font.clone(),
-                        &layer_name.name,
-                        &glm::vec2(left, layer_name.y),
+                        "+ New Layer",
+                        &glm::vec2(left, new_layer.y),
                         ctx,
-                        Color::from_hex("#555555"),
+                        ctx.color_scheme.muted,
                     );
                 }
+                Entry::LayerControls(controls) => Self::render_layer_controls(ctx, text_system, font.clone(), controls, left),
                 Entry::Child(child) => {
-                    if let Some(squid) = ocean.get_mut(child.squid) {
+                    if let Some(squid) = ocean.get(child.squid) {
                         const PREVIEW_PADDING: f32 = 4.0;
                         const PREVIEW_RADIUS: f32 = 8.0;
                         const PREVIEW_SIZE_WITH_PADDING: f32 = 2.0 * PREVIEW_PADDING + 2.0 * PREVIEW_RADIUS;
 
                         // Draw squid preview
-                        squid.render(
+                        self.thumbnails.render(
                             ctx,
-                            Some(PreviewParams {
-                                position: glm::vec2(left + PREVIEW_PADDING, child.y - PREVIEW_PADDING),
-                                radius: PREVIEW_RADIUS,
-                            }),
+                            child.squid,
+                            squid,
+                            &glm::vec2(left + PREVIEW_PADDING, child.y - PREVIEW_PADDING),
+                            PREVIEW_RADIUS,
+                            child.opacity,
                         );
 
                         // Choose text color
                         let color = if selection_contains(selections, child.squid) {
                             ctx.color_scheme.foreground
                         } else {
-                            Color::from_hex("#777777")
+                            ctx.color_scheme.label
                         };
 
-                        // Draw squid name
-                        draw_text(
-                            &mut None,
-                            text_system,
-                            font.clone(),
-                            squid.get_name(),
-                            &glm::vec2(left + PREVIEW_SIZE_WITH_PADDING, child.y),
-                            ctx,
-                            color,
-                        );
+                        let name_position = glm::vec2(left + PREVIEW_SIZE_WITH_PADDING, child.y);
+
+                        match &self.renaming {
+                            Some(renaming) if renaming.squid == child.squid => {
+                                // Draw inline name editor
+                                ctx.ribbon_mesh.render(
+                                    ctx,
+                                    name_position - glm::vec2(4.0, 0.5 * Self::SMALL_STRIP_HEIGHT + 4.0),
+                                    glm::vec2(ctx.width - name_position.x - Self::LEFT_MARGIN, Self::SMALL_STRIP_HEIGHT - 2.0),
+                                    &ctx.color_scheme.input,
+                                );
+
+                                draw_text(
+                                    text_system,
+                                    font.clone(),
+                                    &format!("{}|", renaming.text),
+                                    &name_position,
+                                    ctx,
+                                    ctx.color_scheme.text,
+                                );
+                            }
+                            _ => {
+                                // Draw squid name
+                                draw_text(text_system, font.clone(), squid.get_name(), &name_position, ctx, color);
+                            }
+                        }
                     }
                 }
             }
         }
+
+        self.thumbnails.forget_unused();
+
+        // Clipped text has to be drawn before the scrollbar goes on top of it
+        ctx.flush_text(text_system);
+        ctx.clip = None;
+
+        // Draw scrollbar
+        if let Some((top, length)) = self.get_scrollbar(ctx.height) {
+            ctx.ribbon_mesh.render(
+                ctx,
+                glm::vec2(ctx.width - 2.0 * Self::SCROLLBAR_WIDTH, top),
+                glm::vec2(Self::SCROLLBAR_WIDTH, length),
+                &ctx.color_scheme.muted,
+            );
+        }
     }
 }
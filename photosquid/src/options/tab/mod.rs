@@ -1,19 +1,29 @@
+pub mod export;
 pub mod layers;
 pub mod object;
+pub mod preferences;
 
-use crate::{app::App, capture::Capture, interaction::Interaction, ocean::Ocean, render_ctx::RenderCtx, selection::Selection};
+use crate::{aabb::AABB, app::App, capture::Capture, interaction::Interaction, ocean::Ocean, render_ctx::RenderCtx, selection::Selection};
 
 use glium_text_rusttype::{FontTexture, TextSystem};
 use slotmap::new_key_type;
 use std::rc::Rc;
 
+pub use export::Export;
 pub use layers::Layers;
 pub use object::Object;
+pub use preferences::Preferences;
 
 new_key_type! { pub struct TabRef; }
 
 pub trait Tab {
     fn interact(&mut self, interaction: Interaction, app: &mut App) -> Capture;
 
-    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &mut Ocean, selections: &[Selection]);
+    // Draws the tab within the options panel, which is 'panel'
+    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &Ocean, selections: &[Selection], panel: &AABB);
+
+    // Whether anything in the tab is still animating, so the panel has to keep being redrawn
+    fn is_animating(&self) -> bool {
+        false
+    }
 }
@@ -1,20 +1,674 @@
 use super::Tab;
-use crate::{app::App, capture::Capture, interaction::Interaction, ocean::Ocean, render_ctx::RenderCtx, selection::Selection};
+use crate::{
+    aabb::AABB,
+    app::App,
+    capture::{Capture, KeyCapture},
+    command::DocumentCommand,
+    draw_text::{draw_text, draw_text_centered},
+    interaction::{CharacterInteraction, ClickInteraction, Interaction, KeyInteraction},
+    ocean::Ocean,
+    options::{
+        color_picker::ColorTarget,
+        section::{self, Section},
+    },
+    preferences,
+    render_ctx::RenderCtx,
+    selection::Selection,
+    squid::{SquidProperty, SquidRef},
+    style::{ColorStyle, StyleRef},
+    user_input::{step_focus, Button, Checkbox, ColorWell, Dial, Slider, TextInput},
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
 use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
 use std::rc::Rc;
 
-pub struct Object {}
+// Order of the sections
+const FILL: usize = 0;
+const STYLES: usize = 1;
+const TRANSFORM: usize = 2;
+const ARRANGE: usize = 3;
+
+pub struct Object {
+    target: Option<SquidRef>,
+    fields: Vec<PropertyField>,
+
+    // Turns the target when it can be rotated, placed after the fields
+    rotation_dial: Option<Dial>,
+
+    // Keeps every corner radius the same when the target has rounded corners, placed after the dial
+    corners_link: Option<Checkbox>,
+
+    // Rounds the corners of triangles and paths, placed after the rest
+    corner_slider: Option<Slider>,
+
+    // Edits the color of the style the target is linked to
+    style_well: Option<ColorWell>,
+
+    sections: [Section; 4],
+    style_buttons: Vec<Button>,
+    arrange_buttons: Vec<Button>,
+}
+
+struct PropertyField {
+    property: SquidProperty,
+    input: TextInput,
+}
 
 impl Object {
-    pub fn new() -> Self {
-        Self {}
+    pub const FILL_SECTION: &'static str = "Fill";
+
+    const SECTIONS_TOP: f32 = 64.0;
+
+    // The fill section has the color picker, and the styles section has
+    // which style is linked followed by the style chips and buttons
+    const FILL_HEIGHT: f32 = 444.0;
+    const CHIPS_OFFSET: f32 = 32.0;
+
+    const FIELDS_OFFSET: f32 = 40.0;
+    const BUTTONS_OFFSET: f32 = 8.0;
+    const BUTTON_SPACING: f32 = 44.0;
+    const FIELD_WIDTH: f32 = 104.0;
+    const COLUMN_SPACING: f32 = 120.0;
+    const ROW_SPACING: f32 = 60.0;
+    const CHIP_SIZE: f32 = 16.0;
+    const CHIP_SPACING: f32 = 19.0;
+    const MAX_CORNER_RADIUS: f32 = 100.0;
+
+    pub fn new(preferences: &preferences::Preferences) -> Self {
+        let section = |name| Section::new(name, preferences.is_section_expanded(name));
+        let button = |label: &str, command: DocumentCommand| Button::new(label.into(), Box::new(move |app: &mut App| app.emit(command.clone())));
+
+        Self {
+            target: None,
+            fields: vec![],
+            rotation_dial: None,
+            corners_link: None,
+            corner_slider: None,
+            style_well: None,
+            sections: [section(Self::FILL_SECTION), section("Styles"), section("Transform"), section("Arrange")],
+            style_buttons: vec![
+                button("New Style", DocumentCommand::SaveSelectedStyle),
+                button("Update Style", DocumentCommand::UpdateSelectedStyle),
+            ],
+            arrange_buttons: vec![
+                button("To Front", DocumentCommand::ReorderSelected(0)),
+                button("Forward", DocumentCommand::ShiftSelectedWithinLayer(-1)),
+                button("Backward", DocumentCommand::ShiftSelectedWithinLayer(1)),
+                button("To Back", DocumentCommand::ReorderSelected(usize::MAX)),
+            ],
+        }
+    }
+
+    fn get_section_tops(&self) -> Vec<f32> {
+        let cells = self.get_corner_slider_index() + usize::from(self.corner_slider.is_some());
+        let transform_height = if cells == 0 {
+            0.0
+        } else {
+            Self::FIELDS_OFFSET + cells.div_ceil(2) as f32 * Self::ROW_SPACING
+        };
+        let styles_height = Self::CHIPS_OFFSET + Self::CHIP_SIZE + Self::get_buttons_height(&self.style_buttons);
+        let arrange_height = Self::get_buttons_height(&self.arrange_buttons);
+
+        section::stack(
+            &self.sections,
+            &[Self::FILL_HEIGHT, styles_height, transform_height, arrange_height],
+            Self::SECTIONS_TOP,
+        )
+    }
+
+    fn get_buttons_height(buttons: &[Button]) -> f32 {
+        Self::BUTTONS_OFFSET + buttons.len().div_ceil(2) as f32 * Self::BUTTON_SPACING
+    }
+
+    // Where a section's contents start, if it's expanded
+    fn get_content_top(&self, index: usize) -> Option<f32> {
+        self.sections[index].is_expanded().then(|| self.get_section_tops()[index] + Section::HEIGHT)
+    }
+
+    fn get_styles_top(&self) -> Option<f32> {
+        self.get_content_top(STYLES).map(|top| top + Self::CHIPS_OFFSET)
+    }
+
+    fn get_field_area(i: usize, top: f32, panel: &AABB) -> AABB {
+        let x = panel.min_x + 16.0 + (i % 2) as f32 * Self::COLUMN_SPACING;
+        let y = top + Self::FIELDS_OFFSET + (i / 2) as f32 * Self::ROW_SPACING;
+        let standard = TextInput::standard_area(&glm::vec2(x, y));
+        AABB::new(standard.min_x, standard.min_y, Self::FIELD_WIDTH, standard.height())
+    }
+
+    fn get_style_well_area(top: f32, panel: &AABB) -> AABB {
+        AABB::new(panel.max_x - 16.0 - 32.0, top - 26.0, 32.0, 16.0)
+    }
+
+    // Style chips are followed by one more chip for creating a new style
+    fn get_style_chip_area(i: usize, top: f32, panel: &AABB) -> AABB {
+        let x = panel.min_x + 16.0 + i as f32 * Self::CHIP_SPACING;
+        AABB::new(x, top, Self::CHIP_SIZE, Self::CHIP_SIZE)
+    }
+
+    fn get_button_area(i: usize, top: f32, panel: &AABB) -> AABB {
+        let x = panel.min_x + 16.0 + (i % 2) as f32 * Self::COLUMN_SPACING;
+        let y = top + Self::BUTTONS_OFFSET + (i / 2) as f32 * Self::BUTTON_SPACING;
+        let standard = TextInput::standard_area(&glm::vec2(x, y));
+        AABB::new(standard.min_x, standard.min_y, Self::FIELD_WIDTH, standard.height())
+    }
+
+    // Only a single selected squid has its properties shown
+    fn get_target(selections: &[Selection]) -> Option<SquidRef> {
+        match selections {
+            [selection] => Some(selection.squid_id),
+            _ => None,
+        }
+    }
+
+    fn retarget(&mut self, target: Option<SquidRef>, ocean: &Ocean) {
+        self.target = target;
+        self.fields = target
+            .and_then(|reference| ocean.get(reference))
+            .map(|squid| {
+                squid
+                    .get_properties()
+                    .iter()
+                    .map(|property| PropertyField {
+                        property: *property,
+                        input: if property.is_length() {
+                            TextInput::length(0.0, property.get_label().into())
+                        } else {
+                            TextInput::new("0".into(), property.get_label().into(), property.get_suffix().into())
+                        },
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let rotation = target.and_then(|reference| ocean.get(reference)?.get_property(SquidProperty::Rotation));
+        self.rotation_dial = rotation.map(|degrees| Dial::new(degrees, "".into()));
+
+        let radii: Vec<f32> = target
+            .and_then(|reference| ocean.get(reference))
+            .map(|squid| SquidProperty::CORNERS.iter().filter_map(|property| squid.get_property(*property)).collect())
+            .unwrap_or_default();
+
+        self.corners_link = (!radii.is_empty()).then(|| Checkbox::new("Link Corners".into(), radii.windows(2).all(|pair| pair[0] == pair[1])));
+
+        let corner_radius = target.and_then(|reference| ocean.get(reference)?.get_property(SquidProperty::CornerRadius));
+        self.corner_slider = corner_radius.map(|radius| Slider::new(radius, 0.0, Self::MAX_CORNER_RADIUS, 1.0, "Corner Radius".into(), "".into()));
+        self.style_well = None;
+    }
+
+    // Keeps the style well showing the style the target is linked to
+    fn sync_style_well(&mut self, ocean: &Ocean) {
+        let linked = self.target.and_then(|reference| ocean.get(reference)?.get_style());
+
+        let (style, color) = match linked.and_then(|style| Some((style, ocean.get_style(style)?.color))) {
+            Some(linked) => linked,
+            None => {
+                self.style_well = None;
+                return;
+            }
+        };
+
+        let well = self
+            .style_well
+            .get_or_insert_with(|| ColorWell::new("".into(), color, ColorTarget::Style(style)));
+
+        if well.get_target() != ColorTarget::Style(style) {
+            *well = ColorWell::new("".into(), color, ColorTarget::Style(style));
+        }
+
+        well.set_color(color);
+    }
+
+    fn get_dial_area(&self, top: f32, panel: &AABB) -> AABB {
+        Self::get_field_area(self.fields.len(), top, panel)
+    }
+
+    fn get_corners_link_area(&self, top: f32, panel: &AABB) -> AABB {
+        Self::get_field_area(self.fields.len() + usize::from(self.rotation_dial.is_some()), top, panel)
+    }
+
+    fn get_corner_slider_index(&self) -> usize {
+        self.fields.len() + usize::from(self.rotation_dial.is_some()) + usize::from(self.corners_link.is_some())
+    }
+
+    fn get_corner_slider_area(&self, top: f32, panel: &AABB) -> AABB {
+        Self::get_field_area(self.get_corner_slider_index(), top, panel)
+    }
+
+    // Clicking a section's header expands or collapses it, which is remembered for next time
+    fn interact_sections(&mut self, interaction: &Interaction, app: &mut App) -> Capture {
+        let (button, position) = match interaction {
+            Interaction::Click(ClickInteraction { button, position, .. }) => (*button, *position),
+            _ => return Capture::Miss,
+        };
+
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+        let tops = self.get_section_tops();
+
+        let clicked = match (0..self.sections.len()).find(|i| self.sections[*i].click(button, &position, tops[*i], &panel)) {
+            Some(clicked) => clicked,
+            None => return Capture::Miss,
+        };
+
+        let section = &self.sections[clicked];
+        app.preferences.set_section_expanded(section.get_name(), section.is_expanded());
+
+        match clicked {
+            FILL => app.toolbox.color_picker.set_shown(section.is_expanded()),
+            TRANSFORM => self.fields.iter_mut().for_each(|field| field.input.unfocus()),
+            _ => (),
+        }
+
+        Capture::NoDrag
+    }
+
+    fn interact_styles(styles_top: f32, interaction: &Interaction, app: &mut App) -> Capture {
+        let position = match interaction {
+            Interaction::Click(ClickInteraction {
+                button: MouseButton::Left,
+                position,
+                ..
+            }) => *position,
+            _ => return Capture::Miss,
+        };
+
+        let styles: Vec<StyleRef> = app.ocean.get_styles().map(|(reference, _)| reference).collect();
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+
+        let clicked = (0..=styles.len()).find(|i| Self::get_style_chip_area(*i, styles_top, &panel).intersecting_point(position.x, position.y));
+
+        let squids: Vec<SquidRef> = app
+            .selections
+            .iter()
+            .filter(|selection| selection.limb_id.is_none())
+            .map(|selection| selection.squid_id)
+            .collect();
+
+        let style = match clicked {
+            Some(i) if i < styles.len() => styles[i],
+            Some(_) => {
+                let name = format!("Style {}", styles.len() + 1);
+                app.ocean.add_style(ColorStyle::new(name, app.toolbox.color_picker.calculate_color()))
+            }
+            None => return Capture::Miss,
+        };
+
+        // Clicking the style that everything already uses unlinks it instead
+        let is_unlinking = !squids.is_empty()
+            && squids
+                .iter()
+                .all(|reference| app.ocean.get(*reference).is_some_and(|squid| squid.get_style() == Some(style)));
+
+        for reference in squids {
+            app.ocean.set_squid_style(reference, (!is_unlinking).then_some(style));
+        }
+
+        if let Some(style) = app.ocean.get_style(style) {
+            app.toolbox.color_picker.set_selected_color_no_notif(style.color);
+        }
+
+        app.add_history_marker();
+        Capture::NoDrag
+    }
+
+    // Only what's in expanded sections can be clicked
+    fn click(&mut self, button: MouseButton, position: &glm::Vec2, app: &mut App) -> Capture {
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+        let mut capture = Capture::Miss;
+
+        if let Some(top) = self.get_content_top(TRANSFORM) {
+            let dial_area = self.get_dial_area(top, &panel);
+            let corners_link_area = self.get_corners_link_area(top, &panel);
+            let corner_slider_area = self.get_corner_slider_area(top, &panel);
+
+            for (i, field) in self.fields.iter_mut().enumerate() {
+                if field.input.click(button, position, &Self::get_field_area(i, top, &panel)) == Capture::TakeFocus {
+                    capture = Capture::TakeFocus;
+                }
+            }
+
+            if let Some(dial) = self.rotation_dial.as_mut() {
+                if dial.click(button, position, &dial_area) == Capture::TakeFocus {
+                    capture = Capture::TakeFocus;
+                }
+            }
+
+            if let Some(corners_link) = self.corners_link.as_mut() {
+                if corners_link.click(button, position, &corners_link_area) == Capture::TakeFocus {
+                    capture = Capture::TakeFocus;
+                }
+            }
+
+            if let Some(corner_slider) = self.corner_slider.as_mut() {
+                if corner_slider.click(button, position, &corner_slider_area) == Capture::TakeFocus {
+                    capture = Capture::TakeFocus;
+                }
+            }
+        }
+
+        if let (Some(top), Some(well)) = (self.get_styles_top(), self.style_well.as_mut()) {
+            if well.click(button, position, &Self::get_style_well_area(top, &panel), app) == Capture::TakeFocus {
+                capture = Capture::TakeFocus;
+            }
+        }
+
+        if let Some(top) = self.get_styles_top() {
+            for (i, style_button) in self.style_buttons.iter_mut().enumerate() {
+                if style_button.click(button, position, &Self::get_button_area(i, top + Self::CHIP_SIZE, &panel), app) == Capture::TakeFocus {
+                    return Capture::NoDrag;
+                }
+            }
+        }
+
+        if let Some(top) = self.get_content_top(ARRANGE) {
+            for (i, arrange_button) in self.arrange_buttons.iter_mut().enumerate() {
+                if arrange_button.click(button, position, &Self::get_button_area(i, top, &panel), app) == Capture::TakeFocus {
+                    return Capture::NoDrag;
+                }
+            }
+        }
+
+        capture
+    }
+
+    fn interact_fields(&mut self, interaction: Interaction, app: &mut App) -> Capture {
+        match interaction {
+            Interaction::Click(ClickInteraction { button, position, .. }) => self.click(button, &position, app),
+            Interaction::Drag(drag) => {
+                let panel = app.toolbox.get_options_area(&app.dimensions);
+                let top = self.get_content_top(TRANSFORM);
+                let dial_area = top.map(|top| self.get_dial_area(top, &panel));
+                let corner_slider_area = top.map(|top| self.get_corner_slider_area(top, &panel));
+
+                if let (Some(corner_slider), Some(corner_slider_area)) = (self.corner_slider.as_mut(), corner_slider_area) {
+                    if corner_slider.drag(&drag, &corner_slider_area) == Capture::AllowDrag {
+                        return Capture::AllowDrag;
+                    }
+                }
+
+                match (self.rotation_dial.as_mut(), dial_area) {
+                    (Some(dial), Some(dial_area)) => dial.drag(&drag, &dial_area),
+                    _ => Capture::Miss,
+                }
+            }
+            Interaction::MouseRelease(..) => {
+                // Turning the dial is undone all at once
+                if let Some(dial) = self.rotation_dial.as_mut().filter(|dial| dial.is_dragging()) {
+                    dial.release();
+                    app.add_history_marker();
+                }
+
+                // So is sliding the corner radius
+                if let Some(corner_slider) = self.corner_slider.as_mut().filter(|corner_slider| corner_slider.is_dragging()) {
+                    corner_slider.release();
+                    app.add_history_marker();
+                }
+
+                Capture::Miss
+            }
+            Interaction::Key(KeyInteraction { virtual_keycode }) => {
+                let shift = app.keys_held.contains(&VirtualKeyCode::LShift);
+
+                if let Some(capture) = self.navigate(virtual_keycode, shift) {
+                    return capture;
+                }
+
+                // The color picker is part of the object options too
+                if let Some(key_capture) = app.toolbox.color_picker.key_press(virtual_keycode, app.modifiers_held).to_option() {
+                    return Capture::Keyboard(key_capture);
+                }
+
+                self.fields
+                    .iter_mut()
+                    .find_map(|field| field.input.key_press(virtual_keycode, app.modifiers_held).to_option())
+                    .map_or(Capture::Miss, Capture::Keyboard)
+            }
+            Interaction::Character(CharacterInteraction { character }) => app
+                .toolbox
+                .color_picker
+                .receive_character(character)
+                .to_option()
+                .or_else(|| self.fields.iter_mut().find_map(|field| field.input.receive_character(character).to_option()))
+                .map_or(Capture::Miss, Capture::Keyboard),
+            _ => Capture::Miss,
+        }
+    }
+
+    // Tab/Shift+Tab moves between property fields while one is being edited,
+    // and F6 moves keyboard focus into or out of them
+    fn navigate(&mut self, virtual_keycode: VirtualKeyCode, shift: bool) -> Option<Capture> {
+        if !self.sections[TRANSFORM].is_expanded() {
+            return None;
+        }
+
+        let focused = self.fields.iter().position(|field| field.input.is_focused());
+
+        let next = match virtual_keycode {
+            VirtualKeyCode::Tab if focused.is_some() => step_focus(focused, self.fields.len(), shift),
+            VirtualKeyCode::F6 if focused.is_none() => step_focus(None, self.fields.len(), false),
+            VirtualKeyCode::F6 => None,
+            _ => return None,
+        };
+
+        for field in &mut self.fields {
+            field.input.unfocus();
+        }
+
+        self.fields.get_mut(next?)?.input.focus();
+        Some(Capture::Keyboard(KeyCapture::Capture))
+    }
+
+    // Applies any newly entered values back to the target squid
+    fn apply_fields(&mut self, app: &mut App) {
+        let mut changed = false;
+
+        if let Some(squid) = self.target.and_then(|reference| app.ocean.get_mut(reference)) {
+            let linked = self.corners_link.as_ref().is_some_and(Checkbox::checked);
+
+            for field in &mut self.fields {
+                if let Some(value) = field.input.poll_value() {
+                    // Linked corners all take on whichever radius was entered
+                    let properties: &[SquidProperty] = if linked && field.property.get_corner().is_some() {
+                        &SquidProperty::CORNERS
+                    } else {
+                        std::slice::from_ref(&field.property)
+                    };
+
+                    for property in properties {
+                        squid.set_property(*property, value);
+                    }
+
+                    changed = true;
+                }
+            }
+
+            // Linking evens out the corners to match the top left one
+            if let Some(corners_link) = self.corners_link.as_mut() {
+                if corners_link.poll() == Some(true) {
+                    if let Some(radius) = squid.get_property(SquidProperty::TopLeftRadius) {
+                        for property in SquidProperty::CORNERS {
+                            squid.set_property(property, radius);
+                        }
+
+                        changed = true;
+                    }
+                }
+            }
+
+            if let Some(dial) = self.rotation_dial.as_mut() {
+                if let Some(degrees) = dial.poll() {
+                    squid.set_property(SquidProperty::Rotation, degrees);
+                    changed |= !dial.is_dragging();
+                }
+            }
+
+            if let Some(corner_slider) = self.corner_slider.as_mut() {
+                if let Some(radius) = corner_slider.poll() {
+                    squid.set_property(SquidProperty::CornerRadius, radius);
+                    changed |= !corner_slider.is_dragging();
+                }
+            }
+        }
+
+        if changed {
+            app.add_history_marker();
+        }
+    }
+
+    fn render_styles(ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &Ocean, target: Option<SquidRef>, top: f32, panel: &AABB) {
+        let linked = target.and_then(|reference| ocean.get(reference)).and_then(|squid| squid.get_style());
+        let linked_name = linked.and_then(|style| ocean.get_style(style)).map_or("None", |style| style.name.as_str());
+
+        draw_text(
+            text_system,
+            font.clone(),
+            &format!("Style: {}", linked_name),
+            &glm::vec2(panel.min_x + 16.0, top - 8.0),
+            ctx,
+            ctx.color_scheme.label,
+        );
+
+        let mut count = 0;
+
+        for (i, (reference, style)) in ocean.get_styles().enumerate() {
+            let area = Self::get_style_chip_area(i, top, panel);
+
+            // Outline the style used by the target
+            if linked == Some(reference) {
+                ctx.ribbon_mesh.render(
+                    ctx,
+                    glm::vec2(area.min_x - 2.0, area.min_y - 2.0),
+                    glm::vec2(area.width() + 4.0, area.height() + 4.0),
+                    &ctx.color_scheme.foreground,
+                );
+            }
+
+            ctx.ribbon_mesh
+                .render(ctx, glm::vec2(area.min_x, area.min_y), glm::vec2(area.width(), area.height()), &style.color);
+            count += 1;
+        }
+
+        let area = Self::get_style_chip_area(count, top, panel);
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x, area.min_y),
+            glm::vec2(area.width(), area.height()),
+            &ctx.color_scheme.dark_ribbon,
+        );
+        draw_text_centered(
+            text_system,
+            font,
+            "+",
+            &glm::vec2(area.center_x(), area.max_y - 3.0),
+            ctx,
+            ctx.color_scheme.foreground,
+        );
     }
 }
 
 impl Tab for Object {
-    fn interact(&mut self, _interaction: Interaction, _app: &mut App) -> Capture {
-        Capture::Miss
+    fn interact(&mut self, interaction: Interaction, app: &mut App) -> Capture {
+        let target = Self::get_target(&app.selections);
+
+        if target != self.target {
+            self.retarget(target, &app.ocean);
+        }
+
+        if let capture @ Capture::NoDrag = self.interact_sections(&interaction, app) {
+            return capture;
+        }
+
+        if let Some(styles_top) = self.get_styles_top() {
+            if let capture @ Capture::NoDrag = Self::interact_styles(styles_top, &interaction, app) {
+                return capture;
+            }
+        }
+
+        self.sync_style_well(&app.ocean);
+
+        let capture = self.interact_fields(interaction, app);
+        self.apply_fields(app);
+
+        if let Some(well) = self.style_well.as_mut() {
+            well.set_active(app.toolbox.color_picker.get_target() == well.get_target());
+        }
+
+        capture
     }
 
-    fn render(&mut self, _ctx: &mut RenderCtx, _text_system: &TextSystem, _font: Rc<FontTexture>, _ocean: &mut Ocean, _selections: &[Selection]) {}
+    fn is_animating(&self) -> bool {
+        self.corners_link.as_ref().is_some_and(Checkbox::is_animating)
+    }
+
+    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, ocean: &Ocean, selections: &[Selection], panel: &AABB) {
+        let target = Self::get_target(selections);
+
+        if target != self.target {
+            self.retarget(target, ocean);
+        }
+
+        // Keep values in sync with the squid while they aren't being edited
+        if let Some(squid) = self.target.and_then(|reference| ocean.get(reference)) {
+            for field in self.fields.iter_mut().filter(|field| !field.input.is_focused()) {
+                if let Some(value) = squid.get_property(field.property) {
+                    field.input.set_value(value);
+                }
+            }
+
+            if let Some(dial) = self.rotation_dial.as_mut().filter(|dial| !dial.is_dragging()) {
+                dial.set(squid.get_property(SquidProperty::Rotation).unwrap_or_default());
+            }
+
+            if let Some(corner_slider) = self.corner_slider.as_mut().filter(|corner_slider| !corner_slider.is_dragging()) {
+                corner_slider.set(squid.get_property(SquidProperty::CornerRadius).unwrap_or_default());
+            }
+        }
+
+        self.sync_style_well(ocean);
+
+        for (section, top) in self.sections.iter().zip(self.get_section_tops()) {
+            section.render(ctx, text_system, font.clone(), top, panel);
+        }
+
+        if let Some(top) = self.get_styles_top() {
+            Self::render_styles(ctx, text_system, font.clone(), ocean, self.target, top, panel);
+
+            if let Some(well) = self.style_well.as_mut() {
+                well.render(ctx, text_system, font.clone(), &Self::get_style_well_area(top, panel));
+            }
+
+            for (i, style_button) in self.style_buttons.iter_mut().enumerate() {
+                style_button.render(ctx, text_system, font.clone(), &Self::get_button_area(i, top + Self::CHIP_SIZE, panel));
+            }
+        }
+
+        if let Some(top) = self.get_content_top(TRANSFORM) {
+            let dial_area = self.get_dial_area(top, panel);
+            let corners_link_area = self.get_corners_link_area(top, panel);
+            let corner_slider_area = self.get_corner_slider_area(top, panel);
+
+            for (i, field) in self.fields.iter_mut().enumerate() {
+                field.input.render(ctx, text_system, font.clone(), &Self::get_field_area(i, top, panel));
+            }
+
+            if let Some(dial) = self.rotation_dial.as_mut() {
+                dial.render(ctx, text_system, font.clone(), &dial_area);
+            }
+
+            if let Some(corners_link) = self.corners_link.as_mut() {
+                corners_link.render(ctx, text_system, font.clone(), &corners_link_area);
+            }
+
+            if let Some(corner_slider) = self.corner_slider.as_mut() {
+                corner_slider.render(ctx, text_system, font.clone(), &corner_slider_area);
+            }
+        }
+
+        if let Some(top) = self.get_content_top(ARRANGE) {
+            for (i, arrange_button) in self.arrange_buttons.iter_mut().enumerate() {
+                arrange_button.render(ctx, text_system, font.clone(), &Self::get_button_area(i, top, panel));
+            }
+        }
+    }
 }
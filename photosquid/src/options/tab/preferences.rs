@@ -0,0 +1,333 @@
+use super::Tab;
+use crate::{
+    aabb::AABB,
+    app::App,
+    capture::{Capture, KeyCapture},
+    draw_text::draw_text,
+    frame_pacer::FramePacer,
+    interaction::{CharacterInteraction, ClickInteraction, Interaction, KeyInteraction, ScrollInteraction},
+    ocean::Ocean,
+    preferences,
+    render_ctx::RenderCtx,
+    selection::Selection,
+    units::Unit,
+    user_input::{render_focus_marker, step_focus, Checkbox, Dropdown, TextInput, UserInput},
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Order of the user inputs
+const LIGHT_THEME: usize = 0;
+const AUTOSAVE_MINUTES: usize = 1;
+const UI_SCALE: usize = 2;
+const UNITS: usize = 3;
+const TRANSLATION_SNAPPING: usize = 4;
+const ROTATION_SNAPPING: usize = 5;
+const SNAP_TO_GRID: usize = 6;
+const SNAP_TO_OBJECTS: usize = 7;
+const RADIAL_MENU: usize = 8;
+const TARGET_FPS: usize = 9;
+const VSYNC: usize = 10;
+const MSAA: usize = 11;
+const SUPERSAMPLING: usize = 12;
+const RECT_WIDTH: usize = 13;
+const RECT_HEIGHT: usize = 14;
+const CIRCLE_RADIUS: usize = 15;
+
+pub struct Preferences {
+    user_inputs: Vec<UserInput>,
+    keyboard_focus: Option<usize>,
+    scroll: f32,
+}
+
+impl Preferences {
+    const CONTENT_TOP: f32 = 64.0;
+    const LEFT_MARGIN: f32 = 16.0;
+    const INPUTS_TOP: f32 = 96.0;
+    const INPUT_SPACING: f32 = 48.0;
+
+    pub fn new(preferences: &preferences::Preferences) -> Self {
+        let number = |value: f32, label: &str, suffix: &str| UserInput::TextInput(TextInput::new(value.to_string(), label.into(), suffix.into()));
+        let length = |value: f32, label: &str| UserInput::TextInput(TextInput::length(value, label.into()));
+        let units = Unit::ALL.iter().map(|unit| unit.get_suffix().into()).collect();
+        let unit_index = Unit::ALL.iter().position(|unit| *unit == preferences.unit).unwrap_or_default();
+
+        Self {
+            user_inputs: vec![
+                UserInput::Checkbox(Checkbox::new("Light Theme".into(), preferences.light_theme)),
+                number(preferences.autosave_minutes, "Autosave Interval", " minutes"),
+                number(preferences.ui_scale, "UI Scale", "x"),
+                UserInput::Dropdown(Dropdown::new("Units".into(), units, unit_index)),
+                number(preferences.translation_snapping, "Translation Snapping", ""),
+                number(preferences.rotation_snapping, "Rotation Snapping", " degrees"),
+                UserInput::Checkbox(Checkbox::new("Snap to Grid".into(), preferences.snap_to_grid)),
+                UserInput::Checkbox(Checkbox::new("Snap to Objects".into(), preferences.snap_to_objects)),
+                UserInput::Checkbox(Checkbox::new("Radial Menu".into(), preferences.radial_menu)),
+                number(preferences.target_fps, "Target Frame Rate", " fps"),
+                UserInput::Checkbox(Checkbox::new("VSync".into(), preferences.vsync)),
+                number(preferences.msaa.into(), "Multisampling", " samples"),
+                number(preferences.supersampling, "Supersampling", "x"),
+                length(preferences.rect_width, "Default Rect Width"),
+                length(preferences.rect_height, "Default Rect Height"),
+                length(preferences.circle_radius, "Default Circle Radius"),
+            ],
+            keyboard_focus: None,
+            scroll: 0.0,
+        }
+    }
+
+    fn get_input_area(i: usize, panel: &AABB, scroll: f32) -> AABB {
+        let y = Self::INPUTS_TOP + i as f32 * Self::INPUT_SPACING - scroll;
+        TextInput::standard_area(&glm::vec2(panel.min_x + Self::LEFT_MARGIN, y))
+    }
+
+    // Leaves room below the last input for the note about restarting
+    fn get_max_scroll(&self, window_height: f32) -> f32 {
+        (Self::INPUTS_TOP + (self.user_inputs.len() + 1) as f32 * Self::INPUT_SPACING - window_height).max(0.0)
+    }
+
+    fn set_scroll(&mut self, scroll: f32, window_height: f32) {
+        self.scroll = scroll.clamp(0.0, self.get_max_scroll(window_height));
+    }
+
+    // Scrolls just enough for an input (and its label) to be fully visible
+    fn scroll_into_view(&mut self, index: usize, window_height: f32) {
+        let top = Self::INPUTS_TOP + index as f32 * Self::INPUT_SPACING - Self::INPUT_SPACING;
+        let bottom = top + 2.0 * Self::INPUT_SPACING;
+        let visible_height = window_height - Self::CONTENT_TOP;
+
+        if top - Self::CONTENT_TOP < self.scroll {
+            self.set_scroll(top - Self::CONTENT_TOP, window_height);
+        } else if bottom - Self::CONTENT_TOP > self.scroll + visible_height {
+            self.set_scroll(bottom - Self::CONTENT_TOP - visible_height, window_height);
+        }
+    }
+
+    fn get_number(&mut self, index: usize) -> Option<f32> {
+        self.user_inputs[index].as_text_input_mut().unwrap().poll_value()
+    }
+
+    // Takes on any edited values, where the theme, UI scale, units, snapping, frame rate and render quality apply right away
+    // and vsync and the tool defaults apply the next time photosquid starts
+    fn poll(&mut self, app: &mut App) {
+        if let Some(light_theme) = self.user_inputs[LIGHT_THEME].as_checkbox_mut().unwrap().poll() {
+            app.preferences.light_theme = light_theme;
+            app.color_scheme = app.preferences.get_color_scheme();
+        }
+
+        if let Some(enabled) = self.user_inputs[RADIAL_MENU].as_checkbox_mut().unwrap().poll() {
+            app.preferences.radial_menu = enabled;
+            app.interaction_options.radial_menu = enabled;
+        }
+
+        if let Some(enabled) = self.user_inputs[SNAP_TO_GRID].as_checkbox_mut().unwrap().poll() {
+            app.preferences.snap_to_grid = enabled;
+            app.interaction_options.snap_to_grid = enabled;
+        }
+
+        if let Some(enabled) = self.user_inputs[SNAP_TO_OBJECTS].as_checkbox_mut().unwrap().poll() {
+            app.preferences.snap_to_objects = enabled;
+            app.interaction_options.snap_to_objects = enabled;
+        }
+
+        if let Some(index) = self.user_inputs[UNITS].as_dropdown_mut().unwrap().poll() {
+            app.preferences.unit = Unit::ALL[index];
+        }
+
+        if let Some(vsync) = self.user_inputs[VSYNC].as_checkbox_mut().unwrap().poll() {
+            app.preferences.vsync = vsync;
+        }
+
+        if let Some(minutes) = self.get_number(AUTOSAVE_MINUTES) {
+            app.preferences.autosave_minutes = preferences::Preferences::clamp_autosave_minutes(minutes);
+        }
+
+        if let Some(ui_scale) = self.get_number(UI_SCALE) {
+            app.preferences.ui_scale = ui_scale.clamp(0.75, 2.0);
+            app.update_scale_factor();
+        }
+
+        if let Some(snapping) = self.get_number(TRANSLATION_SNAPPING) {
+            app.preferences.translation_snapping = snapping.max(1.0);
+            app.interaction_options.translation_snapping = app.preferences.translation_snapping;
+        }
+
+        if let Some(snapping) = self.get_number(ROTATION_SNAPPING) {
+            app.preferences.rotation_snapping = snapping.max(0.0);
+            app.interaction_options.rotation_snapping = app.preferences.get_interaction_options().rotation_snapping;
+        }
+
+        if let Some(target_fps) = self.get_number(TARGET_FPS) {
+            app.preferences.target_fps = FramePacer::clamp_target_fps(target_fps);
+            app.frame_pacer.set_target_fps(app.preferences.target_fps);
+        }
+
+        if let Some(msaa) = self.get_number(MSAA) {
+            app.preferences.msaa = msaa as u16;
+        }
+
+        if let Some(supersampling) = self.get_number(SUPERSAMPLING) {
+            app.preferences.supersampling = supersampling;
+        }
+
+        if let Some(width) = self.get_number(RECT_WIDTH) {
+            app.preferences.rect_width = width;
+        }
+
+        if let Some(height) = self.get_number(RECT_HEIGHT) {
+            app.preferences.rect_height = height;
+        }
+
+        if let Some(radius) = self.get_number(CIRCLE_RADIUS) {
+            app.preferences.circle_radius = radius;
+        }
+    }
+
+    fn click(&mut self, button: MouseButton, position: &glm::Vec2, app: &mut App) -> Capture {
+        let panel = app.toolbox.get_options_area(&app.dimensions);
+        let scroll = self.scroll;
+
+        // Inputs scrolled up under the tab bar can't be clicked
+        if position.y < Self::CONTENT_TOP {
+            return Capture::Miss;
+        }
+
+        let index_took_focus =
+            self.user_inputs.iter_mut().enumerate().find_map(|(i, user_input)| {
+                (user_input.click(button, position, &Self::get_input_area(i, &panel, scroll), app) == Capture::TakeFocus).then_some(i)
+            });
+
+        for (i, user_input) in self.user_inputs.iter_mut().enumerate() {
+            if Some(i) != index_took_focus {
+                user_input.unfocus();
+            }
+        }
+
+        self.keyboard_focus = index_took_focus;
+        index_took_focus.map_or(Capture::Miss, |_| Capture::TakeFocus)
+    }
+
+    // Tab/Shift+Tab moves between inputs once keyboard focus is in the tab (which F6 toggles),
+    // and Enter/Space operates the focused input
+    fn navigate(&mut self, virtual_keycode: VirtualKeyCode, shift: bool, app: &mut App) -> Option<Capture> {
+        let is_editing = self.keyboard_focus.is_some_and(|index| self.user_inputs[index].is_editing());
+
+        let next = match virtual_keycode {
+            VirtualKeyCode::Tab if self.keyboard_focus.is_some() => step_focus(self.keyboard_focus, self.user_inputs.len(), shift),
+            VirtualKeyCode::F6 if self.keyboard_focus.is_none() => Some(0),
+            VirtualKeyCode::F6 => None,
+            VirtualKeyCode::Return | VirtualKeyCode::Space if !is_editing && self.keyboard_focus.is_some() => {
+                self.user_inputs[self.keyboard_focus?].activate(app);
+                return Some(Capture::Keyboard(KeyCapture::Capture));
+            }
+            VirtualKeyCode::Escape if !is_editing && self.keyboard_focus.is_some() => {
+                self.keyboard_focus = None;
+                return Some(Capture::Keyboard(KeyCapture::Capture));
+            }
+            _ => return None,
+        };
+
+        if let Some(index) = self.keyboard_focus {
+            self.user_inputs[index].unfocus();
+        }
+
+        self.keyboard_focus = next;
+
+        if let Some(index) = next {
+            self.scroll_into_view(index, app.dimensions.y);
+        }
+
+        // Leaving the tab lets F6 carry on to the tool options
+        next.map(|_| Capture::Keyboard(KeyCapture::Capture))
+    }
+}
+
+impl Tab for Preferences {
+    fn interact(&mut self, interaction: Interaction, app: &mut App) -> Capture {
+        let capture = match interaction {
+            Interaction::Click(ClickInteraction { button, position, .. }) => self.click(button, &position, app),
+            Interaction::Key(KeyInteraction { virtual_keycode }) => {
+                let shift = app.keys_held.contains(&VirtualKeyCode::LShift);
+
+                match self.navigate(virtual_keycode, shift, app) {
+                    Some(capture) => capture,
+                    None => self
+                        .keyboard_focus
+                        .and_then(|index| self.user_inputs[index].key_press(virtual_keycode, app.modifiers_held).to_option())
+                        .map_or(Capture::Miss, Capture::Keyboard),
+                }
+            }
+            Interaction::Drag(drag) => {
+                let panel = app.toolbox.get_options_area(&app.dimensions);
+                let scroll = self.scroll;
+
+                self.user_inputs
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, user_input)| user_input.drag(&drag, &Self::get_input_area(i, &panel, scroll)))
+                    .find(|capture| *capture != Capture::Miss)
+                    .unwrap_or(Capture::Miss)
+            }
+            Interaction::MouseRelease(..) => {
+                self.user_inputs.iter_mut().for_each(UserInput::release);
+                Capture::Miss
+            }
+            Interaction::Scroll(ScrollInteraction { delta, .. }) => {
+                self.set_scroll(self.scroll - delta.y, app.dimensions.y);
+                Capture::Miss
+            }
+            Interaction::Character(CharacterInteraction { character }) => self
+                .user_inputs
+                .iter_mut()
+                .find_map(|user_input| user_input.receive_character(character).to_option())
+                .map_or(Capture::Miss, Capture::Keyboard),
+            _ => Capture::Miss,
+        };
+
+        self.poll(app);
+        capture
+    }
+
+    fn is_animating(&self) -> bool {
+        self.user_inputs.iter().any(UserInput::is_animating)
+    }
+
+    fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, _ocean: &Ocean, _selections: &[Selection], panel: &AABB) {
+        // Keep scroll in range as the window changes size
+        self.set_scroll(self.scroll, ctx.height);
+        let scroll = self.scroll;
+
+        ctx.clip = Some(AABB::new(panel.min_x, Self::CONTENT_TOP, panel.width(), ctx.height - Self::CONTENT_TOP));
+
+        for (i, user_input) in self.user_inputs.iter_mut().enumerate() {
+            user_input.render(ctx, text_system, font.clone(), &Self::get_input_area(i, panel, scroll));
+        }
+
+        if let Some(index) = self.keyboard_focus {
+            render_focus_marker(ctx, &Self::get_input_area(index, panel, scroll));
+        }
+
+        let note_position = glm::vec2(
+            panel.min_x + Self::LEFT_MARGIN,
+            Self::INPUTS_TOP + self.user_inputs.len() as f32 * Self::INPUT_SPACING - scroll,
+        );
+        draw_text(
+            text_system,
+            font.clone(),
+            "VSync and tool defaults apply on restart",
+            &note_position,
+            ctx,
+            ctx.color_scheme.muted,
+        );
+
+        for (i, user_input) in self.user_inputs.iter_mut().enumerate() {
+            user_input.render_overlay(ctx, text_system, font.clone(), &Self::get_input_area(i, panel, scroll));
+        }
+
+        // Clipped text has to be drawn before the clip goes away
+        ctx.flush_text(text_system);
+        ctx.clip = None;
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{color::Color, raster_color::RasterColor, style::ColorStyle};
+use std::{convert::TryInto, fs, path::Path};
+
+// Reading and writing of color palette files, so that swatches can be shared between applications.
+// Supports GIMP palettes (.gpl) and Adobe Swatch Exchange files (.ase)
+
+const ASE_SIGNATURE: &[u8; 4] = b"ASEF";
+const ASE_GROUP_START: u16 = 0xC001;
+const ASE_GROUP_END: u16 = 0xC002;
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+const ASE_COLOR_TYPE_GLOBAL: u16 = 0;
+
+pub fn load(path: &Path) -> Result<Vec<ColorStyle>, String> {
+    if is_ase(path) {
+        let contents = fs::read(path).map_err(|_| "Failed to read palette file")?;
+        parse_ase(&contents)
+    } else {
+        let contents = fs::read_to_string(path).map_err(|_| "Failed to read palette file")?;
+        parse_gpl(&contents)
+    }
+}
+
+pub fn save(path: &Path, styles: &[ColorStyle]) -> Result<(), String> {
+    let contents = if is_ase(path) {
+        write_ase(styles)
+    } else {
+        let name = path.file_stem().map_or("Photosquid".into(), |stem| stem.to_string_lossy());
+        write_gpl(&name, styles).into_bytes()
+    };
+
+    fs::write(path, contents).map_err(|_| "Failed to write palette file to disk".into())
+}
+
+fn is_ase(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("ase"))
+}
+
+pub fn parse_gpl(contents: &str) -> Result<Vec<ColorStyle>, String> {
+    let mut lines = contents.lines();
+
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        return Err("Not a GIMP palette".into());
+    }
+
+    let mut styles = vec![];
+
+    for line in lines.map(str::trim) {
+        // Skip header fields and comments
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let mut channel = || parts.next().and_then(|part| part.parse::<u8>().ok());
+
+        let (r, g, b) = match (channel(), channel(), channel()) {
+            (Some(r), Some(g), Some(b)) => (r, g, b),
+            _ => return Err(format!("Invalid palette entry '{}'", line)),
+        };
+
+        let name = parts.collect::<Vec<_>>().join(" ");
+        let color: Color = RasterColor::new(r, g, b, 255).into();
+        let name = if name.is_empty() { RasterColor::from(color).to_hex() } else { name };
+
+        styles.push(ColorStyle::new(name, color));
+    }
+
+    Ok(styles)
+}
+
+pub fn write_gpl(name: &str, styles: &[ColorStyle]) -> String {
+    let mut contents = format!("GIMP Palette\nName: {}\n#\n", name);
+
+    for style in styles {
+        let RasterColor { r, g, b, .. } = style.color.into();
+        contents.push_str(&format!("{:3} {:3} {:3}\t{}\n", r, g, b, style.name));
+    }
+
+    contents
+}
+
+pub fn parse_ase(contents: &[u8]) -> Result<Vec<ColorStyle>, String> {
+    let mut reader = AseReader { contents, offset: 0 };
+
+    if reader.take(4)? != ASE_SIGNATURE {
+        return Err("Not an Adobe Swatch Exchange file".into());
+    }
+
+    // Version
+    reader.take(4)?;
+
+    let block_count = reader.u32()?;
+    let mut styles = vec![];
+
+    for _ in 0..block_count {
+        let block_type = reader.u16()?;
+        let block_length = reader.u32()? as usize;
+        let mut block = AseReader {
+            contents: reader.take(block_length)?,
+            offset: 0,
+        };
+
+        match block_type {
+            ASE_COLOR_ENTRY => {
+                let name = block.name()?;
+                let model = block.take(4)?;
+
+                let color = match model {
+                    b"RGB " => Color::new(block.f32()?, block.f32()?, block.f32()?, 1.0),
+                    b"Gray" => {
+                        let value = block.f32()?;
+                        Color::new(value, value, value, 1.0)
+                    }
+                    b"CMYK" => {
+                        let (c, m, y, k) = (block.f32()?, block.f32()?, block.f32()?, block.f32()?);
+                        Color::new((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k), 1.0)
+                    }
+                    // Lab colors aren't supported
+                    _ => continue,
+                };
+
+                styles.push(ColorStyle::new(name, color));
+            }
+            ASE_GROUP_START | ASE_GROUP_END => (),
+            _ => return Err("Invalid block in swatch file".into()),
+        }
+    }
+
+    Ok(styles)
+}
+
+pub fn write_ase(styles: &[ColorStyle]) -> Vec<u8> {
+    let mut contents = vec![];
+    contents.extend_from_slice(ASE_SIGNATURE);
+    contents.extend_from_slice(&1u16.to_be_bytes());
+    contents.extend_from_slice(&0u16.to_be_bytes());
+    contents.extend_from_slice(&(styles.len() as u32).to_be_bytes());
+
+    for style in styles {
+        let name: Vec<u16> = style.name.encode_utf16().chain(std::iter::once(0)).collect();
+
+        let mut block = vec![];
+        block.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        name.iter().for_each(|unit| block.extend_from_slice(&unit.to_be_bytes()));
+        block.extend_from_slice(b"RGB ");
+
+        for channel in [style.color.r, style.color.g, style.color.b] {
+            block.extend_from_slice(&channel.to_be_bytes());
+        }
+
+        block.extend_from_slice(&ASE_COLOR_TYPE_GLOBAL.to_be_bytes());
+
+        contents.extend_from_slice(&ASE_COLOR_ENTRY.to_be_bytes());
+        contents.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        contents.extend_from_slice(&block);
+    }
+
+    contents
+}
+
+// Big-endian reader over the contents of an .ase file
+struct AseReader<'a> {
+    contents: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AseReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .contents
+            .get(self.offset..self.offset + length)
+            .ok_or_else(|| "Unexpected end of swatch file".to_string())?;
+        self.offset += length;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    // Null-terminated UTF-16 string prefixed by its length
+    fn name(&mut self) -> Result<String, String> {
+        let length = self.u16()? as usize;
+        let units: Vec<u16> = (0..length).map(|_| self.u16()).collect::<Result<_, _>>()?;
+        let units = units.strip_suffix(&[0]).unwrap_or(&units);
+        Ok(String::from_utf16_lossy(units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ase, parse_gpl, write_ase, write_gpl};
+    use crate::{color::Color, style::ColorStyle};
+
+    fn styles() -> Vec<ColorStyle> {
+        vec![
+            ColorStyle::new("Brand Red".into(), Color::from_hex("#E02020")),
+            ColorStyle::new("Sky".into(), Color::from_hex("#3399FF")),
+        ]
+    }
+
+    #[test]
+    fn palette_gpl_round_trip() {
+        let parsed = parse_gpl(&write_gpl("Brand", &styles())).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "Brand Red");
+        assert_eq!(parsed[1].color, Color::from_hex("#3399FF"));
+        assert!(parse_gpl("not a palette").is_err());
+    }
+
+    #[test]
+    fn palette_ase_round_trip() {
+        let parsed = parse_ase(&write_ase(&styles())).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].name, "Sky");
+        assert_eq!(parsed[0].color, Color::from_hex("#E02020"));
+        assert!(parse_ase(b"ASEF").is_err());
+    }
+}
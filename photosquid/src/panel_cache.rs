@@ -0,0 +1,101 @@
+use crate::{aabb::AABB, affine::Affine2, as_values::AsValues, camera::Camera, render_ctx::RenderCtx};
+use glium::{
+    texture::{MipmapsOption, SrgbTexture2d},
+    uniforms::{MagnifySamplerFilter, MinifySamplerFilter},
+    Display,
+};
+use nalgebra_glm as glm;
+
+// Side panels are rendered into a texture that is only redrawn when
+// their state may have changed, and is composited over the canvas every frame
+pub struct PanelCache {
+    texture: Option<SrgbTexture2d>,
+    stale: bool,
+    camera_position: Option<glm::Vec2>,
+}
+
+impl PanelCache {
+    pub fn new() -> Self {
+        Self {
+            texture: None,
+            stale: true,
+            camera_position: None,
+        }
+    }
+
+    // Panels are redrawn next frame, which is done every frame while something in them is animating
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    // Tool options can show the camera position, so panning has to redraw panels too
+    pub fn track_camera(&mut self, camera: &Camera) {
+        if self.camera_position != Some(camera.position) {
+            self.camera_position = Some(camera.position);
+            self.invalidate();
+        }
+    }
+
+    // Panels are supersampled on displays that would otherwise have MSAA,
+    // since drawing into a texture doesn't get it
+    pub fn get_scale(scale_factor: f64) -> f64 {
+        if scale_factor > 1.0 {
+            scale_factor
+        } else {
+            2.0
+        }
+    }
+
+    // Takes the texture to draw the panels into, if they need to be redrawn.
+    // It should be handed back afterwards using 'finish_refresh'
+    pub fn begin_refresh(&mut self, display: &Display, width: u32, height: u32) -> Option<SrgbTexture2d> {
+        let is_same_size = self.texture.as_ref().is_some_and(|texture| texture.dimensions() == (width, height));
+
+        if is_same_size && !self.stale {
+            return None;
+        }
+
+        self.stale = false;
+
+        match self.texture.take() {
+            Some(texture) if is_same_size => Some(texture),
+            _ => Some(SrgbTexture2d::empty_with_mipmaps(display, MipmapsOption::NoMipmap, width, height).unwrap()),
+        }
+    }
+
+    pub fn finish_refresh(&mut self, texture: SrgbTexture2d) {
+        self.texture = Some(texture);
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, areas: &[AABB]) {
+        let texture = match &self.texture {
+            Some(texture) => texture,
+            None => return,
+        };
+
+        let mesh = ctx.square_xyzuv;
+        let identity = Affine2::identity();
+        let sampler = texture
+            .sampled()
+            .minify_filter(MinifySamplerFilter::Linear)
+            .magnify_filter(MagnifySamplerFilter::Linear);
+
+        let uniforms = glium::uniform! {
+            transformation: identity.as_values(),
+            view: identity.as_values(),
+            projection: glm::Mat4::identity().as_values(),
+            texture_sampler: sampler
+        };
+
+        let previous_clip = ctx.clip;
+
+        // Only copy over the areas covered by panels
+        for area in areas {
+            ctx.clip = Some(*area);
+            ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.television_shader, &uniforms, &Default::default())
+                .unwrap();
+        }
+
+        ctx.clip = previous_clip;
+    }
+}
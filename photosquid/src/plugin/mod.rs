@@ -0,0 +1,125 @@
+mod shape_tool;
+mod squid_list;
+mod star;
+
+use crate::{
+    aabb::AABB, app::App, capture::Capture, data::path::PathAnchor, interaction::Interaction, ocean::Ocean, render_ctx::RenderCtx, user_input::UserInput,
+};
+use nalgebra_glm as glm;
+use std::{path::Path, rc::Rc};
+
+pub use shape_tool::ShapeTool;
+
+// Contributes a new kind of shape.
+// Shapes are made out of path anchors, so they're drawn, saved, and edited like any other path
+pub trait SquidPlugin {
+    fn get_name(&self) -> &str;
+
+    // Icon for the tool that places the shape, in the same format as the built-in tool icons
+    fn get_icon(&self) -> &str;
+
+    // Anchors of the shape when it's centered at the origin and fits within a circle of the given radius,
+    // along with whether the path is closed
+    fn build(&self, radius: f32) -> (Vec<PathAnchor>, bool);
+}
+
+// Contributes a new tool to the toolbox
+pub trait ToolPlugin {
+    fn get_name(&self) -> &str;
+    fn get_icon(&self) -> &str;
+
+    // Options shown in the options panel while the tool is selected
+    fn create_options(&self) -> Vec<UserInput> {
+        vec![]
+    }
+
+    fn interact(&mut self, user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture;
+
+    // Draws anything the tool shows on top of the canvas
+    fn render(&self, _ctx: &mut RenderCtx, _app: &App) {}
+}
+
+// Contributes a new file format to export to
+pub trait ExportPlugin {
+    fn get_name(&self) -> &str;
+    fn get_extension(&self) -> &str;
+    fn export(&self, filename: &Path, view_box: &AABB, ocean: &Ocean) -> std::io::Result<()>;
+}
+
+// Everything plugins have contributed, which is handed to the toolbox and app at startup
+#[derive(Default)]
+pub struct PluginRegistry {
+    pub squids: Vec<Rc<dyn SquidPlugin>>,
+    pub tools: Vec<Box<dyn ToolPlugin>>,
+    pub exporters: Vec<Box<dyn ExportPlugin>>,
+}
+
+impl PluginRegistry {
+    // Registry with the plugins that ship with photosquid
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register_squid(Rc::new(star::Star));
+        registry.register_exporter(Box::new(squid_list::SquidList));
+        registry
+    }
+
+    // Each kind of shape gets a tool for placing it
+    pub fn register_squid(&mut self, plugin: Rc<dyn SquidPlugin>) {
+        self.tools.push(Box::new(ShapeTool::new(plugin.clone())));
+        self.squids.push(plugin);
+    }
+
+    pub fn register_tool(&mut self, plugin: Box<dyn ToolPlugin>) {
+        self.tools.push(plugin);
+    }
+
+    pub fn register_exporter(&mut self, plugin: Box<dyn ExportPlugin>) {
+        self.exporters.push(plugin);
+    }
+
+    pub fn get_squid(&self, name: &str) -> Option<&dyn SquidPlugin> {
+        self.squids
+            .iter()
+            .find(|plugin| plugin.get_name().eq_ignore_ascii_case(name))
+            .map(|plugin| plugin.as_ref())
+    }
+
+    pub fn get_exporter_for(&self, filename: &Path) -> Option<&dyn ExportPlugin> {
+        let extension = filename.extension()?.to_str()?;
+
+        self.exporters
+            .iter()
+            .find(|plugin| plugin.get_extension().eq_ignore_ascii_case(extension))
+            .map(|plugin| plugin.as_ref())
+    }
+}
+
+// Moves a shape built by a plugin to where it's placed
+pub fn place(anchors: Vec<PathAnchor>, center: glm::Vec2) -> Vec<PathAnchor> {
+    anchors
+        .into_iter()
+        .map(|anchor| PathAnchor {
+            point: anchor.point + center,
+            ..anchor
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PluginRegistry;
+    use std::path::Path;
+
+    #[test]
+    fn find_plugins() {
+        let registry = PluginRegistry::with_builtin();
+
+        assert!(registry.get_squid("star").is_some());
+        assert!(registry.get_squid("hexagon").is_none());
+        assert!(registry.get_exporter_for(Path::new("squids.CSV")).is_some());
+        assert!(registry.get_exporter_for(Path::new("drawing.svg")).is_none());
+
+        // Shapes come with a tool for placing them
+        assert_eq!(registry.tools.len(), registry.squids.len());
+    }
+}
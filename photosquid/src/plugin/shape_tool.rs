@@ -0,0 +1,56 @@
+use super::{place, SquidPlugin, ToolPlugin};
+use crate::{
+    app::App,
+    capture::Capture,
+    command::DocumentCommand,
+    interaction::{ClickInteraction, Interaction},
+    squid::Squid,
+    user_input::{TextInput, UserInput},
+};
+use glium::glutin::event::MouseButton;
+use std::rc::Rc;
+
+// Tool for placing a shape contributed by a plugin, one click at a time
+pub struct ShapeTool {
+    plugin: Rc<dyn SquidPlugin>,
+}
+
+impl ShapeTool {
+    pub fn new(plugin: Rc<dyn SquidPlugin>) -> Self {
+        Self { plugin }
+    }
+}
+
+impl ToolPlugin for ShapeTool {
+    fn get_name(&self) -> &str {
+        self.plugin.get_name()
+    }
+
+    fn get_icon(&self) -> &str {
+        self.plugin.get_icon()
+    }
+
+    fn create_options(&self) -> Vec<UserInput> {
+        vec![UserInput::TextInput(TextInput::new("50".into(), "Radius".into(), "".into()))]
+    }
+
+    fn interact(&mut self, user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+        match interaction {
+            Interaction::Click(ClickInteraction {
+                button: MouseButton::Left,
+                position,
+                ..
+            }) => {
+                let world_position = app.camera.get_animated().apply_reverse(&position);
+                let color = app.toolbox.color_picker.calculate_color();
+                let radius = user_inputs[0].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default().max(4.0);
+
+                let (anchors, closed) = self.plugin.build(radius);
+                let squid = Squid::path(place(anchors, world_position), closed, color);
+                app.emit(DocumentCommand::InsertAndSelect(Box::new(squid)));
+                Capture::NoDrag
+            }
+            _ => Capture::Miss,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::ExportPlugin;
+use crate::{aabb::AABB, ocean::Ocean};
+use std::{fmt::Write, fs, path::Path};
+
+// Spreadsheet of the visible squids within the exported area, from lowest to highest,
+// which is handy for laying things out in other programs
+pub struct SquidList;
+
+impl ExportPlugin for SquidList {
+    fn get_name(&self) -> &str {
+        "Squid List"
+    }
+
+    fn get_extension(&self) -> &str {
+        "csv"
+    }
+
+    fn export(&self, filename: &Path, view_box: &AABB, ocean: &Ocean) -> std::io::Result<()> {
+        let mut contents = String::from("name,x,y,width,height,color\n");
+
+        for (reference, _) in ocean.get_visible_squids_lowest() {
+            let squid = match ocean.get(reference) {
+                Some(squid) => squid,
+                None => continue,
+            };
+
+            let bounds = squid.get_bounding_box();
+
+            if !bounds.intersecting_aabb(view_box) {
+                continue;
+            }
+
+            writeln!(
+                contents,
+                "\"{}\",{},{},{},{},{}",
+                squid.get_name().replace('"', "\"\""),
+                bounds.min_x,
+                bounds.min_y,
+                bounds.width(),
+                bounds.height(),
+                squid.get_color().to_rgb_hex(),
+            )
+            .unwrap();
+        }
+
+        fs::write(filename, contents)
+    }
+}
@@ -0,0 +1,53 @@
+use super::SquidPlugin;
+use crate::data::path::PathAnchor;
+use nalgebra_glm as glm;
+
+// Five pointed star
+pub struct Star;
+
+impl Star {
+    const POINTS: usize = 5;
+    const INNER_RADIUS: f32 = 0.4;
+}
+
+impl SquidPlugin for Star {
+    fn get_name(&self) -> &str {
+        "Star"
+    }
+
+    fn get_icon(&self) -> &str {
+        include_str!("../_src_objs/star.obj")
+    }
+
+    fn build(&self, radius: f32) -> (Vec<PathAnchor>, bool) {
+        let corners = 2 * Self::POINTS;
+
+        let anchors = (0..corners)
+            .map(|i| {
+                let distance = if i % 2 == 0 { radius } else { radius * Self::INNER_RADIUS };
+                let angle = -std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * i as f32 / corners as f32;
+                PathAnchor::new(glm::vec2(angle.cos(), angle.sin()) * distance)
+            })
+            .collect();
+
+        (anchors, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Star;
+    use crate::plugin::SquidPlugin;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn build_star() {
+        let (anchors, closed) = Star.build(10.0);
+        assert!(closed);
+        assert_eq!(anchors.len(), 10);
+
+        // First point is straight up
+        assert!(glm::distance(&anchors[0].point, &glm::vec2(0.0, -10.0)) < 0.001);
+        assert!(anchors.iter().all(|anchor| glm::length(&anchor.point) <= 10.001));
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{color_scheme::ColorScheme, frame_pacer::FramePacer, interaction_options::InteractionOptions, render_quality::RenderQuality, units::Unit};
+use angular_units::Rad;
+use std::{fs, path::PathBuf};
+
+// Settings that are remembered between runs.
+// They're stored as a flat TOML file of 'key = value' lines in the user's config directory,
+// any keys left out keep their default and unknown keys are ignored
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preferences {
+    pub light_theme: bool,
+    pub autosave_minutes: f32,
+    pub ui_scale: f32,
+
+    // Path or name of an installed font file to draw the interface with, where empty means the bundled font
+    pub ui_font: String,
+
+    // Unit that lengths are shown in, which can still be typed in any unit
+    pub unit: Unit,
+
+    // Widths of the side panels, which are resized by dragging their inner edges
+    pub tool_panel_width: f32,
+    pub options_panel_width: f32,
+
+    // Names of the panel sections that are collapsed
+    pub collapsed_sections: Vec<String>,
+
+    pub translation_snapping: f32,
+    pub rotation_snapping: f32,
+    pub snap_to_grid: bool,
+    pub snap_to_objects: bool,
+    pub radial_menu: bool,
+    pub target_fps: f32,
+    pub vsync: bool,
+    pub msaa: u16,
+    pub supersampling: f32,
+    pub rect_width: f32,
+    pub rect_height: f32,
+    pub circle_radius: f32,
+}
+
+impl Preferences {
+    // Longest time between autosaves, which keeps the interval well within what a Duration can hold
+    pub const MAX_AUTOSAVE_MINUTES: f32 = 24.0 * 60.0;
+
+    pub fn clamp_autosave_minutes(minutes: f32) -> f32 {
+        minutes.clamp(0.0, Self::MAX_AUTOSAVE_MINUTES)
+    }
+
+    pub fn get_path() -> Option<PathBuf> {
+        Some(dirs_next::config_dir()?.join("photosquid").join("preferences.toml"))
+    }
+
+    // Falls back to the defaults when there aren't any saved preferences yet
+    pub fn load() -> Self {
+        let contents = match Self::get_path().and_then(|path| fs::read_to_string(path).ok()) {
+            Some(contents) => contents,
+            None => return Self::default(),
+        };
+
+        Self::parse(&contents).unwrap_or_else(|message| {
+            eprintln!("{}, using default preferences", message);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::get_path().ok_or("No config directory to save preferences in")?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|_| "Failed to create config directory")?;
+        }
+
+        fs::write(path, self.to_toml()).map_err(|_| "Failed to write preferences file to disk".into())
+    }
+
+    pub fn get_color_scheme(&self) -> ColorScheme {
+        if self.light_theme {
+            ColorScheme::light()
+        } else {
+            ColorScheme::default()
+        }
+    }
+
+    pub fn get_render_quality(&self) -> RenderQuality {
+        RenderQuality::new(self.msaa, self.supersampling)
+    }
+
+    pub fn get_interaction_options(&self) -> InteractionOptions {
+        InteractionOptions {
+            translation_snapping: self.translation_snapping,
+            rotation_snapping: Rad(self.rotation_snapping.to_radians()),
+            snap_to_grid: self.snap_to_grid,
+            snap_to_objects: self.snap_to_objects,
+            radial_menu: self.radial_menu,
+            ..Default::default()
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut preferences = Self::default();
+
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = strip_comment(line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| format!("Invalid preferences: expected 'key = value' on line {}", line_number + 1))?;
+
+            let invalid = || format!("Invalid preferences: bad value '{}' for '{}'", value, key);
+            let number = || value.parse::<f32>().ok().filter(|number| number.is_finite()).ok_or_else(invalid);
+            let boolean = || value.parse::<bool>().map_err(|_| invalid());
+            let string = || unquote(value).ok_or_else(invalid);
+
+            match key {
+                "light_theme" => preferences.light_theme = boolean()?,
+                "autosave_minutes" => preferences.autosave_minutes = Self::clamp_autosave_minutes(number()?),
+                "ui_scale" => preferences.ui_scale = number()?.clamp(0.75, 2.0),
+                "ui_font" => preferences.ui_font = string()?,
+                "unit" => preferences.unit = string().and_then(|suffix| Unit::from_suffix(&suffix).ok_or_else(invalid))?,
+                "tool_panel_width" => preferences.tool_panel_width = number()?,
+                "options_panel_width" => preferences.options_panel_width = number()?,
+                "collapsed_sections" => preferences.collapsed_sections = split_list(&string()?),
+                "translation_snapping" => preferences.translation_snapping = number()?.max(1.0),
+                "rotation_snapping" => preferences.rotation_snapping = number()?.max(0.0),
+                "snap_to_grid" => preferences.snap_to_grid = boolean()?,
+                "snap_to_objects" => preferences.snap_to_objects = boolean()?,
+                "radial_menu" => preferences.radial_menu = boolean()?,
+                "target_fps" => preferences.target_fps = FramePacer::clamp_target_fps(number()?),
+                "vsync" => preferences.vsync = boolean()?,
+                "msaa" => preferences.msaa = number()? as u16,
+                "supersampling" => preferences.supersampling = number()?,
+                "rect_width" => preferences.rect_width = number()?,
+                "rect_height" => preferences.rect_height = number()?,
+                "circle_radius" => preferences.circle_radius = number()?,
+                _ => (),
+            }
+        }
+
+        Ok(preferences)
+    }
+
+    pub fn is_section_expanded(&self, name: &str) -> bool {
+        !self.collapsed_sections.iter().any(|collapsed| collapsed == name)
+    }
+
+    pub fn set_section_expanded(&mut self, name: &str, expanded: bool) {
+        self.collapsed_sections.retain(|collapsed| collapsed != name);
+
+        if !expanded {
+            self.collapsed_sections.push(name.into());
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        format!(
+            "# Photosquid preferences\n\
+             light_theme = {}\n\
+             autosave_minutes = {}\n\
+             ui_scale = {}\n\
+             \n\
+             # Path or name of an installed font file, where an empty name means the bundled font\n\
+             ui_font = {}\n\
+             \n\
+             # Unit that lengths are shown in, one of px, pt, in, cm or mm\n\
+             unit = {}\n\
+             \n\
+             # Widths of the side panels\n\
+             tool_panel_width = {}\n\
+             options_panel_width = {}\n\
+             \n\
+             # Panel sections that are collapsed, as a comma separated list of names\n\
+             collapsed_sections = {}\n\
+             \n\
+             # Snapping, where rotation snapping is in degrees and grid snapping moves and turns in those steps\n\
+             translation_snapping = {}\n\
+             rotation_snapping = {}\n\
+             snap_to_grid = {}\n\
+             snap_to_objects = {}\n\
+             radial_menu = {}\n\
+             \n\
+             # Frame rate, where a target of 0 means as fast as possible\n\
+             target_fps = {}\n\
+             vsync = {}\n\
+             \n\
+             # Render quality, where msaa is samples per pixel and supersampling is how much larger to draw\n\
+             msaa = {}\n\
+             supersampling = {}\n\
+             \n\
+             # Tool defaults\n\
+             rect_width = {}\n\
+             rect_height = {}\n\
+             circle_radius = {}\n",
+            self.light_theme,
+            self.autosave_minutes,
+            self.ui_scale,
+            quote(&self.ui_font),
+            quote(self.unit.get_suffix()),
+            self.tool_panel_width,
+            self.options_panel_width,
+            quote(&self.collapsed_sections.join(", ")),
+            self.translation_snapping,
+            self.rotation_snapping,
+            self.snap_to_grid,
+            self.snap_to_objects,
+            self.radial_menu,
+            self.target_fps,
+            self.vsync,
+            self.msaa,
+            self.supersampling,
+            self.rect_width,
+            self.rect_height,
+            self.circle_radius,
+        )
+    }
+}
+
+// Removes a trailing '#' comment, ignoring any '#' inside of quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, character) in line.char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect()
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut text = String::with_capacity(inner.len());
+    let mut characters = inner.chars();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => text.push(characters.next()?),
+            '"' => return None,
+            _ => text.push(character),
+        }
+    }
+
+    Some(text)
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            light_theme: false,
+            autosave_minutes: 0.0,
+            ui_scale: 1.0,
+            ui_font: String::new(),
+            unit: Unit::Px,
+            tool_panel_width: 256.0,
+            options_panel_width: 256.0,
+            collapsed_sections: vec![],
+            translation_snapping: 1.0,
+            rotation_snapping: 0.0,
+            snap_to_grid: true,
+            snap_to_objects: false,
+            radial_menu: false,
+            target_fps: 60.0,
+            vsync: true,
+            msaa: 4,
+            supersampling: 1.0,
+            rect_width: 100.0,
+            rect_height: 100.0,
+            circle_radius: 50.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Preferences;
+    use crate::units::Unit;
+
+    #[test]
+    fn parse_preferences() {
+        let preferences = Preferences::parse("# comment\nlight_theme = true\nautosave_minutes = 5 # minutes\n\nunknown = 1\n").unwrap();
+        assert!(preferences.light_theme);
+        assert_eq!(preferences.autosave_minutes, 5.0);
+        assert_eq!(preferences.rect_width, Preferences::default().rect_width);
+
+        assert!(Preferences::parse("light_theme = maybe").is_err());
+        assert!(Preferences::parse("ui_scale").is_err());
+        assert!(Preferences::parse("unit = \"km\"").is_err());
+        assert!(Preferences::parse("autosave_minutes = inf").is_err());
+        assert!(Preferences::parse("target_fps = NaN").is_err());
+
+        let preferences = Preferences::parse("autosave_minutes = 1e38\ntarget_fps = 1e-39").unwrap();
+        assert_eq!(preferences.autosave_minutes, Preferences::MAX_AUTOSAVE_MINUTES);
+        assert_eq!(preferences.target_fps, 1.0);
+
+        let preferences = Preferences::parse("collapsed_sections = \" Fill,,Arrange \"").unwrap();
+        assert!(!preferences.is_section_expanded("Fill"));
+        assert!(preferences.is_section_expanded("Transform"));
+        assert_eq!(preferences.collapsed_sections.len(), 2);
+    }
+
+    #[test]
+    fn preferences_round_trip() {
+        let preferences = Preferences {
+            light_theme: true,
+            autosave_minutes: 2.5,
+            ui_scale: 1.25,
+            ui_font: "C:\\Fonts\\\"Fancy\" #1.ttf".into(),
+            rotation_snapping: 15.0,
+            snap_to_objects: true,
+            unit: Unit::Cm,
+            options_panel_width: 320.0,
+            collapsed_sections: vec!["Fill".into(), "Arrange".into()],
+            circle_radius: 20.0,
+            target_fps: 144.0,
+            vsync: false,
+            msaa: 8,
+            supersampling: 1.5,
+            ..Default::default()
+        };
+
+        assert_eq!(Preferences::parse(&preferences.to_toml()), Ok(preferences));
+    }
+}
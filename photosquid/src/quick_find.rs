@@ -0,0 +1,205 @@
+use crate::{aabb::AABB, affine::Affine2, as_values::AsValues, draw_text::draw_text, ocean::Ocean, render_ctx::RenderCtx, squid::SquidRef};
+use glium::glutin::event::VirtualKeyCode;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Overlay for searching squids by name and jumping to them
+pub struct QuickFind {
+    query: String,
+    results: Vec<QuickFindResult>,
+    highlighted: usize,
+}
+
+struct QuickFindResult {
+    squid: SquidRef,
+    name: String,
+}
+
+pub enum QuickFindResponse {
+    Continue,
+    Close,
+    Choose(SquidRef),
+}
+
+impl QuickFind {
+    const WIDTH: f32 = 320.0;
+    const QUERY_HEIGHT: f32 = 40.0;
+    const RESULT_HEIGHT: f32 = 30.0;
+    const MAX_RESULTS: usize = 8;
+    const TOP: f32 = 16.0;
+
+    pub fn new(ocean: &Ocean) -> Self {
+        let mut quick_find = Self {
+            query: String::new(),
+            results: vec![],
+            highlighted: 0,
+        };
+        quick_find.update_results(ocean);
+        quick_find
+    }
+
+    // Matches names and tags, the same as the filter in the layers tab
+    fn update_results(&mut self, ocean: &Ocean) {
+        self.results = ocean
+            .get_interactable_squids_highest()
+            .filter_map(|reference| {
+                let squid = ocean.get(reference)?;
+
+                squid.matches_filter(&self.query).then(|| QuickFindResult {
+                    squid: reference,
+                    name: squid.get_name().into(),
+                })
+            })
+            .take(Self::MAX_RESULTS)
+            .collect();
+
+        self.highlighted = 0;
+    }
+
+    pub fn type_character(&mut self, character: char, ocean: &Ocean) {
+        if !character.is_control() {
+            self.query.push(character);
+            self.update_results(ocean);
+        }
+    }
+
+    pub fn press_key(&mut self, key: VirtualKeyCode, ocean: &Ocean) -> QuickFindResponse {
+        match key {
+            VirtualKeyCode::Escape => return QuickFindResponse::Close,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                return match self.results.get(self.highlighted) {
+                    Some(result) => QuickFindResponse::Choose(result.squid),
+                    None => QuickFindResponse::Close,
+                }
+            }
+            VirtualKeyCode::Back => {
+                self.query.pop();
+                self.update_results(ocean);
+            }
+            VirtualKeyCode::Up => self.highlighted = self.highlighted.saturating_sub(1),
+            VirtualKeyCode::Down if self.highlighted + 1 < self.results.len() => self.highlighted += 1,
+            _ => (),
+        }
+
+        QuickFindResponse::Continue
+    }
+
+    pub fn click(&self, position: &glm::Vec2, window_width: f32) -> QuickFindResponse {
+        let area = Self::get_area(window_width, self.results.len());
+
+        if !area.intersecting_point(position.x, position.y) {
+            return QuickFindResponse::Close;
+        }
+
+        let results_top = area.min_y + Self::QUERY_HEIGHT;
+
+        if position.y < results_top {
+            return QuickFindResponse::Continue;
+        }
+
+        let index = ((position.y - results_top) / Self::RESULT_HEIGHT) as usize;
+
+        match self.results.get(index) {
+            Some(result) => QuickFindResponse::Choose(result.squid),
+            None => QuickFindResponse::Continue,
+        }
+    }
+
+    fn get_area(window_width: f32, result_count: usize) -> AABB {
+        let height = Self::QUERY_HEIGHT + Self::RESULT_HEIGHT * result_count as f32;
+        AABB::new(0.5 * (window_width - Self::WIDTH), Self::TOP, Self::WIDTH, height)
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+        let area = Self::get_area(ctx.width, self.results.len());
+
+        // Render background
+        {
+            let mesh = ctx.square_xyzuv;
+            let identity = Affine2::identity();
+            let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
+            let transformation = Affine2::translation(&glm::vec2(area.center_x(), area.center_y()));
+            let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
+
+            let uniforms = glium::uniform! {
+                transformation: transformation.as_values(),
+                view: identity.as_values(),
+                projection: ctx.projection.as_values(),
+                rectangle_color: ctx.color_scheme.dark_ribbon.as_values(),
+                dimensions: [quad_dimensions.x, quad_dimensions.y],
+                height_scale: 1.0f32,
+                do_shadow: 1
+            };
+
+            let draw_parameters = glium::DrawParameters {
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            };
+
+            ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.rounded_rectangle_shader, &uniforms, &draw_parameters)
+                .unwrap();
+        }
+
+        let left = area.min_x + 16.0;
+
+        // Draw query
+        let (query, query_color) = if self.query.is_empty() {
+            ("Find by name...", ctx.color_scheme.label)
+        } else {
+            (self.query.as_str(), ctx.color_scheme.text)
+        };
+
+        draw_text(
+            text_system,
+            font.clone(),
+            query,
+            &glm::vec2(left, area.min_y + 0.5 * Self::QUERY_HEIGHT + 6.0),
+            ctx,
+            query_color,
+        );
+
+        // Draw results
+        for (i, result) in self.results.iter().enumerate() {
+            let color = if i == self.highlighted {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+
+            draw_text(
+                text_system,
+                font.clone(),
+                &result.name,
+                &glm::vec2(left, area.min_y + Self::QUERY_HEIGHT + (i as f32 + 0.5) * Self::RESULT_HEIGHT + 6.0),
+                ctx,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuickFind;
+    use crate::{color::Color, ocean::Ocean, squid::Squid};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn quick_find_names_and_tags() {
+        let mut ocean = Ocean::default();
+        let named = ocean.insert(Squid::circle(glm::zero(), 10.0, Color::white()));
+        let tagged = ocean.insert(Squid::circle(glm::zero(), 10.0, Color::white()));
+        ocean.get_mut(named).unwrap().set_name("Tree Trunk".into());
+        ocean.get_mut(tagged).unwrap().set_tags(vec!["Trees".into()]);
+
+        let mut quick_find = QuickFind::new(&ocean);
+        "tree".chars().for_each(|character| quick_find.type_character(character, &ocean));
+
+        let mut found: Vec<_> = quick_find.results.iter().map(|result| result.squid).collect();
+        found.sort();
+        let mut expected = vec![named, tagged];
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+}
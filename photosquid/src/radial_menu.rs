@@ -0,0 +1,145 @@
+use crate::{
+    affine::Affine2, as_values::AsValues, color::Color, color_scheme::ColorScheme, context_menu::ContextAction, draw_text::draw_text_centered,
+    render_ctx::RenderCtx,
+};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::{f32::consts::TAU, rc::Rc};
+
+// Marking menu that is opened by holding right-click, where
+// options are chosen by direction instead of by position in a list
+pub struct RadialMenu {
+    center: glm::Vec2,
+    options: Vec<RadialMenuOption>,
+    background_color: Color,
+    highlight_color: Color,
+}
+
+pub struct RadialMenuOption {
+    friendly_name: String,
+    action: ContextAction,
+}
+
+impl RadialMenu {
+    const RADIUS: f32 = 96.0;
+    const DEAD_ZONE: f32 = 24.0;
+    const OPTION_WIDTH: f32 = 104.0;
+    const OPTION_HEIGHT: f32 = 30.0;
+
+    pub fn new(center: glm::Vec2, options: Vec<RadialMenuOption>, background_color: Color, highlight_color: Color) -> Self {
+        Self {
+            center,
+            options,
+            background_color,
+            highlight_color,
+        }
+    }
+
+    pub fn common(center: glm::Vec2, color_scheme: &ColorScheme) -> Self {
+        use ContextAction::*;
+
+        // Options are laid out clockwise starting from straight up
+        Self::new(
+            center,
+            vec![
+                RadialMenuOption::new("Grab", GrabSelected),
+                RadialMenuOption::new("Rotate", RotateSelected),
+                RadialMenuOption::new("Scale", ScaleSelected),
+                RadialMenuOption::new("Redo", Redo),
+                RadialMenuOption::new("Delete", DeleteSelected),
+                RadialMenuOption::new("Undo", Undo),
+                RadialMenuOption::new("Collectively", Collectively),
+                RadialMenuOption::new("Duplicate", DuplicateSelected),
+            ],
+            color_scheme.dark_ribbon,
+            color_scheme.foreground,
+        )
+    }
+
+    // Gets the index of the option in the direction of a point,
+    // pointing inside of the dead zone doesn't choose anything
+    pub fn get_hovered(&self, point: &glm::Vec2) -> Option<usize> {
+        let delta = point - self.center;
+
+        if self.options.is_empty() || glm::length(&delta) < Self::DEAD_ZONE {
+            return None;
+        }
+
+        let slice = TAU / self.options.len() as f32;
+        let angle = delta.x.atan2(-delta.y).rem_euclid(TAU);
+        Some(((angle + 0.5 * slice) / slice) as usize % self.options.len())
+    }
+
+    pub fn select(&self, point: &glm::Vec2) -> Option<ContextAction> {
+        self.get_hovered(point).map(|index| self.options[index].action)
+    }
+
+    fn get_option_position(&self, index: usize) -> glm::Vec2 {
+        let angle = index as f32 * TAU / self.options.len() as f32;
+        self.center + glm::vec2(angle.sin(), -angle.cos()) * Self::RADIUS
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, mouse: Option<glm::Vec2>) {
+        let hovered = mouse.and_then(|mouse| self.get_hovered(&mouse));
+
+        ctx.ring_mesh
+            .render(ctx, self.center, glm::vec2(Self::DEAD_ZONE, Self::DEAD_ZONE), &self.background_color);
+
+        for i in 0..self.options.len() {
+            let position = self.get_option_position(i);
+
+            let (background_color, text_color) = if hovered == Some(i) {
+                (self.highlight_color, Color::white())
+            } else {
+                (self.background_color, ctx.color_scheme.secondary_text)
+            };
+
+            // Render option background
+            {
+                let mesh = ctx.square_xyzuv;
+                let identity = Affine2::identity();
+                let quad_dimensions = glm::vec2(Self::OPTION_WIDTH + 32.0, Self::OPTION_HEIGHT + 32.0);
+                let transformation = Affine2::translation(&position);
+                let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
+
+                let uniforms = glium::uniform! {
+                    transformation: transformation.as_values(),
+                    view: identity.as_values(),
+                    projection: ctx.projection.as_values(),
+                    rectangle_color: background_color.as_values(),
+                    dimensions: [quad_dimensions.x, quad_dimensions.y],
+                    height_scale: 1.0f32,
+                    do_shadow: 1
+                };
+
+                let draw_parameters = glium::DrawParameters {
+                    blend: glium::draw_parameters::Blend::alpha_blending(),
+                    ..Default::default()
+                };
+
+                ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.rounded_rectangle_shader, &uniforms, &draw_parameters)
+                    .unwrap();
+            }
+
+            // Draw friendly name
+            let option = &mut self.options[i];
+            draw_text_centered(
+                text_system,
+                font.clone(),
+                &option.friendly_name,
+                &glm::vec2(position.x, position.y + 5.0),
+                ctx,
+                text_color,
+            );
+        }
+    }
+}
+
+impl RadialMenuOption {
+    pub fn new(friendly_name: impl Into<String>, action: ContextAction) -> Self {
+        Self {
+            friendly_name: friendly_name.into(),
+            action,
+        }
+    }
+}
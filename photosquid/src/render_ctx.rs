@@ -1,26 +1,37 @@
 use crate::{
+    aabb::AABB,
+    affine::Affine2,
     camera::Camera,
-    clearable::Clearable,
-    color::Color,
     color_scheme::ColorScheme,
     mesh::{MeshXyz, MeshXyzUv},
+    units::LengthFormat,
 };
 use glium::{framebuffer::SimpleFrameBuffer, Display, Frame};
-use glium_text_rusttype::{self as glium_text, FontTexture, TextDisplay, TextSystem};
+use glium_text_rusttype::{self as glium_text, FontTexture, TextBatch, TextSystem};
 use nalgebra_glm as glm;
+use std::rc::Rc;
 
 pub struct RenderCtx<'a, 'f> {
     pub target: &'f mut Frame,
     pub framebuffer: &'f mut SimpleFrameBuffer<'f>,
     pub color_shader: &'a glium::Program,
+    pub batch_shader: &'a glium::Program,
     pub hue_value_picker_shader: &'a glium::Program,
     pub saturation_picker_shader: &'a glium::Program,
+    pub alpha_picker_shader: &'a glium::Program,
     pub rounded_rectangle_shader: &'a glium::Program,
+    pub television_shader: &'a glium::Program,
+    pub blur_shader: &'a glium::Program,
     pub projection: &'a glm::Mat4,
-    pub view: &'a glm::Mat4,
+    pub view: &'a Affine2,
     pub width: f32,
     pub height: f32,
     pub scale_factor: f64,
+
+    // Whether to draw straight to the window, so its own multisampling is used,
+    // rather than to the framebuffer
+    pub draw_directly: bool,
+
     pub ribbon_mesh: &'a MeshXyz,
     pub ring_mesh: &'a MeshXyz,
     pub check_mesh: &'a MeshXyz,
@@ -29,19 +40,20 @@ pub struct RenderCtx<'a, 'f> {
     pub camera: &'a Camera,
     pub real_camera: &'a Camera,
     pub display: &'a Display,
+
+    // Area (in logical pixels) that drawing is restricted to
+    pub clip: Option<AABB>,
+
+    // Text waiting to be drawn all at once, along with the clipping area it was queued with.
+    // The batch is handed back to the app afterwards, so it can be reused next frame
+    pub text_batch: Option<TextBatch<Rc<FontTexture>>>,
+    pub text_clip: Option<AABB>,
+
+    // How lengths are shown in user inputs
+    pub length_format: LengthFormat,
 }
 
 impl RenderCtx<'_, '_> {
-    pub fn clear_color(&mut self, color: &Color) {
-        if self.scale_factor > 1.0 {
-            // Non-MSAA
-            color.clear_framebuffer_with(self.framebuffer);
-        } else {
-            // MSAA
-            color.clear_with(self.target);
-        }
-    }
-
     pub fn draw<'a, 'b, V, I, U>(
         &mut self,
         vertex_buffer: V,
@@ -57,26 +69,100 @@ impl RenderCtx<'_, '_> {
     {
         use glium::Surface;
 
-        if self.scale_factor > 1.0 {
-            // Non-MSAA
-            self.framebuffer.draw(vertex_buffer, index_buffer, program, uniforms, draw_parameters)
+        let clipped_draw_parameters;
+        let draw_parameters = if self.clip.is_some() {
+            clipped_draw_parameters = glium::DrawParameters {
+                scissor: self.get_scissor(self.clip),
+                ..draw_parameters.clone()
+            };
+            &clipped_draw_parameters
         } else {
-            // MSAA
+            draw_parameters
+        };
+
+        if self.draw_directly {
             self.target.draw(vertex_buffer, index_buffer, program, uniforms, draw_parameters)
+        } else {
+            self.framebuffer.draw(vertex_buffer, index_buffer, program, uniforms, draw_parameters)
         }
     }
 
-    pub fn draw_text<F, M>(&mut self, text: &TextDisplay<F>, text_system: &TextSystem, matrix: M, color: (f32, f32, f32, f32)) -> Result<(), glium::DrawError>
+    // Queues text to be drawn on top of everything else in the current panel,
+    // see 'flush_text'
+    pub fn draw_text<M>(&mut self, text_system: &TextSystem, font: Rc<FontTexture>, text: &str, matrix: M, color: (f32, f32, f32, f32))
     where
         M: Into<[[f32; 4]; 4]>,
-        F: std::ops::Deref<Target = FontTexture>,
     {
-        if self.scale_factor > 1.0 {
-            // Non-MSAA
-            glium_text::draw(text, text_system, self.framebuffer, matrix, color)
+        let needs_flush = self
+            .text_batch
+            .as_ref()
+            .is_some_and(|batch| !batch.is_empty() && (!Rc::ptr_eq(batch.get_texture(), &font) || self.text_clip != self.clip));
+
+        if needs_flush {
+            self.flush_text(text_system);
+        }
+
+        // Batches are reused for as long as the font stays the same, so their buffers don't have to be reallocated
+        if !self.text_batch.as_ref().is_some_and(|batch| Rc::ptr_eq(batch.get_texture(), &font)) {
+            self.text_batch = Some(TextBatch::new(text_system, font));
+        }
+
+        let text_batch = self.text_batch.as_mut().unwrap();
+
+        if text_batch.is_empty() {
+            self.text_clip = self.clip;
+        }
+
+        text_batch.push(text, matrix, color);
+    }
+
+    // Draws all queued text in a single draw call
+    pub fn flush_text(&mut self, text_system: &TextSystem) {
+        if self.text_batch.as_ref().is_none_or(TextBatch::is_empty) {
+            return;
+        }
+
+        let draw_parameters = glium::DrawParameters {
+            scissor: self.get_scissor(self.text_clip),
+            ..glium_text::default_draw_parameters()
+        };
+        let sampler_behavior = glium_text::default_sampler_behavior();
+        let text_batch = self.text_batch.as_mut().unwrap();
+
+        if self.draw_directly {
+            glium_text::draw_batch_with_params(text_batch, text_system, self.target, sampler_behavior, &draw_parameters)
         } else {
-            // MSAA
-            glium_text::draw(text, text_system, self.target, matrix, color)
+            glium_text::draw_batch_with_params(text_batch, text_system, self.framebuffer, sampler_behavior, &draw_parameters)
         }
+        .unwrap();
+    }
+
+    // Size in physical pixels of what's being drawn to
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        use glium::Surface;
+
+        if self.draw_directly {
+            self.target.get_dimensions()
+        } else {
+            self.framebuffer.get_dimensions()
+        }
+    }
+
+    // Converts the clipping area into a scissor rectangle in physical pixels
+    fn get_scissor(&self, clip: Option<AABB>) -> Option<glium::Rect> {
+        clip.map(|clip| {
+            let scale_factor = self.scale_factor as f32;
+            let min_x = (clip.min_x * scale_factor).max(0.0);
+            let min_y = ((self.height - clip.max_y) * scale_factor).max(0.0);
+            let max_x = (clip.max_x * scale_factor).max(min_x);
+            let max_y = ((self.height - clip.min_y) * scale_factor).max(min_y);
+
+            glium::Rect {
+                left: min_x as u32,
+                bottom: min_y as u32,
+                width: (max_x - min_x) as u32,
+                height: (max_y - min_y) as u32,
+            }
+        })
     }
 }
@@ -0,0 +1,63 @@
+// How smoothly the canvas is drawn, traded off against speed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderQuality {
+    // Samples per pixel for multisample anti-aliasing, where 0 turns it off
+    pub msaa: u16,
+
+    // How many times larger than the window the canvas is drawn when drawing indirectly,
+    // before being shrunk down to fit
+    pub supersampling: f32,
+}
+
+impl RenderQuality {
+    pub const MSAA_LEVELS: [u16; 5] = [0, 2, 4, 8, 16];
+    pub const MAX_SUPERSAMPLING: f32 = 4.0;
+
+    // Rounds down to the nearest supported settings
+    pub fn new(msaa: u16, supersampling: f32) -> Self {
+        Self {
+            msaa: Self::MSAA_LEVELS.iter().copied().filter(|level| *level <= msaa).max().unwrap_or_default(),
+            supersampling: if supersampling.is_finite() {
+                supersampling.clamp(1.0, Self::MAX_SUPERSAMPLING)
+            } else {
+                1.0
+            },
+        }
+    }
+
+    // Drawing straight to the window is only possible when the window is already drawn at this quality,
+    // since its multisampling is chosen when it's created.
+    // Otherwise the canvas is drawn to a texture first, and then onto the window
+    pub fn can_draw_directly(&self, scale_factor: f64, window_msaa: u16) -> bool {
+        scale_factor == 1.0 && self.supersampling == 1.0 && self.msaa == window_msaa
+    }
+
+    pub fn get_texture_dimensions(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        let scale = |length: u32| ((length as f32 * self.supersampling).round() as u32).max(1);
+        (scale(width), scale(height))
+    }
+}
+
+impl Default for RenderQuality {
+    fn default() -> Self {
+        Self { msaa: 4, supersampling: 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderQuality;
+
+    #[test]
+    fn choose_render_quality() {
+        assert_eq!(RenderQuality::new(6, 0.5), RenderQuality { msaa: 4, supersampling: 1.0 });
+        assert_eq!(RenderQuality::new(64, f32::NAN).msaa, 16);
+        assert_eq!(RenderQuality::new(1, 2.0).get_texture_dimensions((640, 480)), (1280, 960));
+
+        // Only the window's own quality can be drawn directly
+        assert!(RenderQuality::default().can_draw_directly(1.0, 4));
+        assert!(!RenderQuality::default().can_draw_directly(2.0, 4));
+        assert!(!RenderQuality::new(8, 1.0).can_draw_directly(1.0, 4));
+        assert!(!RenderQuality::new(4, 1.5).can_draw_directly(1.0, 4));
+    }
+}
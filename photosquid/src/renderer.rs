@@ -0,0 +1,86 @@
+use crate::{affine::Affine2, as_values::AsValues, clearable::Clearable, color::Color, mesh::MeshXyz, render_ctx::RenderCtx, vertex::VertexXYRGBA};
+use glium::VertexBuffer;
+
+// Drawing that doesn't depend on which graphics API is underneath,
+// so code written against it can be moved to another backend without changes
+pub trait Renderer {
+    // Triangles uploaded ahead of time, in whatever form the backend keeps them
+    type Mesh;
+
+    fn clear_color(&mut self, color: &Color);
+
+    // Draws a mesh in a single color, where the transformation takes it straight onto the screen
+    fn draw_mesh(&mut self, mesh: &Self::Mesh, transformation: &Affine2, color: &Color);
+
+    // Draws a mesh in a single color in world space, blending it over what's already been drawn
+    fn draw_mesh_in_world(&mut self, mesh: &Self::Mesh, transformation: &Affine2, color: &Color);
+
+    // Draws a list of triangles that are in world space and carry their own colors,
+    // blending each one over what's already been drawn
+    fn draw_colored_triangles(&mut self, vertices: &[VertexXYRGBA]);
+}
+
+// OpenGL backend
+impl Renderer for RenderCtx<'_, '_> {
+    type Mesh = MeshXyz;
+
+    fn clear_color(&mut self, color: &Color) {
+        if self.draw_directly {
+            color.clear_with(self.target);
+        } else {
+            color.clear_framebuffer_with(self.framebuffer);
+        }
+    }
+
+    fn draw_mesh(&mut self, mesh: &MeshXyz, transformation: &Affine2, color: &Color) {
+        let identity = Affine2::identity();
+
+        let uniforms = glium::uniform! {
+            transformation: transformation.as_values(),
+            view: identity.as_values(),
+            projection: self.projection.as_values(),
+            color: color.as_values()
+        };
+
+        self.draw(&mesh.vertex_buffer, &mesh.indices, self.color_shader, &uniforms, &Default::default())
+            .unwrap();
+    }
+
+    fn draw_mesh_in_world(&mut self, mesh: &MeshXyz, transformation: &Affine2, color: &Color) {
+        let uniforms = glium::uniform! {
+            transformation: transformation.as_values(),
+            view: self.view.as_values(),
+            projection: self.projection.as_values(),
+            color: color.as_values()
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: glium::draw_parameters::Blend::alpha_blending(),
+            ..Default::default()
+        };
+
+        self.draw(&mesh.vertex_buffer, &mesh.indices, self.color_shader, &uniforms, &draw_parameters)
+            .unwrap();
+    }
+
+    fn draw_colored_triangles(&mut self, vertices: &[VertexXYRGBA]) {
+        if vertices.is_empty() {
+            return;
+        }
+
+        let vertex_buffer = VertexBuffer::new(self.display, vertices).unwrap();
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+
+        let uniforms = glium::uniform! {
+            view: self.view.as_values(),
+            projection: self.projection.as_values(),
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: glium::draw_parameters::Blend::alpha_blending(),
+            ..Default::default()
+        };
+
+        self.draw(&vertex_buffer, indices, self.batch_shader, &uniforms, &draw_parameters).unwrap();
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{aabb::AABB, app::App, color::Color, command::DocumentCommand, export::export, plugin, squid::Squid};
+use angular_units::Rad;
+use itertools::Itertools;
+use mlua::{HookTriggers, Lua, LuaOptions, Scope, StdLib, Value, Variadic};
+use nalgebra_glm as glm;
+use std::{
+    cell::{Cell, RefCell},
+    fs,
+    path::Path,
+};
+
+// Scripts are Lua, with functions for querying and changing the ocean added as globals.
+// Only the table, string and math libraries are loaded, so scripts can't touch the
+// rest of the system except through 'export' and 'run'
+pub const HELP: &[&str] = &[
+    "circle(x, y, radius), rect(x, y, width, height), tri(x, y, size)",
+    "shape(name, x, y, radius), for shapes from plugins",
+    "color(r, g, b), recolor(r, g, b), select_all(), select_none()",
+    "move(dx, dy), rotate(degrees), duplicate(), delete()",
+    "count(), find(name or tag), selected(), play()",
+    "export('file.svg'), run('file.lua'), for i = 1, 10 do ... end",
+];
+
+// Deepest that scripts can run other scripts, which stops a script that runs itself
+const MAX_RUN_DEPTH: usize = 16;
+
+// Most Lua instructions that a script can run, since scripts run on the UI thread
+const MAX_INSTRUCTIONS: usize = 10_000_000;
+const INSTRUCTIONS_PER_CHECK: u32 = 1000;
+
+// Most memory that a script can allocate
+const MAX_MEMORY: usize = 64 * 1024 * 1024;
+
+// Changes are emitted as document commands, so a whole script is undone at once.
+// Returns anything the script printed, along with the values of a lone expression
+pub fn run(source: &str, app: &mut App) -> Result<Vec<String>, String> {
+    let lua = create_lua().map_err(|error| describe_error(&error))?;
+
+    let runner = RefCell::new(Runner {
+        color: app.toolbox.color_picker.calculate_color(),
+        app,
+        output: vec![],
+        depth: 0,
+    });
+
+    let result = lua.scope(|scope| {
+        add_document_functions(&lua, scope, &runner)?;
+        add_query_functions(&lua, scope, &runner)?;
+        run_line(&lua, source)
+    });
+
+    match result {
+        Ok(values) => {
+            let mut output = runner.into_inner().output;
+            output.extend(values);
+            Ok(output)
+        }
+        Err(error) => Err(describe_error(&error)),
+    }
+}
+
+// Functions that change the document
+fn add_document_functions<'lua, 'scope, 'a: 'scope>(lua: &'lua Lua, scope: &Scope<'lua, 'scope>, runner: &'scope RefCell<Runner<'a>>) -> mlua::Result<()> {
+    let globals = lua.globals();
+
+    globals.set(
+        "circle",
+        scope.create_function(move |_, (x, y, radius): (f32, f32, f32)| {
+            runner.borrow_mut().insert(|color| Squid::circle(glm::vec2(x, y), radius, color));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "rect",
+        scope.create_function(move |_, (x, y, width, height): (f32, f32, f32, f32)| {
+            let (position, size) = (glm::vec2(x, y), glm::vec2(width, height));
+            runner.borrow_mut().insert(|color| Squid::rect(position, size, Rad(0.0), color, 0.0, false));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "tri",
+        scope.create_function(move |_, (x, y, size): (f32, f32, f32)| {
+            let half = 0.5 * size;
+            let center = glm::vec2(x, y);
+            let p = [center + glm::vec2(0.0, -half), center + glm::vec2(half, half), center + glm::vec2(-half, half)];
+            runner.borrow_mut().insert(|color| Squid::tri(p, Rad(0.0), color));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "shape",
+        scope.create_function(move |_, (shape, x, y, radius): (String, f32, f32, f32)| {
+            let mut runner = runner.borrow_mut();
+            let plugin = runner
+                .app
+                .plugins
+                .get_squid(&shape)
+                .ok_or_else(|| mlua::Error::RuntimeError(format!("Unknown shape '{}'", shape)))?;
+            let (anchors, closed) = plugin.build(radius);
+            let anchors = plugin::place(anchors, glm::vec2(x, y));
+            runner.insert(|color| Squid::path(anchors, closed, color));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "color",
+        scope.create_function(move |_, (r, g, b): (f32, f32, f32)| {
+            runner.borrow_mut().color = rgb(r, g, b);
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "recolor",
+        scope.create_function(move |_, (r, g, b): (f32, f32, f32)| {
+            runner.borrow_mut().app.emit(DocumentCommand::SetSelectedColor(rgb(r, g, b)));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "move",
+        scope.create_function(move |_, (dx, dy): (f32, f32)| {
+            runner.borrow_mut().app.emit(DocumentCommand::TranslateSelected(glm::vec2(dx, dy)));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "rotate",
+        scope.create_function(move |_, degrees: f32| {
+            runner.borrow_mut().app.emit(DocumentCommand::RotateSelected(Rad(degrees.to_radians())));
+            Ok(())
+        })?,
+    )?;
+
+    for (name, command) in [
+        ("select_all", DocumentCommand::SelectAll),
+        ("select_none", DocumentCommand::ClearSelection),
+        ("duplicate", DocumentCommand::DuplicateSelected),
+        ("delete", DocumentCommand::DeleteSelected),
+    ] {
+        globals.set(
+            name,
+            scope.create_function(move |_, ()| {
+                runner.borrow_mut().app.emit(command.clone());
+                Ok(())
+            })?,
+        )?;
+    }
+
+    Ok(())
+}
+
+// Functions that look at the document or work with files
+fn add_query_functions<'lua, 'scope, 'a: 'scope>(lua: &'lua Lua, scope: &Scope<'lua, 'scope>, runner: &'scope RefCell<Runner<'a>>) -> mlua::Result<()> {
+    let globals = lua.globals();
+
+    globals.set(
+        "count",
+        scope.create_function(move |_, ()| {
+            let mut runner = runner.borrow_mut();
+            runner.app.apply_commands();
+            Ok(runner.app.ocean.get_squids_unordered().count())
+        })?,
+    )?;
+
+    globals.set(
+        "find",
+        scope.create_function(move |_, filter: String| {
+            let mut runner = runner.borrow_mut();
+            runner.app.apply_commands();
+
+            let ocean = &runner.app.ocean;
+            Ok(ocean
+                .get_squids_lowest()
+                .filter_map(|reference| ocean.get(reference))
+                .filter(|squid| squid.matches_filter(&filter))
+                .map(describe)
+                .collect::<Vec<String>>())
+        })?,
+    )?;
+
+    globals.set(
+        "selected",
+        scope.create_function(move |_, ()| {
+            let mut runner = runner.borrow_mut();
+            runner.app.apply_commands();
+
+            let app = &runner.app;
+            Ok(app
+                .get_selected_squids()
+                .into_iter()
+                .filter_map(|reference| app.ocean.get(reference))
+                .map(describe)
+                .collect::<Vec<String>>())
+        })?,
+    )?;
+
+    globals.set(
+        "play",
+        scope.create_function(move |_, ()| {
+            runner.borrow_mut().app.play_macro();
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "help",
+        scope.create_function(move |_, ()| {
+            runner.borrow_mut().output.extend(HELP.iter().map(|line| line.to_string()));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "print",
+        scope.create_function(move |_, values: Variadic<Value>| {
+            let line = values.iter().map(|value| value.to_string()).collect::<mlua::Result<Vec<_>>>()?.join("  ");
+            runner.borrow_mut().output.push(line);
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "export",
+        scope.create_function(move |_, filename: String| {
+            let mut runner = runner.borrow_mut();
+            let app = &mut runner.app;
+            app.apply_commands();
+
+            let view_box = app.ocean.get_content_bounds().unwrap_or_else(|| AABB::new(0.0, 0.0, 1.0, 1.0));
+
+            export(Path::new(&filename), &view_box, &app.export_options.size, &app.ocean)
+                .map_err(|_| mlua::Error::RuntimeError(format!("Failed to export to '{}'", filename)))?;
+
+            runner.output.push(format!("Exported to '{}'", filename));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "run",
+        scope.create_function(move |lua, filename: String| {
+            let source = fs::read_to_string(&filename).map_err(|_| mlua::Error::RuntimeError(format!("Failed to read script '{}'", filename)))?;
+
+            runner.borrow_mut().enter_script()?;
+            let result = lua.load(&source).set_name(filename).exec();
+            runner.borrow_mut().depth -= 1;
+            result
+        })?,
+    )?;
+
+    Ok(())
+}
+
+fn create_lua() -> mlua::Result<Lua> {
+    let lua = Lua::new_with(StdLib::TABLE | StdLib::STRING | StdLib::MATH, LuaOptions::default())?;
+    lua.set_memory_limit(MAX_MEMORY)?;
+
+    // The base library can still read files, which is left to 'run'
+    for name in ["dofile", "loadfile"] {
+        lua.globals().set(name, Value::Nil)?;
+    }
+
+    let checks = Cell::new(0);
+
+    lua.set_hook(HookTriggers::new().every_nth_instruction(INSTRUCTIONS_PER_CHECK), move |_, _| {
+        checks.set(checks.get() + 1);
+
+        if checks.get() * INSTRUCTIONS_PER_CHECK as usize > MAX_INSTRUCTIONS {
+            return Err(mlua::Error::RuntimeError("Script took too long to run".into()));
+        }
+
+        Ok(())
+    });
+
+    Ok(lua)
+}
+
+// Runs a line from the console, which can be a lone expression like 'count()' to print
+fn run_line(lua: &Lua, source: &str) -> mlua::Result<Vec<String>> {
+    let function = match lua.load(format!("return {}", source)).set_name("console").into_function() {
+        Ok(function) => function,
+        Err(_) => {
+            lua.load(source).set_name("console").exec()?;
+            return Ok(vec![]);
+        }
+    };
+
+    let mut lines = vec![];
+
+    for value in function.call::<_, Variadic<Value>>(())? {
+        match value {
+            Value::Nil => (),
+            Value::Table(table) => {
+                for value in table.sequence_values::<Value>() {
+                    lines.push(value?.to_string()?);
+                }
+            }
+            value => lines.push(value.to_string()?),
+        }
+    }
+
+    Ok(lines)
+}
+
+// First line of an error, without Lua's stack traceback
+fn describe_error(error: &mlua::Error) -> String {
+    match error {
+        mlua::Error::CallbackError { cause, .. } => describe_error(cause),
+        mlua::Error::RuntimeError(message) | mlua::Error::SyntaxError { message, .. } => message.lines().next().unwrap_or_default().to_string(),
+        error => error.to_string(),
+    }
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::new(r / 255.0, g / 255.0, b / 255.0, 1.0)
+}
+
+struct Runner<'a> {
+    app: &'a mut App,
+    color: Color,
+    output: Vec<String>,
+
+    // How many scripts deep the runner is
+    depth: usize,
+}
+
+impl<'a> Runner<'a> {
+    fn insert(&mut self, create: impl FnOnce(Color) -> Squid) {
+        let squid = create(self.color);
+        self.app.emit(DocumentCommand::InsertAndSelect(Box::new(squid)));
+    }
+
+    fn enter_script(&mut self) -> mlua::Result<()> {
+        if self.depth >= MAX_RUN_DEPTH {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Scripts can't run other scripts more than {} deep",
+                MAX_RUN_DEPTH
+            )));
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+}
+
+// Name, kind and position of a squid on one line
+fn describe(squid: &Squid) -> String {
+    squid.get_info().iter().take(2).join("  ")
+}
+
+// Evaluates an expression typed in place of a number, such as '1920/2+16'
+pub fn evaluate_number(text: &str) -> Option<f32> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    parse_expression(&text)
+        .map(|expression| expression.evaluate())
+        .filter(|value| value.is_finite())
+}
+
+#[derive(Debug, PartialEq)]
+enum Expression {
+    Number(f32),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, char, Box<Expression>),
+}
+
+// Deepest that parentheses and negations can nest, which stops long inputs like '((((...' from overflowing the stack
+const MAX_NESTING: usize = 64;
+
+fn parse_expression(text: &str) -> Option<Expression> {
+    let mut parser = ExpressionParser {
+        characters: text.chars().collect(),
+        next: 0,
+        depth: 0,
+    };
+
+    parser.parse_sum().filter(|_| parser.next == parser.characters.len())
+}
+
+struct ExpressionParser {
+    characters: Vec<char>,
+    next: usize,
+    depth: usize,
+}
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.next).copied()
+    }
+
+    fn parse_sum(&mut self) -> Option<Expression> {
+        let mut expression = self.parse_product()?;
+
+        while let Some(operator) = self.peek().filter(|c| *c == '+' || *c == '-') {
+            self.next += 1;
+            expression = Expression::Binary(Box::new(expression), operator, Box::new(self.parse_product()?));
+        }
+
+        Some(expression)
+    }
+
+    fn parse_product(&mut self) -> Option<Expression> {
+        let mut expression = self.parse_factor()?;
+
+        while let Some(operator) = self.peek().filter(|c| *c == '*' || *c == '/' || *c == '%') {
+            self.next += 1;
+            expression = Expression::Binary(Box::new(expression), operator, Box::new(self.parse_factor()?));
+        }
+
+        Some(expression)
+    }
+
+    fn parse_factor(&mut self) -> Option<Expression> {
+        match self.peek()? {
+            '-' => {
+                self.next += 1;
+                let value = self.parse_nested(Self::parse_factor)?;
+                Some(Expression::Negate(Box::new(value)))
+            }
+            '(' => {
+                self.next += 1;
+                let expression = self.parse_nested(Self::parse_sum)?;
+                (self.peek()? == ')').then_some(())?;
+                self.next += 1;
+                Some(expression)
+            }
+            _ => {
+                let start = self.next;
+
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.next += 1;
+                }
+
+                self.characters[start..self.next]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .ok()
+                    .map(Expression::Number)
+            }
+        }
+    }
+
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Option<Expression>) -> Option<Expression> {
+        if self.depth >= MAX_NESTING {
+            return None;
+        }
+
+        self.depth += 1;
+        let expression = parse(self);
+        self.depth -= 1;
+        expression
+    }
+}
+
+impl Expression {
+    fn evaluate(&self) -> f32 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Negate(value) => -value.evaluate(),
+            Self::Binary(a, operator, b) => {
+                let (a, b) = (a.evaluate(), b.evaluate());
+
+                match operator {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    '/' => a / b,
+                    _ => a.rem_euclid(b),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_lua, describe_error, evaluate_number, run_line, MAX_NESTING};
+
+    #[test]
+    fn evaluate_expressions() {
+        assert_eq!(evaluate_number("12.5"), Some(12.5));
+        assert_eq!(evaluate_number("1920 / 2 + 16"), Some(976.0));
+        assert_eq!(evaluate_number("1+2*3"), Some(7.0));
+        assert_eq!(evaluate_number("(1+2)*3"), Some(9.0));
+        assert_eq!(evaluate_number("-7%4"), Some(1.0));
+
+        assert_eq!(evaluate_number("2*"), None);
+        assert_eq!(evaluate_number("(1"), None);
+        assert_eq!(evaluate_number("x"), None);
+        assert_eq!(evaluate_number("1/0"), None);
+    }
+
+    #[test]
+    fn expression_nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert_eq!(evaluate_number(&nested(MAX_NESTING)), Some(1.0));
+        assert_eq!(evaluate_number(&nested(MAX_NESTING + 1)), None);
+
+        // Long inputs are rejected instead of overflowing the stack
+        assert_eq!(evaluate_number(&nested(100_000)), None);
+        assert_eq!(evaluate_number(&format!("{}1", "-".repeat(100_000))), None);
+    }
+
+    #[test]
+    fn console_lines() {
+        let lua = create_lua().unwrap();
+
+        assert_eq!(run_line(&lua, "1 + 2, 'squid'").unwrap(), vec!["3", "squid"]);
+        assert_eq!(run_line(&lua, "{ 'a', 'b' }").unwrap(), vec!["a", "b"]);
+        assert_eq!(run_line(&lua, "x = 4").unwrap(), Vec::<String>::new());
+        assert_eq!(run_line(&lua, "x * 2").unwrap(), vec!["8"]);
+
+        let error = run_line(&lua, "error('oops')").unwrap_err();
+        assert_eq!(describe_error(&error), "[string \"console\"]:1: oops");
+    }
+
+    #[test]
+    fn script_limits() {
+        let lua = create_lua().unwrap();
+
+        // Scripts that never finish are stopped instead of freezing the UI thread
+        let error = run_line(&lua, "while true do end").unwrap_err();
+        assert!(describe_error(&error).contains("too long"));
+
+        // Scripts can't reach the rest of the system
+        assert_eq!(
+            run_line(&lua, "io == nil and os == nil and require == nil and dofile == nil").unwrap(),
+            vec!["true"]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{aabb::AABB, affine::Affine2, as_values::AsValues, draw_text::draw_text, render_ctx::RenderCtx, script};
+use glium::glutin::event::VirtualKeyCode;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Overlay for typing in scripts and seeing what they print
+pub struct ScriptConsole {
+    input: String,
+    lines: Vec<ConsoleLine>,
+    previous_input: Option<String>,
+}
+
+struct ConsoleLine {
+    text: String,
+    is_error: bool,
+}
+
+pub enum ScriptConsoleResponse {
+    Continue,
+    Close,
+    Run(String),
+}
+
+impl ScriptConsole {
+    const WIDTH: f32 = 640.0;
+    const LINE_HEIGHT: f32 = 30.0;
+    const MAX_LINES: usize = 12;
+    const BOTTOM_MARGIN: f32 = 64.0;
+
+    pub fn new() -> Self {
+        let mut console = Self {
+            input: String::new(),
+            lines: vec![],
+            previous_input: None,
+        };
+        for line in script::HELP {
+            console.print(line.to_string(), false);
+        }
+        console
+    }
+
+    pub fn print(&mut self, text: String, is_error: bool) {
+        self.lines.push(ConsoleLine { text, is_error });
+
+        if self.lines.len() > Self::MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+
+    pub fn type_character(&mut self, character: char) {
+        if !character.is_control() {
+            self.input.push(character);
+        }
+    }
+
+    pub fn press_key(&mut self, key: VirtualKeyCode) -> ScriptConsoleResponse {
+        match key {
+            VirtualKeyCode::Escape => return ScriptConsoleResponse::Close,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter if !self.input.trim().is_empty() => {
+                let input = std::mem::take(&mut self.input);
+                self.print(format!("> {}", input), false);
+                self.previous_input = Some(input.clone());
+                return ScriptConsoleResponse::Run(input);
+            }
+            VirtualKeyCode::Back => {
+                self.input.pop();
+            }
+            VirtualKeyCode::Up => {
+                if let Some(previous_input) = &self.previous_input {
+                    self.input = previous_input.clone();
+                }
+            }
+            _ => (),
+        }
+
+        ScriptConsoleResponse::Continue
+    }
+
+    pub fn click(&self, position: &glm::Vec2, window_width: f32, window_height: f32) -> ScriptConsoleResponse {
+        if Self::get_area(window_width, window_height).intersecting_point(position.x, position.y) {
+            ScriptConsoleResponse::Continue
+        } else {
+            ScriptConsoleResponse::Close
+        }
+    }
+
+    // Sits along the bottom of the canvas with room for the most recent lines and the input
+    fn get_area(window_width: f32, window_height: f32) -> AABB {
+        let height = Self::LINE_HEIGHT * (Self::MAX_LINES + 1) as f32;
+        AABB::new(
+            0.5 * (window_width - Self::WIDTH),
+            window_height - Self::BOTTOM_MARGIN - height,
+            Self::WIDTH,
+            height,
+        )
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+        let area = Self::get_area(ctx.width, ctx.height);
+
+        // Render background
+        {
+            let mesh = ctx.square_xyzuv;
+            let identity = Affine2::identity();
+            let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
+            let transformation = Affine2::translation(&glm::vec2(area.center_x(), area.center_y()));
+            let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
+
+            let uniforms = glium::uniform! {
+                transformation: transformation.as_values(),
+                view: identity.as_values(),
+                projection: ctx.projection.as_values(),
+                rectangle_color: ctx.color_scheme.dark_ribbon.as_values(),
+                dimensions: [quad_dimensions.x, quad_dimensions.y],
+                height_scale: 1.0f32,
+                do_shadow: 1
+            };
+
+            let draw_parameters = glium::DrawParameters {
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            };
+
+            ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.rounded_rectangle_shader, &uniforms, &draw_parameters)
+                .unwrap();
+        }
+
+        let left = area.min_x + 16.0;
+        let baseline = |i: usize| area.min_y + (i as f32 + 0.5) * Self::LINE_HEIGHT + 6.0;
+
+        // Draw output, with the newest lines closest to the input
+        let first = Self::MAX_LINES - self.lines.len();
+
+        for (i, line) in self.lines.iter().enumerate() {
+            let color = if line.is_error {
+                ctx.color_scheme.error
+            } else {
+                ctx.color_scheme.secondary_text
+            };
+
+            draw_text(text_system, font.clone(), &line.text, &glm::vec2(left, baseline(first + i)), ctx, color);
+        }
+
+        // Draw input
+        let (input, input_color) = if self.input.is_empty() {
+            ("Type a script...", ctx.color_scheme.label)
+        } else {
+            (self.input.as_str(), ctx.color_scheme.text)
+        };
+
+        draw_text(text_system, font, input, &glm::vec2(left, baseline(Self::MAX_LINES)), ctx, input_color);
+    }
+}
@@ -2,10 +2,13 @@ use crate::shader;
 
 pub struct Shaders {
     pub color_shader: glium::Program,
+    pub batch_shader: glium::Program,
     pub hue_value_picker_shader: glium::Program,
     pub saturation_picker_shader: glium::Program,
+    pub alpha_picker_shader: glium::Program,
     pub rounded_rectangle_shader: glium::Program,
     pub television_shader: glium::Program,
+    pub blur_shader: glium::Program,
 }
 
 impl Shaders {
@@ -21,6 +24,15 @@ impl Shaders {
         )
         .unwrap();
 
+        let batch_shader = from_code_that_outputs_srgb(
+            display,
+            include_str!("_src_shaders/batch/vertex.glsl"),
+            include_str!("_src_shaders/batch/fragment.glsl"),
+            None,
+            true,
+        )
+        .unwrap();
+
         let hue_value_picker_shader = from_code_that_outputs_srgb(
             display,
             include_str!("_src_shaders/color_picker/hue_value/vertex.glsl"),
@@ -39,6 +51,15 @@ impl Shaders {
         )
         .unwrap();
 
+        let alpha_picker_shader = from_code_that_outputs_srgb(
+            display,
+            include_str!("_src_shaders/color_picker/alpha/vertex.glsl"),
+            include_str!("_src_shaders/color_picker/alpha/fragment.glsl"),
+            None,
+            true,
+        )
+        .unwrap();
+
         let rounded_rectangle_shader = from_code_that_outputs_srgb(
             display,
             include_str!("_src_shaders/rounded_rectangle/vertex.glsl"),
@@ -57,12 +78,24 @@ impl Shaders {
         )
         .unwrap();
 
+        let blur_shader = from_code_that_outputs_srgb(
+            display,
+            include_str!("_src_shaders/blur/vertex.glsl"),
+            include_str!("_src_shaders/blur/fragment.glsl"),
+            None,
+            false,
+        )
+        .unwrap();
+
         Self {
             color_shader,
+            batch_shader,
             hue_value_picker_shader,
             saturation_picker_shader,
+            alpha_picker_shader,
             rounded_rectangle_shader,
             television_shader,
+            blur_shader,
         }
     }
 }
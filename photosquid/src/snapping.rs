@@ -0,0 +1,50 @@
+use crate::aabb::AABB;
+use nalgebra_glm as glm;
+
+// How close in pixels an edge or center has to come to one of another squid to snap to it
+pub const OBJECT_SNAP_DISTANCE: f32 = 8.0;
+
+// Smallest nudge that lines up an edge or the center of the bounds being moved with
+// an edge or center of one of the others along each axis, where axes with nothing within reach aren't nudged
+pub fn get_object_snap(moving: &AABB, others: &[AABB], reach: f32) -> glm::Vec2 {
+    let x = get_closest_offset(
+        [moving.min_x, moving.center_x(), moving.max_x],
+        others.iter().flat_map(|other| [other.min_x, other.center_x(), other.max_x]),
+        reach,
+    );
+
+    let y = get_closest_offset(
+        [moving.min_y, moving.center_y(), moving.max_y],
+        others.iter().flat_map(|other| [other.min_y, other.center_y(), other.max_y]),
+        reach,
+    );
+
+    glm::vec2(x, y)
+}
+
+fn get_closest_offset(from: [f32; 3], to: impl Iterator<Item = f32>, reach: f32) -> f32 {
+    to.flat_map(|target| from.map(|value| target - value))
+        .filter(|offset| offset.abs() <= reach)
+        .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_object_snap;
+    use crate::aabb::AABB;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn object_snap() {
+        let others = [AABB::new(0.0, 0.0, 100.0, 100.0), AABB::new(300.0, 40.0, 20.0, 20.0)];
+
+        // Left edge lines up with the right edge of the first, and centers line up vertically
+        let moving = AABB::new(103.0, 44.0, 50.0, 10.0);
+        assert_eq!(get_object_snap(&moving, &others, 8.0), glm::vec2(-3.0, 1.0));
+
+        // Nothing close enough
+        let moving = AABB::new(150.0, 200.0, 50.0, 10.0);
+        assert_eq!(get_object_snap(&moving, &others, 8.0), glm::vec2(0.0, 0.0));
+    }
+}
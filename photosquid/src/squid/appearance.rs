@@ -0,0 +1,121 @@
+use super::{Squid, SquidKind};
+use crate::{color::Color, data::rect::BorderRadii, style::ColorStyle};
+
+// Everything about how a squid looks apart from its shape, which can be copied from one squid to another
+// or saved as a style
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Appearance {
+    pub color: Color,
+
+    // Radii of rounded corners, if the squid can have them
+    pub radii: Option<BorderRadii>,
+
+    pub blur: f32,
+}
+
+impl Appearance {
+    pub fn to_style(self, name: String) -> ColorStyle {
+        ColorStyle {
+            name,
+            color: self.color,
+            radii: self.radii,
+            blur: Some(self.blur),
+        }
+    }
+
+    // Same look with whatever the style sets instead
+    pub fn with_style(&self, style: &ColorStyle) -> Appearance {
+        Appearance {
+            color: style.color,
+            radii: style.radii.filter(|_| self.radii.is_some()).or(self.radii),
+            blur: style.blur.unwrap_or(self.blur),
+        }
+    }
+}
+
+impl Squid {
+    pub fn get_appearance(&self) -> Appearance {
+        let radii = match &self.kind {
+            SquidKind::Rect(rect) => Some(rect.data.get_real().radii),
+            SquidKind::Tri(tri) => Some(BorderRadii::new(tri.data.get_real().corner_radius)),
+            SquidKind::Path(path) => Some(BorderRadii::new(path.data.get_real().corner_radius)),
+            SquidKind::Circle(_) => None,
+        };
+
+        Appearance {
+            color: self.get_color(),
+            radii,
+            blur: self.blur,
+        }
+    }
+
+    // Takes on the look of another squid without changing shape. Shapes with a single
+    // corner radius are rounded as much as the roundest corner of a rect
+    pub fn set_appearance(&mut self, appearance: &Appearance) {
+        self.blur = appearance.blur;
+        self.set_color(appearance.color);
+
+        let radii = match appearance.radii {
+            Some(radii) => radii,
+            None => return,
+        };
+
+        let corner_radius = radii.to_array().iter().copied().fold(0.0, f32::max);
+
+        match &mut self.kind {
+            SquidKind::Rect(rect) => {
+                let mut new_data = *rect.data.get_real();
+                new_data.radii = radii;
+                rect.data.set(new_data);
+            }
+            SquidKind::Tri(tri) => {
+                let mut new_data = *tri.data.get_real();
+                new_data.corner_radius = corner_radius;
+                tri.data.set(new_data);
+            }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.corner_radius = corner_radius;
+                path.data.set(new_data);
+            }
+            SquidKind::Circle(_) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, data::rect::BorderRadii, squid::Squid};
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn paste_appearance() {
+        let red = Color::new(1.0, 0.0, 0.0, 0.5);
+        let mut rect = Squid::rect(glm::zero(), glm::vec2(100.0, 60.0), Rad(0.0), red, 0.0, false);
+        rect.set_appearance(&super::Appearance {
+            color: red,
+            radii: Some([4.0, 8.0, 0.0, 2.0].into()),
+            blur: 3.0,
+        });
+
+        // Circles take the color and blur but have no corners
+        let mut circle = Squid::circle(glm::vec2(50.0, 50.0), 10.0, Color::white());
+        circle.set_appearance(&rect.get_appearance());
+        assert_eq!(circle.get_color(), red);
+        assert_eq!(circle.get_blur(), 3.0);
+        assert_eq!(circle.get_appearance().radii, None);
+
+        // Triangles take the roundest corner, and copying from them rounds every corner of a rect the same
+        let mut tri = Squid::tri([glm::zero(), glm::vec2(10.0, 0.0), glm::vec2(0.0, 10.0)], Rad(0.0), Color::white());
+        tri.set_appearance(&rect.get_appearance());
+        assert_eq!(tri.get_appearance().radii, Some(BorderRadii::new(8.0)));
+
+        rect.set_appearance(&tri.get_appearance());
+        assert_eq!(rect.get_appearance().radii, Some(BorderRadii::new(8.0)));
+        assert_eq!(
+            rect.get_extreme_points(),
+            Squid::rect(glm::zero(), glm::vec2(100.0, 60.0), Rad(0.0), red, 0.0, false).get_extreme_points()
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use super::{
-    behavior::{DilateBehavior, RevolveBehavior, SpreadBehavior, TranslateBehavior},
-    Initiation, PreviewParams, HANDLE_RADIUS,
+    behavior::{DilateBehavior, RevolveBehavior, SpreadBehavior, TranslateBehavior, SHIFT_ROTATION_SNAPPING},
+    get_even_factor, Drawable, Initiation, PreviewParams, HANDLE_RADIUS,
 };
 use crate::{
     accumulator::Accumulator,
-    as_values::AsValues,
+    affine::Affine2,
     camera::Camera,
     capture::Capture,
     components,
@@ -12,18 +12,19 @@ use crate::{
     interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
     math::angle_difference,
     mesh::MeshXyz,
-    render_ctx::RenderCtx,
-    smooth::Smooth,
+    mesh_cache::{MeshCache, MeshKey},
+    smooth::{MultiLerp, Smooth},
 };
 use angular_units::Rad;
-use glium::glutin::event::MouseButton;
+use glium::{glutin::event::MouseButton, Display};
 use nalgebra_glm as glm;
 use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize)]
 pub struct Circle {
     #[serde(skip)]
-    pub mesh: Option<MeshXyz>,
+    pub mesh: Option<Rc<MeshXyz>>,
 
     pub data: Smooth<CircleData>,
 
@@ -57,12 +58,15 @@ pub struct Circle {
 }
 
 impl Circle {
-    pub fn render(&mut self, ctx: &mut RenderCtx, as_preview: Option<PreviewParams>) {
-        let CircleData { position, radius, color, .. } = self.data.get_animated();
-
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache) {
         if self.mesh.is_none() {
-            self.mesh = Some(MeshXyz::new_shape_circle(ctx.display));
+            self.mesh = Some(mesh_cache.get(MeshKey::Circle, display));
         }
+    }
+
+    pub fn get_drawable(&self, as_preview: Option<PreviewParams>) -> Option<Drawable<'_>> {
+        let CircleData { position, radius, color, .. } = self.data.get_animated();
+        let mesh = self.mesh.as_ref()?;
 
         let (render_position, render_radius) = if let Some(preview) = &as_preview {
             (preview.position, preview.radius * 0.5)
@@ -70,23 +74,14 @@ impl Circle {
             (position.reveal(), radius)
         };
 
-        let mut transformation = glm::translation(&glm::vec2_to_vec3(&render_position));
-        transformation = glm::scale(&transformation, &glm::vec3(render_radius, render_radius, 0.0));
-
-        let uniforms = glium::uniform! {
-            transformation: transformation.as_values(),
-            view: if as_preview.is_some() {
-                glm::identity::<f32, 4>().as_values()
-            } else {
-                ctx.view.as_values()
-            },
-            projection: ctx.projection.as_values(),
-            color: color.as_values()
-        };
+        let mut transformation = Affine2::translation(&render_position);
+        transformation = transformation.scale(&glm::vec2(render_radius, render_radius));
 
-        let mesh = self.mesh.as_ref().unwrap();
-        ctx.draw(&mesh.vertex_buffer, &mesh.indices, ctx.color_shader, &uniforms, &Default::default())
-            .unwrap();
+        Some(Drawable {
+            mesh,
+            transformation,
+            color: *color,
+        })
     }
 
     pub fn interact(&mut self, interaction: &Interaction, camera: &Camera) -> Capture {
@@ -112,10 +107,10 @@ impl Circle {
                     return Capture::AllowDrag;
                 }
             }
-            Interaction::Drag(DragInteraction { current, delta, .. }) => {
+            Interaction::Drag(DragInteraction { current, delta, modifiers, .. }) => {
                 if self.scale_rotating {
                     // Since rotating and scaling at same time, it doesn't apply to others
-                    self.reposition_radius(current, camera);
+                    self.reposition_radius(current, camera, modifiers.shift());
                 } else if self.translate_behavior.moving {
                     return Capture::MoveSelectedSquids {
                         delta_in_world: camera.apply_reverse_to_vector(delta),
@@ -174,26 +169,45 @@ impl Circle {
         glm::distance(&real.position.reveal(), &point) < real.radius
     }
 
-    pub fn build(&self, document: &mut svg::Document) {
+    // Circles stay round, so they're scaled evenly
+    pub fn stretch(&mut self, factor: &glm::Vec2, origin: &glm::Vec2) {
+        let factor = get_even_factor(factor);
+
+        let mut new_data = *self.data.get_real();
+        new_data.radius *= factor;
+        new_data.position = MultiLerp::Linear(origin + (new_data.position.reveal() - origin) * factor);
+        self.data.set(new_data);
+    }
+
+    pub fn build(&self, document: &mut svg::Document, opacity: f32) {
         use svg::Node;
 
-        let CircleData { position, radius, .. } = self.data.get_real();
+        let CircleData { position, radius, color, .. } = self.data.get_real();
         let position = position.reveal();
 
         let circle = svg::node::element::Circle::new()
             .set("r", *radius)
             .set("cx", position.x)
             .set("cy", position.y)
-            .set("color", "rgba(255, 0, 0, 0)");
+            .set("fill", color.to_rgb_hex())
+            .set("fill-opacity", color.a * opacity);
         document.append(circle);
     }
 
-    fn reposition_radius(&mut self, mouse: &glm::Vec2, camera: &Camera) {
+    fn reposition_radius(&mut self, mouse: &glm::Vec2, camera: &Camera, shift: bool) {
         let real_in_world = self.data.get_real();
         let target_in_world = camera.apply_reverse(mouse);
+        let delta_theta = self.get_delta_rotation(mouse, camera);
+
+        // Circles only snap their turning while shift is held
+        let snapping = if shift { SHIFT_ROTATION_SNAPPING } else { Rad(0.0) };
 
         let mut new_data = *real_in_world;
-        new_data.virtual_rotation += self.get_delta_rotation(mouse, camera);
+
+        if let Some(delta_theta) = self.rotation_accumulator.accumulate(&delta_theta, snapping) {
+            new_data.virtual_rotation += delta_theta;
+        }
+
         new_data.radius = glm::distance(&real_in_world.position.reveal(), &target_in_world);
         self.data.set(new_data);
     }
@@ -203,7 +217,7 @@ impl Circle {
         let screen_position = camera.apply(&real.position.reveal());
 
         let old_rotation = real.virtual_rotation + *self.rotation_accumulator.residue();
-        let new_rotation = Rad(-1.0 * (mouse_position.y - screen_position.y).atan2(mouse_position.x - screen_position.x));
+        let new_rotation = camera.apply_reverse_to_rotation(Rad(-1.0 * (mouse_position.y - screen_position.y).atan2(mouse_position.x - screen_position.x)));
 
         angle_difference(old_rotation, new_rotation)
     }
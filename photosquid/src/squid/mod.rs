@@ -1,30 +1,47 @@
-pub mod behavior;
+mod appearance;
 mod circle;
+pub mod path;
+mod property;
 mod rect;
 mod tri;
 
 use self::behavior::TranslateBehavior;
 use crate::{
+    aabb::AABB,
     accumulator::Accumulator,
+    affine::Affine2,
     algorithm::get_triangle_center,
     approx_instant,
+    arrange::Axis,
     camera::{Camera, IDENTITY_CAMERA},
     capture::Capture,
     color::Color,
     color_scheme::ColorScheme,
     context_menu::{ContextAction, ContextMenu, ContextMenuOption},
-    data::{rect::BorderRadii, CircleData, RectData, TriData},
+    data::{
+        path::{round_each_corner, PathAnchor},
+        rect::BorderRadii,
+        CircleData, PathData, RectData, TriData,
+    },
     interaction::Interaction,
     interaction_options::InteractionOptions,
-    render_ctx::RenderCtx,
+    mesh::MeshXyz,
+    mesh_cache::MeshCache,
+    ocean::Similarity,
     selection::{NewSelection, NewSelectionInfo, Selection},
     smooth::{MultiLerp, NoLerp, Smooth},
+    style::StyleRef,
 };
-use angular_units::Rad;
+use angular_units::{Angle, Rad};
+pub use appearance::Appearance;
 use circle::Circle;
+use glium::Display;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use nalgebra_glm as glm;
+use path::Path;
+pub use photosquid_core::squid::{behavior, SquidId, SquidRef};
+pub use property::SquidProperty;
 use rect::Rect;
 use serde::{Deserialize, Serialize};
 use slotmap::new_key_type;
@@ -32,7 +49,6 @@ use std::{cmp::Ordering, time::Instant};
 use tri::Tri;
 
 new_key_type! {
-    pub struct SquidRef;
     pub struct SquidLimbRef;
 }
 
@@ -41,16 +57,31 @@ enum SquidKind {
     Rect(Rect),
     Circle(Circle),
     Tri(Tri),
+    Path(Path),
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Squid {
+    // Stays the same across saving and loading, files from before squids had ids get new ones
+    #[serde(default)]
+    id: SquidId,
+
     name: Option<String>,
 
     #[serde(with = "approx_instant")]
     created: Instant,
 
     kind: SquidKind,
+
+    #[serde(default)]
+    style: Option<StyleRef>,
+
+    // Radius of the gaussian blur the squid is drawn with, in world units
+    #[serde(default)]
+    blur: f32,
+
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 impl Squid {
@@ -62,6 +93,7 @@ impl Squid {
             color: NoLerp(color),
             radii: BorderRadii::new(radii),
             is_viewport,
+            shear: glm::Vec2::zeros(),
         };
 
         Self::rect_from(data)
@@ -73,10 +105,13 @@ impl Squid {
             created: Instant::now(),
             kind: SquidKind::Rect(Rect {
                 mesh: None,
+                mesh_key: None,
                 data: Smooth::new(data, None),
                 moving_corner: None,
                 opposite_corner_position: None,
                 translate_behavior: Default::default(),
+                radius_drag: None,
+                shearing_edge: None,
                 rotating: false,
                 rotation_accumulator: Accumulator::new(),
                 prescale_size: data.size,
@@ -84,6 +119,10 @@ impl Squid {
                 revolve_behavior: Default::default(),
                 dilate_behavior: Default::default(),
             }),
+            id: SquidId::new(),
+            style: None,
+            blur: 0.0,
+            tags: vec![],
         }
     }
 
@@ -113,6 +152,10 @@ impl Squid {
                 revolve_behavior: Default::default(),
                 dilate_behavior: Default::default(),
             }),
+            id: SquidId::new(),
+            style: None,
+            blur: 0.0,
+            tags: vec![],
         }
     }
 
@@ -124,6 +167,7 @@ impl Squid {
             position: MultiLerp::From(position),
             rotation,
             color: NoLerp(color),
+            corner_radius: 0.0,
         };
 
         Self::tri_from(data)
@@ -137,9 +181,10 @@ impl Squid {
             created: Instant::now(),
             kind: SquidKind::Tri(Tri {
                 mesh: None,
+                mesh_shape: None,
                 data: Smooth::new(data, None),
-                mesh_p: p,
                 moving_point: None,
+                points_before_move: [glm::Vec2::zeros(); 3],
                 translate_behavior: Default::default(),
                 rotating: false,
                 rotation_accumulator: Accumulator::new(),
@@ -149,15 +194,102 @@ impl Squid {
                 revolve_behavior: Default::default(),
                 dilate_behavior: Default::default(),
             }),
+            id: SquidId::new(),
+            style: None,
+            blur: 0.0,
+            tags: vec![],
+        }
+    }
+
+    // Creates a path from anchors in world space
+    pub fn path(anchors: Vec<PathAnchor>, closed: bool, color: Color) -> Self {
+        let position = anchors.iter().fold(glm::zero::<glm::Vec2>(), |sum, anchor| sum + anchor.point) / anchors.len().max(1) as f32;
+
+        let data = PathData {
+            position: MultiLerp::From(position),
+            rotation: Rad(0.0),
+            scale: 1.0,
+            color: NoLerp(color),
+            corner_radius: 0.0,
+        };
+
+        let anchors = anchors
+            .iter()
+            .map(|anchor| PathAnchor {
+                point: anchor.point - position,
+                ..*anchor
+            })
+            .collect();
+
+        Self::path_from(data, anchors, closed)
+    }
+
+    pub fn path_from(data: PathData, anchors: Vec<PathAnchor>, closed: bool) -> Self {
+        Self {
+            name: None,
+            created: Instant::now(),
+            kind: SquidKind::Path(Path {
+                mesh: None,
+                mesh_anchors: vec![],
+                pending_mesh: None,
+                data: Smooth::new(data, None),
+                anchors,
+                closed,
+                moving_anchor: None,
+                translate_behavior: Default::default(),
+                rotating: false,
+                rotation_accumulator: Accumulator::new(),
+                prescale_size: data.scale,
+                spread_behavior: Default::default(),
+                revolve_behavior: Default::default(),
+                dilate_behavior: Default::default(),
+            }),
+            id: SquidId::new(),
+            style: None,
+            blur: 0.0,
+            tags: vec![],
         }
     }
 
-    // Renders squid in regular state
-    pub fn render(&mut self, ctx: &mut RenderCtx, as_preview: Option<PreviewParams>) {
+    // Refreshes any cached meshes so the squid can be rendered immutably
+    // New meshes are made right away so they don't pop in late, otherwise
+    // they're tessellated in the background and the old mesh is shown in the meantime
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache, immediately: bool) {
         match &mut self.kind {
-            SquidKind::Rect(rect) => rect.render(ctx, as_preview),
-            SquidKind::Circle(circle) => circle.render(ctx, as_preview),
-            SquidKind::Tri(tri) => tri.render(ctx, as_preview),
+            SquidKind::Rect(rect) => rect.prepare(display, mesh_cache, immediately),
+            SquidKind::Circle(circle) => circle.prepare(display, mesh_cache),
+            SquidKind::Tri(tri) => tri.prepare(display, mesh_cache),
+            SquidKind::Path(path) => path.prepare(display, mesh_cache, immediately),
+        }
+    }
+
+    // Whether the squid has yet to get a mesh that's expensive to make
+    pub fn needs_mesh(&self) -> bool {
+        match &self.kind {
+            SquidKind::Rect(rect) => rect.needs_mesh(),
+            SquidKind::Circle(_) | SquidKind::Tri(_) => false,
+            SquidKind::Path(path) => path.needs_mesh(),
+        }
+    }
+
+    // Whether the squid is still moving to where it was last put
+    pub fn is_animating(&self) -> bool {
+        match &self.kind {
+            SquidKind::Rect(rect) => rect.data.is_animating(),
+            SquidKind::Circle(circle) => circle.data.is_animating(),
+            SquidKind::Tri(tri) => tri.data.is_animating(),
+            SquidKind::Path(path) => path.data.is_animating(),
+        }
+    }
+
+    // Mesh and placement to draw the squid with, must be prepared beforehand.
+    // Drawing many squids at once is done with a 'SquidBatch'
+    pub fn get_drawable(&self, as_preview: Option<PreviewParams>) -> Option<Drawable<'_>> {
+        match &self.kind {
+            SquidKind::Rect(rect) => rect.get_drawable(as_preview),
+            SquidKind::Circle(circle) => circle.get_drawable(as_preview),
+            SquidKind::Tri(tri) => tri.get_drawable(as_preview),
+            SquidKind::Path(path) => path.get_drawable(as_preview),
         }
     }
 
@@ -173,6 +305,9 @@ impl Squid {
                 for corner in rect.get_relative_corners() {
                     output.push(camera.apply(&(position.reveal() + corner)));
                 }
+
+                output.extend(rect.get_radius_handles(camera));
+                output.extend(rect.get_shear_handles(camera));
             }
             SquidKind::Circle(circle) => {
                 let CircleData { position, .. } = circle.data.get_animated();
@@ -189,6 +324,13 @@ impl Squid {
                     output.push(point);
                 }
             }
+            SquidKind::Path(path) => {
+                let PathData { position, .. } = path.data.get_animated();
+
+                output.push(camera.apply(&position.reveal()));
+                output.push(path.get_rotate_handle(camera));
+                output.extend(path.get_animated_screen_points(camera));
+            }
         }
     }
 
@@ -200,6 +342,7 @@ impl Squid {
             SquidKind::Rect(rect) => rect.interact(interaction, camera),
             SquidKind::Circle(circle) => circle.interact(interaction, camera),
             SquidKind::Tri(tri) => tri.interact(interaction, camera),
+            SquidKind::Path(path) => path.interact(interaction, camera),
         }
     }
 
@@ -208,10 +351,11 @@ impl Squid {
             SquidKind::Rect(rect) => Some(&mut rect.translate_behavior),
             SquidKind::Circle(circle) => Some(&mut circle.translate_behavior),
             SquidKind::Tri(tri) => Some(&mut tri.translate_behavior),
+            SquidKind::Path(path) => Some(&mut path.translate_behavior),
         }
     }
 
-    fn reposition_by(&mut self, delta: glm::Vec2) {
+    pub fn reposition_by(&mut self, delta: glm::Vec2) {
         if delta == glm::zero::<glm::Vec2>() {
             return;
         }
@@ -232,6 +376,11 @@ impl Squid {
                 new_data.position = MultiLerp::Linear(new_data.position.reveal() + delta);
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.position = MultiLerp::Linear(new_data.position.reveal() + delta);
+                path.data.set(new_data);
+            }
         }
     }
 
@@ -240,10 +389,11 @@ impl Squid {
             SquidKind::Rect(rect) => Some(&mut rect.rotation_accumulator),
             SquidKind::Circle(circle) => Some(&mut circle.rotation_accumulator),
             SquidKind::Tri(tri) => Some(&mut tri.rotation_accumulator),
+            SquidKind::Path(path) => Some(&mut path.rotation_accumulator),
         }
     }
 
-    fn rotate_by(&mut self, delta_theta: Rad<f32>) {
+    pub fn rotate_by(&mut self, delta_theta: Rad<f32>) {
         match &mut self.kind {
             SquidKind::Rect(rect) => {
                 let mut new_data = *rect.data.get_real();
@@ -260,6 +410,11 @@ impl Squid {
                 new_data.rotation += delta_theta;
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.rotation += delta_theta;
+                path.data.set(new_data);
+            }
         }
     }
 
@@ -274,11 +429,10 @@ impl Squid {
         self.reposition_by(delta);
     }
 
-    // Rotates a squid body
-    pub fn rotate(&mut self, mouse_delta_theta: Rad<f32>, options: &InteractionOptions) {
-        let delta_theta = self
-            .rotate_behavior()
-            .and_then(|behavior| behavior.accumulate(&mouse_delta_theta, options.rotation_snapping));
+    // Rotates a squid body, in steps of 15 degrees while shift is held
+    pub fn rotate(&mut self, mouse_delta_theta: Rad<f32>, shift: bool, options: &InteractionOptions) {
+        let snapping = behavior::get_rotation_snapping(options, shift);
+        let delta_theta = self.rotate_behavior().and_then(|behavior| behavior.accumulate(&mouse_delta_theta, snapping));
 
         if let Some(delta_theta) = delta_theta {
             self.rotate_by(delta_theta);
@@ -292,7 +446,6 @@ impl Squid {
                 let mut new_data = *rect.data.get_real();
                 new_data.size = total_scale_factor * rect.prescale_size;
                 rect.data.set(new_data);
-                rect.mesh = None;
             }
             SquidKind::Circle(circle) => {
                 let mut new_data = *circle.data.get_real();
@@ -304,6 +457,11 @@ impl Squid {
                 new_data.p = tri.prescale_size.map(|axis| MultiLerp::Linear(total_scale_factor * axis));
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.scale = path.prescale_size * total_scale_factor;
+                path.data.set(new_data);
+            }
         }
     }
 
@@ -325,14 +483,19 @@ impl Squid {
                 new_data.position = MultiLerp::Linear(tri.spread_behavior.express(current));
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.position = MultiLerp::Linear(path.spread_behavior.express(current));
+                path.data.set(new_data);
+            }
         }
     }
 
-    // Revolves a squid body around point
-    pub fn revolve(&mut self, current: &glm::Vec2, options: &InteractionOptions) {
+    // Revolves a squid body around point, in steps of 15 degrees while shift is held
+    pub fn revolve(&mut self, current: &glm::Vec2, shift: bool, options: &InteractionOptions) {
         match &mut self.kind {
             SquidKind::Rect(rect) => {
-                if let Some(expression) = rect.revolve_behavior.express(current, options) {
+                if let Some(expression) = rect.revolve_behavior.express(current, shift, options) {
                     let mut new_data = *rect.data.get_real();
                     new_data.position = MultiLerp::Circle(expression.apply_origin_rotation_to_center(), expression.origin);
                     new_data.rotation += expression.delta_object_rotation;
@@ -340,7 +503,7 @@ impl Squid {
                 }
             }
             SquidKind::Circle(circle) => {
-                if let Some(expression) = circle.revolve_behavior.express(current, options) {
+                if let Some(expression) = circle.revolve_behavior.express(current, shift, options) {
                     let mut new_data = *circle.data.get_real();
                     new_data.position = MultiLerp::Circle(expression.apply_origin_rotation_to_center(), expression.origin);
                     new_data.virtual_rotation += expression.delta_object_rotation;
@@ -348,13 +511,21 @@ impl Squid {
                 }
             }
             SquidKind::Tri(tri) => {
-                if let Some(expression) = tri.revolve_behavior.express(current, options) {
+                if let Some(expression) = tri.revolve_behavior.express(current, shift, options) {
                     let mut new_data = *tri.data.get_real();
                     new_data.position = MultiLerp::Circle(expression.apply_origin_rotation_to_center(), expression.origin);
                     new_data.rotation += expression.delta_object_rotation;
                     tri.data.set(new_data);
                 }
             }
+            SquidKind::Path(path) => {
+                if let Some(expression) = path.revolve_behavior.express(current, shift, options) {
+                    let mut new_data = *path.data.get_real();
+                    new_data.position = MultiLerp::Circle(expression.apply_origin_rotation_to_center(), expression.origin);
+                    new_data.rotation += expression.delta_object_rotation;
+                    path.data.set(new_data);
+                }
+            }
         }
     }
 
@@ -367,7 +538,6 @@ impl Squid {
                 new_data.position = MultiLerp::Linear(expression.position);
                 new_data.size = expression.total_scale_factor * rect.prescale_size;
                 rect.data.set(new_data);
-                rect.mesh = None;
             }
             SquidKind::Circle(circle) => {
                 let mut new_data = *circle.data.get_real();
@@ -383,6 +553,13 @@ impl Squid {
                 new_data.p = tri.prescale_size.map(|axis| MultiLerp::Linear(expression.total_scale_factor * axis));
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                let expression = path.dilate_behavior.express(current);
+                new_data.position = MultiLerp::Linear(expression.position);
+                new_data.scale = path.prescale_size * expression.total_scale_factor;
+                path.data.set(new_data);
+            }
         }
     }
 
@@ -420,6 +597,16 @@ impl Squid {
                     });
                 }
             }
+            SquidKind::Path(path) => {
+                if path.is_point_over(underneath, camera) {
+                    return Some(NewSelection {
+                        selection: Selection::new(self_reference, None),
+                        info: NewSelectionInfo {
+                            color: Some(*path.data.get_real().color),
+                        },
+                    });
+                }
+            }
         }
 
         None
@@ -437,6 +624,7 @@ impl Squid {
             SquidKind::Rect(rect) => rect.is_point_over(mouse_position, camera),
             SquidKind::Circle(circle) => circle.is_point_over(mouse_position, camera),
             SquidKind::Tri(tri) => tri.is_point_over(mouse_position, camera),
+            SquidKind::Path(path) => path.is_point_over(mouse_position, camera),
         }
     }
 
@@ -449,21 +637,146 @@ impl Squid {
         }
     }
 
-    pub fn build(&self, document: &mut svg::Document) {
+    // Corners of the squid in world space if it's a viewport
+    pub fn get_viewport_corners(&self) -> Option<Vec<glm::Vec2>> {
+        match &self.kind {
+            SquidKind::Rect(rect) if rect.data.get_real().is_viewport => Some(rect.get_world_corners()),
+            _ => None,
+        }
+    }
+
+    pub fn is_point_over_viewport(&self, mouse_position: glm::Vec2, camera: &Camera) -> bool {
         match &self.kind {
-            SquidKind::Rect(rect) => rect.build(document),
-            SquidKind::Circle(circle) => circle.build(document),
-            SquidKind::Tri(tri) => tri.build(document),
+            SquidKind::Rect(rect) if rect.data.get_real().is_viewport => rect.is_point_inside(mouse_position, camera),
+            _ => false,
+        }
+    }
+
+    pub fn set_viewport_size(&mut self, size: glm::Vec2) {
+        if let SquidKind::Rect(rect) = &mut self.kind {
+            let mut new_data = *rect.data.get_real();
+
+            if new_data.is_viewport {
+                new_data.size = size;
+                rect.data.set(new_data);
+            }
+        }
+    }
+
+    // Extreme points of the squid in world space, the box around them tightly fits the squid
+    pub fn get_extreme_points(&self) -> Vec<glm::Vec2> {
+        match &self.kind {
+            SquidKind::Rect(rect) => {
+                let real = rect.data.get_real();
+                let RectData { position, size, .. } = real;
+                let frame = real.get_frame();
+
+                [(1.0, 1.0), (-1.0, 1.0), (1.0, -1.0), (-1.0, -1.0f32)]
+                    .iter()
+                    .map(|&p| frame.apply_to_vector(&glm::vec2(p.0 * size.x / 2.0, p.1 * size.y / 2.0)) + position.reveal())
+                    .collect()
+            }
+            SquidKind::Circle(circle) => {
+                let CircleData { position, radius, .. } = circle.data.get_real();
+                let radius = radius.abs();
+
+                [glm::vec2(radius, 0.0), glm::vec2(-radius, 0.0), glm::vec2(0.0, radius), glm::vec2(0.0, -radius)]
+                    .iter()
+                    .map(|offset| position.reveal() + offset)
+                    .collect()
+            }
+            SquidKind::Tri(tri) => {
+                let TriData { p, position, rotation, .. } = tri.data.get_real();
+
+                p.iter()
+                    .map(|point| glm::rotate_vec2(&point.reveal(), -rotation.scalar()) + position.reveal())
+                    .collect()
+            }
+            SquidKind::Path(path) => path.get_world_control_points(),
+        }
+    }
+
+    // Stretches the squid along the x and y axes of the world around a point,
+    // as much as it can without becoming a different kind of squid
+    pub fn stretch(&mut self, factor: &glm::Vec2, origin: &glm::Vec2) {
+        match &mut self.kind {
+            SquidKind::Rect(rect) => rect.stretch(factor, origin),
+            SquidKind::Circle(circle) => circle.stretch(factor, origin),
+            SquidKind::Tri(tri) => tri.stretch(factor, origin),
+            SquidKind::Path(path) => path.stretch(factor, origin),
+        }
+    }
+
+    // Adds squid to an svg document, where opacity is the opacity of its layer
+    pub fn build(&self, document: &mut svg::Document, opacity: f32) {
+        match &self.kind {
+            SquidKind::Rect(rect) => rect.build(document, opacity),
+            SquidKind::Circle(circle) => circle.build(document, opacity),
+            SquidKind::Tri(tri) => tri.build(document, opacity),
+            SquidKind::Path(path) => path.build(document, opacity),
         }
     }
 
     // Attempt to get a context menu for if a quid is underneath a point
     pub fn try_context_menu(&self, underneath: glm::Vec2, camera: &Camera, _self_reference: SquidRef, color_scheme: &ColorScheme) -> Option<ContextMenu> {
-        if self.is_point_over(underneath, camera) {
-            Some(common_context_menu(underneath, color_scheme))
-        } else {
-            None
+        use ContextAction::*;
+
+        if !self.is_point_over(underneath, camera) {
+            return None;
         }
+
+        let mut options = common_context_options();
+
+        match &self.kind {
+            SquidKind::Rect(rect) => {
+                options.push(ContextMenuOption::new("Set as Viewport", "", SetAsViewport));
+
+                if !rect.data.get_real().is_viewport {
+                    options.push(ContextMenuOption::new("Convert to Path", "", ConvertToPath));
+                }
+            }
+            SquidKind::Tri(_) => {
+                options.push(ContextMenuOption::new("Edit Points", "", EditPoints));
+                options.push(ContextMenuOption::new("Convert to Path", "", ConvertToPath));
+            }
+            SquidKind::Path(_) => options.push(ContextMenuOption::new("Edit Points", "", EditPoints)),
+            SquidKind::Circle(_) => options.push(ContextMenuOption::new("Convert to Path", "", ConvertToPath)),
+        }
+
+        options.push(ContextMenuOption::submenu(
+            "Select",
+            vec![
+                ContextMenuOption::new("Same Fill Color", "", SelectSimilar(Similarity::FillColor)),
+                ContextMenuOption::new("Same Kind", "", SelectSimilar(Similarity::Kind)),
+                ContextMenuOption::new("Same Layer", "", SelectSimilar(Similarity::Layer)),
+                ContextMenuOption::new("By Tag...", "", SelectByTag),
+            ],
+        ));
+
+        options.push(ContextMenuOption::new("Rename...", "F2", RenameSelected));
+        options.push(ContextMenuOption::new("Edit Tags...", "", EditTags));
+
+        options.push(ContextMenuOption::submenu(
+            "Arrange",
+            vec![
+                ContextMenuOption::new("Bring to Front", "", BringToFront),
+                ContextMenuOption::new("Bring Forward", "PgUp", BringForward),
+                ContextMenuOption::new("Send Backward", "PgDn", SendBackward),
+                ContextMenuOption::new("Send to Back", "", SendToBack),
+            ],
+        ));
+
+        options.push(ContextMenuOption::submenu(
+            "Distribute",
+            vec![
+                ContextMenuOption::new("Equal Horizontal Gaps", "", EqualGaps(Axis::Horizontal)),
+                ContextMenuOption::new("Equal Vertical Gaps", "", EqualGaps(Axis::Vertical)),
+                ContextMenuOption::new("Match Width", "", MatchSize(Axis::Horizontal)),
+                ContextMenuOption::new("Match Height", "", MatchSize(Axis::Vertical)),
+            ],
+        ));
+
+        Some(ContextMenu::new(underneath, options, color_scheme.dark_ribbon))
     }
 
     // Attempts to set the color of a squid
@@ -484,12 +797,45 @@ impl Squid {
                 new_data.color = NoLerp(color);
                 tri.data.set(new_data);
             }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                new_data.color = NoLerp(color);
+                path.data.set(new_data);
+            }
+        }
+    }
+
+    // Gets the current (non-animated) color of a squid
+    pub fn get_color(&self) -> Color {
+        match &self.kind {
+            SquidKind::Rect(rect) => *rect.data.get_real().color,
+            SquidKind::Circle(circle) => *circle.data.get_real().color,
+            SquidKind::Tri(tri) => *tri.data.get_real().color,
+            SquidKind::Path(path) => *path.data.get_real().color,
         }
     }
 
+    // Shared color style that the squid takes its color from, if any
+    pub fn get_style(&self) -> Option<StyleRef> {
+        self.style
+    }
+
+    pub fn set_style(&mut self, style: Option<StyleRef>) {
+        self.style = style;
+    }
+
+    // Whether both are rects, circles, triangles or paths
+    pub fn is_same_kind(&self, other: &Squid) -> bool {
+        std::mem::discriminant(&self.kind) == std::mem::discriminant(&other.kind)
+    }
+
+    pub fn get_blur(&self) -> f32 {
+        self.blur
+    }
+
     // Duplicates a squid
     pub fn duplicate(&self, offset: &glm::Vec2) -> Squid {
-        match &self.kind {
+        let mut duplicate = match &self.kind {
             SquidKind::Rect(rect) => {
                 let mut real = *rect.data.get_real();
                 real.position = MultiLerp::From(real.position.reveal() + offset);
@@ -505,6 +851,264 @@ impl Squid {
                 real.position = MultiLerp::From(real.position.reveal() + offset);
                 Squid::tri_from(real)
             }
+            SquidKind::Path(path) => {
+                let mut real = *path.data.get_real();
+                real.position = MultiLerp::From(real.position.reveal() + offset);
+                Squid::path_from(real, path.anchors.clone(), path.closed)
+            }
+        };
+
+        duplicate.style = self.style;
+        duplicate.blur = self.blur;
+        duplicate.tags = self.tags.clone();
+        duplicate
+    }
+
+    // Resizes the squid to fill a box in world space, used while drawing it out
+    pub fn fit_to_box(&mut self, min: &glm::Vec2, max: &glm::Vec2) {
+        let center = 0.5 * (min + max);
+        let size = (max - min).map(|component| component.max(1.0));
+
+        match &mut self.kind {
+            SquidKind::Rect(rect) => {
+                let mut new_data = *rect.data.get_real();
+                new_data.position = MultiLerp::From(center);
+                new_data.size = size;
+                rect.data.set(new_data);
+                rect.data.finish();
+            }
+            SquidKind::Circle(circle) => {
+                let mut new_data = *circle.data.get_real();
+                new_data.position = MultiLerp::From(center);
+                new_data.radius = 0.5 * glm::comp_min(&size);
+                circle.data.set(new_data);
+                circle.data.finish();
+            }
+            SquidKind::Tri(tri) => {
+                let corners = [glm::vec2(0.0, -0.5), glm::vec2(0.5, 0.5), glm::vec2(-0.5, 0.5)].map(|corner| corner.component_mul(&size));
+                let offset = get_triangle_center(corners);
+
+                let mut new_data = *tri.data.get_real();
+                new_data.position = MultiLerp::From(center + offset);
+                new_data.p = corners.map(|corner| MultiLerp::From(corner - offset));
+                tri.data.set(new_data);
+                tri.data.finish();
+            }
+            SquidKind::Path(_) => (),
+        }
+    }
+
+    // Points that can be moved individually with the edit points tool, in world space
+    pub fn get_editable_points(&self) -> Vec<glm::Vec2> {
+        match &self.kind {
+            SquidKind::Rect(_) | SquidKind::Tri(_) => self.to_curved_path().map_or_else(Vec::new, |path| path.get_world_anchors()),
+            SquidKind::Circle(_) => vec![],
+            SquidKind::Path(path) => path.get_world_anchors(),
+        }
+    }
+
+    pub fn move_editable_point(&mut self, index: usize, world_position: &glm::Vec2) {
+        // Rects can't keep their shape once a corner is moved, so they become paths
+        if let SquidKind::Rect(_) = &self.kind {
+            self.convert_to_path();
+        }
+
+        match &mut self.kind {
+            SquidKind::Tri(tri) => tri.move_point(index, world_position),
+            SquidKind::Path(path) => path.move_anchor(index, world_position),
+            _ => (),
+        }
+    }
+
+    // Adds a point along the edge of the squid near a position in world space,
+    // returns the index of the new point
+    pub fn insert_editable_point(&mut self, world_position: &glm::Vec2, tolerance: f32) -> Option<usize> {
+        if let SquidKind::Path(path) = &mut self.kind {
+            return path.insert_anchor(world_position, tolerance);
+        }
+
+        let mut path = self.to_curved_path()?;
+        let index = path.insert_anchor(world_position, tolerance)?;
+        self.kind = SquidKind::Path(path);
+        Some(index)
+    }
+
+    pub fn remove_editable_point(&mut self, index: usize) -> bool {
+        match &mut self.kind {
+            SquidKind::Path(path) => path.remove_anchor(index),
+            _ => false,
+        }
+    }
+
+    // Corners of the squid in world space, for squids that are convex polygons
+    pub fn get_polygon(&self) -> Option<Vec<glm::Vec2>> {
+        match &self.kind {
+            SquidKind::Rect(_) if self.as_viewport().is_some() => None,
+            SquidKind::Rect(_) | SquidKind::Tri(_) => self.to_path().map(|path| path.get_world_anchors()),
+            SquidKind::Circle(_) | SquidKind::Path(_) => None,
+        }
+    }
+
+    // New squid with the same fill, shaped like a polygon in world space
+    pub fn with_polygon(&self, points: &[glm::Vec2]) -> Squid {
+        let mut squid = Squid::path(points.iter().copied().map(PathAnchor::new).collect(), true, self.get_color());
+        squid.name = self.name.clone();
+        squid.style = self.style;
+        squid.blur = self.blur;
+        squid
+    }
+
+    // Same shape as a closed path, for squids made of straight edges
+    fn to_path(&self) -> Option<Path> {
+        let (position, rotation, color, corner_radius, points) = match &self.kind {
+            SquidKind::Rect(rect) => {
+                let RectData {
+                    position,
+                    size,
+                    rotation,
+                    color,
+                    shear,
+                    ..
+                } = *rect.data.get_real();
+
+                // Shear is kept by slanting the corners, since paths can only be rotated
+                let shearing = Affine2::shearing(&shear);
+                let corners =
+                    [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0f32)].map(|(x, y)| shearing.apply_to_vector(&(glm::vec2(x * size.x, y * size.y) / 2.0)));
+                (position, rotation, color, 0.0, corners.to_vec())
+            }
+            SquidKind::Tri(tri) => {
+                let TriData {
+                    p,
+                    position,
+                    rotation,
+                    color,
+                    corner_radius,
+                } = *tri.data.get_real();
+                (position, rotation, color, corner_radius, p.map(|point| point.reveal()).to_vec())
+            }
+            SquidKind::Circle(_) | SquidKind::Path(_) => return None,
+        };
+
+        let data = PathData {
+            position: MultiLerp::From(position.reveal()),
+            rotation,
+            scale: 1.0,
+            color,
+            corner_radius,
+        };
+
+        match Self::path_from(data, points.into_iter().map(PathAnchor::new).collect(), true).kind {
+            SquidKind::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    // Same shape as a closed path, including the curves of rounded corners and circles
+    fn to_curved_path(&self) -> Option<Path> {
+        let (data, anchors) = match &self.kind {
+            SquidKind::Rect(rect) => {
+                let real = rect.data.get_real();
+
+                if real.is_viewport {
+                    return None;
+                }
+
+                let RectData {
+                    position,
+                    size,
+                    rotation,
+                    color,
+                    radii,
+                    shear,
+                    ..
+                } = *real;
+
+                // Corners go around the same way as 'to_path', and are rounded before being slanted
+                // since slanting the control points of a curve slants the curve too
+                let [top_left, top_right, bottom_left, bottom_right] = radii.to_array();
+                let corners =
+                    [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0f32)].map(|(x, y)| PathAnchor::new(glm::vec2(x * size.x.abs(), y * size.y.abs()) / 2.0));
+                let shearing = Affine2::shearing(&shear);
+
+                let anchors = round_each_corner(&corners, true, &[top_left, top_right, bottom_right, bottom_left])
+                    .iter()
+                    .map(|anchor| PathAnchor {
+                        point: shearing.apply_to_vector(&anchor.point),
+                        handle_in: shearing.apply_to_vector(&anchor.handle_in),
+                        handle_out: shearing.apply_to_vector(&anchor.handle_out),
+                    })
+                    .collect();
+
+                let data = PathData {
+                    position: MultiLerp::From(position.reveal()),
+                    rotation,
+                    scale: 1.0,
+                    color,
+                    corner_radius: 0.0,
+                };
+
+                (data, anchors)
+            }
+            SquidKind::Circle(circle) => {
+                let CircleData {
+                    position,
+                    radius,
+                    color,
+                    virtual_rotation,
+                } = *circle.data.get_real();
+
+                // Four curves that each make a quarter of the circle
+                let handle = 4.0 / 3.0 * (std::f32::consts::FRAC_PI_8).tan() * radius;
+
+                let anchors = (0..4)
+                    .map(|i| {
+                        let angle = i as f32 * std::f32::consts::FRAC_PI_2;
+                        let outwards = glm::vec2(angle.cos(), angle.sin());
+                        let along = glm::vec2(-outwards.y, outwards.x);
+                        PathAnchor::new(outwards * radius).with_handle(along * handle)
+                    })
+                    .collect();
+
+                let data = PathData {
+                    position: MultiLerp::From(position.reveal()),
+                    rotation: virtual_rotation,
+                    scale: 1.0,
+                    color,
+                    corner_radius: 0.0,
+                };
+
+                (data, anchors)
+            }
+            SquidKind::Tri(_) => return self.to_path(),
+            SquidKind::Path(_) => return None,
+        };
+
+        match Self::path_from(data, anchors, true).kind {
+            SquidKind::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    // Turns the squid into a path with the same shape, so its points can be edited.
+    // Returns whether it was turned into one
+    pub fn convert_to_path(&mut self) -> bool {
+        match self.to_curved_path() {
+            Some(path) => {
+                self.kind = SquidKind::Path(path);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Turns a rect into a viewport
+    pub fn set_as_viewport(&mut self) {
+        if let SquidKind::Rect(rect) = &mut self.kind {
+            let mut new_data = *rect.data.get_real();
+            new_data.is_viewport = true;
+            new_data.shear = glm::Vec2::zeros();
+            rect.data.set(new_data);
         }
     }
 
@@ -514,6 +1118,7 @@ impl Squid {
             SquidKind::Rect(rect) => rect.initiate(initiation),
             SquidKind::Circle(circle) => circle.initiate(initiation),
             SquidKind::Tri(tri) => tri.initiate(initiation),
+            SquidKind::Path(path) => path.initiate(initiation),
         }
     }
 
@@ -525,9 +1130,83 @@ impl Squid {
             Rect(rect) => rect.data.get_animated().position.reveal(),
             Circle(circle) => circle.data.get_animated().position.reveal(),
             Tri(tri) => tri.data.get_animated().position.reveal(),
+            Path(path) => path.data.get_animated().position.reveal(),
+        }
+    }
+
+    // Radius of a circle around the center that contains the whole squid
+    pub fn get_bounding_radius(&self) -> f32 {
+        use SquidKind::*;
+
+        match &self.kind {
+            Rect(rect) => 0.5 * glm::length(&rect.data.get_animated().size),
+            Circle(circle) => circle.data.get_animated().radius.abs(),
+            Tri(tri) => tri.data.get_animated().p.iter().map(|point| glm::length(&point.reveal())).fold(0.0, f32::max),
+            Path(path) => path.get_max_distance() * path.data.get_animated().scale.abs(),
         }
     }
 
+    // Conservative world space box around the squid
+    pub fn get_bounding_box(&self) -> AABB {
+        let center = self.get_center();
+        let radius = self.get_bounding_radius();
+        AABB::new(center.x - radius, center.y - radius, 2.0 * radius, 2.0 * radius)
+    }
+
+    // Live size to show next to the cursor while a handle is being dragged to resize the squid
+    pub fn get_size_readout(&self) -> Option<String> {
+        match &self.kind {
+            SquidKind::Rect(rect) if rect.moving_corner.is_some() => {
+                let size = rect.data.get_real().size.abs();
+                Some(format!("{:.1} x {:.1}", size.x, size.y))
+            }
+            SquidKind::Circle(circle) if circle.scale_rotating => Some(format!("r {:.1}", circle.data.get_real().radius.abs())),
+            _ => None,
+        }
+    }
+
+    // Lines describing the squid for the object info overlay
+    pub fn get_info(&self) -> Vec<String> {
+        let kind = match &self.kind {
+            SquidKind::Rect(rect) if rect.data.get_real().is_viewport => "Viewport",
+            SquidKind::Rect(_) => "Rect",
+            SquidKind::Circle(_) => "Circle",
+            SquidKind::Tri(_) => "Tri",
+            SquidKind::Path(_) => "Path",
+        };
+
+        let size = match &self.kind {
+            SquidKind::Rect(rect) => rect.data.get_real().size.abs(),
+            SquidKind::Circle(circle) => glm::vec2(2.0, 2.0) * circle.data.get_real().radius.abs(),
+            _ => AABB::from_points(&self.get_extreme_points()).map_or(glm::zero(), |bounds| glm::vec2(bounds.width(), bounds.height())),
+        };
+
+        let mut lines = vec![
+            format!("{} ({})", self.get_name(), kind),
+            format!(
+                "X {:.1}  Y {:.1}",
+                self.get_property(SquidProperty::X).unwrap_or_default(),
+                self.get_property(SquidProperty::Y).unwrap_or_default()
+            ),
+            format!("{:.1} x {:.1}", size.x, size.y),
+        ];
+
+        if let Some(rotation) = self.get_property(SquidProperty::Rotation) {
+            lines.push(format!("Rotation {:.1} deg", rotation));
+        }
+
+        lines
+    }
+
+    pub fn get_id(&self) -> SquidId {
+        self.id
+    }
+
+    // For when the squid is a copy of one that still exists
+    pub fn renew_id(&mut self) {
+        self.id = SquidId::new();
+    }
+
     // Opaque name getter/setter
     pub fn get_name(&self) -> &str {
         self.name.as_deref().unwrap_or_else(|| match &self.kind {
@@ -540,6 +1219,7 @@ impl Squid {
             }
             SquidKind::Circle(_) => "Unnamed Circle",
             SquidKind::Tri(_) => "Unnamed Tri",
+            SquidKind::Path(_) => "Unnamed Path",
         })
     }
 
@@ -547,6 +1227,27 @@ impl Squid {
         self.name = Some(name);
     }
 
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    // Tags are compared ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        self.tags.iter().any(|existing| existing.to_lowercase() == tag)
+    }
+
+    // Whether the name or any tag contains the filter text, where no filter matches everything
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+
+        filter.is_empty() || self.get_name().to_lowercase().contains(&filter) || self.tags.iter().any(|tag| tag.to_lowercase().contains(&filter))
+    }
+
     // Returns the world positions of all "opaque" handles (aka handles that will take priority over new selections)
     pub fn get_opaque_handles(&self) -> Vec<glm::Vec2> {
         match &self.kind {
@@ -568,6 +1269,15 @@ impl Squid {
                     .chain(std::iter::once(tri.get_rotate_handle(&IDENTITY_CAMERA)))
                     .collect_vec()
             }
+            SquidKind::Path(path) => {
+                let data = path.data.get_animated();
+
+                path.anchors
+                    .iter()
+                    .map(|anchor| Path::to_world(&data, &anchor.point))
+                    .chain(std::iter::once(path.get_rotate_handle(&IDENTITY_CAMERA)))
+                    .collect_vec()
+            }
         }
     }
 }
@@ -592,10 +1302,41 @@ impl Ord for Squid {
     }
 }
 
+// Clones are the same squid, such as within the undo history, so they keep their id
 impl Clone for Squid {
     fn clone(&self) -> Self {
-        self.duplicate(&glm::zero())
+        let mut clone = self.duplicate(&glm::zero());
+        clone.id = self.id;
+        clone
+    }
+}
+
+// Single factor to scale by instead of stretching, which is however much the more stretched axis changes
+fn get_even_factor(factor: &glm::Vec2) -> f32 {
+    if (factor.x - 1.0).abs() >= (factor.y - 1.0).abs() {
+        factor.x
+    } else {
+        factor.y
+    }
+}
+
+// Makes a name from a pattern, where {n} is the position of the squid counting from 1
+// and {name} is its current name
+pub fn apply_name_pattern(pattern: &str, name: &str, n: usize) -> String {
+    pattern.replace("{n}", &n.to_string()).replace("{name}", name)
+}
+
+// Reads tags separated by commas, leaving out empty and repeated ones
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing.to_lowercase() == tag.to_lowercase()) {
+            tags.push(tag.into());
+        }
     }
+
+    tags
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -614,21 +1355,25 @@ lazy_static! {
     pub static ref HANDLE_SIZE: glm::Vec2 = glm::vec2(HANDLE_RADIUS, HANDLE_RADIUS);
 }
 
-pub fn common_context_menu(underneath: glm::Vec2, color_scheme: &ColorScheme) -> ContextMenu {
+// Options that every kind of squid has in its context menu
+pub fn common_context_options() -> Vec<ContextMenuOption> {
     use ContextAction::*;
 
-    ContextMenu::new(
-        underneath,
-        vec![
-            ContextMenuOption::new("Delete", "X", DeleteSelected),
-            ContextMenuOption::new("Duplicate", "Shift+D", DuplicateSelected),
-            ContextMenuOption::new("Grab", "G", GrabSelected),
-            ContextMenuOption::new("Rotate", "R", RotateSelected),
-            ContextMenuOption::new("Scale", "S", ScaleSelected),
-            ContextMenuOption::new("Collectively", "C", Collectively),
-        ],
-        color_scheme.dark_ribbon,
-    )
+    vec![
+        ContextMenuOption::new("Delete", "X", DeleteSelected),
+        ContextMenuOption::new("Duplicate", "Shift+D", DuplicateSelected),
+        ContextMenuOption::new("Grab", "G", GrabSelected),
+        ContextMenuOption::new("Rotate", "R", RotateSelected),
+        ContextMenuOption::new("Scale", "S", ScaleSelected),
+        ContextMenuOption::new("Collectively", "C", Collectively),
+    ]
+}
+
+// Everything needed to draw a squid
+pub struct Drawable<'a> {
+    pub mesh: &'a MeshXyz,
+    pub transformation: Affine2,
+    pub color: Color,
 }
 
 pub struct PreviewParams {
@@ -0,0 +1,522 @@
+use super::{
+    behavior::{self, DilateBehavior, RevolveBehavior, SpreadBehavior, TranslateBehavior},
+    Drawable, Initiation, PreviewParams, HANDLE_RADIUS,
+};
+use crate::{
+    accumulator::Accumulator,
+    affine::Affine2,
+    camera::Camera,
+    capture::Capture,
+    components,
+    data::{
+        path::{round_corners, PathAnchor},
+        PathData,
+    },
+    interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
+    math::DivOrZero,
+    mesh::MeshXyz,
+    mesh_cache::MeshCache,
+    smooth::{MultiLerp, Smooth},
+    tessellator::{Shape, Tessellation, TessellationTicket},
+};
+use angular_units::{Angle, Rad};
+use glium::{glutin::event::MouseButton, Display};
+use lyon::{algorithms::hit_test::hit_test_path, path::FillRule};
+use nalgebra_glm as glm;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+#[derive(Serialize, Deserialize)]
+pub struct Path {
+    #[serde(skip)]
+    pub mesh: Option<Rc<MeshXyz>>,
+
+    // Anchors that the current mesh was made from
+    #[serde(skip)]
+    pub mesh_anchors: Vec<PathAnchor>,
+
+    // Newer mesh that's still being tessellated, along with the anchors it's made from
+    #[serde(skip)]
+    pub pending_mesh: Option<(TessellationTicket, Vec<PathAnchor>)>,
+
+    pub data: Smooth<PathData>,
+
+    // Relative to the position, before rotation and scale are applied
+    pub anchors: Vec<PathAnchor>,
+
+    pub closed: bool,
+
+    // Move point
+    #[serde(skip)]
+    pub moving_anchor: Option<usize>,
+
+    // Translate
+    #[serde(skip)]
+    pub translate_behavior: TranslateBehavior,
+
+    // Rotate
+    #[serde(skip)]
+    pub rotating: bool,
+
+    #[serde(skip)]
+    pub rotation_accumulator: Accumulator<Rad<f32>>,
+
+    // Scale
+    #[serde(skip)]
+    pub prescale_size: f32,
+
+    // Spread
+    #[serde(skip)]
+    pub spread_behavior: SpreadBehavior,
+
+    // Revolve
+    #[serde(skip)]
+    pub revolve_behavior: RevolveBehavior,
+
+    // Dilate
+    #[serde(skip)]
+    pub dilate_behavior: DilateBehavior,
+}
+
+// Builds a lyon path that goes through each anchor in order
+pub fn build_lyon_path(anchors: &[PathAnchor], closed: bool) -> lyon::path::Path {
+    fn to_point(vector: glm::Vec2) -> lyon::math::Point {
+        lyon::math::point(vector.x, vector.y)
+    }
+
+    let mut builder = lyon::path::Path::builder();
+
+    if let Some(first) = anchors.first() {
+        builder.begin(to_point(first.point));
+
+        for pair in anchors.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            builder.cubic_bezier_to(to_point(from.point + from.handle_out), to_point(to.point + to.handle_in), to_point(to.point));
+        }
+
+        if let (true, Some(last)) = (closed, anchors.last()) {
+            builder.cubic_bezier_to(
+                to_point(last.point + last.handle_out),
+                to_point(first.point + first.handle_in),
+                to_point(first.point),
+            );
+        }
+
+        builder.end(closed);
+    }
+
+    builder.build()
+}
+
+// Builds svg path data that goes through each anchor in order, after moving them with 'to_world'
+pub fn build_svg_data(anchors: &[PathAnchor], closed: bool, to_world: impl Fn(glm::Vec2) -> glm::Vec2) -> svg::node::element::path::Data {
+    let to_tuple = |local: glm::Vec2| {
+        let world = to_world(local);
+        (world.x, world.y)
+    };
+
+    let mut path_data = svg::node::element::path::Data::new();
+
+    let first = match anchors.first() {
+        Some(first) => first,
+        None => return path_data,
+    };
+
+    path_data = path_data.move_to(to_tuple(first.point));
+
+    let mut segments: Vec<(PathAnchor, PathAnchor)> = anchors.windows(2).map(|pair| (pair[0], pair[1])).collect();
+
+    if let (true, Some(last)) = (closed, anchors.last()) {
+        segments.push((*last, *first));
+    }
+
+    for (from, to) in segments {
+        let (x1, y1) = to_tuple(from.point + from.handle_out);
+        let (x2, y2) = to_tuple(to.point + to.handle_in);
+        let (x, y) = to_tuple(to.point);
+        path_data = path_data.cubic_curve_to((x1, y1, x2, y2, x, y));
+    }
+
+    if closed {
+        path_data = path_data.close();
+    }
+
+    path_data
+}
+
+fn get_control_points(from: &PathAnchor, to: &PathAnchor) -> [glm::Vec2; 4] {
+    [from.point, from.point + from.handle_out, to.point + to.handle_in, to.point]
+}
+
+fn get_point_on_segment(from: &PathAnchor, to: &PathAnchor, t: f32) -> glm::Vec2 {
+    let [p0, p1, p2, p3] = get_control_points(from, to);
+    let u = 1.0 - t;
+    p0 * (u * u * u) + p1 * (3.0 * u * u * t) + p2 * (3.0 * u * t * t) + p3 * (t * t * t)
+}
+
+// Splits the curve between two anchors in two at 't' (de Casteljau),
+// giving the updated anchors along with the new one between them
+fn split_segment(from: &PathAnchor, to: &PathAnchor, t: f32) -> [PathAnchor; 3] {
+    let [p0, p1, p2, p3] = get_control_points(from, to);
+
+    let q0 = glm::lerp(&p0, &p1, t);
+    let q1 = glm::lerp(&p1, &p2, t);
+    let q2 = glm::lerp(&p2, &p3, t);
+    let r0 = glm::lerp(&q0, &q1, t);
+    let r1 = glm::lerp(&q1, &q2, t);
+    let middle = glm::lerp(&r0, &r1, t);
+
+    [
+        PathAnchor { handle_out: q0 - p0, ..*from },
+        PathAnchor {
+            point: middle,
+            handle_in: r0 - middle,
+            handle_out: r1 - middle,
+        },
+        PathAnchor { handle_in: q2 - p3, ..*to },
+    ]
+}
+
+impl Path {
+    // Converts a point relative to the path into world space
+    pub fn to_world(data: &PathData, local: &glm::Vec2) -> glm::Vec2 {
+        glm::rotate_vec2(&(data.scale * local), -data.rotation.scalar()) + data.position.reveal()
+    }
+
+    fn to_local(data: &PathData, world: &glm::Vec2) -> glm::Vec2 {
+        let unrotated = glm::rotate_vec2(&(world - data.position.reveal()), data.rotation.scalar());
+        unrotated.map(|component| component.div_or_zero(data.scale))
+    }
+
+    // Anchor points and control handles in world space, the curve never leaves the shape they make
+    pub fn get_world_control_points(&self) -> Vec<glm::Vec2> {
+        let data = self.data.get_real();
+
+        self.anchors
+            .iter()
+            .flat_map(|anchor| [anchor.point + anchor.handle_in, anchor.point, anchor.point + anchor.handle_out])
+            .map(|point| Self::to_world(data, &point))
+            .collect()
+    }
+
+    pub fn get_animated_screen_points(&self, camera: &Camera) -> Vec<glm::Vec2> {
+        let data = self.data.get_animated();
+
+        self.anchors.iter().map(|anchor| camera.apply(&Self::to_world(&data, &anchor.point))).collect()
+    }
+
+    pub fn get_world_anchors(&self) -> Vec<glm::Vec2> {
+        let data = self.data.get_real();
+
+        self.anchors.iter().map(|anchor| Self::to_world(data, &anchor.point)).collect()
+    }
+
+    // Moves an anchor (along with its handles) to a position in world space
+    pub fn move_anchor(&mut self, index: usize, world_position: &glm::Vec2) {
+        let local = Self::to_local(self.data.get_real(), world_position);
+
+        if let Some(anchor) = self.anchors.get_mut(index) {
+            anchor.point = local;
+        }
+    }
+
+    // Adds an anchor where the path passes closest to a point in world space without changing its shape,
+    // as long as it passes within 'tolerance', returns the index of the new anchor
+    pub fn insert_anchor(&mut self, world_position: &glm::Vec2, tolerance: f32) -> Option<usize> {
+        const SAMPLES: usize = 32;
+
+        let local = Self::to_local(self.data.get_real(), world_position);
+        let segment_count = if self.closed {
+            self.anchors.len()
+        } else {
+            self.anchors.len().saturating_sub(1)
+        };
+
+        let (segment, t, distance) = (0..segment_count)
+            .flat_map(|segment| (0..=SAMPLES).map(move |i| (segment, i as f32 / SAMPLES as f32)))
+            .map(|(segment, t)| {
+                let [from, to] = self.get_segment(segment);
+                (segment, t, glm::distance(&get_point_on_segment(&from, &to, t), &local))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))?;
+
+        if distance * self.data.get_real().scale.abs() > tolerance {
+            return None;
+        }
+
+        let [from, to] = self.get_segment(segment);
+        let [from, middle, to] = split_segment(&from, &to, t);
+        let next = (segment + 1) % self.anchors.len();
+
+        self.anchors[segment] = from;
+        self.anchors[next] = to;
+        self.anchors.insert(segment + 1, middle);
+        Some(segment + 1)
+    }
+
+    // Removes an anchor, as long as enough would be left over to still make a shape
+    pub fn remove_anchor(&mut self, index: usize) -> bool {
+        let minimum = if self.closed { 3 } else { 2 };
+
+        if index >= self.anchors.len() || self.anchors.len() <= minimum {
+            return false;
+        }
+
+        self.anchors.remove(index);
+        true
+    }
+
+    fn get_segment(&self, segment: usize) -> [PathAnchor; 2] {
+        [self.anchors[segment], self.anchors[(segment + 1) % self.anchors.len()]]
+    }
+
+    // Farthest any part of the path reaches from its position, before scaling
+    pub fn get_max_distance(&self) -> f32 {
+        self.anchors
+            .iter()
+            .flat_map(|anchor| [anchor.point + anchor.handle_in, anchor.point, anchor.point + anchor.handle_out])
+            .map(|point| glm::length(&point))
+            .fold(0.0, f32::max)
+    }
+
+    pub fn get_rotate_handle(&self, camera: &Camera) -> glm::Vec2 {
+        let data = self.data.get_animated();
+        let distance = self.get_max_distance() * data.scale.abs() + 24.0;
+        components::get_rotate_handle(data.position.reveal(), data.rotation, distance, camera)
+    }
+
+    pub fn needs_mesh(&self) -> bool {
+        self.mesh.is_none() && self.pending_mesh.is_none() && self.mesh_anchors != self.get_outline()
+    }
+
+    // Anchors that are drawn and exported, which have their sharp corners rounded
+    pub fn get_outline(&self) -> Vec<PathAnchor> {
+        round_corners(&self.anchors, self.closed, self.data.get_real().corner_radius)
+    }
+
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache, immediately: bool) {
+        // Upload mesh once it's done being tessellated
+        if let Some((ticket, _)) = &self.pending_mesh {
+            if let Some(tessellation) = mesh_cache.take(*ticket) {
+                let (_, anchors) = self.pending_mesh.take().unwrap();
+                self.set_mesh(&tessellation, anchors, display);
+            }
+        }
+
+        if self.pending_mesh.is_some() {
+            return;
+        }
+
+        let outline = self.get_outline();
+
+        if self.mesh_anchors == outline {
+            return;
+        }
+
+        // Open paths are filled as if they were closed
+        let shape = Shape::Fill(build_lyon_path(&outline, true));
+
+        if immediately && self.mesh.is_none() {
+            self.set_mesh(&shape.tessellate().unwrap_or_default(), outline, display);
+        } else {
+            // Keep showing the old mesh in the meantime
+            self.pending_mesh = Some((mesh_cache.tessellate(shape), outline));
+        }
+    }
+
+    fn set_mesh(&mut self, tessellation: &Tessellation, anchors: Vec<PathAnchor>, display: &Display) {
+        self.mesh = if tessellation.indices.is_empty() {
+            None
+        } else {
+            Some(Rc::new(MeshXyz::from_tessellation(tessellation, display)))
+        };
+
+        self.mesh_anchors = anchors;
+    }
+
+    pub fn get_drawable(&self, as_preview: Option<PreviewParams>) -> Option<Drawable<'_>> {
+        let PathData {
+            position,
+            rotation,
+            scale,
+            color,
+            ..
+        } = self.data.get_animated();
+
+        let mesh = self.mesh.as_ref()?;
+
+        let (render_position, render_scale) = if let Some(preview) = &as_preview {
+            (preview.position, preview.radius.div_or_zero(self.get_max_distance()))
+        } else {
+            (position.reveal(), scale)
+        };
+
+        let transformation = Affine2::translation(&render_position)
+            .rotate(-rotation.scalar())
+            .scale(&glm::vec2(render_scale, render_scale));
+
+        Some(Drawable {
+            mesh,
+            transformation,
+            color: *color,
+        })
+    }
+
+    pub fn interact(&mut self, interaction: &Interaction, camera: &Camera) -> Capture {
+        match interaction {
+            Interaction::PreClick => {
+                self.translate_behavior.moving = false;
+                self.rotating = false;
+                self.moving_anchor = None;
+            }
+            Interaction::Click(ClickInteraction {
+                button: MouseButton::Left,
+                position,
+                ..
+            }) => {
+                for (i, point) in self.get_animated_screen_points(camera).iter().enumerate() {
+                    if glm::distance(position, point) <= HANDLE_RADIUS * 2.0 {
+                        self.moving_anchor = Some(i);
+                        return Capture::AllowDrag;
+                    }
+                }
+
+                if glm::distance(position, &self.get_rotate_handle(camera)) <= HANDLE_RADIUS * 2.0 {
+                    self.rotating = true;
+                    return Capture::AllowDrag;
+                }
+
+                if self.is_point_over(*position, camera) {
+                    self.translate_behavior.moving = true;
+                    return Capture::AllowDrag;
+                }
+            }
+            Interaction::Drag(DragInteraction {
+                delta,
+                current: mouse_position,
+                modifiers,
+                ..
+            }) => {
+                if let Some(index) = self.moving_anchor {
+                    self.move_anchor(index, &camera.apply_reverse(mouse_position));
+                } else if self.rotating {
+                    return Capture::RotateSelectedSquids {
+                        delta_theta: behavior::get_delta_rotation(
+                            &self.data.get_real().position.reveal(),
+                            self.data.get_real().rotation,
+                            mouse_position,
+                            &self.rotation_accumulator,
+                            camera,
+                        ),
+                        shift: modifiers.shift(),
+                    };
+                } else if self.translate_behavior.moving {
+                    return Capture::MoveSelectedSquids {
+                        delta_in_world: camera.apply_reverse_to_vector(delta),
+                    };
+                }
+            }
+            Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+                self.rotating = false;
+                self.moving_anchor = None;
+                self.translate_behavior.accumulator.clear();
+                self.rotation_accumulator.clear();
+            }
+            _ => (),
+        }
+
+        Capture::Miss
+    }
+
+    pub fn initiate(&mut self, initiation: Initiation) {
+        match initiation {
+            Initiation::Translate => {
+                self.translate_behavior.moving = true;
+                self.moving_anchor = None;
+            }
+            Initiation::Rotate => (),
+            Initiation::Scale => self.prescale_size = self.data.get_real().scale,
+            Initiation::Spread { point, center } => {
+                self.spread_behavior = SpreadBehavior {
+                    point,
+                    origin: center,
+                    start: self.data.get_real().position.reveal(),
+                };
+            }
+            Initiation::Revolve { point, center } => self.revolve_behavior.set(&center, &self.data.get_real().position.reveal(), &point),
+            Initiation::Dilate { point, center } => {
+                self.prescale_size = self.data.get_real().scale;
+                self.dilate_behavior = DilateBehavior {
+                    point,
+                    origin: center,
+                    start: self.data.get_real().position.reveal(),
+                };
+            }
+        }
+    }
+
+    pub fn is_point_over(&self, mouse_position: glm::Vec2, camera: &Camera) -> bool {
+        let local = Self::to_local(self.data.get_real(), &camera.apply_reverse(&mouse_position));
+        let lyon_path = build_lyon_path(&self.get_outline(), true);
+
+        hit_test_path(&lyon::math::point(local.x, local.y), lyon_path.iter(), FillRule::NonZero, 0.1)
+    }
+
+    // Stretching along the axes of the world is a different stretch within the rotated space of the anchors
+    pub fn stretch(&mut self, factor: &glm::Vec2, origin: &glm::Vec2) {
+        let mut new_data = *self.data.get_real();
+        let rotation = new_data.rotation.scalar();
+        let stretch_local = |local: glm::Vec2| glm::rotate_vec2(&glm::rotate_vec2(&local, -rotation).component_mul(factor), rotation);
+
+        for anchor in &mut self.anchors {
+            anchor.point = stretch_local(anchor.point);
+            anchor.handle_in = stretch_local(anchor.handle_in);
+            anchor.handle_out = stretch_local(anchor.handle_out);
+        }
+
+        new_data.position = MultiLerp::Linear(origin + (new_data.position.reveal() - origin).component_mul(factor));
+        self.data.set(new_data);
+    }
+
+    pub fn build(&self, document: &mut svg::Document, opacity: f32) {
+        use svg::Node;
+
+        let data = self.data.get_real();
+        let outline = self.get_outline();
+
+        if outline.is_empty() {
+            return;
+        }
+
+        let path = svg::node::element::Path::new()
+            .set("d", build_svg_data(&outline, self.closed, |local| Self::to_world(data, &local)))
+            .set("fill", data.color.to_rgb_hex())
+            .set("fill-opacity", data.color.a * opacity);
+        document.append(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_point_on_segment, split_segment};
+    use crate::data::path::PathAnchor;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn split_segment_keeps_shape() {
+        let from = PathAnchor::new(glm::vec2(0.0, 0.0)).with_handle(glm::vec2(40.0, 90.0));
+        let to = PathAnchor::new(glm::vec2(200.0, 10.0)).with_handle(glm::vec2(30.0, -60.0));
+        let [first, middle, last] = split_segment(&from, &to, 0.3);
+
+        assert_eq!(first.point, from.point);
+        assert_eq!(last.point, to.point);
+        assert!(glm::distance(&middle.point, &get_point_on_segment(&from, &to, 0.3)) < 0.001);
+
+        // Both halves trace out the original curve
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!(glm::distance(&get_point_on_segment(&first, &middle, t), &get_point_on_segment(&from, &to, 0.3 * t)) < 0.01);
+            assert!(glm::distance(&get_point_on_segment(&middle, &last, t), &get_point_on_segment(&from, &to, 0.3 + 0.7 * t)) < 0.01);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::{Squid, SquidKind};
+use crate::smooth::MultiLerp;
+use angular_units::Rad;
+use nalgebra_glm as glm;
+
+// Numeric property of a squid that can be edited directly
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SquidProperty {
+    X,
+    Y,
+    Width,
+    Height,
+    Radius,
+    Rotation,
+    TopLeftRadius,
+    TopRightRadius,
+    BottomLeftRadius,
+    BottomRightRadius,
+    CornerRadius,
+    Blur,
+}
+
+impl SquidProperty {
+    // Radii of a rect's corners, in the same order as 'BorderRadii::to_array'
+    pub const CORNERS: [SquidProperty; 4] = [Self::TopLeftRadius, Self::TopRightRadius, Self::BottomLeftRadius, Self::BottomRightRadius];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Width => "Width",
+            Self::Height => "Height",
+            Self::Radius => "Radius",
+            Self::Rotation => "Rotation",
+            Self::TopLeftRadius => "Top Left",
+            Self::TopRightRadius => "Top Right",
+            Self::BottomLeftRadius => "Bottom Left",
+            Self::BottomRightRadius => "Bottom Right",
+            Self::CornerRadius => "Corner Radius",
+            Self::Blur => "Blur",
+        }
+    }
+
+    // Which of a rect's corners the property is the radius of, indexing into 'CORNERS'
+    pub fn get_corner(&self) -> Option<usize> {
+        match self {
+            Self::TopLeftRadius => Some(0),
+            Self::TopRightRadius => Some(1),
+            Self::BottomLeftRadius => Some(2),
+            Self::BottomRightRadius => Some(3),
+            _ => None,
+        }
+    }
+
+    // Lengths can be entered in any unit
+    pub fn is_length(&self) -> bool {
+        !matches!(self, Self::Rotation)
+    }
+
+    pub fn get_suffix(&self) -> &'static str {
+        match self {
+            Self::Rotation => " deg",
+            _ => "",
+        }
+    }
+}
+
+impl Squid {
+    pub fn get_properties(&self) -> &'static [SquidProperty] {
+        use SquidProperty::*;
+
+        match &self.kind {
+            SquidKind::Rect(_) => &[
+                X,
+                Y,
+                Width,
+                Height,
+                TopLeftRadius,
+                TopRightRadius,
+                BottomLeftRadius,
+                BottomRightRadius,
+                Rotation,
+                Blur,
+            ],
+            SquidKind::Circle(_) => &[X, Y, Radius, Blur],
+            SquidKind::Tri(_) | SquidKind::Path(_) => &[X, Y, Rotation, Blur],
+        }
+    }
+
+    // Gets the current (non-animated) value of a property,
+    // rotations are in degrees
+    pub fn get_property(&self, property: SquidProperty) -> Option<f32> {
+        use SquidProperty::*;
+
+        let position = self.get_real_position();
+
+        match (&self.kind, property) {
+            (_, X) => Some(position.x),
+            (_, Y) => Some(position.y),
+            (_, Blur) => Some(self.blur),
+            (SquidKind::Rect(rect), Width) => Some(rect.data.get_real().size.x),
+            (SquidKind::Rect(rect), Height) => Some(rect.data.get_real().size.y),
+            (SquidKind::Rect(rect), Rotation) => Some(rect.data.get_real().rotation.0.to_degrees()),
+            (SquidKind::Rect(rect), TopLeftRadius | TopRightRadius | BottomLeftRadius | BottomRightRadius) => {
+                property.get_corner().map(|corner| rect.data.get_real().radii.to_array()[corner])
+            }
+            (SquidKind::Circle(circle), Radius) => Some(circle.data.get_real().radius),
+            (SquidKind::Tri(tri), Rotation) => Some((tri.data.get_real().rotation + tri.virtual_rotation).0.to_degrees()),
+            (SquidKind::Path(path), Rotation) => Some(path.data.get_real().rotation.0.to_degrees()),
+            (SquidKind::Tri(tri), CornerRadius) => Some(tri.data.get_real().corner_radius),
+            (SquidKind::Path(path), CornerRadius) => Some(path.data.get_real().corner_radius),
+            _ => None,
+        }
+    }
+
+    pub fn set_property(&mut self, property: SquidProperty, value: f32) {
+        use SquidProperty::*;
+
+        if property == Blur {
+            self.blur = value.max(0.0);
+            return;
+        }
+
+        let position = self.get_real_position();
+
+        let new_position = match property {
+            X => Some(glm::vec2(value, position.y)),
+            Y => Some(glm::vec2(position.x, value)),
+            _ => None,
+        };
+
+        match &mut self.kind {
+            SquidKind::Rect(rect) => {
+                let mut new_data = *rect.data.get_real();
+                match property {
+                    Width => new_data.size.x = value,
+                    Height => new_data.size.y = value,
+                    Rotation => new_data.rotation = Rad(value.to_radians()),
+                    _ => (),
+                }
+                if let Some(corner) = property.get_corner() {
+                    let mut radii = new_data.radii.to_array();
+                    radii[corner] = value.max(0.0);
+                    new_data.radii = radii.into();
+                }
+                if let Some(new_position) = new_position {
+                    new_data.position = MultiLerp::Linear(new_position);
+                }
+                rect.data.set(new_data);
+            }
+            SquidKind::Circle(circle) => {
+                let mut new_data = *circle.data.get_real();
+                if property == Radius {
+                    new_data.radius = value;
+                }
+                if let Some(new_position) = new_position {
+                    new_data.position = MultiLerp::Linear(new_position);
+                }
+                circle.data.set(new_data);
+            }
+            SquidKind::Tri(tri) => {
+                let mut new_data = *tri.data.get_real();
+                match property {
+                    Rotation => new_data.rotation = Rad(value.to_radians()) - tri.virtual_rotation,
+                    CornerRadius => new_data.corner_radius = value.max(0.0),
+                    _ => (),
+                }
+                if let Some(new_position) = new_position {
+                    new_data.position = MultiLerp::Linear(new_position);
+                }
+                tri.data.set(new_data);
+            }
+            SquidKind::Path(path) => {
+                let mut new_data = *path.data.get_real();
+                match property {
+                    Rotation => new_data.rotation = Rad(value.to_radians()),
+                    CornerRadius => new_data.corner_radius = value.max(0.0),
+                    _ => (),
+                }
+                if let Some(new_position) = new_position {
+                    new_data.position = MultiLerp::Linear(new_position);
+                }
+                path.data.set(new_data);
+            }
+        }
+    }
+
+    fn get_real_position(&self) -> glm::Vec2 {
+        match &self.kind {
+            SquidKind::Rect(rect) => rect.data.get_real().position.reveal(),
+            SquidKind::Circle(circle) => circle.data.get_real().position.reveal(),
+            SquidKind::Tri(tri) => tri.data.get_real().position.reveal(),
+            SquidKind::Path(path) => path.data.get_real().position.reveal(),
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use super::{
     behavior::{self, DilateBehavior, RevolveBehavior, SpreadBehavior, TranslateBehavior},
-    Initiation, PreviewParams, HANDLE_RADIUS,
+    get_even_factor, Drawable, Initiation, PreviewParams, HANDLE_RADIUS,
 };
 use crate::{
     accumulator::Accumulator,
+    affine::Affine2,
     algorithm,
-    as_values::AsValues,
     camera::Camera,
     capture::Capture,
     components,
@@ -13,22 +13,27 @@ use crate::{
     interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
     math::DivOrZero,
     mesh::MeshXyz,
-    render_ctx::RenderCtx,
+    mesh_cache::{MeshCache, MeshKey},
     smooth::{MultiLerp, Smooth},
 };
 use angular_units::{Angle, Rad};
-use glium::glutin::event::MouseButton;
+use glium::{glutin::event::MouseButton, Display};
 use lyon::{
     geom::Box2D,
-    path::{math::point, Winding},
+    path::{math::point, Event, Winding},
 };
 use nalgebra_glm as glm;
 use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize)]
 pub struct Rect {
     #[serde(skip)]
-    pub mesh: Option<MeshXyz>,
+    pub mesh: Option<Rc<MeshXyz>>,
+
+    // Which mesh the current mesh is
+    #[serde(skip)]
+    pub mesh_key: Option<MeshKey>,
 
     pub data: Smooth<RectData>,
 
@@ -44,6 +49,14 @@ pub struct Rect {
     #[serde(skip)]
     pub translate_behavior: TranslateBehavior,
 
+    // Corner radius
+    #[serde(skip)]
+    pub radius_drag: Option<RadiusDrag>,
+
+    // Shear, which edge's handle is being dragged
+    #[serde(skip)]
+    pub shearing_edge: Option<usize>,
+
     // Rotate
     #[serde(skip)]
     pub rotating: bool,
@@ -89,6 +102,26 @@ impl Corner {
     }
 }
 
+// Corner radius being dragged, along with where it started
+#[derive(Copy, Clone)]
+pub struct RadiusDrag {
+    corner: usize,
+    start_radius: f32,
+    start: glm::Vec2,
+}
+
+// Which way each corner is from the center, in the same order as 'BorderRadii::to_array'
+const RADIUS_CORNERS: [(f32, f32); 4] = [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)];
+
+// Which way the middle of each edge is from the center, going top, right, bottom, left
+const SHEAR_EDGES: [(f32, f32); 4] = [(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)];
+
+// Shearing stops before the rect would get too close to collapsing into a line
+const MIN_SHEAR_DETERMINANT: f32 = 0.1;
+
+// Radius handles stay at least this many pixels in from their corners, so they can still be grabbed when a corner is sharp
+const MIN_RADIUS_HANDLE_INSET: f32 = 16.0;
+
 impl From<usize> for Corner {
     fn from(corner_index: usize) -> Self {
         use Corner::*;
@@ -123,12 +156,14 @@ impl Rect {
     pub fn get_relative_corners(&self) -> Vec<glm::Vec2> {
         // Use non-animated version always for now?
         // It seems to look better this way
-        let RectData { size, rotation, .. } = self.data.get_animated();
+        let animated = self.data.get_animated();
+        let RectData { size, .. } = animated;
+        let frame = animated.get_frame();
 
         [(1.0, 1.0), (-1.0, 1.0), (1.0, -1.0), (-1.0, -1.0f32)]
             .iter()
             .map(|&p| glm::vec2(p.0 * size.x / 2.0, p.1 * size.y / 2.0))
-            .map(|p| glm::rotate_vec2(&p, -1.0 * rotation.scalar()))
+            .map(|p| frame.apply_to_vector(&p))
             .collect()
     }
 
@@ -138,58 +173,172 @@ impl Rect {
         self.get_relative_corners().iter().map(|p| p + position.reveal()).collect()
     }
 
+    // Radius handles sit where the rounding of each corner is centered
+    pub fn get_radius_handles(&self, camera: &Camera) -> Vec<glm::Vec2> {
+        let animated = self.data.get_animated();
+        let RectData {
+            position,
+            size,
+            radii,
+            is_viewport,
+            ..
+        } = animated;
+
+        if is_viewport {
+            return vec![];
+        }
+
+        let frame = animated.get_frame();
+        let half = size.abs() * 0.5;
+        let min_inset = MIN_RADIUS_HANDLE_INSET / camera.get_scale().max(0.001);
+
+        RADIUS_CORNERS
+            .iter()
+            .zip(radii.to_array())
+            .map(|(&(x, y), radius)| {
+                let inset = radius.max(min_inset).min(half.x.min(half.y));
+                let relative = glm::vec2(x * (half.x - inset), y * (half.y - inset));
+                camera.apply(&(position.reveal() + frame.apply_to_vector(&relative)))
+            })
+            .collect()
+    }
+
+    // Shear handles sit in the middle of each edge
+    pub fn get_shear_handles(&self, camera: &Camera) -> Vec<glm::Vec2> {
+        let animated = self.data.get_animated();
+
+        if animated.is_viewport {
+            return vec![];
+        }
+
+        let frame = animated.get_frame();
+        let half = animated.size * 0.5;
+
+        SHEAR_EDGES
+            .iter()
+            .map(|&(x, y)| camera.apply(&(animated.position.reveal() + frame.apply_to_vector(&glm::vec2(x * half.x, y * half.y)))))
+            .collect()
+    }
+
+    // Dragging the top or bottom edge slants the rect sideways, and the left or right edge slants it up or down
+    fn drag_shear(&mut self, edge: usize, mouse: &glm::Vec2, camera: &Camera) {
+        let mut new_data = *self.data.get_real();
+        let (x, y) = SHEAR_EDGES[edge];
+        let half = new_data.size * 0.5;
+        let offset = glm::rotate_vec2(&(camera.apply_reverse(mouse) - new_data.position.reveal()), new_data.rotation.scalar());
+
+        if x == 0.0 {
+            new_data.shear.x = offset.x.div_or_zero(y * half.y);
+        } else {
+            new_data.shear.y = offset.y.div_or_zero(x * half.x);
+        }
+
+        if 1.0 - new_data.shear.x * new_data.shear.y >= MIN_SHEAR_DETERMINANT {
+            self.data.set(new_data);
+        }
+    }
+
+    // Changes the radius of the corner being dragged, or of every corner when 'all_corners' is set
+    fn drag_radius(&mut self, radius_drag: RadiusDrag, mouse: &glm::Vec2, camera: &Camera, all_corners: bool) {
+        let mut new_data = *self.data.get_real();
+        let (x, y) = RADIUS_CORNERS[radius_drag.corner];
+        let delta = self.get_local_point(mouse, camera) - radius_drag.start;
+
+        // Moving towards the center rounds the corner more
+        let max_radius = 0.5 * new_data.size.x.abs().min(new_data.size.y.abs());
+        let radius = (radius_drag.start_radius - 0.5 * (x * delta.x + y * delta.y)).clamp(0.0, max_radius);
+
+        let mut radii = new_data.radii.to_array();
+
+        if all_corners {
+            radii = [radius; 4];
+        } else {
+            radii[radius_drag.corner] = radius;
+        }
+
+        new_data.radii = radii.into();
+        self.data.set(new_data);
+    }
+
+    // Where a point on screen is relative to the center of the rect, before it's sheared and rotated
+    fn get_local_point(&self, point: &glm::Vec2, camera: &Camera) -> glm::Vec2 {
+        let real = self.data.get_real();
+
+        real.get_frame().inverse().map_or_else(glm::Vec2::zeros, |inverse| {
+            inverse.apply_to_vector(&(camera.apply_reverse(point) - real.position.reveal()))
+        })
+    }
+
     fn get_screen_corners(&self, camera: &Camera) -> Vec<glm::Vec2> {
         let RectData { position, .. } = self.data.get_animated();
 
         self.get_relative_corners().iter().map(|p| camera.apply(&(p + position.reveal()))).collect()
     }
 
-    fn reposition_corner(&mut self, from: RectScaleFrom, mouse: &glm::Vec2, camera: &Camera) {
+    // Holding shift keeps the proportions the rect had when its corner was grabbed
+    fn reposition_corner(&mut self, from: RectScaleFrom, mouse: &glm::Vec2, camera: &Camera, proportional: bool) {
         let real = self.data.get_real();
-        let rotation = real.rotation.scalar();
+        let frame = real.get_frame();
         let mouse_in_world = camera.apply_reverse(mouse);
 
+        let to_frame = match frame.inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+
         match from {
             RectScaleFrom::Corner => {
                 let pivot = self.opposite_corner_position.unwrap();
-                let frame_vector = glm::rotate_vec2(&(pivot - mouse_in_world), rotation);
+                let frame_vector = to_frame.apply_to_vector(&(pivot - mouse_in_world));
 
-                let size = frame_vector.component_mul(&match self.moving_corner.unwrap() {
+                let flip = match self.moving_corner.unwrap() {
                     Corner::ZeroZero => glm::vec2(1.0, 1.0),
                     Corner::XZero => glm::vec2(-1.0, 1.0),
                     Corner::ZeroY => glm::vec2(1.0, -1.0),
                     Corner::XY => glm::vec2(-1.0, -1.0),
-                });
+                };
+
+                let mut size = frame_vector.component_mul(&flip);
+
+                if proportional {
+                    size = keep_aspect_ratio(&size, &self.prescale_size);
+                }
+
+                // The moved corner ends up wherever the constrained size puts it
+                let corner = pivot - frame.apply_to_vector(&size.component_mul(&flip));
 
                 let mut new_data = *real;
-                new_data.position = MultiLerp::Linear(0.5 * (mouse_in_world + pivot));
+                new_data.position = MultiLerp::Linear(0.5 * (corner + pivot));
                 new_data.size = size;
                 self.data.set(new_data);
-                self.mesh = None;
             }
             RectScaleFrom::Center => {
-                let abs_size = 2.0 * glm::rotate_vec2(&(real.position.reveal() - mouse_in_world), rotation);
+                let abs_size = 2.0 * to_frame.apply_to_vector(&(real.position.reveal() - mouse_in_world));
 
-                let new_size = abs_size.component_mul(&match self.moving_corner.unwrap() {
+                let mut new_size = abs_size.component_mul(&match self.moving_corner.unwrap() {
                     Corner::ZeroZero => glm::vec2(1.0, 1.0),
                     Corner::XZero => glm::vec2(-1.0, 1.0),
                     Corner::ZeroY => glm::vec2(1.0, -1.0),
                     Corner::XY => glm::vec2(-1.0, -1.0),
                 });
 
+                if proportional {
+                    new_size = keep_aspect_ratio(&new_size, &self.prescale_size);
+                }
+
                 let mut new_data = *real;
                 new_data.size = new_size;
                 self.data.set(new_data);
-                self.mesh = None;
             }
         }
     }
 
     pub fn is_point_over(&self, mouse_position: glm::Vec2, camera: &Camera) -> bool {
-        if self.data.get_animated().is_viewport {
-            return false;
-        }
+        !self.data.get_animated().is_viewport && self.is_point_inside(mouse_position, camera)
+    }
 
+    // Whether a point on screen is within the rect, even if it's a viewport
+    pub fn is_point_inside(&self, mouse_position: glm::Vec2, camera: &Camera) -> bool {
         let underneath = camera.apply_reverse(&mouse_position);
 
         let corners: Vec<glm::Vec2> = self.get_world_corners();
@@ -202,6 +351,8 @@ impl Rect {
             Interaction::PreClick => {
                 self.translate_behavior.moving = false;
                 self.rotating = false;
+                self.radius_drag = None;
+                self.shearing_edge = None;
                 self.moving_corner = None;
                 self.opposite_corner_position = None;
             }
@@ -216,6 +367,18 @@ impl Rect {
 
                         self.moving_corner = Some(i.into());
                         self.opposite_corner_position = Some(world_corners[usize::from(Corner::from(i).opposite())]);
+                        self.prescale_size = self.data.get_real().size;
+                        return Capture::AllowDrag;
+                    }
+                }
+
+                for (corner, handle) in self.get_radius_handles(camera).iter().enumerate() {
+                    if glm::distance(position, handle) <= HANDLE_RADIUS * 2.0 {
+                        self.radius_drag = Some(RadiusDrag {
+                            corner,
+                            start_radius: self.data.get_real().radii.to_array()[corner],
+                            start: self.get_local_point(position, camera),
+                        });
                         return Capture::AllowDrag;
                     }
                 }
@@ -225,6 +388,13 @@ impl Rect {
                     return Capture::AllowDrag;
                 }
 
+                for (edge, handle) in self.get_shear_handles(camera).iter().enumerate() {
+                    if glm::distance(position, handle) <= HANDLE_RADIUS * 2.0 {
+                        self.shearing_edge = Some(edge);
+                        return Capture::AllowDrag;
+                    }
+                }
+
                 if self.is_point_over(*position, camera) {
                     self.translate_behavior.moving = true;
                     return Capture::AllowDrag;
@@ -238,7 +408,12 @@ impl Rect {
             }) => {
                 if self.moving_corner.is_some() {
                     let from = if modifiers.alt() { RectScaleFrom::Center } else { RectScaleFrom::Corner };
-                    self.reposition_corner(from, mouse_position, camera);
+                    self.reposition_corner(from, mouse_position, camera, modifiers.shift());
+                } else if let Some(radius_drag) = self.radius_drag {
+                    // Holding shift rounds every corner together
+                    self.drag_radius(radius_drag, mouse_position, camera, modifiers.shift());
+                } else if let Some(edge) = self.shearing_edge {
+                    self.drag_shear(edge, mouse_position, camera);
                 } else if self.rotating {
                     // When the rectangle's width is negative, the rotation handle is PI radians ahead of it's angle
                     // compared to the actual rotation of the shape,
@@ -252,6 +427,7 @@ impl Rect {
                     return Capture::RotateSelectedSquids {
                         delta_theta: compensation
                             + behavior::get_delta_rotation(&real.position.reveal(), real.rotation, mouse_position, &self.rotation_accumulator, camera),
+                        shift: modifiers.shift(),
                     };
                 } else if self.translate_behavior.moving {
                     return Capture::MoveSelectedSquids {
@@ -261,6 +437,8 @@ impl Rect {
             }
             Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
                 self.rotating = false;
+                self.radius_drag = None;
+                self.shearing_edge = None;
                 self.moving_corner = None;
                 self.translate_behavior.accumulator.clear();
                 self.rotation_accumulator.clear();
@@ -302,74 +480,135 @@ impl Rect {
         }
     }
 
-    pub fn render(&mut self, ctx: &mut RenderCtx, as_preview: Option<PreviewParams>) {
+    pub fn needs_mesh(&self) -> bool {
+        self.mesh.is_none() && !self.data.get_animated().is_viewport
+    }
+
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache, immediately: bool) {
+        let animated = self.data.get_animated();
+
+        if animated.is_viewport {
+            return;
+        }
+
+        let key = MeshKey::rounded_rect(animated.size, animated.radii);
+
+        // Corners being rounded by hand follow the mouse without waiting on the background tessellator
+        let immediately = (self.mesh.is_none() && immediately) || (self.radius_drag.is_some() && self.mesh_key != Some(key));
+
+        if immediately {
+            self.mesh = Some(mesh_cache.get(key, display));
+            self.mesh_key = Some(key);
+        } else if self.mesh_key != Some(key) {
+            // Keep showing the old mesh stretched to fit until the new one is ready
+            if let Some(mesh) = mesh_cache.get_eventually(key, display) {
+                self.mesh = Some(mesh);
+                self.mesh_key = Some(key);
+            }
+        }
+    }
+
+    pub fn get_drawable(&self, as_preview: Option<PreviewParams>) -> Option<Drawable<'_>> {
         let RectData {
             position,
             size,
             rotation,
             color,
             is_viewport,
+            shear,
             ..
         } = self.data.get_animated();
 
+        // Don't draw viewport
         if is_viewport {
-            // Don't draw viewport
-        } else {
-            // Refresh mesh
-            {
-                let real = self.data.get_real();
-                let animated = self.data.get_animated();
+            return None;
+        }
 
-                // Don't use margin of error
-                if self.mesh.is_none() || real.radii != animated.radii || real.size != animated.size {
-                    self.mesh = Some(MeshXyz::new_rect(ctx.display, animated.size, animated.radii));
-                }
-            }
+        let mesh = self.mesh.as_ref()?;
+        let mesh_key = self.mesh_key?;
 
-            // Translate
-            let mut transformation = glm::translation(&glm::vec2_to_vec3(&if let Some(preview) = &as_preview {
-                preview.position
-            } else {
-                position.reveal()
-            }));
-
-            // Rotate
-            transformation = glm::rotate(&transformation, rotation.scalar(), &glm::vec3(0.0, 0.0, -1.0));
-
-            // Scale
-            if let Some(preview) = &as_preview {
-                let max_size = glm::comp_max(&size.abs());
-                let preview_scale = preview.radius.div_or_zero(max_size);
-                transformation = glm::scale(&transformation, &glm::vec3(preview_scale, preview_scale, 0.0));
-            }
+        // Translate
+        let mut transformation = Affine2::translation(&if let Some(preview) = &as_preview {
+            preview.position
+        } else {
+            position.reveal()
+        });
 
-            let uniforms = glium::uniform! {
-                transformation: transformation.as_values(),
-                view: if as_preview.is_some() {
-                    glm::identity::<f32, 4>().as_values()
-                } else {
-                    ctx.view.as_values()
-                },
-                projection: ctx.projection.as_values(),
-                color: color.as_values()
-            };
+        // Rotate and shear
+        transformation = transformation.rotate(-rotation.scalar()).shear(&shear);
 
-            let mesh = self.mesh.as_ref().unwrap();
-            ctx.draw(&mesh.vertex_buffer, &mesh.indices, ctx.color_shader, &uniforms, &Default::default())
-                .unwrap();
+        // Scale
+        if let Some(preview) = &as_preview {
+            let max_size = glm::comp_max(&size.abs());
+            let preview_scale = preview.radius.div_or_zero(max_size);
+            transformation = transformation.scale(&glm::vec2(preview_scale, preview_scale));
         }
+
+        // Stretch mesh to fit
+        transformation = transformation.scale(&mesh_key.get_fit(&size));
+
+        Some(Drawable {
+            mesh,
+            transformation,
+            color: *color,
+        })
+    }
+
+    // Rects can only stretch along their own sides, so ones that are turned or slanted are scaled evenly instead
+    pub fn stretch(&mut self, factor: &glm::Vec2, origin: &glm::Vec2) {
+        let mut new_data = *self.data.get_real();
+        let quarter_turns = new_data.rotation.0 / std::f32::consts::FRAC_PI_2;
+        let is_upright = new_data.shear == glm::zero::<glm::Vec2>() && (quarter_turns - quarter_turns.round()).abs() < 0.001;
+
+        let factor = if is_upright { *factor } else { glm::vec2(1.0, 1.0) * get_even_factor(factor) };
+        let along_sides = if is_upright && (quarter_turns.round() as i32).rem_euclid(2) == 1 {
+            glm::vec2(factor.y, factor.x)
+        } else {
+            factor
+        };
+
+        new_data.size = new_data.size.component_mul(&along_sides);
+        new_data.position = MultiLerp::Linear(origin + (new_data.position.reveal() - origin).component_mul(&factor));
+        self.data.set(new_data);
     }
 
-    pub fn build(&self, document: &mut svg::Document) {
-        let RectData { position, size, radii, .. } = self.data.get_real();
-        let position = position.reveal();
+    pub fn build(&self, document: &mut svg::Document, opacity: f32) {
+        use svg::{node::element::path::Data, Node};
+
+        let real = self.data.get_real();
+
+        if real.is_viewport {
+            return;
+        }
+
+        // The outline is made around the center, and then moved into place by the transform
+        let hw = real.size.x.abs() * 0.5;
+        let hh = real.size.y.abs() * 0.5;
+
+        let mut builder = lyon::path::Path::builder();
+        builder.add_rounded_rectangle(&Box2D::new(point(-hw, -hh), point(hw, hh)), &real.radii.into(), Winding::Positive);
+
+        let mut path_data = Data::new();
+
+        for event in builder.build().iter() {
+            path_data = match event {
+                Event::Begin { at } => path_data.move_to((at.x, at.y)),
+                Event::Line { to, .. } => path_data.line_to((to.x, to.y)),
+                Event::Quadratic { ctrl, to, .. } => path_data.quadratic_curve_to((ctrl.x, ctrl.y, to.x, to.y)),
+                Event::Cubic { ctrl1, ctrl2, to, .. } => path_data.cubic_curve_to((ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y)),
+                Event::End { close: true, .. } => path_data.close(),
+                Event::End { .. } => path_data,
+            };
+        }
 
-        let x = position.x;
-        let y = position.y;
-        let hw = size.x * 0.5;
-        let hh = size.y * 0.5;
+        let transform = Affine2::translation(&real.position.reveal()) * real.get_frame();
 
-        // builder.add_rounded_rectangle(&Box2D::new(point(x - hw, y - hh), point(x + hw, y + hh)), &radii.into(), Winding::Positive, &[]);
+        let path = svg::node::element::Path::new()
+            .set("d", path_data)
+            .set("transform", transform.to_svg_transform())
+            .set("fill", real.color.to_rgb_hex())
+            .set("fill-opacity", real.color.a * opacity);
+        document.append(path);
     }
 }
 
@@ -378,3 +617,114 @@ enum RectScaleFrom {
     Corner,
     Center,
 }
+
+// Grows or shrinks 'original' until it covers 'size', while staying flipped the same way as 'size'
+fn keep_aspect_ratio(size: &glm::Vec2, original: &glm::Vec2) -> glm::Vec2 {
+    let scale = size.x.abs().div_or_zero(original.x.abs()).max(size.y.abs().div_or_zero(original.y.abs()));
+    glm::vec2(original.x.abs().copysign(size.x), original.y.abs().copysign(size.y)) * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keep_aspect_ratio, RadiusDrag};
+    use crate::{
+        camera::Camera,
+        color::Color,
+        squid::{Squid, SquidKind},
+    };
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn drag_corner_radius() {
+        let camera = Camera::identity(glm::vec2(800.0, 600.0));
+        let mut squid = Squid::rect(glm::vec2(0.0, 0.0), glm::vec2(100.0, 60.0), Rad(0.0), Color::default(), 0.0, false);
+        let rect = match &mut squid.kind {
+            SquidKind::Rect(rect) => rect,
+            _ => unreachable!(),
+        };
+
+        let handle = rect.get_radius_handles(&camera)[0];
+        let start = rect.get_local_point(&handle, &camera);
+        let drag = RadiusDrag {
+            corner: 0,
+            start_radius: 0.0,
+            start,
+        };
+
+        // Moving the top left handle inwards only rounds that corner
+        rect.drag_radius(drag, &(handle + glm::vec2(10.0, 10.0)), &camera, false);
+        assert_eq!(rect.data.get_real().radii.to_array(), [10.0, 0.0, 0.0, 0.0]);
+
+        // Holding shift rounds every corner, which can't go past half the shorter side
+        rect.drag_radius(drag, &(handle + glm::vec2(100.0, 100.0)), &camera, true);
+        assert_eq!(rect.data.get_real().radii.to_array(), [30.0; 4]);
+    }
+
+    #[test]
+    fn shear_from_edge() {
+        let camera = Camera::identity(glm::vec2(800.0, 600.0));
+        let mut squid = Squid::rect(glm::vec2(0.0, 0.0), glm::vec2(100.0, 60.0), Rad(0.0), Color::default(), 0.0, false);
+        let rect = match &mut squid.kind {
+            SquidKind::Rect(rect) => rect,
+            _ => unreachable!(),
+        };
+
+        // Pulling the top edge sideways by its height slants the rect by one
+        let top = rect.get_shear_handles(&camera)[0];
+        rect.drag_shear(0, &(top + glm::vec2(-30.0, 0.0)), &camera);
+        rect.data.finish();
+        assert_eq!(rect.data.get_real().shear, glm::vec2(1.0, 0.0));
+
+        // Corners and hit testing follow the slant
+        assert!(rect.get_world_corners().contains(&glm::vec2(20.0, -30.0)));
+        assert!(rect.is_point_inside(camera.apply(&glm::vec2(70.0, 29.0)), &camera));
+        assert!(!rect.is_point_inside(camera.apply(&glm::vec2(-70.0, 29.0)), &camera));
+
+        // Shearing the other way too is only allowed until the rect would collapse
+        let right = rect.get_shear_handles(&camera)[1];
+        rect.drag_shear(1, &(right + glm::vec2(0.0, 50.0)), &camera);
+        assert_eq!(rect.data.get_real().shear, glm::vec2(1.0, 0.0));
+    }
+
+    #[test]
+    fn convert_to_path() {
+        // Each rounded corner becomes two anchors joined by a curve
+        let mut squid = Squid::rect(glm::vec2(10.0, 20.0), glm::vec2(100.0, 60.0), Rad(0.0), Color::default(), 10.0, false);
+        assert!(squid.convert_to_path());
+        let anchors = match &squid.kind {
+            SquidKind::Path(path) => path.get_world_anchors(),
+            _ => unreachable!(),
+        };
+        assert_eq!(anchors.len(), 8);
+        assert!(anchors.contains(&glm::vec2(-30.0, -10.0)));
+        assert!(anchors.contains(&glm::vec2(-40.0, 0.0)));
+
+        // Circles become four curves, and viewports stay as they are
+        let mut circle = Squid::circle(glm::vec2(0.0, 0.0), 50.0, Color::default());
+        assert!(circle.convert_to_path());
+        assert!(matches!(&circle.kind, SquidKind::Path(path) if path.get_world_anchors().len() == 4));
+
+        let mut viewport = Squid::rect(glm::vec2(0.0, 0.0), glm::vec2(100.0, 60.0), Rad(0.0), Color::default(), 0.0, true);
+        assert!(!viewport.convert_to_path());
+    }
+
+    #[test]
+    fn object_info() {
+        let mut rect = Squid::rect(glm::vec2(10.0, 20.0), glm::vec2(100.0, 60.0), Rad(0.5), Color::default(), 0.0, false);
+        rect.set_name("Header".into());
+        assert_eq!(rect.get_info(), vec!["Header (Rect)", "X 10.0  Y 20.0", "100.0 x 60.0", "Rotation 28.6 deg"]);
+
+        // Circles don't have a rotation to show
+        let circle = Squid::circle(glm::vec2(0.0, 0.0), 5.0, Color::default());
+        assert_eq!(circle.get_info(), vec!["Unnamed Circle (Circle)", "X 0.0  Y 0.0", "10.0 x 10.0"]);
+    }
+
+    #[test]
+    fn scale_proportionally() {
+        let original = glm::vec2(100.0, 50.0);
+        assert_eq!(keep_aspect_ratio(&glm::vec2(150.0, 60.0), &original), glm::vec2(150.0, 75.0));
+        assert_eq!(keep_aspect_ratio(&glm::vec2(-20.0, 40.0), &original), glm::vec2(-80.0, 40.0));
+        assert_eq!(keep_aspect_ratio(&glm::vec2(20.0, -5.0), &-original), glm::vec2(20.0, -10.0));
+    }
+}
@@ -1,18 +1,23 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, rc::Rc};
 
 use crate::{
     accumulator::Accumulator,
+    affine::Affine2,
     algorithm::{get_distance_between_point_and_triangle, get_triangle_center, is_point_inside_triangle},
-    as_values::AsValues,
     camera::Camera,
     capture::Capture,
     components,
-    data::TriData,
+    data::{
+        path::{round_corners, PathAnchor},
+        TriData,
+    },
     interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
     math::DivOrZero,
     mesh::MeshXyz,
-    render_ctx::RenderCtx,
+    mesh_cache::{MeshCache, MeshKey},
     smooth::{MultiLerp, Smooth},
+    squid::path::{build_lyon_path, build_svg_data},
+    tessellator::Shape,
 };
 use angular_units::{Angle, Rad};
 use glium::{glutin::event::MouseButton, Display};
@@ -22,25 +27,29 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     behavior::{self, DilateBehavior, RevolveBehavior, SpreadBehavior, TranslateBehavior},
-    Initiation, PreviewParams, HANDLE_RADIUS,
+    Drawable, Initiation, PreviewParams, HANDLE_RADIUS,
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct Tri {
     #[serde(skip)]
-    pub mesh: Option<MeshXyz>,
+    pub mesh: Option<Rc<MeshXyz>>,
 
-    pub data: Smooth<TriData>,
-
-    // Keep track of which points the mesh is made of,
-    // so that we know when we have to re-create it
+    // Points and corner radius that the mesh was made from when it has rounded corners,
+    // otherwise the shared unit triangle mesh is used
     #[serde(skip)]
-    pub mesh_p: [glm::Vec2; 3],
+    pub mesh_shape: Option<([glm::Vec2; 3], f32)>,
+
+    pub data: Smooth<TriData>,
 
     // Move point
     #[serde(skip)]
     pub moving_point: Option<usize>, // (zero indexed)
 
+    // Where the points were in world space when one started moving
+    #[serde(skip)]
+    pub points_before_move: [glm::Vec2; 3],
+
     // Translate
     #[serde(skip)]
     pub translate_behavior: TranslateBehavior,
@@ -73,15 +82,15 @@ pub struct Tri {
 }
 
 impl Tri {
-    pub fn render(&mut self, ctx: &mut RenderCtx, as_preview: Option<PreviewParams>) {
+    pub fn get_drawable(&self, as_preview: Option<PreviewParams>) -> Option<Drawable<'_>> {
         let TriData {
             position, p, rotation, color, ..
         } = self.data.get_animated();
 
-        let p = p.map(|point| point.reveal() + position.reveal());
+        let relative = p.map(|point| point.reveal());
+        let p = relative.map(|point| point + position.reveal());
         let position = self.data.get_animated().position.reveal();
-
-        self.refresh_mesh(ctx.display);
+        let mesh = self.mesh.as_ref()?;
 
         let (render_position, render_size) = if let Some(preview) = &as_preview {
             let max_distance = p.map(|point| glm::distance(&point, &position)).iter().fold(0.0f32, |a, &b| a.max(b));
@@ -93,42 +102,60 @@ impl Tri {
 
         let transformation = {
             let mut matrix;
-            matrix = glm::translation(&glm::vec2_to_vec3(&render_position));
-            matrix = glm::rotate(&matrix, rotation.scalar(), &glm::vec3(0.0, 0.0, -1.0));
-            matrix = glm::scale(&matrix, &glm::vec3(render_size, render_size, 0.0));
-            matrix
-        };
+            matrix = Affine2::translation(&render_position);
+            matrix = matrix.rotate(-rotation.scalar());
+            matrix = matrix.scale(&glm::vec2(render_size, render_size));
 
-        let uniforms = glium::uniform! {
-            transformation: transformation.as_values(),
-            view: if as_preview.is_some() {
-                glm::identity::<f32, 4>().as_values()
-            } else {
-                ctx.view.as_values()
-            },
-            projection: ctx.projection.as_values(),
-            color: color.as_values()
+            // Stretch the triangle the mesh was made from onto the points
+            let from_mesh = match self.mesh_shape {
+                Some((points, _)) => get_basis(&points).inverse()?,
+                None => Affine2::identity(),
+            };
+
+            matrix * get_basis(&relative) * from_mesh
         };
 
-        let mesh = self.mesh.as_ref().unwrap();
-        ctx.draw(&mesh.vertex_buffer, &mesh.indices, ctx.color_shader, &uniforms, &Default::default())
-            .unwrap();
+        Some(Drawable {
+            mesh,
+            transformation,
+            color: *color,
+        })
     }
 
-    pub fn refresh_mesh(&mut self, display: &Display) {
-        let TriData { p, .. } = self.data.get_animated();
+    pub fn prepare(&mut self, display: &Display, mesh_cache: &mut MeshCache) {
+        let real = self.data.get_real();
+
+        if real.corner_radius <= 0.0 {
+            if self.mesh.is_none() || self.mesh_shape.is_some() {
+                self.mesh = Some(mesh_cache.get(MeshKey::Triangle, display));
+                self.mesh_shape = None;
+            }
+            return;
+        }
+
+        // Rounded corners don't keep their shape when stretched, so each rounded triangle gets its own mesh
+        let shape = (real.p.map(|point| point.reveal()), real.corner_radius);
 
-        let p = p.map(|point| point.reveal());
+        if self.mesh_shape != Some(shape) {
+            let tessellation = Shape::Fill(build_lyon_path(&Self::get_outline(&shape.0, shape.1), true))
+                .tessellate()
+                .unwrap_or_default();
 
-        let model_point_mismatch = p.iter().zip(self.mesh_p).any(|(a, b)| glm::distance2(&a, &b) > 1.0);
+            self.mesh = if tessellation.indices.is_empty() {
+                None
+            } else {
+                Some(Rc::new(MeshXyz::from_tessellation(&tessellation, display)))
+            };
 
-        if self.mesh.is_none() || model_point_mismatch {
-            // Data points are far enough from existing mesh that we will need
-            // to re-create it
-            self.mesh = Some(MeshXyz::new_shape_triangle(display, p));
+            self.mesh_shape = Some(shape);
         }
     }
 
+    // Anchors that go around the triangle, with its corners rounded
+    fn get_outline(points: &[glm::Vec2; 3], corner_radius: f32) -> Vec<PathAnchor> {
+        round_corners(&points.map(PathAnchor::new), true, corner_radius)
+    }
+
     pub fn get_animated_screen_points(&self, camera: &Camera) -> [glm::Vec2; 3] {
         let TriData { p, position, rotation, .. } = self.data.get_animated();
 
@@ -171,6 +198,7 @@ impl Tri {
                 for (i, corner) in self.get_animated_screen_points(camera).iter().enumerate() {
                     if glm::distance(position, corner) <= HANDLE_RADIUS * 2.0 {
                         self.moving_point = Some(i);
+                        self.points_before_move = self.get_world_points();
                         return Capture::AllowDrag;
                     }
                 }
@@ -188,10 +216,11 @@ impl Tri {
             Interaction::Drag(DragInteraction {
                 delta,
                 current: mouse_position,
+                modifiers,
                 ..
             }) => {
                 if self.moving_point.is_some() {
-                    self.reposition_point(mouse_position, camera);
+                    self.reposition_point(mouse_position, camera, modifiers.shift());
                 } else if self.rotating {
                     return Capture::RotateSelectedSquids {
                         delta_theta: behavior::get_delta_rotation(
@@ -201,6 +230,7 @@ impl Tri {
                             &self.rotation_accumulator,
                             camera,
                         ),
+                        shift: modifiers.shift(),
                     };
                 } else if self.translate_behavior.moving {
                     return Capture::MoveSelectedSquids {
@@ -263,25 +293,76 @@ impl Tri {
         is_point_inside_triangle(underneath, world_p)
     }
 
-    pub fn build(&self, _document: &svg::Document) {}
-
-    fn reposition_point(&mut self, mouse_position: &glm::Vec2, camera: &Camera) {
-        let TriData { p, position, rotation, .. } = self.data.get_real();
+    pub fn stretch(&mut self, factor: &glm::Vec2, origin: &glm::Vec2) {
+        let points = self.get_world_points().map(|point| origin + (point - origin).component_mul(factor));
+        self.set_world_points(points);
+    }
 
-        let position = position.reveal();
+    pub fn build(&self, document: &mut svg::Document, opacity: f32) {
+        use svg::Node;
 
-        let mut p = p.map(|point| glm::rotate_vec2(&point.reveal(), -rotation.scalar()));
-        let mouse_world_position = camera.apply_reverse(mouse_position);
-        let new_single_p = mouse_world_position - position;
+        let TriData {
+            p,
+            position,
+            rotation,
+            color,
+            corner_radius,
+        } = self.data.get_real();
+
+        let to_world = |local: glm::Vec2| glm::rotate_vec2(&local, -rotation.scalar()) + position.reveal();
+        let outline = Self::get_outline(&p.map(|point| point.reveal()), *corner_radius);
+
+        let path = svg::node::element::Path::new()
+            .set("d", build_svg_data(&outline, true, to_world))
+            .set("fill", color.to_rgb_hex())
+            .set("fill-opacity", color.a * opacity);
+        document.append(path);
+    }
 
+    // Holding shift scales the whole triangle instead, so it keeps its shape
+    fn reposition_point(&mut self, mouse_position: &glm::Vec2, camera: &Camera, proportional: bool) {
         if let Some(index) = self.moving_point {
-            p[index] = new_single_p;
+            let world_position = camera.apply_reverse(mouse_position);
+
+            if proportional {
+                self.scale_from_opposite_side(index, &world_position);
+            } else {
+                self.move_point(index, &world_position);
+            }
+        }
+    }
+
+    // Moves one of the points to a position in world space
+    pub fn move_point(&mut self, index: usize, world_position: &glm::Vec2) {
+        let mut points = self.get_world_points();
+
+        if let Some(point) = points.get_mut(index) {
+            *point = *world_position;
         }
 
-        let delta_center = get_triangle_center(p);
-        let new_position = position + delta_center;
+        self.set_world_points(points);
+    }
+
+    // Scales the triangle uniformly about the middle of the side across from a point,
+    // so that the point follows a position in world space as closely as it can
+    fn scale_from_opposite_side(&mut self, index: usize, world_position: &glm::Vec2) {
+        let start = self.points_before_move;
+        let pivot = 0.5 * (start[(index + 1) % 3] + start[(index + 2) % 3]);
+        let reach = start[index] - pivot;
+        let factor = glm::dot(&(world_position - pivot), &reach).div_or_zero(glm::dot(&reach, &reach));
+
+        self.set_world_points(start.map(|point| pivot + (point - pivot) * factor));
+    }
+
+    fn get_world_points(&self) -> [glm::Vec2; 3] {
+        let TriData { p, position, rotation, .. } = self.data.get_real();
+        p.map(|point| glm::rotate_vec2(&point.reveal(), -rotation.scalar()) + position.reveal())
+    }
 
-        let p = p.map(|point| point - delta_center);
+    fn set_world_points(&mut self, points: [glm::Vec2; 3]) {
+        let rotation = self.data.get_real().rotation;
+        let new_position = get_triangle_center(points);
+        let p = points.map(|point| point - new_position);
 
         // Set new data as the new target points, with zero rotation applied
 
@@ -289,7 +370,7 @@ impl Tri {
         // The virtual rotation will be adjusted to compensate
         // HACK: Instantly snap rotation back to 0.0
         {
-            self.virtual_rotation += *rotation;
+            self.virtual_rotation += rotation;
 
             let mut_real = self.data.manual_get_real();
             mut_real.p = p.map(|point| MultiLerp::Linear(point));
@@ -303,3 +384,41 @@ impl Tri {
         }
     }
 }
+
+// Moves the unit triangle onto three points
+fn get_basis(points: &[glm::Vec2; 3]) -> Affine2 {
+    Affine2::from_basis(&(points[1] - points[0]), &(points[2] - points[0]), &points[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        squid::{Squid, SquidKind},
+    };
+    use angular_units::Rad;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn scale_triangle_proportionally() {
+        let mut squid = Squid::tri([glm::vec2(0.0, 0.0), glm::vec2(40.0, 0.0), glm::vec2(20.0, 30.0)], Rad(0.0), Color::default());
+        let tri = match &mut squid.kind {
+            SquidKind::Tri(tri) => tri,
+            _ => unreachable!(),
+        };
+
+        tri.moving_point = Some(2);
+        tri.points_before_move = tri.get_world_points();
+
+        // Pulling the top point up doubles the size, leaving the middle of the bottom side in place
+        tri.scale_from_opposite_side(2, &glm::vec2(25.0, 60.0));
+
+        for (point, expected) in tri
+            .get_world_points()
+            .iter()
+            .zip([glm::vec2(-20.0, 0.0), glm::vec2(60.0, 0.0), glm::vec2(20.0, 60.0)])
+        {
+            assert!(glm::distance(point, &expected) < 0.001);
+        }
+    }
+}
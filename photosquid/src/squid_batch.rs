@@ -0,0 +1,35 @@
+use crate::{as_values::AsValues, renderer::Renderer, squid::Drawable, vertex::VertexXYRGBA};
+use nalgebra_glm as glm;
+
+// Collects squids into a single vertex buffer so they can all be drawn at once.
+// Vertices are moved into world space ahead of time and carry their own color,
+// and squids stay in the order they were added, so blending still layers them correctly
+#[derive(Default)]
+pub struct SquidBatch {
+    vertices: Vec<VertexXYRGBA>,
+}
+
+impl SquidBatch {
+    pub fn push(&mut self, drawable: &Drawable, opacity: f32) {
+        let color = drawable.color.with_opacity(opacity).as_values();
+
+        self.vertices.extend(drawable.mesh.triangles.iter().map(|vertex| VertexXYRGBA {
+            position: drawable.transformation.apply(&glm::Vec2::from(vertex.position)).into(),
+            color,
+        }));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    // Draws everything collected so far in a single draw call
+    pub fn flush<R: Renderer>(&mut self, renderer: &mut R) {
+        if self.is_empty() {
+            return;
+        }
+
+        renderer.draw_colored_triangles(&self.vertices);
+        self.vertices.clear();
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{aabb::AABB, draw_text::draw_text, ocean::Ocean, render_ctx::RenderCtx, selection::Selection};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Thin bar along the bottom of the canvas, which shows where the mouse is,
+// how far the view is zoomed, what is selected, whether a macro is being recorded and whether snapping is off
+pub struct StatusBar {
+    text: String,
+}
+
+impl StatusBar {
+    pub const HEIGHT: f32 = 24.0;
+
+    pub fn new() -> Self {
+        Self { text: String::new() }
+    }
+
+    pub fn update(&mut self, mouse_in_world: Option<glm::Vec2>, zoom: f32, ocean: &Ocean, selections: &[Selection], recording_macro: bool, snapping: bool) {
+        let position = match mouse_in_world {
+            Some(position) => format!("X: {:.1}  Y: {:.1}", position.x, position.y),
+            None => "X: -  Y: -".into(),
+        };
+
+        let layer = ocean.get_layers().get(ocean.get_current_layer_index()).map_or("", |layer| layer.get_name());
+
+        self.text = format!(
+            "{}     Zoom: {:.0}%     Selected: {}     Layer: {}",
+            position,
+            zoom * 100.0,
+            selections.len(),
+            layer
+        );
+
+        if recording_macro {
+            self.text.push_str("     Recording Macro");
+        }
+
+        if !snapping {
+            self.text.push_str("     Snapping Off");
+        }
+    }
+
+    // Spans the canvas between the side panels
+    pub fn get_area(panel_areas: &[AABB; 2]) -> AABB {
+        let left = panel_areas[0].max_x;
+        let right = panel_areas[1].min_x;
+        let bottom = panel_areas[0].max_y;
+        AABB::new(left, bottom - Self::HEIGHT, (right - left).max(0.0), Self::HEIGHT)
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x, area.min_y),
+            glm::vec2(area.width(), area.height()),
+            &ctx.color_scheme.dark_ribbon,
+        );
+
+        draw_text(
+            text_system,
+            font,
+            &self.text,
+            &glm::vec2(area.min_x + 12.0, area.center_y() + 6.0),
+            ctx,
+            ctx.color_scheme.secondary_text,
+        );
+    }
+}
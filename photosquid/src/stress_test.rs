@@ -0,0 +1,52 @@
+use crate::{color::Color, ocean::Ocean, squid::Squid};
+use angular_units::Rad;
+use nalgebra_glm as glm;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Procedurally generated documents for benchmarking rendering, selection, and history
+
+const COLUMNS: usize = 100;
+const SPACING: f32 = 48.0;
+
+// Fills the ocean with more squids laid out in a grid, continuing after any squids already there.
+// Squids are generated from a fixed seed so that runs are comparable with each other
+pub fn generate(ocean: &mut Ocean, count: usize) {
+    let start = ocean.get_squids_unordered().count();
+
+    for i in start..start + count {
+        let mut rng = StdRng::seed_from_u64(i as u64);
+        let position = glm::vec2((i % COLUMNS) as f32 * SPACING, (i / COLUMNS) as f32 * SPACING);
+        let color = Color::from_hsv(rng.gen(), rng.gen_range(0.4..1.0), rng.gen_range(0.6..1.0));
+        let rotation = Rad(rng.gen_range(0.0..std::f32::consts::TAU));
+
+        let squid = match i % 3 {
+            0 => {
+                let size = glm::vec2(rng.gen_range(8.0..40.0), rng.gen_range(8.0..40.0));
+                Squid::rect(position, size, rotation, color, rng.gen_range(0.0..4.0), false)
+            }
+            1 => Squid::circle(position, rng.gen_range(4.0..20.0), color),
+            _ => {
+                let size = rng.gen_range(8.0..20.0);
+                let p = [glm::vec2(0.0, -size), glm::vec2(size, size), glm::vec2(-size, size)].map(|point| point + position);
+                Squid::tri(p, rotation, color)
+            }
+        };
+
+        ocean.insert(squid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocean::Ocean;
+
+    #[test]
+    fn stress_test_appends() {
+        let mut ocean = Ocean::default();
+
+        super::generate(&mut ocean, 150);
+        super::generate(&mut ocean, 150);
+
+        assert_eq!(ocean.get_squids_unordered().count(), 300);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{data::rect::BorderRadii, vertex::Vertex};
+use lyon::{
+    geom::Box2D,
+    path::{math::point, Winding},
+    tessellation::{
+        BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator, StrokeVertex, TessellationError, VertexBuffers,
+    },
+};
+use nalgebra_glm as glm;
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex, PoisonError},
+    thread,
+};
+
+// Tessellates shapes on a pool of worker threads, so that
+// resizing complex shapes doesn't stall the UI thread.
+// Every ticket gets a result, where shapes that couldn't be tessellated come back empty
+// and are drawn as nothing, rather than being waited on forever
+pub struct Tessellator {
+    jobs: mpsc::Sender<Job>,
+    results: mpsc::Receiver<(TessellationTicket, Tessellation)>,
+    completed: HashMap<TessellationTicket, Tessellation>,
+    next_ticket: u64,
+    in_progress: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TessellationTicket(u64);
+
+#[derive(Clone, Debug)]
+pub enum Shape {
+    RoundedRect { size: glm::Vec2, radii: BorderRadii },
+    Fill(lyon::path::Path),
+}
+
+#[derive(Default)]
+pub struct Tessellation {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u16>,
+}
+
+struct Job {
+    ticket: TessellationTicket,
+    shape: Shape,
+}
+
+impl Tessellator {
+    const MAX_WORKERS: usize = 4;
+
+    pub fn new() -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, results) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+
+        // Leave a core for the UI thread
+        let worker_count = thread::available_parallelism()
+            .map_or(1, |count| count.get().saturating_sub(1))
+            .clamp(1, Self::MAX_WORKERS);
+
+        for _ in 0..worker_count {
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+
+            thread::spawn(move || loop {
+                // Only receiving happens while locked, so the lock is still fine to use if another worker panicked
+                let job = match job_receiver.lock().unwrap_or_else(PoisonError::into_inner).recv() {
+                    Ok(job) => job,
+                    Err(_) => return,
+                };
+
+                let tessellation = panic::catch_unwind(AssertUnwindSafe(|| job.shape.tessellate()))
+                    .ok()
+                    .and_then(Result::ok)
+                    .unwrap_or_default();
+
+                if result_sender.send((job.ticket, tessellation)).is_err() {
+                    return;
+                }
+            });
+        }
+
+        Self {
+            jobs,
+            results,
+            completed: HashMap::new(),
+            next_ticket: 0,
+            in_progress: 0,
+        }
+    }
+
+    // Queues a shape to be tessellated, the result can be claimed with 'take' once it's done
+    pub fn submit(&mut self, shape: Shape) -> TessellationTicket {
+        let ticket = TessellationTicket(self.next_ticket);
+        self.next_ticket += 1;
+
+        if let Err(error) = self.jobs.send(Job { ticket, shape }) {
+            // Workers are gone, so do it here instead
+            self.completed.insert(ticket, error.0.shape.tessellate().unwrap_or_default());
+        } else {
+            self.in_progress += 1;
+        }
+
+        ticket
+    }
+
+    // Collects any tessellations that have finished since last time
+    pub fn receive(&mut self) {
+        while let Ok((ticket, tessellation)) = self.results.try_recv() {
+            self.completed.insert(ticket, tessellation);
+            self.in_progress -= 1;
+        }
+    }
+
+    // Whether any shapes are still being tessellated
+    pub fn is_busy(&self) -> bool {
+        self.in_progress > 0
+    }
+
+    pub fn take(&mut self, ticket: TessellationTicket) -> Option<Tessellation> {
+        self.completed.remove(&ticket)
+    }
+}
+
+impl Shape {
+    pub fn tessellate(&self) -> Result<Tessellation, TessellationError> {
+        match self {
+            Self::RoundedRect { size, radii } => {
+                let width = size.x.abs();
+                let height = size.y.abs();
+
+                let mut builder = lyon::path::Path::builder();
+                builder.add_rounded_rectangle(
+                    &Box2D::new(point(-width / 2.0, -height / 2.0), point(width / 2.0, height / 2.0)),
+                    &radii.into(),
+                    Winding::Positive,
+                );
+                tessellate_fill(&builder.build())
+            }
+            Self::Fill(lyon_path) => tessellate_fill(lyon_path),
+        }
+    }
+}
+
+pub fn tessellate_fill(lyon_path: &lyon::path::Path) -> Result<Tessellation, TessellationError> {
+    // Will contain the result of the tessellation.
+    let mut geometry: VertexBuffers<Vertex, u16> = VertexBuffers::new();
+    let mut tessellator = FillTessellator::new();
+
+    // Create tessellated geometry for fill
+    tessellator.tessellate_path(
+        lyon_path,
+        &FillOptions::default(),
+        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| Vertex {
+            position: vertex.position().to_array(),
+        }),
+    )?;
+
+    Ok(Tessellation {
+        vertices: geometry.vertices,
+        indices: geometry.indices,
+    })
+}
+
+pub fn tessellate_stroke(lyon_path: &lyon::path::Path, line_width: f32) -> Result<Tessellation, TessellationError> {
+    let mut geometry: VertexBuffers<Vertex, u16> = VertexBuffers::new();
+    let mut tessellator = StrokeTessellator::new();
+
+    // Create tessellated geometry for stroke
+    tessellator.tessellate_path(
+        lyon_path,
+        &StrokeOptions::default().with_line_width(line_width),
+        &mut BuffersBuilder::new(&mut geometry, |vertex: StrokeVertex| Vertex {
+            position: vertex.position().to_array(),
+        }),
+    )?;
+
+    Ok(Tessellation {
+        vertices: geometry.vertices,
+        indices: geometry.indices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shape, Tessellator};
+    use crate::data::rect::BorderRadii;
+    use lyon::path::{math::point, Winding};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn tessellator_delivers_results() {
+        let mut tessellator = Tessellator::new();
+        let shape = Shape::RoundedRect {
+            size: glm::vec2(100.0, 50.0),
+            radii: BorderRadii::new(10.0),
+        };
+
+        let ticket = tessellator.submit(shape.clone());
+
+        let tessellation = loop {
+            tessellator.receive();
+
+            if let Some(tessellation) = tessellator.take(ticket) {
+                break tessellation;
+            }
+
+            std::thread::yield_now();
+        };
+
+        assert_eq!(tessellation.indices, shape.tessellate().unwrap().indices);
+        assert!(tessellator.take(ticket).is_none());
+        assert!(!tessellator.is_busy());
+    }
+
+    #[test]
+    fn tessellate_paths_in_parallel() {
+        let mut tessellator = Tessellator::new();
+
+        let tickets: Vec<_> = (1..=8)
+            .map(|size| {
+                let mut builder = lyon::path::Path::builder();
+                builder.add_circle(point(0.0, 0.0), size as f32 * 10.0, Winding::Positive);
+                tessellator.submit(Shape::Fill(builder.build()))
+            })
+            .collect();
+
+        assert!(tessellator.is_busy());
+
+        while tessellator.is_busy() {
+            tessellator.receive();
+            std::thread::yield_now();
+        }
+
+        assert!(tickets
+            .into_iter()
+            .all(|ticket| tessellator.take(ticket).is_some_and(|tessellation| !tessellation.indices.is_empty())));
+    }
+
+    #[test]
+    fn tessellation_failures_come_back_empty() {
+        // More vertices than 16-bit indices can refer to
+        let mut builder = lyon::path::Path::builder();
+        builder.begin(point(0.0, 0.0));
+
+        for i in 1..70_000 {
+            let angle = i as f32 / 70_000.0 * std::f32::consts::TAU;
+            builder.line_to(point(angle.sin() * 10_000.0, (1.0 - angle.cos()) * 10_000.0));
+        }
+
+        builder.end(true);
+
+        let shape = Shape::Fill(builder.build());
+        assert!(shape.tessellate().is_err());
+
+        let mut tessellator = Tessellator::new();
+        let ticket = tessellator.submit(shape);
+
+        while tessellator.is_busy() {
+            tessellator.receive();
+            std::thread::yield_now();
+        }
+
+        assert!(tessellator.take(ticket).is_some_and(|tessellation| tessellation.indices.is_empty()));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    affine::Affine2,
+    as_values::AsValues,
+    color::Color,
+    mesh::MeshXyz,
+    render_ctx::RenderCtx,
+    squid::{Drawable, PreviewParams, Squid, SquidRef},
+};
+use glium::{
+    framebuffer::SimpleFrameBuffer,
+    texture::SrgbTexture2d,
+    uniforms::{MagnifySamplerFilter, MinifySamplerFilter},
+    Blend, BlendingFunction, LinearBlendingFactor, Surface,
+};
+use nalgebra_glm as glm;
+use std::collections::{HashMap, HashSet};
+
+// Small pictures of squids for the layers tab, which are only redrawn when the squid looks different.
+// Thumbnails have premultiplied alpha, so they blend the same as drawing the squid directly would
+#[derive(Default)]
+pub struct ThumbnailCache {
+    thumbnails: HashMap<SquidRef, Thumbnail>,
+    used: HashSet<SquidRef>,
+}
+
+struct Thumbnail {
+    texture: SrgbTexture2d,
+    signature: Signature,
+}
+
+// Everything a thumbnail's picture depends on
+#[derive(PartialEq)]
+struct Signature {
+    mesh: *const MeshXyz,
+    transformation: Affine2,
+    color: Color,
+    opacity: f32,
+    size: u32,
+}
+
+impl ThumbnailCache {
+    // Draws the thumbnail of a squid centered on a point and fitting within a radius
+    pub fn render(&mut self, ctx: &mut RenderCtx, reference: SquidRef, squid: &Squid, center: &glm::Vec2, radius: f32, opacity: f32) {
+        // Leave a little room so edges aren't cut off
+        let extent = radius + 1.0;
+
+        let drawable = match squid.get_drawable(Some(PreviewParams {
+            position: glm::vec2(extent, extent),
+            radius,
+        })) {
+            Some(drawable) => drawable,
+            None => return,
+        };
+
+        let signature = Signature {
+            mesh: drawable.mesh,
+            transformation: drawable.transformation,
+            color: drawable.color,
+            opacity,
+            size: (2.0 * extent * ctx.scale_factor as f32).ceil() as u32,
+        };
+
+        self.used.insert(reference);
+
+        if self.thumbnails.get(&reference).is_none_or(|thumbnail| thumbnail.signature != signature) {
+            let texture = draw_thumbnail(ctx, &drawable, opacity, extent, signature.size);
+            self.thumbnails.insert(reference, Thumbnail { texture, signature });
+        }
+
+        let texture = &self.thumbnails[&reference].texture;
+        let sampler = texture
+            .sampled()
+            .minify_filter(MinifySamplerFilter::Linear)
+            .magnify_filter(MagnifySamplerFilter::Linear);
+
+        // Texture rows go from bottom to top, so the square is flipped vertically
+        let uniforms = glium::uniform! {
+            transformation: Affine2::translation(center).scale(&glm::vec2(extent, -extent)).as_values(),
+            view: Affine2::identity().as_values(),
+            projection: ctx.projection.as_values(),
+            texture_sampler: sampler
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: premultiplied(LinearBlendingFactor::One),
+            ..Default::default()
+        };
+
+        let mesh = ctx.square_xyzuv;
+        ctx.draw(&mesh.vertex_buffer, mesh.indices, ctx.television_shader, &uniforms, &draw_parameters)
+            .unwrap();
+    }
+
+    // Forgets thumbnails that haven't been drawn since the last time
+    pub fn forget_unused(&mut self) {
+        let used = std::mem::take(&mut self.used);
+        self.thumbnails.retain(|reference, _| used.contains(reference));
+    }
+}
+
+fn draw_thumbnail(ctx: &RenderCtx, drawable: &Drawable, opacity: f32, extent: f32, size: u32) -> SrgbTexture2d {
+    let texture = SrgbTexture2d::empty(ctx.display, size, size).unwrap();
+
+    {
+        let mut framebuffer = SimpleFrameBuffer::new(ctx.display, &texture).unwrap();
+        framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+        let uniforms = glium::uniform! {
+            transformation: drawable.transformation.as_values(),
+            view: Affine2::identity().as_values(),
+            projection: glm::ortho(0.0, 2.0 * extent, 2.0 * extent, 0.0, 100.0, -100.0).as_values(),
+            color: drawable.color.with_opacity(opacity).as_values()
+        };
+
+        let draw_parameters = glium::DrawParameters {
+            blend: premultiplied(LinearBlendingFactor::SourceAlpha),
+            ..Default::default()
+        };
+
+        framebuffer
+            .draw(
+                &drawable.mesh.vertex_buffer,
+                &drawable.mesh.indices,
+                ctx.color_shader,
+                &uniforms,
+                &draw_parameters,
+            )
+            .unwrap();
+    }
+
+    texture
+}
+
+// Blending that results in premultiplied alpha, where colors that aren't premultiplied yet
+// are multiplied by their alpha on the way in
+pub fn premultiplied(source: LinearBlendingFactor) -> Blend {
+    let function = |source| BlendingFunction::Addition {
+        source,
+        destination: LinearBlendingFactor::OneMinusSourceAlpha,
+    };
+
+    Blend {
+        color: function(source),
+        alpha: function(LinearBlendingFactor::One),
+        constant_value: (0.0, 0.0, 0.0, 0.0),
+    }
+}
@@ -1,7 +1,9 @@
+use super::shape_drag::{self, ShapeDrag};
 use crate::{
     app::App,
     capture::Capture,
-    interaction::{ClickInteraction, Interaction},
+    command::DocumentCommand,
+    interaction::{ClickInteraction, Interaction, MouseReleaseInteraction},
     squid::Squid,
     user_input::UserInput,
 };
@@ -16,11 +18,24 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
         }) => {
             let world_position = app.camera.get_animated().apply_reverse(&position);
             let color = app.toolbox.color_picker.calculate_color();
-            let radius = user_inputs[0].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default().max(4.0);
+            let radius = user_inputs[0].as_text_input_mut().unwrap().get_value().max(4.0);
 
-            app.insert(Squid::circle(world_position, radius, color));
+            let squid = Squid::circle(world_position, radius, color);
+            app.shape_drag = Some(ShapeDrag {
+                squid: squid.get_id(),
+                anchor: world_position,
+            });
+            app.emit(DocumentCommand::Insert(Box::new(squid)));
             Capture::AllowDrag
         }
+        Interaction::Drag(drag) => {
+            shape_drag::drag(app, &drag);
+            Capture::AllowDrag
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            app.shape_drag = None;
+            Capture::Miss
+        }
         _ => Capture::Miss,
     }
 }
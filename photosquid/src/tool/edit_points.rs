@@ -0,0 +1,113 @@
+use crate::{
+    app::App,
+    capture::{Capture, KeyCapture},
+    command::DocumentCommand,
+    interaction::{ClickInteraction, DragInteraction, Interaction, KeyInteraction},
+    render_ctx::RenderCtx,
+    selection::{NewSelection, TrySelectResult},
+    squid::{Squid, SquidRef, HANDLE_RADIUS, HANDLE_SIZE},
+    user_input::UserInput,
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use nalgebra_glm as glm;
+
+// Only the first selected squid has its points edited
+fn get_editing_squid(app: &App) -> Option<SquidRef> {
+    app.selections.first().map(|selection| selection.squid_id)
+}
+
+// Shows the points of the squid being edited, with the point being edited larger than the rest
+pub fn render(ctx: &mut RenderCtx, app: &App) {
+    let squid_id = match get_editing_squid(app) {
+        Some(squid_id) => squid_id,
+        None => return,
+    };
+
+    let points = app.ocean.get(squid_id).map_or_else(Vec::new, |squid| squid.get_editable_points());
+
+    for (i, point) in points.iter().enumerate() {
+        let size = if app.editing_point == Some((squid_id, i)) {
+            1.5 * *HANDLE_SIZE
+        } else {
+            *HANDLE_SIZE
+        };
+        ctx.ring_mesh.render(ctx, ctx.camera.apply(point), size, &ctx.color_scheme.foreground);
+    }
+}
+
+pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            let camera = app.camera.get_animated();
+
+            let squid_id = get_editing_squid(app);
+
+            if let (Some(squid_id), Some(squid)) = (squid_id, squid_id.and_then(|squid_id| app.ocean.get(squid_id))) {
+                let points = squid.get_editable_points();
+
+                // Grab an existing point
+                if let Some(index) = points
+                    .iter()
+                    .position(|point| glm::distance(&camera.apply(point), &position) <= HANDLE_RADIUS * 2.0)
+                {
+                    app.editing_point = Some((squid_id, index));
+                    return Capture::AllowDrag;
+                }
+
+                // Clicking along an edge adds a point there, which is tried out on a copy first to know where it goes
+                let tolerance = camera.apply_reverse_to_scale(HANDLE_RADIUS * 2.0);
+                let world_position = camera.apply_reverse(&position);
+
+                if let Some(index) = squid.clone().insert_editable_point(&world_position, tolerance) {
+                    let id = squid.get_id();
+                    app.editing_point = Some((squid_id, index));
+                    app.emit(DocumentCommand::InsertPoint(id, world_position, tolerance));
+                    return Capture::AllowDrag;
+                }
+            }
+
+            // Otherwise, pick which squid to edit
+            app.editing_point = None;
+
+            match app.ocean.try_select(position, &camera, &[]) {
+                TrySelectResult::New(NewSelection { selection, .. }) => app.selections = vec![selection],
+                _ => app.selections.clear(),
+            }
+
+            Capture::NoDrag
+        }
+        Interaction::Drag(DragInteraction { current, .. }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&current);
+
+            if let Some((squid_id, index)) = app.editing_point {
+                if let Some(id) = app.ocean.get(squid_id).map(Squid::get_id) {
+                    app.emit(DocumentCommand::MovePoint(id, index, world_position));
+                }
+            }
+
+            Capture::AllowDrag
+        }
+        Interaction::Key(KeyInteraction {
+            virtual_keycode: VirtualKeyCode::Delete | VirtualKeyCode::Back,
+        }) => {
+            // Squids refuse to lose points that they need, which is checked on a copy
+            let removal = app.editing_point.and_then(|(squid_id, index)| {
+                let squid = app.ocean.get(squid_id)?;
+                squid.clone().remove_editable_point(index).then(|| (squid.get_id(), index))
+            });
+
+            if let Some((id, index)) = removal {
+                app.editing_point = None;
+                app.emit(DocumentCommand::RemovePoint(id, index));
+                Capture::Keyboard(KeyCapture::Capture)
+            } else {
+                Capture::Miss
+            }
+        }
+        _ => Capture::Miss,
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{
+    app::App,
+    capture::Capture,
+    interaction::{ClickInteraction, Interaction},
+    user_input::UserInput,
+};
+use glium::glutin::event::MouseButton;
+
+pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            app.pick_color_at(&position);
+            Capture::NoDrag
+        }
+        _ => Capture::Miss,
+    }
+}
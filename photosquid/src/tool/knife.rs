@@ -0,0 +1,92 @@
+use super::render_stroke;
+use crate::{
+    algorithm::split_convex_polygon,
+    app::App,
+    capture::Capture,
+    command::DocumentCommand,
+    interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
+    render_ctx::RenderCtx,
+    squid::{Squid, SquidRef},
+    user_input::UserInput,
+};
+use glium::glutin::event::MouseButton;
+use nalgebra_glm as glm;
+
+// Line being dragged across squids with the knife tool, in world space
+pub struct KnifeLine {
+    pub start: glm::Vec2,
+    pub current: glm::Vec2,
+}
+
+impl KnifeLine {
+    pub fn render(&self, ctx: &mut RenderCtx) {
+        let mut builder = lyon::path::Path::builder();
+        builder.begin(lyon::math::point(self.start.x, self.start.y));
+        builder.line_to(lyon::math::point(self.current.x, self.current.y));
+        builder.end(false);
+        render_stroke(ctx, &builder.build());
+    }
+}
+
+// Splits every squid that the line goes all the way across into two,
+// which take the place of the original in its layer and become the selection
+fn cut(app: &mut App, knife_line: &KnifeLine) {
+    let references: Vec<SquidRef> = app.ocean.get_interactable_squids_highest().collect();
+    let mut cuts = vec![];
+
+    for reference in references {
+        let squid = match app.ocean.get(reference) {
+            Some(squid) => squid,
+            None => continue,
+        };
+
+        let halves = squid
+            .get_polygon()
+            .and_then(|polygon| split_convex_polygon(&polygon, &knife_line.start, &knife_line.current));
+
+        if let Some(halves) = halves {
+            let replacements: Vec<Squid> = halves.iter().map(|half| squid.with_polygon(half)).collect();
+            cuts.push(DocumentCommand::Replace(squid.get_id(), replacements));
+        }
+    }
+
+    if !cuts.is_empty() {
+        app.emit(DocumentCommand::ClearSelection);
+        cuts.into_iter().for_each(|cut| app.emit(cut));
+    }
+}
+
+pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&position);
+
+            app.knife_line = Some(KnifeLine {
+                start: world_position,
+                current: world_position,
+            });
+            Capture::AllowDrag
+        }
+        Interaction::Drag(DragInteraction { current, .. }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&current);
+
+            if let Some(knife_line) = &mut app.knife_line {
+                knife_line.current = world_position;
+            }
+
+            Capture::AllowDrag
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => match app.knife_line.take() {
+            Some(knife_line) => {
+                cut(app, &knife_line);
+                Capture::NoDrag
+            }
+            None => Capture::Miss,
+        },
+        _ => Capture::Miss,
+    }
+}
@@ -1,20 +1,36 @@
 mod circle;
+mod edit_points;
+mod eyedropper;
+mod knife;
 mod pan;
+mod pen;
+mod pencil;
 mod pointer;
 mod rect;
+mod shape_drag;
 mod tri;
+mod viewport;
+mod zoom;
 
 use crate::{
     aabb::AABB,
+    affine::Affine2,
     app::{
         App,
         SaveMethod::{Save, SaveAs},
     },
     camera::EasySmoothCamera,
     capture::{Capture, KeyCapture},
-    interaction::{ClickInteraction, Interaction, KeyInteraction},
+    color::Color,
+    export::{ExportBounds, ExportSize},
+    interaction::{CharacterInteraction, ClickInteraction, Interaction, KeyInteraction, ScrollInteraction},
+    mesh::MeshXyz,
+    plugin::ToolPlugin,
+    preferences::Preferences,
     render_ctx::RenderCtx,
-    user_input::{Button, Checkbox, TextInput, UserInput},
+    renderer::Renderer,
+    tessellator::{tessellate_stroke, Tessellation},
+    user_input::{render_focus_marker, step_focus, Button, Checkbox, Dial, Slider, TextInput, UserInput},
 };
 use glium::glutin::event::VirtualKeyCode;
 use glium_text_rusttype::{FontTexture, TextSystem};
@@ -23,32 +39,86 @@ use slotmap::new_key_type;
 use std::rc::Rc;
 use VirtualKeyCode::Escape;
 
+pub use knife::KnifeLine;
+pub use pen::PenPath;
+pub use pencil::PencilStroke;
+pub use shape_drag::ShapeDrag;
+pub use zoom::ZoomBox;
+
 new_key_type! { pub struct ToolKey; }
 
+// Where tool options sit within the tools panel, which starts with the column of tool icons
+const OPTIONS_LEFT: f32 = 64.0;
+const OPTIONS_RIGHT_MARGIN: f32 = 16.0;
+const OPTIONS_BOTTOM_MARGIN: f32 = 16.0;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum ToolKind {
     MainMenu,
     Circle,
+    EditPoints,
+    Eyedropper,
+    Knife,
     Pan,
+    Pen,
+    Pencil,
+    Plugin,
     Pointer,
     Rect,
     Tri,
+    Viewport,
+    Zoom,
+}
+
+impl ToolKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ToolKind::MainMenu => "Main Menu",
+            ToolKind::Circle => "Circle Tool",
+            ToolKind::EditPoints => "Edit Points Tool",
+            ToolKind::Eyedropper => "Eyedropper Tool",
+            ToolKind::Knife => "Knife Tool",
+            ToolKind::Pan => "Pan Tool",
+            ToolKind::Pen => "Pen Tool",
+            ToolKind::Pencil => "Pencil Tool",
+            ToolKind::Plugin => "Plugin Tool",
+            ToolKind::Pointer => "Pointer Tool",
+            ToolKind::Rect => "Rect Tool",
+            ToolKind::Tri => "Tri Tool",
+            ToolKind::Viewport => "Viewport Tool",
+            ToolKind::Zoom => "Zoom Tool",
+        }
+    }
 }
 
 pub struct Tool {
     kind: ToolKind,
     user_inputs: Vec<UserInput>,
+    keyboard_focus: Option<usize>,
+    scroll: f32,
+    plugin: Option<Box<dyn ToolPlugin>>,
 }
 
 impl Tool {
     pub fn main_menu() -> Self {
         Self {
             kind: ToolKind::MainMenu,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
             user_inputs: vec![
                 UserInput::Button(Button::new("Open".to_string(), Box::new(|app| app.load()))),
                 UserInput::Button(Button::new("Save".to_string(), Box::new(|app| app.save(Save)))),
                 UserInput::Button(Button::new("Save As".to_string(), Box::new(|app| app.save(SaveAs)))),
+                UserInput::TextInput(TextInput::new("0".into(), "Export Margin".into(), "".into())),
+                UserInput::TextInput(TextInput::plain("auto".into(), "Export Size".into())),
+                UserInput::Checkbox(Checkbox::new("Export Content Bounds".into(), false)),
                 UserInput::Button(Button::new("Export".to_string(), Box::new(|app| app.export()))),
+                UserInput::Button(Button::new("Import Palette".to_string(), Box::new(|app| app.import_palette()))),
+                UserInput::Button(Button::new("Export Palette".to_string(), Box::new(|app| app.export_palette()))),
+                UserInput::Button(Button::new("Keyboard Shortcuts".to_string(), Box::new(|app| app.show_keymap()))),
+                UserInput::Button(Button::new("Load Keymap".to_string(), Box::new(|app| app.load_keymap()))),
+                UserInput::Button(Button::new("Save Keymap".to_string(), Box::new(|app| app.save_keymap()))),
                 UserInput::Button(Button::new("About".to_string(), Box::new(|app| app.about()))),
             ],
         }
@@ -57,13 +127,39 @@ impl Tool {
     pub fn circle() -> Self {
         Self {
             kind: ToolKind::Circle,
-            user_inputs: vec![UserInput::TextInput(TextInput::new("50".into(), "Initial Radius".into(), "".into()))],
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![UserInput::TextInput(TextInput::length(50.0, "Initial Radius".into()))],
+        }
+    }
+
+    pub fn edit_points() -> Self {
+        Self {
+            kind: ToolKind::EditPoints,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![],
+        }
+    }
+
+    pub fn eyedropper() -> Self {
+        Self {
+            kind: ToolKind::Eyedropper,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![],
         }
     }
 
     pub fn pan() -> Self {
         Self {
             kind: ToolKind::Pan,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
             user_inputs: vec![
                 UserInput::TextInput(TextInput::new("0".into(), "Camera X".into(), "".into())),
                 UserInput::TextInput(TextInput::new("0".into(), "Camera Y".into(), "".into())),
@@ -71,12 +167,58 @@ impl Tool {
         }
     }
 
+    pub fn knife() -> Self {
+        Self {
+            kind: ToolKind::Knife,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![],
+        }
+    }
+
+    pub fn pen() -> Self {
+        Self {
+            kind: ToolKind::Pen,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![],
+        }
+    }
+
+    pub fn pencil() -> Self {
+        Self {
+            kind: ToolKind::Pencil,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![UserInput::TextInput(TextInput::new("2".into(), "Smoothing".into(), " px".into()))],
+        }
+    }
+
+    pub fn plugin(plugin: Box<dyn ToolPlugin>) -> Self {
+        Self {
+            kind: ToolKind::Plugin,
+            keyboard_focus: None,
+            scroll: 0.0,
+            user_inputs: plugin.create_options(),
+            plugin: Some(plugin),
+        }
+    }
+
     pub fn pointer() -> Self {
         Self {
             kind: ToolKind::Pointer,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
             user_inputs: vec![
                 UserInput::TextInput(TextInput::new("0".into(), "Translation Snapping".into(), "".into())),
                 UserInput::TextInput(TextInput::new("0".into(), "Rotation Snapping".into(), " degrees".into())),
+                UserInput::Checkbox(Checkbox::new("Radial Menu".into(), false)),
+                UserInput::Checkbox(Checkbox::new("Light Theme".into(), false)),
+                UserInput::Button(Button::new("Load Color Scheme".to_string(), Box::new(|app| app.load_color_scheme()))),
             ],
         }
     }
@@ -84,12 +226,14 @@ impl Tool {
     pub fn rect() -> Self {
         Self {
             kind: ToolKind::Rect,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
             user_inputs: vec![
-                UserInput::TextInput(TextInput::new("100".into(), "Initial Width".into(), "".into())),
-                UserInput::TextInput(TextInput::new("100".into(), "Initial Height".into(), "".into())),
-                UserInput::TextInput(TextInput::new("0".into(), "Initial Rotation".into(), " degrees".into())),
-                UserInput::TextInput(TextInput::new("0".into(), "Initial Corner Radii".into(), "".into())),
-                UserInput::Checkbox(Checkbox::new("Create Viewport".into(), false)),
+                UserInput::TextInput(TextInput::length(100.0, "Initial Width".into())),
+                UserInput::TextInput(TextInput::length(100.0, "Initial Height".into())),
+                UserInput::Dial(Dial::new(0.0, "Initial Rotation".into())),
+                UserInput::Slider(Slider::new(0.0, 0.0, 100.0, 1.0, "Initial Corner Radii".into(), "".into())),
             ],
         }
     }
@@ -97,7 +241,60 @@ impl Tool {
     pub fn tri() -> Self {
         Self {
             kind: ToolKind::Tri,
-            user_inputs: vec![UserInput::TextInput(TextInput::new("0".into(), "Initial Rotation".into(), " degrees".into()))],
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![UserInput::Dial(Dial::new(0.0, "Initial Rotation".into()))],
+        }
+    }
+
+    pub fn viewport() -> Self {
+        Self {
+            kind: ToolKind::Viewport,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![
+                UserInput::TextInput(TextInput::length(1280.0, "Width".into())),
+                UserInput::TextInput(TextInput::length(720.0, "Height".into())),
+                UserInput::TextInput(TextInput::new("96".into(), "DPI".into(), "".into())),
+            ],
+        }
+    }
+
+    pub fn zoom() -> Self {
+        Self {
+            kind: ToolKind::Zoom,
+            keyboard_focus: None,
+            scroll: 0.0,
+            plugin: None,
+            user_inputs: vec![],
+        }
+    }
+
+    // Starts the tool's options out with the user's preferred defaults
+    pub fn apply_preferences(&mut self, preferences: &Preferences) {
+        let kind = self.kind;
+
+        let mut set = |index: usize, value: f32| {
+            if let Some(text_input) = self.user_inputs[index].as_text_input_mut() {
+                text_input.set_value(value);
+            }
+        };
+
+        match kind {
+            ToolKind::Circle => set(0, preferences.circle_radius),
+            ToolKind::Rect => {
+                set(0, preferences.rect_width);
+                set(1, preferences.rect_height);
+            }
+            ToolKind::Pointer => {
+                set(0, preferences.translation_snapping);
+                set(1, preferences.rotation_snapping);
+                self.user_inputs[2].as_checkbox_mut().unwrap().set_checked(preferences.radial_menu);
+                self.user_inputs[3].as_checkbox_mut().unwrap().set_checked(preferences.light_theme);
+            }
+            _ => (),
         }
     }
 
@@ -105,10 +302,18 @@ impl Tool {
         match self.kind {
             ToolKind::MainMenu => Capture::Miss,
             ToolKind::Circle => circle::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::EditPoints => edit_points::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Eyedropper => eyedropper::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Knife => knife::interact(&mut self.user_inputs, interaction, app),
             ToolKind::Pan => pan::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Pen => pen::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Pencil => pencil::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Plugin => self.plugin.as_mut().unwrap().interact(&mut self.user_inputs, interaction, app),
             ToolKind::Pointer => pointer::interact(&mut self.user_inputs, interaction, app),
             ToolKind::Rect => rect::interact(&mut self.user_inputs, interaction, app),
             ToolKind::Tri => tri::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Viewport => viewport::interact(&mut self.user_inputs, interaction, app),
+            ToolKind::Zoom => zoom::interact(&mut self.user_inputs, interaction, app),
         }
     }
 
@@ -117,6 +322,28 @@ impl Tool {
         let capture = self.interact_options_impl(interaction, app);
 
         // Post interaction
+        if self.kind == ToolKind::MainMenu {
+            if let Some(new_content) = self.user_inputs[3].as_text_input_mut().unwrap().poll() {
+                app.export_options.margin = new_content.parse::<f32>().unwrap_or_default();
+            }
+
+            if let Some(new_content) = self.user_inputs[4].as_text_input_mut().unwrap().poll() {
+                app.export_options.size = ExportSize::parse(new_content);
+            }
+
+            if let Some(content_bounds) = self.user_inputs[5].as_checkbox_mut().unwrap().poll() {
+                app.export_options.bounds = if content_bounds { ExportBounds::Content } else { ExportBounds::Viewport };
+            }
+        }
+
+        if self.kind == ToolKind::Pointer {
+            pointer::poll_to_set_program_wide_options(&mut self.user_inputs, app);
+        }
+
+        if self.kind == ToolKind::Viewport {
+            viewport::poll(&mut self.user_inputs, app);
+        }
+
         if self.kind == ToolKind::Pan {
             let existing_position = app.camera.get_real().position;
 
@@ -136,16 +363,22 @@ impl Tool {
     }
 
     fn interact_options_impl(&mut self, interaction: Interaction, app: &mut App) -> Capture {
+        let panel = app.toolbox.get_tools_area(&app.dimensions);
+
         match interaction {
             Interaction::Click(ClickInteraction { button, position, .. }) => {
+                let areas = get_input_areas(&self.user_inputs, &panel, self.scroll);
+
                 let index_took_focus = self.user_inputs.iter_mut().enumerate().find_map(|(i, user_input)| {
-                    if user_input.click(button, &position, &get_nth_input_area(i), app) == Capture::TakeFocus {
+                    if user_input.click(button, &position, &areas[i], app) == Capture::TakeFocus {
                         Some(i)
                     } else {
                         None
                     }
                 });
 
+                self.keyboard_focus = index_took_focus;
+
                 if let Some(index_took_focus) = index_took_focus {
                     for (i, user_input) in self.user_inputs.iter_mut().enumerate() {
                         if i != index_took_focus {
@@ -155,20 +388,50 @@ impl Tool {
                     return Capture::TakeFocus;
                 }
             }
-            Interaction::Key(KeyInteraction { virtual_keycode }) => {
-                let shift = app.keys_held.contains(&VirtualKeyCode::LShift);
+            Interaction::Drag(drag) => {
+                let areas = get_input_areas(&self.user_inputs, &panel, self.scroll);
 
+                if let Some(capture) = self
+                    .user_inputs
+                    .iter_mut()
+                    .zip(areas.iter())
+                    .map(|(user_input, area)| user_input.drag(&drag, area))
+                    .find(|capture| *capture != Capture::Miss)
+                {
+                    return capture;
+                }
+            }
+            Interaction::Character(CharacterInteraction { character }) => {
                 if let Some(key_capture) = self
                     .user_inputs
                     .iter_mut()
-                    .find_map(|user_input| user_input.key_press(virtual_keycode, shift).to_option())
+                    .find_map(|user_input| user_input.receive_character(character).to_option())
                 {
                     return Capture::Keyboard(key_capture);
                 }
+            }
+            Interaction::MouseRelease(..) => {
+                for user_input in &mut self.user_inputs {
+                    user_input.release();
+                }
+            }
+            Interaction::Scroll(ScrollInteraction { delta, .. }) => {
+                self.set_scroll(self.scroll - delta.y, &panel);
+            }
+            Interaction::Key(KeyInteraction { virtual_keycode }) => {
+                let shift = app.keys_held.contains(&VirtualKeyCode::LShift);
+                let modifiers = app.modifiers_held;
 
-                if self.kind == ToolKind::Pointer && virtual_keycode == Escape {
-                    app.selections.clear();
-                    return Capture::Keyboard(KeyCapture::Capture);
+                if let Some(capture) = self.navigate(virtual_keycode, shift, app) {
+                    return capture;
+                }
+
+                if let Some(key_capture) = self
+                    .user_inputs
+                    .iter_mut()
+                    .find_map(|user_input| user_input.key_press(virtual_keycode, modifiers).to_option())
+                {
+                    return Capture::Keyboard(key_capture);
                 }
             }
             _ => (),
@@ -177,7 +440,51 @@ impl Tool {
         Capture::Miss
     }
 
-    pub fn render_options(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>) {
+    // Keyboard traversal of the tool options, Tab/Shift+Tab moves between inputs,
+    // F6 enters or leaves the panel, and Enter/Space operates the input with focus
+    fn navigate(&mut self, virtual_keycode: VirtualKeyCode, shift: bool, app: &mut App) -> Option<Capture> {
+        let is_editing = self
+            .keyboard_focus
+            .and_then(|index| self.user_inputs.get(index))
+            .is_some_and(UserInput::is_editing);
+
+        match virtual_keycode {
+            VirtualKeyCode::Tab => self.move_keyboard_focus(step_focus(self.keyboard_focus, self.user_inputs.len(), shift)),
+            VirtualKeyCode::F6 if self.keyboard_focus.is_some() => {
+                self.unfocus_options();
+                return None;
+            }
+            VirtualKeyCode::F6 => self.move_keyboard_focus(step_focus(None, self.user_inputs.len(), false)),
+            VirtualKeyCode::Return | VirtualKeyCode::Space if !is_editing => {
+                self.user_inputs.get_mut(self.keyboard_focus?)?.activate(app);
+            }
+            Escape if !is_editing && self.keyboard_focus.is_some() => self.keyboard_focus = None,
+            _ => return None,
+        }
+
+        Some(Capture::Keyboard(KeyCapture::Capture))
+    }
+
+    fn move_keyboard_focus(&mut self, keyboard_focus: Option<usize>) {
+        for user_input in &mut self.user_inputs {
+            user_input.unfocus();
+        }
+
+        self.keyboard_focus = keyboard_focus;
+
+        // Text inputs are ready to be typed into as soon as they're reached
+        if let Some(UserInput::TextInput(text_input)) = keyboard_focus.and_then(|index| self.user_inputs.get_mut(index)) {
+            text_input.focus();
+        }
+    }
+
+    // Releases keyboard focus, e.g. when it moves over to another panel
+    pub fn unfocus_options(&mut self) {
+        self.move_keyboard_focus(None);
+    }
+
+    // Draws the tool options within the tools panel, which is 'panel'
+    pub fn render_options(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, panel: &AABB) {
         // Pre-render
         if self.kind == ToolKind::Pan {
             let x_input = self.user_inputs[0].as_text_input_mut().unwrap();
@@ -191,17 +498,110 @@ impl Tool {
             }
         }
 
+        // Keep scroll in range as the window changes size
+        self.set_scroll(self.scroll, panel);
+
         // Render
-        for i in 0..self.user_inputs.len() {
-            self.user_inputs[i].render(ctx, text_system, font.clone(), &get_nth_input_area(i));
+        let areas = get_input_areas(&self.user_inputs, panel, self.scroll);
+
+        for (user_input, area) in self.user_inputs.iter_mut().zip(areas.iter()) {
+            user_input.render(ctx, text_system, font.clone(), area);
+        }
+
+        for (user_input, area) in self.user_inputs.iter_mut().zip(areas.iter()) {
+            user_input.render_overlay(ctx, text_system, font.clone(), area);
+        }
+
+        if let Some(area) = self.keyboard_focus.and_then(|index| areas.get(index)) {
+            render_focus_marker(ctx, area);
+        }
+    }
+
+    // Lets options that don't fit in the panel be scrolled to
+    fn set_scroll(&mut self, scroll: f32, panel: &AABB) {
+        let bottom = get_input_areas(&self.user_inputs, panel, 0.0)
+            .last()
+            .map_or(0.0, |area| area.max_y + OPTIONS_BOTTOM_MARGIN);
+        self.scroll = scroll.clamp(0.0, (bottom - panel.max_y).max(0.0));
+    }
+
+    // Keeps the tool options in sync with the document
+    pub fn update(&mut self, app: &App) {
+        if self.kind == ToolKind::Viewport {
+            viewport::update(&mut self.user_inputs, app);
+        }
+
+        if self.keyboard_focus.is_some_and(|index| index >= self.user_inputs.len()) {
+            self.keyboard_focus = None;
+        }
+    }
+
+    // Draws anything the tool shows on top of the canvas
+    pub fn render_canvas(&self, ctx: &mut RenderCtx, app: &App) {
+        match self.kind {
+            ToolKind::EditPoints => edit_points::render(ctx, app),
+            ToolKind::Viewport => viewport::render(ctx, app),
+            ToolKind::Plugin => self.plugin.as_ref().unwrap().render(ctx, app),
+            _ => (),
         }
     }
 
     pub fn kind(&self) -> ToolKind {
         self.kind
     }
+
+    pub fn is_animating(&self) -> bool {
+        self.user_inputs.iter().any(UserInput::is_animating)
+    }
+
+    pub fn get_name(&self) -> &str {
+        match &self.plugin {
+            Some(plugin) => plugin.get_name(),
+            None => self.kind.get_name(),
+        }
+    }
+}
+
+// Buttons don't have a label above them, so they can be packed closer together.
+// Inputs sit to the right of the tool icons and stretch with the panel
+fn get_input_areas(user_inputs: &[UserInput], panel: &AABB, scroll: f32) -> Vec<AABB> {
+    let mut y = 128.0 - scroll;
+    let mut previous: Option<&UserInput> = None;
+
+    user_inputs
+        .iter()
+        .map(|user_input| {
+            if let Some(previous) = previous {
+                y += match (previous, user_input) {
+                    (UserInput::Checkbox(_), UserInput::Button(_)) => 64.0,
+                    (_, UserInput::Button(_)) => 44.0,
+                    _ => 80.0,
+                };
+            }
+
+            previous = Some(user_input);
+            let standard = TextInput::standard_area(&glm::vec2(panel.min_x + OPTIONS_LEFT, y));
+            AABB::new(
+                standard.min_x,
+                standard.min_y,
+                panel.width() - OPTIONS_LEFT - OPTIONS_RIGHT_MARGIN,
+                standard.height(),
+            )
+        })
+        .collect()
 }
 
-fn get_nth_input_area(n: usize) -> AABB {
-    TextInput::standard_area(&glm::vec2(64.0, 128.0 + n as f32 * 80.0))
+// Draws a thin line along a path in world space, for shapes that tools are still working on
+fn render_stroke(ctx: &mut RenderCtx, path: &lyon::path::Path) {
+    let line_width = 2.0 / ctx.camera.get_scale();
+    render_in_world(ctx, &tessellate_stroke(path, line_width).unwrap_or_default(), &ctx.color_scheme.foreground);
+}
+
+fn render_in_world(ctx: &mut RenderCtx, tessellation: &Tessellation, color: &Color) {
+    if tessellation.indices.is_empty() {
+        return;
+    }
+
+    let mesh = MeshXyz::from_tessellation(tessellation, ctx.display);
+    ctx.draw_mesh_in_world(&mesh, &Affine2::identity(), color);
 }
@@ -5,6 +5,7 @@ use crate::{
     interaction::{DragInteraction, Interaction},
     user_input::UserInput,
 };
+use std::time::Instant;
 
 pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
     match interaction {
@@ -18,6 +19,7 @@ pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &
             let new_camera_location = real_camera.position - real_camera.apply_reverse_to_vector(&delta);
 
             app.camera.set_location(new_camera_location);
+            app.pan_inertia.record(&delta, Instant::now());
             Capture::AllowDrag
         }
         Interaction::Click { .. } => Capture::AllowDrag,
@@ -0,0 +1,96 @@
+use super::render_stroke;
+use crate::{
+    app::App,
+    capture::{Capture, KeyCapture},
+    data::path::PathAnchor,
+    interaction::{ClickInteraction, DragInteraction, Interaction, KeyInteraction},
+    render_ctx::RenderCtx,
+    squid::{path::build_lyon_path, HANDLE_RADIUS, HANDLE_SIZE},
+    user_input::UserInput,
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use nalgebra_glm as glm;
+
+// Path that's still being drawn with the pen tool, in world space
+#[derive(Default)]
+pub struct PenPath {
+    pub anchors: Vec<PathAnchor>,
+}
+
+impl PenPath {
+    // Shows the path so far, along with the segment that the next click would add
+    pub fn render(&self, ctx: &mut RenderCtx, mouse_position: Option<glm::Vec2>) {
+        let mut anchors = self.anchors.clone();
+
+        if let Some(mouse_position) = mouse_position {
+            anchors.push(PathAnchor::new(ctx.camera.apply_reverse(&mouse_position)));
+        }
+
+        render_stroke(ctx, &build_lyon_path(&anchors, false));
+
+        for anchor in &self.anchors {
+            ctx.ring_mesh
+                .render(ctx, ctx.camera.apply(&anchor.point), *HANDLE_SIZE, &ctx.color_scheme.foreground);
+        }
+
+        // Handles being dragged out of the latest anchor
+        if let Some(last) = self.anchors.last().filter(|last| last.handle_out != glm::zero::<glm::Vec2>()) {
+            for handle in [last.handle_in, last.handle_out] {
+                ctx.ring_mesh.render(
+                    ctx,
+                    ctx.camera.apply(&(last.point + handle)),
+                    0.5 * *HANDLE_SIZE,
+                    &ctx.color_scheme.secondary_text,
+                );
+            }
+        }
+    }
+}
+
+pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            let camera = app.camera.get_animated();
+
+            // Clicking the first anchor again closes the path
+            let closes = app.pen_path.as_ref().is_some_and(|pen_path| {
+                pen_path.anchors.len() > 2 && glm::distance(&camera.apply(&pen_path.anchors[0].point), &position) <= HANDLE_RADIUS * 2.0
+            });
+
+            if closes {
+                app.finish_pen_path(true);
+                return Capture::NoDrag;
+            }
+
+            let anchor = PathAnchor::new(camera.apply_reverse(&position));
+            app.pen_path.get_or_insert_with(PenPath::default).anchors.push(anchor);
+            Capture::AllowDrag
+        }
+        Interaction::Drag(DragInteraction { current, .. }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&current);
+
+            // Dragging after placing an anchor pulls out its control handles
+            if let Some(anchor) = app.pen_path.as_mut().and_then(|pen_path| pen_path.anchors.last_mut()) {
+                *anchor = anchor.with_handle(world_position - anchor.point);
+            }
+
+            Capture::AllowDrag
+        }
+        Interaction::Key(KeyInteraction { virtual_keycode }) if app.pen_path.is_some() => match virtual_keycode {
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                app.finish_pen_path(false);
+                Capture::Keyboard(KeyCapture::Capture)
+            }
+            VirtualKeyCode::Escape => {
+                app.pen_path = None;
+                Capture::Keyboard(KeyCapture::Capture)
+            }
+            _ => Capture::Miss,
+        },
+        _ => Capture::Miss,
+    }
+}
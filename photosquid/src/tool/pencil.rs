@@ -0,0 +1,74 @@
+use super::render_stroke;
+use crate::{
+    algorithm::simplify_polyline,
+    app::App,
+    capture::Capture,
+    command::DocumentCommand,
+    data::path::PathAnchor,
+    interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
+    render_ctx::RenderCtx,
+    squid::{path::build_lyon_path, Squid},
+    user_input::UserInput,
+};
+use glium::glutin::event::MouseButton;
+use nalgebra_glm as glm;
+
+// Freehand line that's still being drawn with the pencil tool, in world space
+#[derive(Default)]
+pub struct PencilStroke {
+    pub points: Vec<glm::Vec2>,
+}
+
+impl PencilStroke {
+    pub fn render(&self, ctx: &mut RenderCtx) {
+        let anchors: Vec<PathAnchor> = self.points.iter().copied().map(PathAnchor::new).collect();
+        render_stroke(ctx, &build_lyon_path(&anchors, false));
+    }
+}
+
+pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&position);
+            app.pencil_stroke = Some(PencilStroke { points: vec![world_position] });
+            Capture::AllowDrag
+        }
+        Interaction::Drag(DragInteraction { current, .. }) => {
+            let world_position = app.camera.get_animated().apply_reverse(&current);
+
+            if let Some(pencil_stroke) = &mut app.pencil_stroke {
+                if pencil_stroke.points.last() != Some(&world_position) {
+                    pencil_stroke.points.push(world_position);
+                }
+            }
+
+            Capture::AllowDrag
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            let pencil_stroke = match app.pencil_stroke.take() {
+                Some(pencil_stroke) => pencil_stroke,
+                None => return Capture::Miss,
+            };
+
+            // Smoothing is in screen pixels, so strokes feel the same at any zoom
+            let smoothing = user_inputs[0].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default().max(0.0);
+            let points = simplify_polyline(&pencil_stroke.points, smoothing / app.camera.get_real().get_scale());
+
+            if points.len() >= 2 {
+                let color = app.toolbox.color_picker.calculate_color();
+                app.emit(DocumentCommand::Insert(Box::new(Squid::path(
+                    PathAnchor::smooth_through(&points),
+                    false,
+                    color,
+                ))));
+            }
+
+            Capture::NoDrag
+        }
+        _ => Capture::Miss,
+    }
+}
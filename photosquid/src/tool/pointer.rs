@@ -2,9 +2,13 @@ use crate::{
     app::App,
     bool_poll::BoolPoll,
     capture::{Capture, KeyCapture},
+    color_scheme::ColorScheme,
     interaction::{ClickInteraction, DragInteraction, Interaction, KeyInteraction},
+    keymap::Action,
     math::get_point_delta_rotation,
     operation::Operation,
+    options::color_picker::ColorTarget,
+    radial_menu::RadialMenu,
     selection::{NewSelection, TrySelectResult},
     squid::Initiation,
     user_input::UserInput,
@@ -40,7 +44,7 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
 
                     // Notify UI of changes
                     if let Some(its_color) = info.color {
-                        app.toolbox.color_picker.set_selected_color_no_notif(its_color);
+                        app.toolbox.color_picker.edit(ColorTarget::Selection, its_color);
                     }
 
                     if let Some(squid) = app.ocean.get_mut(selection.squid_id) {
@@ -51,6 +55,11 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
                 TrySelectResult::Discard => app.selections.clear(),
             }
 
+            if button == MouseButton::Right && app.interaction_options.radial_menu {
+                app.radial_menu = Some(RadialMenu::common(position, &app.color_scheme));
+                return Capture::NoDrag;
+            }
+
             if button == MouseButton::Right {
                 app.context_menu = app.ocean.try_context_menu(position, &app.camera.get_animated(), &app.color_scheme);
 
@@ -61,11 +70,19 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
 
             Capture::AllowDrag
         }
-        Interaction::Drag(DragInteraction { current: mouse_position, .. }) => match &mut app.operation {
-            Some(Operation::Rotate { point, rotation }) => {
+        Interaction::Drag(DragInteraction {
+            current: mouse_position,
+            modifiers,
+            ..
+        }) => match &mut app.operation {
+            Some(Operation::Rotate { point, rotation, total }) => {
                 let delta_theta = get_point_delta_rotation(point, &mouse_position, *rotation) - Rad::pi_over_2();
                 *rotation += delta_theta;
-                Capture::RotateSelectedSquids { delta_theta }
+                *total += delta_theta;
+                Capture::RotateSelectedSquids {
+                    delta_theta,
+                    shift: modifiers.shift(),
+                }
             }
             Some(Operation::Scale { origin, point }) => {
                 let d0 = glm::distance(origin, point);
@@ -77,9 +94,14 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
             Some(Operation::Spread { .. }) => Capture::SpreadSelectedSquids {
                 current: app.camera.get_animated().apply_reverse(&mouse_position),
             },
-            Some(Operation::Revolve { .. }) => Capture::RevolveSelectedSquids {
-                current: app.camera.get_animated().apply_reverse(&mouse_position),
-            },
+            Some(operation @ Operation::Revolve { .. }) => {
+                let current = app.camera.get_animated().apply_reverse(&mouse_position);
+                operation.track_revolve(&current);
+                Capture::RevolveSelectedSquids {
+                    current,
+                    shift: modifiers.shift(),
+                }
+            }
             Some(Operation::Dilate { .. }) => Capture::DilateSelectedSquids {
                 current: app.camera.get_animated().apply_reverse(&mouse_position),
             },
@@ -100,8 +122,8 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
 }
 
 fn pointer_handle_hotkey(app: &mut App, virtual_keycode: VirtualKeyCode) -> Capture {
-    match virtual_keycode {
-        VirtualKeyCode::G => {
+    match app.keymap.get_action(virtual_keycode, app.modifiers_held) {
+        Some(Action::Grab) => {
             if app.perform_next_operation_collectively.poll() {
                 if let Some(center) = app.get_selection_group_center() {
                     app.initiate(Initiation::Spread {
@@ -114,7 +136,7 @@ fn pointer_handle_hotkey(app: &mut App, virtual_keycode: VirtualKeyCode) -> Capt
             }
             Capture::Keyboard(KeyCapture::Capture)
         }
-        VirtualKeyCode::R => {
+        Some(Action::Rotate) => {
             if app.perform_next_operation_collectively.poll() {
                 if let Some(center) = app.get_selection_group_center() {
                     app.initiate(Initiation::Revolve {
@@ -127,7 +149,7 @@ fn pointer_handle_hotkey(app: &mut App, virtual_keycode: VirtualKeyCode) -> Capt
             }
             Capture::Keyboard(KeyCapture::Capture)
         }
-        VirtualKeyCode::S => {
+        Some(Action::Scale) => {
             if app.perform_next_operation_collectively.poll() {
                 if let Some(center) = app.get_selection_group_center() {
                     app.initiate(Initiation::Dilate {
@@ -140,7 +162,7 @@ fn pointer_handle_hotkey(app: &mut App, virtual_keycode: VirtualKeyCode) -> Capt
             }
             Capture::Keyboard(KeyCapture::Capture)
         }
-        VirtualKeyCode::C => {
+        Some(Action::Collectively) => {
             app.perform_next_operation_collectively = !app.perform_next_operation_collectively;
             Capture::Keyboard(KeyCapture::Capture)
         }
@@ -148,7 +170,7 @@ fn pointer_handle_hotkey(app: &mut App, virtual_keycode: VirtualKeyCode) -> Capt
     }
 }
 
-fn poll_to_set_program_wide_options(user_inputs: &mut [UserInput], app: &mut App) {
+pub fn poll_to_set_program_wide_options(user_inputs: &mut [UserInput], app: &mut App) {
     if let Some(new_content) = user_inputs[0].as_text_input_mut().unwrap().poll() {
         app.interaction_options.translation_snapping = new_content.parse::<f32>().unwrap_or_default().max(1.0);
     }
@@ -156,4 +178,12 @@ fn poll_to_set_program_wide_options(user_inputs: &mut [UserInput], app: &mut App
     if let Some(new_content) = user_inputs[1].as_text_input_mut().unwrap().poll() {
         app.interaction_options.rotation_snapping = Rad(new_content.parse::<f32>().unwrap_or_default().max(0.0) * std::f32::consts::PI / 180.0);
     }
+
+    if let Some(enabled) = user_inputs[2].as_checkbox_mut().unwrap().poll() {
+        app.interaction_options.radial_menu = enabled;
+    }
+
+    if let Some(light) = user_inputs[3].as_checkbox_mut().unwrap().poll() {
+        app.color_scheme = if light { ColorScheme::light() } else { ColorScheme::default() };
+    }
 }
@@ -1,7 +1,9 @@
+use super::shape_drag::{self, ShapeDrag};
 use crate::{
     app::App,
     capture::Capture,
-    interaction::{ClickInteraction, Interaction},
+    command::DocumentCommand,
+    interaction::{ClickInteraction, Interaction, MouseReleaseInteraction},
     squid::Squid,
     user_input::UserInput,
 };
@@ -19,15 +21,26 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
             let world_position = app.camera.get_animated().apply_reverse(&position);
             let color = app.toolbox.color_picker.calculate_color();
 
-            let width = user_inputs[0].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default().max(4.0);
-            let height = user_inputs[1].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default().max(4.0);
-            let rotation = Rad(user_inputs[2].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default() * std::f32::consts::PI / 180.0);
-            let radii = user_inputs[3].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default();
-            let is_viewport = user_inputs[4].as_checkbox_mut().unwrap().checked();
-
-            app.insert(Squid::rect(world_position, glm::vec2(width, height), rotation, color, radii, is_viewport));
+            let width = user_inputs[0].as_text_input_mut().unwrap().get_value().max(4.0);
+            let height = user_inputs[1].as_text_input_mut().unwrap().get_value().max(4.0);
+            let rotation = Rad(user_inputs[2].as_dial().unwrap().value().to_radians());
+            let radii = user_inputs[3].as_slider().unwrap().value();
+            let squid = Squid::rect(world_position, glm::vec2(width, height), rotation, color, radii, false);
+            app.shape_drag = Some(ShapeDrag {
+                squid: squid.get_id(),
+                anchor: world_position,
+            });
+            app.emit(DocumentCommand::Insert(Box::new(squid)));
+            Capture::AllowDrag
+        }
+        Interaction::Drag(drag) => {
+            shape_drag::drag(app, &drag);
             Capture::AllowDrag
         }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            app.shape_drag = None;
+            Capture::Miss
+        }
         _ => Capture::Miss,
     }
 }
@@ -0,0 +1,44 @@
+use crate::{app::App, command::DocumentCommand, interaction::DragInteraction, squid::SquidId};
+use nalgebra_glm as glm;
+
+// Drags shorter than this (in pixels) leave newly placed shapes at their initial size
+const MIN_DRAG_DISTANCE: f32 = 4.0;
+
+// Shape that was just placed by a creation tool, and is being drawn out by dragging
+#[derive(Copy, Clone)]
+pub struct ShapeDrag {
+    pub squid: SquidId,
+    pub anchor: glm::Vec2,
+}
+
+// Sizes the shape being drawn out to span from where it was placed to the mouse,
+// holding shift keeps it square and holding alt grows it out from the center
+pub fn drag(app: &mut App, drag: &DragInteraction) {
+    let ShapeDrag { squid, anchor } = match &app.shape_drag {
+        Some(shape_drag) => *shape_drag,
+        None => return,
+    };
+
+    if glm::distance(&drag.start, &drag.current) < MIN_DRAG_DISTANCE {
+        return;
+    }
+
+    let mut delta = app.camera.get_animated().apply_reverse(&drag.current) - anchor;
+
+    if drag.modifiers.shift() {
+        let side = glm::comp_max(&delta.abs());
+        delta = glm::vec2(side.copysign(delta.x), side.copysign(delta.y));
+    }
+
+    let (corner, opposite_corner) = if drag.modifiers.alt() {
+        (anchor - delta, anchor + delta)
+    } else {
+        (anchor, anchor + delta)
+    };
+
+    app.emit(DocumentCommand::FitToBox(
+        squid,
+        glm::min2(&corner, &opposite_corner),
+        glm::max2(&corner, &opposite_corner),
+    ));
+}
@@ -1,7 +1,9 @@
+use super::shape_drag::{self, ShapeDrag};
 use crate::{
     app::App,
     capture::Capture,
-    interaction::{ClickInteraction, Interaction},
+    command::DocumentCommand,
+    interaction::{ClickInteraction, Interaction, MouseReleaseInteraction},
     squid::Squid,
     user_input::UserInput,
 };
@@ -20,9 +22,9 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
             let world_position = camera.apply_reverse(&click_coords);
             let color = app.toolbox.color_picker.calculate_color();
 
-            let rotation = Rad(user_inputs[0].as_text_input_mut().unwrap().text().parse::<f32>().unwrap_or_default() * std::f32::consts::PI / 180.0);
+            let rotation = Rad(user_inputs[0].as_dial().unwrap().value().to_radians());
 
-            app.insert(Squid::tri(
+            let squid = Squid::tri(
                 [
                     world_position + glm::vec2(0.0, -50.0),
                     world_position + glm::vec2(50.0, 50.0),
@@ -30,10 +32,23 @@ pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &m
                 ],
                 rotation,
                 color,
-            ));
+            );
+            app.shape_drag = Some(ShapeDrag {
+                squid: squid.get_id(),
+                anchor: world_position,
+            });
+            app.emit(DocumentCommand::Insert(Box::new(squid)));
 
             Capture::AllowDrag
         }
+        Interaction::Drag(drag) => {
+            shape_drag::drag(app, &drag);
+            Capture::AllowDrag
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            app.shape_drag = None;
+            Capture::Miss
+        }
         _ => Capture::Miss,
     }
 }
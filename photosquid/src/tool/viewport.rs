@@ -0,0 +1,182 @@
+use super::{
+    render_in_world, render_stroke,
+    shape_drag::{self, ShapeDrag},
+};
+use crate::{
+    app::App,
+    capture::Capture,
+    command::DocumentCommand,
+    interaction::{ClickInteraction, Interaction, MouseReleaseInteraction},
+    render_ctx::RenderCtx,
+    selection::Selection,
+    squid::{Squid, SquidRef},
+    tessellator::tessellate_fill,
+    user_input::{Button, UserInput},
+};
+use angular_units::Rad;
+use glium::glutin::event::MouseButton;
+use nalgebra_glm as glm;
+
+// User inputs after the size and DPI inputs are buttons for each viewport
+const DPI: usize = 2;
+const LIST_START: usize = 3;
+
+fn get_selected_viewport(app: &App) -> Option<SquidRef> {
+    app.selections
+        .iter()
+        .map(|selection| selection.squid_id)
+        .find(|reference| app.ocean.get(*reference).is_some_and(|squid| squid.as_viewport().is_some()))
+}
+
+// Size that new viewports start out with, in pixels
+fn get_size(user_inputs: &mut [UserInput]) -> glm::Vec2 {
+    let mut parse = |index: usize| user_inputs[index].as_text_input_mut().unwrap().get_value().max(1.0);
+    glm::vec2(parse(0), parse(1))
+}
+
+pub fn interact(user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            ..
+        }) => {
+            let camera = app.camera.get_animated();
+
+            // Clicking inside an existing viewport selects it, so that it can be moved around
+            let underneath = app
+                .ocean
+                .get_interactable_squids_highest()
+                .find(|reference| app.ocean.get(*reference).is_some_and(|squid| squid.is_point_over_viewport(position, &camera)));
+
+            if let Some(reference) = underneath {
+                app.selections = vec![Selection::new(reference, None)];
+                return Capture::AllowDrag;
+            }
+
+            let world_position = camera.apply_reverse(&position);
+            let color = app.toolbox.color_picker.calculate_color();
+            let squid = Squid::rect(world_position, get_size(user_inputs), Rad(0.0), color, 0.0, true);
+            app.shape_drag = Some(ShapeDrag {
+                squid: squid.get_id(),
+                anchor: world_position,
+            });
+            app.emit(DocumentCommand::InsertAndSelect(Box::new(squid)));
+            Capture::AllowDrag
+        }
+        Interaction::Drag(drag) => {
+            if app.shape_drag.is_some() {
+                shape_drag::drag(app, &drag);
+                return Capture::AllowDrag;
+            }
+
+            Capture::MoveSelectedSquids {
+                delta_in_world: app.camera.get_animated().apply_reverse_to_vector(&drag.delta),
+            }
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            app.shape_drag = None;
+            Capture::Miss
+        }
+        _ => Capture::Miss,
+    }
+}
+
+// Resizes the selected viewport when its size is typed in, and takes on the document's new DPI
+pub fn poll(user_inputs: &mut [UserInput], app: &mut App) {
+    if let Some(dpi) = user_inputs[DPI].as_text_input_mut().unwrap().poll_value() {
+        app.emit(DocumentCommand::SetDpi(dpi));
+    }
+
+    let width = user_inputs[0].as_text_input_mut().unwrap().poll().is_some();
+    let height = user_inputs[1].as_text_input_mut().unwrap().poll().is_some();
+
+    if width || height {
+        let size = get_size(user_inputs);
+
+        if let Some(id) = get_selected_viewport(app).and_then(|reference| app.ocean.get(reference)).map(Squid::get_id) {
+            app.emit(DocumentCommand::SetViewportSize(id, size));
+        }
+    }
+}
+
+// Keeps the size inputs showing the selected viewport, and the list of viewports up to date
+pub fn update(user_inputs: &mut Vec<UserInput>, app: &App) {
+    if let Some(viewport) = get_selected_viewport(app).and_then(|reference| app.ocean.get(reference)?.as_viewport()) {
+        for (index, length) in [viewport.size.x, viewport.size.y].iter().enumerate() {
+            let text_input = user_inputs[index].as_text_input_mut().unwrap();
+
+            if !text_input.is_focused() {
+                text_input.set_value(length.abs());
+            }
+        }
+    }
+
+    let dpi_input = user_inputs[DPI].as_text_input_mut().unwrap();
+
+    if !dpi_input.is_focused() {
+        dpi_input.set_value(app.ocean.get_dpi());
+    }
+
+    let viewports: Vec<(SquidRef, String)> = app
+        .ocean
+        .get_squids_lowest()
+        .filter_map(|reference| {
+            let squid = app.ocean.get(reference)?;
+            squid.as_viewport()?;
+            Some((reference, squid.get_name().to_string()))
+        })
+        .collect();
+
+    let listed = user_inputs[LIST_START..].iter().map(|user_input| user_input.as_button().map(Button::text));
+
+    if listed.eq(viewports.iter().map(|(_, name)| Some(name.as_str()))) {
+        return;
+    }
+
+    user_inputs.truncate(LIST_START);
+    user_inputs.extend(
+        viewports
+            .into_iter()
+            .map(|(reference, name)| UserInput::Button(Button::new(name, Box::new(move |app| app.selections = vec![Selection::new(reference, None)])))),
+    );
+}
+
+// Outlines every viewport, and dims everything outside of the selected one
+pub fn render(ctx: &mut RenderCtx, app: &App) {
+    fn add_polygon(builder: &mut lyon::path::path::Builder, points: &[glm::Vec2]) {
+        builder.begin(lyon::math::point(points[0].x, points[0].y));
+
+        for point in &points[1..] {
+            builder.line_to(lyon::math::point(point.x, point.y));
+        }
+
+        builder.end(true);
+    }
+
+    let selected = get_selected_viewport(app);
+
+    for (reference, _) in app.ocean.get_visible_squids_lowest() {
+        let corners = match app.ocean.get(reference).and_then(Squid::get_viewport_corners) {
+            Some(corners) => corners,
+            None => continue,
+        };
+
+        let mut builder = lyon::path::Path::builder();
+
+        if selected == Some(reference) {
+            let window = ctx.camera.window;
+            let screen = [glm::zero(), glm::vec2(window.x, 0.0), window, glm::vec2(0.0, window.y)].map(|corner| ctx.camera.apply_reverse(&corner));
+
+            // Both the screen and the viewport make up the outline, so only what's between them is filled
+            add_polygon(&mut builder, &screen);
+            add_polygon(&mut builder, &corners);
+            render_in_world(ctx, &tessellate_fill(&builder.build()).unwrap_or_default(), &ctx.color_scheme.shade);
+
+            builder = lyon::path::Path::builder();
+        }
+
+        add_polygon(&mut builder, &corners);
+        render_stroke(ctx, &builder.build());
+    }
+}
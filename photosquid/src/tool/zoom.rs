@@ -0,0 +1,88 @@
+use crate::{
+    app::App,
+    capture::Capture,
+    interaction::{ClickInteraction, DragInteraction, Interaction, MouseReleaseInteraction},
+    render_ctx::RenderCtx,
+    user_input::UserInput,
+};
+use glium::glutin::event::MouseButton;
+use nalgebra_glm as glm;
+
+// How much a single click zooms in or out
+const CLICK_ZOOM: f32 = 1.5;
+
+// Boxes smaller than this (in pixels) are treated as clicks
+const MIN_BOX_SIZE: f32 = 6.0;
+
+// Area on screen being dragged out with the zoom tool
+pub struct ZoomBox {
+    pub start: glm::Vec2,
+    pub current: glm::Vec2,
+    pub zoom_out: bool,
+}
+
+impl ZoomBox {
+    fn is_click(&self) -> bool {
+        glm::comp_max(&(self.current - self.start).abs()) < MIN_BOX_SIZE
+    }
+
+    pub fn render(&self, ctx: &mut RenderCtx) {
+        if self.is_click() {
+            return;
+        }
+
+        let min = glm::min2(&self.start, &self.current);
+        let size = (self.current - self.start).abs();
+        let color = ctx.color_scheme.foreground;
+
+        ctx.ribbon_mesh.render(ctx, min, size, &color.with_opacity(0.1));
+
+        // Outline
+        ctx.ribbon_mesh.render(ctx, min, glm::vec2(size.x, 1.0), &color);
+        ctx.ribbon_mesh
+            .render(ctx, glm::vec2(min.x, min.y + size.y - 1.0), glm::vec2(size.x, 1.0), &color);
+        ctx.ribbon_mesh.render(ctx, min, glm::vec2(1.0, size.y), &color);
+        ctx.ribbon_mesh
+            .render(ctx, glm::vec2(min.x + size.x - 1.0, min.y), glm::vec2(1.0, size.y), &color);
+    }
+}
+
+pub fn interact(_user_inputs: &mut [UserInput], interaction: Interaction, app: &mut App) -> Capture {
+    match interaction {
+        Interaction::Click(ClickInteraction {
+            button: MouseButton::Left,
+            position,
+            modifiers,
+        }) => {
+            app.zoom_box = Some(ZoomBox {
+                start: position,
+                current: position,
+                zoom_out: modifiers.alt(),
+            });
+            Capture::AllowDrag
+        }
+        Interaction::Drag(DragInteraction { current, .. }) => {
+            if let Some(zoom_box) = &mut app.zoom_box {
+                zoom_box.current = current;
+            }
+            Capture::AllowDrag
+        }
+        Interaction::MouseRelease(MouseReleaseInteraction { button: MouseButton::Left, .. }) => {
+            let zoom_box = match app.zoom_box.take() {
+                Some(zoom_box) => zoom_box,
+                None => return Capture::Miss,
+            };
+
+            if zoom_box.is_click() {
+                let zoom = if zoom_box.zoom_out { 1.0 / CLICK_ZOOM } else { CLICK_ZOOM };
+                app.zoom_at(zoom, &zoom_box.start);
+            } else {
+                let camera = app.camera.get_real().framing_area(&zoom_box.start, &zoom_box.current);
+                app.camera.set(camera);
+            }
+
+            Capture::NoDrag
+        }
+        _ => Capture::Miss,
+    }
+}
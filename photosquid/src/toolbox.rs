@@ -1,5 +1,6 @@
 use crate::{
-    as_values::AsValues,
+    aabb::AABB,
+    affine::Affine2,
     capture::Capture,
     color::Color,
     interaction::{ClickInteraction, DragInteraction, Interaction},
@@ -7,12 +8,16 @@ use crate::{
     ocean::Ocean,
     options,
     options::color_picker::ColorPicker,
+    plugin::ToolPlugin,
+    preferences::Preferences,
     press_animation::PressAnimation,
     render_ctx::RenderCtx,
+    renderer::Renderer,
     selection::Selection,
     smooth::Smooth,
     tool::{Tool, ToolKey, ToolKind},
     tool_button::ToolButton,
+    user_input::step_focus,
     ColorScheme,
 };
 use glium::{glutin::event::MouseButton, Display};
@@ -21,12 +26,21 @@ use nalgebra_glm as glm;
 use slotmap::SlotMap;
 use std::{rc::Rc, time::Duration};
 
+// Side panel that can be resized by dragging its inner edge
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Panel {
+    Tools,
+    Options,
+}
+
 pub struct ToolBox {
     buttons: Vec<ToolButton>,
     icon_size: f32,
     padding: f32,
     width: f32,
     full_width: f32,
+    options_width: f32,
+    resizing: Option<Panel>,
     selection: SelectionIndicator,
     tab_selection: SelectionIndicator,
     options_tab_region_height: f32,
@@ -36,13 +50,21 @@ pub struct ToolBox {
 }
 
 impl ToolBox {
+    pub const MIN_PANEL_WIDTH: f32 = 256.0;
+    pub const MAX_PANEL_WIDTH: f32 = 512.0;
+
+    // How far into a panel its edge can be grabbed from
+    const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+
     pub fn new(display: &Display) -> Self {
         ToolBox {
             buttons: vec![],
             icon_size: 48.0,
             padding: 16.0,
             width: 48.0,
-            full_width: 256.0,
+            full_width: Self::MIN_PANEL_WIDTH,
+            options_width: Self::MIN_PANEL_WIDTH,
+            resizing: None,
             selection: SelectionIndicator::new(glm::zero(), false, display),
             tab_selection: SelectionIndicator::new(glm::vec2(10_000_000.0, 0.0), true, display),
             color_picker: Default::default(),
@@ -51,7 +73,7 @@ impl ToolBox {
         }
     }
 
-    pub fn create_standard_tools(&mut self, tools: &mut SlotMap<ToolKey, Tool>, display: &Display) {
+    pub fn create_standard_tools(&mut self, tools: &mut SlotMap<ToolKey, Tool>, display: &Display, preferences: &Preferences) {
         // Create tools and corresponding tool buttons
 
         self.add_tool_button(ToolButton::new(
@@ -102,15 +124,96 @@ impl ToolBox {
             None,
         ));
 
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/eyedropper.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::eyedropper()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/pen.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::pen()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/pencil.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::pencil()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/zoom.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::zoom()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/edit_points.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::edit_points()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/viewport.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::viewport()),
+            display,
+            None,
+        ));
+
+        self.add_tool_button(ToolButton::new(
+            include_str!("_src_objs/knife.obj"),
+            PressAnimation::Deform,
+            tools.insert(Tool::knife()),
+            display,
+            None,
+        ));
+
+        for tool in tools.values_mut() {
+            tool.apply_preferences(preferences);
+        }
+
         // Select first non-menu tool
         self.select_tool(1);
     }
 
-    pub fn create_standard_options_tabs(&mut self, tabs: &mut SlotMap<options::tab::TabRef, Box<dyn options::tab::Tab>>, display: &Display) {
+    // Tools contributed by plugins go after the standard ones
+    pub fn create_plugin_tools(&mut self, tools: &mut SlotMap<ToolKey, Tool>, display: &Display, plugins: Vec<Box<dyn ToolPlugin>>) {
+        for plugin in plugins {
+            let icon = plugin.get_icon().to_string();
+            self.add_tool_button(ToolButton::new(
+                &icon,
+                PressAnimation::Deform,
+                tools.insert(Tool::plugin(plugin)),
+                display,
+                None,
+            ));
+        }
+    }
+
+    pub fn create_standard_options_tabs(
+        &mut self,
+        tabs: &mut SlotMap<options::tab::TabRef, Box<dyn options::tab::Tab>>,
+        display: &Display,
+        preferences: &Preferences,
+    ) {
+        self.color_picker.set_shown(preferences.is_section_expanded(options::tab::Object::FILL_SECTION));
+
         self.add_options_tab_button(options::TabButton::new(
             include_str!("_src_objs/object.obj"),
             PressAnimation::Deform,
-            tabs.insert(Box::new(options::tab::Object::new())),
+            tabs.insert(Box::new(options::tab::Object::new(preferences))),
             display,
             None,
         ));
@@ -123,6 +226,22 @@ impl ToolBox {
             None,
         ));
 
+        self.add_options_tab_button(options::TabButton::new(
+            include_str!("_src_objs/export.obj"),
+            PressAnimation::Deform,
+            tabs.insert(Box::new(options::tab::Export::new())),
+            display,
+            None,
+        ));
+
+        self.add_options_tab_button(options::TabButton::new(
+            include_str!("_src_objs/preferences.obj"),
+            PressAnimation::Deform,
+            tabs.insert(Box::new(options::tab::Preferences::new(preferences))),
+            display,
+            None,
+        ));
+
         self.select_tab(0);
     }
 
@@ -134,10 +253,58 @@ impl ToolBox {
         self.options_tab_buttons.push(options_tab);
     }
 
-    fn is_on_object_options(&self) -> bool {
+    // Areas of the window covered by the tool panel and the options panel
+    pub fn get_panel_areas(&self, window: &glm::Vec2) -> [AABB; 2] {
+        [
+            AABB::new(0.0, 0.0, self.full_width, window.y),
+            AABB::new(window.x - self.options_width, 0.0, self.options_width, window.y),
+        ]
+    }
+
+    pub fn get_tools_area(&self, window: &glm::Vec2) -> AABB {
+        self.get_panel_areas(window)[0]
+    }
+
+    pub fn get_options_area(&self, window: &glm::Vec2) -> AABB {
+        self.get_panel_areas(window)[1]
+    }
+
+    pub fn get_panel_widths(&self) -> [f32; 2] {
+        [self.full_width, self.options_width]
+    }
+
+    pub fn set_panel_widths(&mut self, [tools, options]: [f32; 2]) {
+        self.full_width = tools.clamp(Self::MIN_PANEL_WIDTH, Self::MAX_PANEL_WIDTH);
+        self.options_width = options.clamp(Self::MIN_PANEL_WIDTH, Self::MAX_PANEL_WIDTH);
+    }
+
+    // Which panel's inner edge is under the mouse, if any
+    fn get_panel_edge(&self, mouse: &glm::Vec2, screen_width: f32) -> Option<Panel> {
+        if (self.full_width - Self::RESIZE_HANDLE_WIDTH..=self.full_width).contains(&mouse.x) {
+            Some(Panel::Tools)
+        } else if (screen_width - self.options_width..=screen_width - self.options_width + Self::RESIZE_HANDLE_WIDTH).contains(&mouse.x) {
+            Some(Panel::Options)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_on_object_options(&self) -> bool {
         self.tab_selection.external_index == 0
     }
 
+    pub fn is_showing_color_picker(&self) -> bool {
+        self.is_on_object_options() && self.color_picker.is_shown()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.buttons.iter().any(ToolButton::is_animating)
+            || self.options_tab_buttons.iter().any(options::TabButton::is_animating)
+            || self.selection.position.is_animating()
+            || self.tab_selection.position.is_animating()
+            || self.color_picker.is_animating()
+    }
+
     pub fn select_tool(&mut self, index: usize) {
         if index < self.buttons.len() {
             for button in &mut self.buttons {
@@ -160,9 +327,31 @@ impl ToolBox {
         }
     }
 
+    // Moves the tool selection up or down the ribbon, wrapping around at the ends
+    pub fn select_adjacent_tool(&mut self, backwards: bool) {
+        if let Some(index) = step_focus(Some(self.selection.external_index), self.buttons.len(), backwards) {
+            self.select_tool(index);
+        }
+    }
+
+    pub fn select_adjacent_tab(&mut self, backwards: bool) {
+        if let Some(index) = step_focus(Some(self.tab_selection.external_index), self.options_tab_buttons.len(), backwards) {
+            self.select_tab(index);
+        }
+    }
+
     pub fn click(&mut self, interaction: Interaction, screen_width: f32, screen_height: f32) -> Capture {
         let ClickInteraction { button, position: mouse, .. } = interaction.as_click().unwrap();
 
+        // Panel edges
+        if *button == MouseButton::Left {
+            self.resizing = self.get_panel_edge(mouse, screen_width);
+
+            if self.resizing.is_some() {
+                return Capture::AllowDrag;
+            }
+        }
+
         // Tool ribbon
         if *button == MouseButton::Left && mouse.x < self.width {
             let index = self.get_index_for_mouse_y(mouse.y, screen_height);
@@ -175,14 +364,18 @@ impl ToolBox {
         }
 
         // Options tab picker and color picker
-        if *button == MouseButton::Left && mouse.x > screen_width - 256.0 {
+        if *button == MouseButton::Left && mouse.x > screen_width - self.options_width {
             if let Some(index) = self.get_options_tab_index_for_mouse(*mouse, screen_width) {
                 // Change options tab if another options tab was selected
                 self.select_tab(index);
                 return Capture::AllowDrag;
             }
 
-            if self.is_on_object_options() && self.color_picker.click(*button, *mouse, screen_width) {
+            if self.is_showing_color_picker()
+                && self
+                    .color_picker
+                    .click(*button, *mouse, &self.get_options_area(&glm::vec2(screen_width, screen_height)))
+            {
                 // Do color picker if applicable
                 return Capture::AllowDrag;
             }
@@ -191,17 +384,29 @@ impl ToolBox {
         Capture::Miss
     }
 
-    pub fn mouse_release(&mut self, button: MouseButton) {
+    // Returns whether a panel was being resized, so that its new size can be remembered
+    pub fn mouse_release(&mut self, button: MouseButton) -> bool {
         self.color_picker.mouse_release(button);
+        self.resizing.take().is_some()
     }
 
     pub fn drag(&mut self, _button: MouseButton, interaction: &Interaction, screen_width: f32) -> Capture {
-        if let Interaction::Drag(DragInteraction { start, .. }) = *interaction {
-            if self.is_on_object_options() && self.color_picker.is_selecting_color() {
-                self.color_picker.drag(interaction, screen_width)?;
+        if let Interaction::Drag(DragInteraction { start, current, .. }) = *interaction {
+            match self.resizing {
+                Some(Panel::Tools) => self.set_panel_widths([current.x, self.options_width]),
+                Some(Panel::Options) => self.set_panel_widths([self.full_width, screen_width - current.x]),
+                None => (),
             }
 
-            if start.x <= self.full_width || start.x >= screen_width - 256.0 {
+            if self.resizing.is_some() {
+                return Capture::AllowDrag;
+            }
+
+            if self.is_showing_color_picker() && self.color_picker.is_selecting_color() {
+                self.color_picker.drag(interaction, &self.get_options_area(&glm::vec2(screen_width, 0.0)))?;
+            }
+
+            if start.x <= self.full_width || start.x >= screen_width - self.options_width {
                 return Capture::AllowDrag;
             }
         }
@@ -290,7 +495,7 @@ impl ToolBox {
     }
 
     fn calculate_beginning_x(&self, window_width: f32) -> f32 {
-        window_width - 256.0 / 2.0 - self.calculate_stripe_width() / 2.0 + self.icon_size / 2.0
+        window_width - self.options_width / 2.0 - self.calculate_stripe_width() / 2.0 + self.icon_size / 2.0
     }
 
     fn calculate_center_x_for_index(&self, window_width: f32, index: usize) -> f32 {
@@ -302,6 +507,20 @@ impl ToolBox {
         (num_buttons as f32) * self.icon_size + (num_buttons as f32 - 1.0).max(0.0) * self.padding
     }
 
+    pub fn select_tool_of_kind(&mut self, tools: &SlotMap<ToolKey, Tool>, kind: ToolKind) {
+        if let Some(index) = self
+            .buttons
+            .iter()
+            .position(|button| tools.get(button.key).is_some_and(|tool| tool.kind() == kind))
+        {
+            self.select_tool(index);
+        }
+    }
+
+    pub fn get_tool_at(&self, index: usize) -> Option<ToolKey> {
+        Some(self.buttons.get(index)?.key)
+    }
+
     pub fn get_selected(&self) -> Option<ToolKey> {
         Some(self.buttons.get(self.selection.external_index)?.key)
     }
@@ -315,7 +534,7 @@ impl ToolBox {
         color_scheme: &ColorScheme,
         text_system: &TextSystem,
         font: Rc<FontTexture>,
-        ocean: &mut Ocean,
+        ocean: &Ocean,
         selections: &[Selection],
     ) {
         // Background
@@ -329,15 +548,21 @@ impl ToolBox {
 
         // Tool Options
         if let Some(tool_key) = self.get_selected() {
-            tools[tool_key].render_options(ctx, text_system, font.clone());
+            let tools_area = self.get_tools_area(&glm::vec2(ctx.width, ctx.height));
+            tools[tool_key].render_options(ctx, text_system, font.clone(), &tools_area);
         }
 
         // Selection
         self.selection.render(ctx, &color_scheme.foreground);
 
         // Options background
-        ctx.ribbon_mesh
-            .render(ctx, glm::vec2(ctx.width - 256.0, 0.0), glm::vec2(256.0, ctx.height), &color_scheme.dark_ribbon);
+        let options_area = self.get_options_area(&glm::vec2(ctx.width, ctx.height));
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(options_area.min_x, 0.0),
+            glm::vec2(options_area.width(), ctx.height),
+            &color_scheme.dark_ribbon,
+        );
 
         // Options Tabs
         for (i, button) in self.options_tab_buttons.iter_mut().enumerate() {
@@ -355,14 +580,14 @@ impl ToolBox {
         self.tab_selection.render(ctx, &color_scheme.foreground);
 
         // Draw hue/value picker
-        if self.is_on_object_options() {
-            self.color_picker.render(ctx);
+        if self.is_showing_color_picker() {
+            self.color_picker.render(ctx, text_system, font.clone(), &options_area);
         }
 
         // Draw panel for tab of options menu
         let options_tab_key = self.options_tab_buttons[self.tab_selection.external_index].key;
         if let Some(tab) = options_tabs.get_mut(options_tab_key) {
-            tab.render(ctx, text_system, font, ocean, selections);
+            tab.render(ctx, text_system, font, ocean, selections, &options_area);
         }
     }
 }
@@ -387,25 +612,16 @@ impl SelectionIndicator {
     pub fn render(&self, ctx: &mut RenderCtx, color: &Color) {
         let position = self.position.get_animated();
 
-        let identity = glm::identity::<f32, 4>();
-        let mut transformation = glm::translation(&glm::vec2_to_vec3(&position));
+        let mut transformation = Affine2::translation(&position);
 
-        transformation = glm::scale(&transformation, &glm::vec3(16.0, 16.0, 0.0));
-        transformation = glm::scale(&transformation, &glm::vec3(0.5, 0.5, 0.0)); // (since icons are in 2x2 meters, we have downscale by factor of 2)
+        transformation = transformation.scale(&glm::vec2(16.0, 16.0));
+        transformation = transformation.scale(&glm::vec2(0.5, 0.5)); // (since icons are in 2x2 meters, we have downscale by factor of 2)
 
         if self.horizontal {
-            transformation = glm::rotate(&transformation, std::f32::consts::FRAC_PI_2, &glm::vec3(0.0, 0.0, 1.0));
+            transformation = transformation.rotate(std::f32::consts::FRAC_PI_2);
         }
 
-        let uniforms = glium::uniform! {
-            transformation: transformation.as_values(),
-            view: identity.as_values(),
-            projection: ctx.projection.as_values(),
-            color: color.as_values()
-        };
-
-        ctx.draw(&self.mesh.vertex_buffer, &self.mesh.indices, ctx.color_shader, &uniforms, &Default::default())
-            .unwrap();
+        ctx.draw_mesh(&self.mesh, &transformation, color);
     }
 }
 
@@ -1,36 +1,39 @@
 use std::rc::Rc;
 
-use crate::{app::App, as_values::AsValues, color::Color, draw_text::draw_text_centered};
+use crate::{affine::Affine2, app::App, as_values::AsValues, color::Color, draw_text::draw_text_centered};
 use glium::glutin::event::MouseButton;
-use glium_text_rusttype::{FontTexture, TextDisplay, TextSystem};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
 
 use crate::{aabb::AABB, capture::Capture, render_ctx::RenderCtx};
 
 pub struct Button {
     text: String,
-    text_display: Option<TextDisplay<Rc<FontTexture>>>,
     action: Box<dyn FnMut(&mut App)>,
 }
 
 impl Button {
     pub fn new(text: String, action: Box<dyn FnMut(&mut App)>) -> Self {
-        Self {
-            text,
-            text_display: None,
-            action,
-        }
+        Self { text, action }
     }
 
     pub fn click(&mut self, _mouse_button: MouseButton, position: &glm::Vec2, area: &AABB, app: &mut App) -> Capture {
         if area.intersecting_point(position.x, position.y) {
-            (self.action)(app);
+            self.activate(app);
             return Capture::TakeFocus;
         }
 
         Capture::Miss
     }
 
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn activate(&mut self, app: &mut App) {
+        (self.action)(app);
+    }
+
     pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
         self.render_box(ctx, area);
         self.render_text(ctx, text_system, font, area);
@@ -38,14 +41,14 @@ impl Button {
 
     fn render_box(&self, ctx: &mut RenderCtx, area: &AABB) {
         let mesh = ctx.square_xyzuv;
-        let identity = glm::identity::<f32, 4>();
+        let identity = Affine2::identity();
 
         let quad_dimensions = glm::vec2(area.width(), area.height() + 32.0);
         let dead_space = quad_dimensions - glm::vec2(area.width(), area.height());
         let min = glm::vec2(area.min_x, area.min_y);
 
-        let transformation = glm::translation(&glm::vec2_to_vec3(&(min + quad_dimensions * 0.5 - dead_space * 0.5)));
-        let transformation = glm::scale(&transformation, &glm::vec2_to_vec3(&(quad_dimensions * 0.5)));
+        let transformation = Affine2::translation(&(min + quad_dimensions * 0.5 - dead_space * 0.5));
+        let transformation = transformation.scale(&(quad_dimensions * 0.5));
 
         let uniforms = glium::uniform! {
             transformation: transformation.as_values(),
@@ -72,14 +75,6 @@ impl Button {
 
         let color = Color::from_hex("#FFFFFF");
 
-        draw_text_centered(
-            &mut self.text_display,
-            text_system,
-            font,
-            &self.text,
-            &(input_area_center + relative_position),
-            ctx,
-            color,
-        );
+        draw_text_centered(text_system, font, &self.text, &(input_area_center + relative_position), ctx, color);
     }
 }
@@ -1,16 +1,15 @@
 use crate::{
-    aabb::AABB, as_values::AsValues, capture::Capture, color::Color, draw_text::draw_text_centered, icon_button::IconButton, press_animation::PressAnimation,
-    render_ctx::RenderCtx, smooth::Smooth,
+    aabb::AABB, affine::Affine2, as_values::AsValues, capture::Capture, color::Color, draw_text::draw_text_centered, icon_button::IconButton,
+    press_animation::PressAnimation, render_ctx::RenderCtx, smooth::Smooth,
 };
 use glium::glutin::event::MouseButton;
-use glium_text_rusttype::{FontTexture, TextDisplay, TextSystem};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
 use std::{rc::Rc, time::Duration};
 
 #[allow(dead_code)]
 pub struct Checkbox {
     label: String,
-    label_display: Option<TextDisplay<Rc<FontTexture>>>,
     checked: bool,
     color: Option<Smooth<Color>>,
     checkmark: Option<IconButton<()>>,
@@ -22,7 +21,6 @@ impl Checkbox {
     pub fn new(default_label: String, checked: bool) -> Self {
         Self {
             label: default_label,
-            label_display: None,
             checked,
             color: None,
             checkmark: None,
@@ -59,10 +57,20 @@ impl Checkbox {
         self.has_new_content = true;
     }
 
+    pub fn set_checked(&mut self, checked: bool) {
+        if self.checked != checked {
+            self.toggle();
+        }
+    }
+
     pub fn checked(&self) -> bool {
         self.checked
     }
 
+    pub fn is_animating(&self) -> bool {
+        self.color.as_ref().is_some_and(Smooth::is_animating) || self.checkmark.as_ref().is_some_and(IconButton::is_animating)
+    }
+
     fn update_checkmark(&mut self, ctx: &mut RenderCtx) {
         if self.color.is_none() {
             self.color = Some(Smooth::new(ctx.color_scheme.light_ribbon, Some(Duration::from_millis(200))));
@@ -98,27 +106,25 @@ impl Checkbox {
         let relative_position = glm::vec2(0.0, -28.0);
 
         draw_text_centered(
-            &mut self.label_display,
             text_system,
             font,
             &self.label,
             &(input_area_center + relative_position),
             ctx,
-            Color::from_hex("#777777"),
+            ctx.color_scheme.label,
         );
     }
 
     fn render_box(&self, ctx: &mut RenderCtx, area: &AABB) {
         let mesh = ctx.square_xyzuv;
-        let identity = glm::identity::<f32, 4>();
+        let identity = Affine2::identity();
         let quad_dimensions = glm::vec2(area.height() + 28.0 + 20.0, area.height() + 28.0);
         let dead_space = quad_dimensions - glm::vec2(area.width(), area.height());
-        let transformation = glm::translation(&glm::vec3(
+        let transformation = Affine2::translation(&glm::vec2(
             area.min_x + quad_dimensions.x * 0.5 - dead_space.x * 0.5,
             area.min_y + quad_dimensions.y * 0.5 - dead_space.y * 0.5,
-            0.0,
         ));
-        let transformation = glm::scale(&transformation, &glm::vec3(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5, 0.0));
+        let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
 
         let uniforms = glium::uniform! {
             transformation: transformation.as_values(),
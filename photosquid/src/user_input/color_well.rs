@@ -0,0 +1,84 @@
+use crate::{aabb::AABB, app::App, capture::Capture, color::Color, draw_text::draw_text_centered, options::color_picker::ColorTarget, render_ctx::RenderCtx};
+use glium::glutin::event::MouseButton;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Swatch showing a color, which has the color picker edit it when clicked
+pub struct ColorWell {
+    label: String,
+    color: Color,
+    target: ColorTarget,
+    active: bool,
+}
+
+impl ColorWell {
+    pub fn new(label: String, color: Color, target: ColorTarget) -> Self {
+        Self {
+            label,
+            color,
+            target,
+            active: false,
+        }
+    }
+
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2, area: &AABB, app: &mut App) -> Capture {
+        if button == MouseButton::Left && area.intersecting_point(position.x, position.y) {
+            self.activate(app);
+            Capture::TakeFocus
+        } else {
+            Capture::Miss
+        }
+    }
+
+    // Routes the color picker to this well's target
+    pub fn activate(&mut self, app: &mut App) {
+        self.active = true;
+        app.toolbox.color_picker.edit(self.target, self.color);
+    }
+
+    // Keeps the well in sync with whether the color picker is still editing its target
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn get_target(&self) -> ColorTarget {
+        self.target
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        let outline = if self.active {
+            ctx.color_scheme.foreground
+        } else {
+            ctx.color_scheme.light_ribbon
+        };
+
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x - 2.0, area.min_y - 2.0),
+            glm::vec2(area.width() + 4.0, area.height() + 4.0),
+            &outline,
+        );
+        ctx.ribbon_mesh
+            .render(ctx, glm::vec2(area.min_x, area.min_y), glm::vec2(area.width(), area.height()), &self.color);
+
+        if !self.label.is_empty() {
+            draw_text_centered(
+                text_system,
+                font,
+                &self.label,
+                &glm::vec2(area.center_x(), area.min_y - 14.0),
+                ctx,
+                ctx.color_scheme.label,
+            );
+        }
+    }
+}
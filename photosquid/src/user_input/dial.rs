@@ -0,0 +1,191 @@
+use crate::{aabb::AABB, capture::Capture, draw_text::draw_text_centered, interaction::DragInteraction, render_ctx::RenderCtx};
+use glium::glutin::event::MouseButton;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Angle in degrees picked by dragging around a circle, clockwise from pointing right
+pub struct Dial {
+    label: String,
+    value: f32,
+    dragging: bool,
+    has_new_content: bool,
+}
+
+impl Dial {
+    const KNOB_SIZE: f32 = 3.0;
+
+    // Degrees that holding shift (or stepping with the keyboard) snaps to
+    const SNAPPING: f32 = 15.0;
+
+    pub fn new(value: f32, label: String) -> Self {
+        Self {
+            label,
+            value: normalize(value),
+            dragging: false,
+            has_new_content: false,
+        }
+    }
+
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2, area: &AABB) -> Capture {
+        self.dragging = button == MouseButton::Left && area.intersecting_point(position.x, position.y);
+
+        if self.dragging {
+            self.turn_to(position, area, false);
+            Capture::TakeFocus
+        } else {
+            Capture::Miss
+        }
+    }
+
+    // Keeps following the mouse after being grabbed, holding shift snaps to steps
+    pub fn drag(&mut self, drag: &DragInteraction, area: &AABB) -> Capture {
+        if self.dragging {
+            self.turn_to(&drag.current, area, drag.modifiers.shift());
+            Capture::AllowDrag
+        } else {
+            Capture::Miss
+        }
+    }
+
+    pub fn release(&mut self) {
+        self.dragging = false;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    // Turns to the next step, going back around after a full turn
+    pub fn increment(&mut self) {
+        self.set(((self.value + Self::SNAPPING) / Self::SNAPPING).floor() * Self::SNAPPING);
+        self.has_new_content = true;
+    }
+
+    pub fn poll(&mut self) -> Option<f32> {
+        if self.has_new_content {
+            self.has_new_content = false;
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, value: f32) {
+        self.value = normalize(value);
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        let center = Self::get_center(area);
+        let radius = Self::get_radius(area);
+        let color = if self.dragging { ctx.color_scheme.foreground } else { ctx.color_scheme.label };
+        let direction = glm::vec2(self.value.to_radians().cos(), self.value.to_radians().sin());
+
+        ctx.ring_mesh.render(ctx, center, glm::vec2(radius, radius), &ctx.color_scheme.light_ribbon);
+        ctx.ring_mesh.render(
+            ctx,
+            center + direction * (radius - Self::KNOB_SIZE),
+            glm::vec2(Self::KNOB_SIZE, Self::KNOB_SIZE),
+            &ctx.color_scheme.foreground,
+        );
+
+        let readout_center = glm::vec2((area.min_x + radius * 2.0 + area.max_x) * 0.5, area.center_y());
+
+        draw_text_centered(
+            text_system,
+            font.clone(),
+            &format!("{} deg", (self.value * 10.0).round() / 10.0),
+            &(readout_center + glm::vec2(0.0, 4.0)),
+            ctx,
+            color,
+        );
+        draw_text_centered(
+            text_system,
+            font,
+            &self.label,
+            &glm::vec2(area.center_x(), area.center_y() - 28.0),
+            ctx,
+            ctx.color_scheme.label,
+        );
+    }
+
+    // The dial sits on the left of its area, with the angle written next to it
+    fn get_center(area: &AABB) -> glm::Vec2 {
+        glm::vec2(area.min_x + Self::get_radius(area), area.center_y())
+    }
+
+    fn get_radius(area: &AABB) -> f32 {
+        area.height() * 0.5
+    }
+
+    fn turn_to(&mut self, position: &glm::Vec2, area: &AABB, snap: bool) {
+        let offset = position - Self::get_center(area);
+
+        // Too close to the center to tell which way it's pointing
+        if offset.norm() < 1.0 {
+            return;
+        }
+
+        let degrees = offset.y.atan2(offset.x).to_degrees();
+        let step = if snap { Self::SNAPPING } else { 1.0 };
+        let previous = self.value;
+        self.set((degrees / step).round() * step);
+
+        if self.value != previous {
+            self.has_new_content = true;
+        }
+    }
+}
+
+// Keeps angles within a single turn
+fn normalize(degrees: f32) -> f32 {
+    let degrees = degrees.rem_euclid(360.0);
+
+    if degrees >= 360.0 {
+        0.0
+    } else {
+        degrees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, Dial};
+    use crate::{aabb::AABB, interaction::DragInteraction};
+    use glium::glutin::event::{ModifiersState, MouseButton};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn turn_dial() {
+        assert_eq!(normalize(-90.0), 270.0);
+        assert_eq!(normalize(720.0), 0.0);
+
+        // The dial's center is at (10, 10)
+        let area = AABB::new(0.0, 0.0, 100.0, 20.0);
+        let mut dial = Dial::new(0.0, "Rotation".into());
+        dial.click(MouseButton::Left, &glm::vec2(10.0, 19.0), &area);
+        assert_eq!(dial.poll(), Some(90.0));
+
+        let drag = |x: f32, y: f32, modifiers: ModifiersState| DragInteraction {
+            delta: glm::vec2(0.0, 0.0),
+            start: glm::vec2(10.0, 19.0),
+            current: glm::vec2(x, y),
+            modifiers,
+        };
+
+        dial.drag(&drag(0.0, 0.0, ModifiersState::empty()), &area);
+        assert_eq!(dial.poll(), Some(225.0));
+        dial.drag(&drag(20.0, 8.0, ModifiersState::SHIFT), &area);
+        assert_eq!(dial.poll(), Some(345.0));
+
+        dial.increment();
+        assert_eq!(dial.value(), 0.0);
+
+        dial.release();
+        assert!(!dial.is_dragging());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    aabb::AABB,
+    capture::{Capture, KeyCapture},
+    draw_text::draw_text_centered,
+    render_ctx::RenderCtx,
+};
+use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Choice between a few named options, which are listed below it while open
+pub struct Dropdown {
+    label: String,
+    options: Vec<String>,
+    selected: usize,
+    highlighted: usize,
+    expanded: bool,
+    has_new_content: bool,
+}
+
+impl Dropdown {
+    const ROW_HEIGHT: f32 = 28.0;
+
+    pub fn new(label: String, options: Vec<String>, selected: usize) -> Self {
+        Self {
+            label,
+            options,
+            selected,
+            highlighted: selected,
+            expanded: false,
+            has_new_content: false,
+        }
+    }
+
+    pub fn click(&mut self, _button: MouseButton, position: &glm::Vec2, area: &AABB) -> Capture {
+        if self.expanded {
+            let clicked = (0..self.options.len()).find(|i| self.get_option_area(*i, area).intersecting_point(position.x, position.y));
+
+            if let Some(index) = clicked {
+                self.choose(index);
+                return Capture::TakeFocus;
+            }
+
+            self.expanded = false;
+            return if area.intersecting_point(position.x, position.y) {
+                Capture::TakeFocus
+            } else {
+                Capture::Miss
+            };
+        }
+
+        if area.intersecting_point(position.x, position.y) {
+            self.expand();
+            return Capture::TakeFocus;
+        }
+
+        Capture::Miss
+    }
+
+    // Up/Down moves through the options while open, Enter/Space picks one and Escape closes without picking
+    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode) -> KeyCapture {
+        if !self.expanded {
+            return KeyCapture::Miss;
+        }
+
+        let count = self.options.len();
+
+        match virtual_keycode {
+            VirtualKeyCode::Up => self.highlighted = (self.highlighted + count - 1) % count,
+            VirtualKeyCode::Down => self.highlighted = (self.highlighted + 1) % count,
+            VirtualKeyCode::Return | VirtualKeyCode::Space => self.choose(self.highlighted),
+            VirtualKeyCode::Escape => self.expanded = false,
+            _ => return KeyCapture::Miss,
+        }
+
+        KeyCapture::Capture
+    }
+
+    pub fn toggle(&mut self) {
+        if self.expanded {
+            self.expanded = false;
+        } else {
+            self.expand();
+        }
+    }
+
+    pub fn unfocus(&mut self) {
+        self.expanded = false;
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn poll(&mut self) -> Option<usize> {
+        if self.has_new_content {
+            self.has_new_content = false;
+            Some(self.selected)
+        } else {
+            None
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, selected: usize) {
+        if selected < self.options.len() {
+            self.selected = selected;
+        }
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        let center = glm::vec2(area.center_x(), area.center_y());
+        let text = self.options.get(self.selected).map_or("", String::as_str);
+        let color = if self.expanded { ctx.color_scheme.foreground } else { ctx.color_scheme.label };
+
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x, area.min_y),
+            glm::vec2(area.width(), area.height()),
+            &ctx.color_scheme.light_ribbon,
+        );
+
+        draw_text_centered(text_system, font.clone(), text, &(center + glm::vec2(0.0, 4.0)), ctx, color);
+        draw_text_centered(text_system, font, &self.label, &(center + glm::vec2(0.0, -28.0)), ctx, ctx.color_scheme.label);
+    }
+
+    // Draws the list of options while open, should be called after everything it may cover is drawn
+    pub fn render_overlay(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        if !self.expanded {
+            return;
+        }
+
+        // Text that's already queued would otherwise end up on top of the list
+        ctx.flush_text(text_system);
+
+        for (i, option) in self.options.iter().enumerate() {
+            let option_area = self.get_option_area(i, area);
+            let background = if i == self.highlighted {
+                ctx.color_scheme.light_ribbon
+            } else {
+                ctx.color_scheme.dark_ribbon
+            };
+
+            ctx.ribbon_mesh.render(
+                ctx,
+                glm::vec2(option_area.min_x, option_area.min_y),
+                glm::vec2(option_area.width(), option_area.height()),
+                &background,
+            );
+
+            let color = if i == self.selected {
+                ctx.color_scheme.foreground
+            } else {
+                ctx.color_scheme.label
+            };
+
+            draw_text_centered(
+                text_system,
+                font.clone(),
+                option,
+                &glm::vec2(option_area.center_x(), option_area.center_y() + 4.0),
+                ctx,
+                color,
+            );
+        }
+
+        ctx.flush_text(text_system);
+    }
+
+    fn get_option_area(&self, index: usize, area: &AABB) -> AABB {
+        AABB::new(area.min_x, area.max_y + index as f32 * Self::ROW_HEIGHT, area.width(), Self::ROW_HEIGHT)
+    }
+
+    fn expand(&mut self) {
+        self.expanded = true;
+        self.highlighted = self.selected;
+    }
+
+    fn choose(&mut self, index: usize) {
+        self.expanded = false;
+
+        if index != self.selected {
+            self.selected = index;
+            self.has_new_content = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dropdown;
+    use crate::{aabb::AABB, capture::Capture};
+    use glium::glutin::event::{MouseButton, VirtualKeyCode};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn choose_from_dropdown() {
+        let mut dropdown = Dropdown::new("Format".into(), vec!["SVG".into(), "PNG".into(), "JPEG".into()], 0);
+        let area = AABB::new(0.0, 0.0, 100.0, 20.0);
+
+        // Keyboard
+        dropdown.toggle();
+        dropdown.key_press(VirtualKeyCode::Up);
+        dropdown.key_press(VirtualKeyCode::Return);
+        assert!(!dropdown.is_expanded());
+        assert_eq!(dropdown.poll(), Some(2));
+        assert_eq!(dropdown.poll(), None);
+
+        // Mouse, the second option is listed just below the first
+        assert_eq!(dropdown.click(MouseButton::Left, &glm::vec2(50.0, 10.0), &area), Capture::TakeFocus);
+        assert!(dropdown.is_expanded());
+        dropdown.click(MouseButton::Left, &glm::vec2(50.0, 20.0 + 28.0 + 14.0), &area);
+        assert_eq!(dropdown.poll(), Some(1));
+
+        // Clicking elsewhere closes without choosing
+        dropdown.toggle();
+        assert_eq!(dropdown.click(MouseButton::Left, &glm::vec2(500.0, 500.0), &area), Capture::Miss);
+        assert!(!dropdown.is_expanded());
+        assert_eq!(dropdown.poll(), None);
+    }
+}
@@ -1,19 +1,28 @@
 mod button;
 mod checkbox;
+mod color_well;
+mod dial;
+mod dropdown;
+mod slider;
 mod text_input;
 
 pub use button::Button;
 pub use checkbox::Checkbox;
+pub use color_well::ColorWell;
+pub use dial::Dial;
+pub use dropdown::Dropdown;
+pub use slider::Slider;
 pub use text_input::TextInput;
 
 use crate::{
     aabb::AABB,
     app::App,
     capture::{Capture, KeyCapture},
+    interaction::DragInteraction,
     render_ctx::RenderCtx,
 };
 use enum_as_inner::EnumAsInner;
-use glium::glutin::event::{MouseButton, VirtualKeyCode};
+use glium::glutin::event::{ModifiersState, MouseButton, VirtualKeyCode};
 use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
 use std::rc::Rc;
@@ -26,6 +35,14 @@ pub enum UserInput {
     Checkbox(Checkbox),
 
     Button(Button),
+
+    Slider(Slider),
+
+    Dropdown(Dropdown),
+
+    Dial(Dial),
+
+    ColorWell(ColorWell),
 }
 
 impl UserInput {
@@ -34,14 +51,47 @@ impl UserInput {
             Self::TextInput(text_input) => text_input.click(mouse_button, position, area),
             Self::Checkbox(checkbox) => checkbox.click(mouse_button, position, area),
             Self::Button(button) => button.click(mouse_button, position, area, app),
+            Self::Slider(slider) => slider.click(mouse_button, position, area),
+            Self::Dropdown(dropdown) => dropdown.click(mouse_button, position, area),
+            Self::Dial(dial) => dial.click(mouse_button, position, area),
+            Self::ColorWell(color_well) => color_well.click(mouse_button, position, area, app),
         }
     }
 
-    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode, shift: bool) -> KeyCapture {
+    pub fn drag(&mut self, drag: &DragInteraction, area: &AABB) -> Capture {
         match self {
-            Self::TextInput(text_input) => text_input.key_press(virtual_keycode, shift),
+            Self::TextInput(text_input) => text_input.drag(drag),
+            Self::Slider(slider) => slider.drag(drag, area),
+            Self::Dial(dial) => dial.drag(drag, area),
+            _ => Capture::Miss,
+        }
+    }
+
+    pub fn release(&mut self) {
+        match self {
+            Self::TextInput(text_input) => text_input.release(),
+            Self::Slider(slider) => slider.release(),
+            Self::Dial(dial) => dial.release(),
+            _ => (),
+        }
+    }
+
+    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode, modifiers: ModifiersState) -> KeyCapture {
+        match self {
+            Self::TextInput(text_input) => text_input.key_press(virtual_keycode, modifiers),
             Self::Checkbox(..) => KeyCapture::Miss,
             Self::Button(..) => KeyCapture::Miss,
+            Self::Slider(..) => KeyCapture::Miss,
+            Self::Dropdown(dropdown) => dropdown.key_press(virtual_keycode),
+            Self::Dial(..) => KeyCapture::Miss,
+            Self::ColorWell(..) => KeyCapture::Miss,
+        }
+    }
+
+    pub fn receive_character(&mut self, character: char) -> KeyCapture {
+        match self {
+            Self::TextInput(text_input) => text_input.receive_character(character),
+            _ => KeyCapture::Miss,
         }
     }
 
@@ -50,6 +100,37 @@ impl UserInput {
             Self::TextInput(text_input) => text_input.render(ctx, text_system, font, area),
             Self::Checkbox(checkbox) => checkbox.render(ctx, text_system, font, area),
             Self::Button(button) => button.render(ctx, text_system, font, area),
+            Self::Slider(slider) => slider.render(ctx, text_system, font, area),
+            Self::Dropdown(dropdown) => dropdown.render(ctx, text_system, font, area),
+            Self::Dial(dial) => dial.render(ctx, text_system, font, area),
+            Self::ColorWell(color_well) => color_well.render(ctx, text_system, font, area),
+        }
+    }
+
+    pub fn is_animating(&self) -> bool {
+        match self {
+            Self::Checkbox(checkbox) => checkbox.is_animating(),
+            _ => false,
+        }
+    }
+
+    // Draws anything that goes on top of the other inputs, like an open dropdown's options
+    pub fn render_overlay(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        if let Self::Dropdown(dropdown) = self {
+            dropdown.render_overlay(ctx, text_system, font, area);
+        }
+    }
+
+    // Does what clicking on the input would, so it can be operated from the keyboard
+    pub fn activate(&mut self, app: &mut App) {
+        match self {
+            Self::TextInput(text_input) => text_input.focus(),
+            Self::Checkbox(checkbox) => checkbox.toggle(),
+            Self::Button(button) => button.activate(app),
+            Self::Slider(slider) => slider.increment(),
+            Self::Dropdown(dropdown) => dropdown.toggle(),
+            Self::Dial(dial) => dial.increment(),
+            Self::ColorWell(color_well) => color_well.activate(app),
         }
     }
 
@@ -58,6 +139,57 @@ impl UserInput {
             Self::TextInput(text_input) => text_input.unfocus(),
             Self::Checkbox(..) => (),
             Self::Button(..) => (),
+            Self::Slider(slider) => slider.release(),
+            Self::Dropdown(dropdown) => dropdown.unfocus(),
+            Self::Dial(dial) => dial.release(),
+            Self::ColorWell(..) => (),
+        }
+    }
+
+    // Whether keys should go to the input rather than be used for moving between inputs
+    pub fn is_editing(&self) -> bool {
+        match self {
+            Self::TextInput(text_input) => text_input.is_focused(),
+            Self::Dropdown(dropdown) => dropdown.is_expanded(),
+            _ => false,
         }
     }
 }
+
+// Moves keyboard focus to the next (or previous) of 'count' inputs, wrapping around at the ends
+pub fn step_focus(focus: Option<usize>, count: usize, backwards: bool) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+
+    Some(match (focus, backwards) {
+        (None, false) => 0,
+        (None, true) => count - 1,
+        (Some(index), false) => (index + 1) % count,
+        (Some(index), true) => (index + count - 1) % count,
+    })
+}
+
+// Marks the input that keyboard focus is on
+pub fn render_focus_marker(ctx: &mut RenderCtx, area: &AABB) {
+    ctx.ribbon_mesh.render(
+        ctx,
+        glm::vec2(area.min_x - 28.0, area.center_y() - 8.0),
+        glm::vec2(4.0, 16.0),
+        &ctx.color_scheme.foreground,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step_focus;
+
+    #[test]
+    fn step_focus_wraps() {
+        assert_eq!(step_focus(None, 3, false), Some(0));
+        assert_eq!(step_focus(None, 3, true), Some(2));
+        assert_eq!(step_focus(Some(2), 3, false), Some(0));
+        assert_eq!(step_focus(Some(0), 3, true), Some(2));
+        assert_eq!(step_focus(Some(1), 0, false), None);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{aabb::AABB, capture::Capture, draw_text::draw_text_centered, interaction::DragInteraction, render_ctx::RenderCtx};
+use glium::glutin::event::MouseButton;
+use glium_text_rusttype::{FontTexture, TextSystem};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// Number picked by dragging along a track between a minimum and maximum
+pub struct Slider {
+    label: String,
+    suffix: String,
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    dragging: bool,
+    has_new_content: bool,
+}
+
+impl Slider {
+    const TRACK_HEIGHT: f32 = 4.0;
+    const KNOB_SIZE: f32 = 12.0;
+
+    pub fn new(value: f32, min: f32, max: f32, step: f32, label: String, suffix: String) -> Self {
+        Self {
+            label,
+            suffix,
+            value: snap(value, min, max, step),
+            min,
+            max,
+            step,
+            dragging: false,
+            has_new_content: false,
+        }
+    }
+
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2, area: &AABB) -> Capture {
+        self.dragging = button == MouseButton::Left && area.intersecting_point(position.x, position.y);
+
+        if self.dragging {
+            self.slide_to(position.x, area);
+            Capture::TakeFocus
+        } else {
+            Capture::Miss
+        }
+    }
+
+    // Keeps following the mouse after being grabbed, even once it leaves the track
+    pub fn drag(&mut self, drag: &DragInteraction, area: &AABB) -> Capture {
+        if self.dragging {
+            self.slide_to(drag.current.x, area);
+            Capture::AllowDrag
+        } else {
+            Capture::Miss
+        }
+    }
+
+    pub fn release(&mut self) {
+        self.dragging = false;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    // Moves up by a step, starting back over at the minimum after the maximum
+    pub fn increment(&mut self) {
+        let value = if self.value >= self.max { self.min } else { self.value + self.step };
+        self.set(value);
+        self.has_new_content = true;
+    }
+
+    pub fn poll(&mut self) -> Option<f32> {
+        if self.has_new_content {
+            self.has_new_content = false;
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, value: f32) {
+        self.value = snap(value, self.min, self.max, self.step);
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        let center = glm::vec2(area.center_x(), area.center_y());
+        let track_y = center.y + 10.0;
+        let fraction = self.get_fraction();
+
+        draw_text_centered(
+            text_system,
+            font.clone(),
+            &self.label,
+            &(center + glm::vec2(0.0, -28.0)),
+            ctx,
+            ctx.color_scheme.label,
+        );
+
+        let color = if self.dragging { ctx.color_scheme.foreground } else { ctx.color_scheme.label };
+
+        draw_text_centered(text_system, font, &self.get_readout(), &(center + glm::vec2(0.0, -2.0)), ctx, color);
+
+        let track_position = glm::vec2(area.min_x, track_y - Self::TRACK_HEIGHT * 0.5);
+        ctx.ribbon_mesh
+            .render(ctx, track_position, glm::vec2(area.width(), Self::TRACK_HEIGHT), &ctx.color_scheme.light_ribbon);
+        ctx.ribbon_mesh.render(
+            ctx,
+            track_position,
+            glm::vec2(area.width() * fraction, Self::TRACK_HEIGHT),
+            &ctx.color_scheme.foreground,
+        );
+        ctx.ribbon_mesh.render(
+            ctx,
+            glm::vec2(area.min_x + area.width() * fraction - Self::KNOB_SIZE * 0.5, track_y - Self::KNOB_SIZE * 0.5),
+            glm::vec2(Self::KNOB_SIZE, Self::KNOB_SIZE),
+            &ctx.color_scheme.foreground,
+        );
+    }
+
+    fn slide_to(&mut self, x: f32, area: &AABB) {
+        let fraction = ((x - area.min_x) / area.width()).clamp(0.0, 1.0);
+        let previous = self.value;
+        self.set(self.min + fraction * (self.max - self.min));
+
+        if self.value != previous {
+            self.has_new_content = true;
+        }
+    }
+
+    fn get_fraction(&self) -> f32 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    // Shows as many decimal places as the step needs
+    fn get_readout(&self) -> String {
+        let decimals = if self.step > 0.0 && self.step < 1.0 {
+            (-self.step.log10()).ceil() as usize
+        } else {
+            0
+        };
+
+        format!("{:.*}{}", decimals, self.value, self.suffix)
+    }
+}
+
+// Rounds a value to the nearest step, staying within the range
+fn snap(value: f32, min: f32, max: f32, step: f32) -> f32 {
+    let value = value.clamp(min, max);
+
+    if step > 0.0 {
+        (min + ((value - min) / step).round() * step).clamp(min, max)
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snap, Slider};
+    use crate::aabb::AABB;
+    use glium::glutin::event::MouseButton;
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn slide_by_steps() {
+        assert_eq!(snap(12.4, 0.0, 100.0, 5.0), 10.0);
+        assert_eq!(snap(-3.0, 0.0, 100.0, 5.0), 0.0);
+        assert_eq!(snap(0.3, 0.0, 1.0, 0.25), 0.25);
+
+        let area = AABB::new(0.0, 0.0, 100.0, 20.0);
+        let mut slider = Slider::new(0.0, 0.0, 50.0, 1.0, "Corner Radius".into(), "".into());
+        slider.click(MouseButton::Left, &glm::vec2(50.0, 10.0), &area);
+        assert_eq!(slider.poll(), Some(25.0));
+        assert_eq!(slider.poll(), None);
+        assert_eq!(slider.get_readout(), "25");
+
+        slider.increment();
+        assert_eq!(slider.value(), 26.0);
+    }
+}
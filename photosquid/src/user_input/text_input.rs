@@ -1,21 +1,28 @@
 use crate::{
     aabb::AABB,
+    affine::Affine2,
     as_values::AsValues,
     capture::{Capture, KeyCapture},
-    color::Color,
-    draw_text::draw_text_centered,
+    ctrl_or_cmd::CtrlOrCmd,
+    draw_text::{draw_text_centered, get_text_width},
+    interaction::DragInteraction,
     render_ctx::RenderCtx,
+    script::evaluate_number,
+    units::{split_unit, LengthFormat},
 };
-use glium::glutin::event::{MouseButton, VirtualKeyCode};
-use glium_text_rusttype::{FontTexture, TextDisplay, TextSystem};
+use glium::glutin::event::{ModifiersState, MouseButton, VirtualKeyCode};
+use glium_text_rusttype::{FontTexture, TextSystem};
 use nalgebra_glm as glm;
-use std::rc::Rc;
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+thread_local! {
+    // Text that was last cut or copied from any text input
+    static CLIPBOARD: RefCell<String> = const { RefCell::new(String::new()) };
+}
 
 pub struct TextInput {
     text: String,
     label: String,
-    text_display: Option<TextDisplay<Rc<FontTexture>>>,
-    label_display: Option<TextDisplay<Rc<FontTexture>>>,
     pre_edit: String,
     has_new_content: bool,
     focused: bool,
@@ -23,6 +30,22 @@ pub struct TextInput {
     input_error: bool,
     suffix: String,
     default_text: String,
+
+    // Where typing goes (in characters), and the other end of the selection when there is one
+    caret: usize,
+    anchor: Option<usize>,
+
+    // Decides which characters can be typed, and what they become
+    char_map: fn(char) -> Option<char>,
+
+    // Whether the text is a number that can be typed as an expression
+    is_numeric: bool,
+
+    // Value when dragging on the label started, while the number is being scrubbed
+    scrub_start: Option<f32>,
+
+    // Unit that a length is shown in, lengths can be typed in any unit and are converted to this one
+    length_format: Option<LengthFormat>,
 }
 
 impl TextInput {
@@ -30,19 +53,60 @@ impl TextInput {
         Self {
             pre_edit: default_text.clone(),
             default_text: default_text.clone(),
+            caret: default_text.chars().count(),
+            anchor: None,
             text: default_text,
             label: default_label,
-            text_display: None,
-            label_display: None,
             has_new_content: false,
             focused: false,
             just_focused: false,
             input_error: false,
             suffix,
+            char_map: Self::numeric_map,
+            is_numeric: true,
+            scrub_start: None,
+            length_format: None,
         }
     }
 
-    pub fn click(&mut self, _button: MouseButton, position: &glm::Vec2, area: &AABB) -> Capture {
+    // Text input for a length in world units, like '10cm' or '2in'
+    pub fn length(pixels: f32, default_label: String) -> Self {
+        let format = LengthFormat::default();
+
+        Self {
+            char_map: Self::length_map,
+            length_format: Some(format),
+            ..Self::new(format_value(pixels), default_label, format!(" {}", format.unit.get_suffix()))
+        }
+    }
+
+    // Text input that accepts any text, e.g. for names
+    pub fn plain(default_text: String, default_label: String) -> Self {
+        Self {
+            char_map: Self::text_map,
+            is_numeric: false,
+            ..Self::new(default_text, default_label, "".into())
+        }
+    }
+
+    // Text input that accepts hexadecimal digits instead of decimal numbers
+    pub fn hex(default_text: String, default_label: String) -> Self {
+        Self {
+            char_map: Self::hex_map,
+            is_numeric: false,
+            ..Self::new(default_text, default_label, "".into())
+        }
+    }
+
+    pub fn click(&mut self, button: MouseButton, position: &glm::Vec2, area: &AABB) -> Capture {
+        self.scrub_start = None;
+
+        // Dragging sideways on the label of a number changes it
+        if button == MouseButton::Left && self.is_numeric && !self.focused && Self::get_label_area(area).intersecting_point(position.x, position.y) {
+            self.scrub_start = Some(self.evaluate_text().unwrap_or_default());
+            return Capture::TakeFocus;
+        }
+
         let was_focused = self.focused;
         self.focused = area.intersecting_point(position.x, position.y);
         self.just_focused = self.focused && !was_focused;
@@ -51,67 +115,219 @@ impl TextInput {
         if self.focused {
             if self.just_focused {
                 self.pre_edit = self.text.clone();
+                self.move_caret(self.text.chars().count(), false);
             }
             Capture::TakeFocus
-        } else {
-            self.ensure_not_empty();
+        } else if was_focused {
+            self.commit();
 
-            if was_focused && self.text != self.pre_edit {
+            if self.text != self.pre_edit {
                 self.has_new_content = true;
             }
 
+            Capture::Miss
+        } else {
+            self.ensure_not_empty();
+
             Capture::Miss
         }
     }
 
-    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode, shift: bool) -> KeyCapture {
+    pub fn key_press(&mut self, virtual_keycode: VirtualKeyCode, modifiers: ModifiersState) -> KeyCapture {
         if !self.focused {
             return KeyCapture::Miss;
         }
 
-        if virtual_keycode == VirtualKeyCode::Back {
-            if shift {
-                self.clear();
-                self.input_error = false;
-            } else {
-                self.backspace();
-                self.input_error = false;
+        let shift = modifiers.shift();
+        let length = self.text.chars().count();
+        self.input_error = false;
+
+        match virtual_keycode {
+            VirtualKeyCode::Back if shift => self.clear(),
+            VirtualKeyCode::Back => self.erase(|caret| caret.saturating_sub(1)..caret),
+            VirtualKeyCode::Delete => self.erase(|caret| caret..(caret + 1).min(length)),
+            VirtualKeyCode::Left => self.move_caret(self.get_step_target(false, shift), shift),
+            VirtualKeyCode::Right => self.move_caret(self.get_step_target(true, shift), shift),
+            VirtualKeyCode::Home => self.move_caret(0, shift),
+            VirtualKeyCode::End => self.move_caret(length, shift),
+            VirtualKeyCode::A if modifiers.ctrl_or_cmd() => {
+                self.anchor = Some(0);
+                self.caret = length;
+            }
+            VirtualKeyCode::C if modifiers.ctrl_or_cmd() => self.copy(),
+            VirtualKeyCode::X if modifiers.ctrl_or_cmd() => {
+                self.copy();
+                self.erase_selection();
             }
-            return KeyCapture::Capture;
+            VirtualKeyCode::V if modifiers.ctrl_or_cmd() => {
+                let pasted = CLIPBOARD.with(|clipboard| clipboard.borrow().clone());
+                pasted.chars().for_each(|character| {
+                    self.receive_character(character);
+                });
+            }
+            VirtualKeyCode::Escape => {
+                self.focused = false;
+                self.text = self.pre_edit.clone();
+                self.move_caret(self.text.chars().count(), false);
+            }
+            VirtualKeyCode::Return if self.evaluate() => self.unfocus(),
+            VirtualKeyCode::Return => self.input_error = true,
+
+            // Keys that type characters (see 'receive_character') shouldn't also act as shortcuts
+            _ => (),
         }
 
-        if virtual_keycode == VirtualKeyCode::Escape {
-            self.focused = false;
-            self.text = self.pre_edit.clone();
-            self.text_display = None;
-            return KeyCapture::Capture;
+        KeyCapture::Capture
+    }
+
+    // Holding shift scrubs in finer steps
+    pub fn drag(&mut self, drag: &DragInteraction) -> Capture {
+        let start = match self.scrub_start {
+            Some(start) => start,
+            None => return Capture::Miss,
+        };
+
+        let step = if drag.modifiers.shift() { 0.1 } else { 1.0 };
+        let value = start + (drag.current.x - drag.start.x).round() * step;
+        let text = format_number(value);
+
+        if text != self.text {
+            self.set(&text);
+            self.has_new_content = true;
         }
 
-        if virtual_keycode == VirtualKeyCode::Return {
-            self.unfocus();
-            return KeyCapture::Capture;
+        Capture::AllowDrag
+    }
+
+    pub fn release(&mut self) {
+        self.scrub_start = None;
+    }
+
+    // Typing is received as characters rather than keys,
+    // so that shifted symbols and text from input methods come through as intended
+    pub fn receive_character(&mut self, character: char) -> KeyCapture {
+        if !self.focused || character.is_control() {
+            return KeyCapture::Miss;
         }
 
-        if let Some(character) = Self::numeric_map(virtual_keycode) {
-            self.type_character(character);
-            self.input_error = false;
-            return KeyCapture::Capture;
-        } else if virtual_keycode != VirtualKeyCode::LShift {
-            self.input_error = true;
+        match (self.char_map)(character) {
+            Some(character) => {
+                self.type_character(character);
+                self.input_error = false;
+            }
+            None => self.input_error = true,
         }
 
-        KeyCapture::Miss
+        KeyCapture::Capture
+    }
+
+    // Starts editing without a click, e.g. when tabbing into the input
+    pub fn focus(&mut self) {
+        if !self.focused {
+            self.focused = true;
+            self.just_focused = true;
+            self.input_error = false;
+            self.pre_edit = self.text.clone();
+            self.move_caret(self.text.chars().count(), false);
+        }
     }
 
     pub fn unfocus(&mut self) {
+        self.scrub_start = None;
+
         if self.focused {
             self.focused = false;
             self.has_new_content = true;
-            self.ensure_not_empty();
+            self.commit();
         }
     }
 
+    // Finishes editing, expressions that can't be evaluated go back to what was there before
+    fn commit(&mut self) {
+        if !self.evaluate() {
+            self.set(&self.pre_edit.clone());
+        }
+
+        self.ensure_not_empty();
+    }
+
+    // Replaces a numeric expression with its value, returns whether it could be evaluated
+    fn evaluate(&mut self) -> bool {
+        if !self.is_numeric || self.text.trim().is_empty() {
+            return true;
+        }
+
+        match self.evaluate_text() {
+            Some(value) => {
+                self.set(&format_number(value));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Value of a numeric expression, where lengths typed in another unit are converted to the one they're shown in
+    fn evaluate_text(&self) -> Option<f32> {
+        let format = match self.length_format {
+            Some(format) => format,
+            None => return evaluate_number(&self.text),
+        };
+
+        match split_unit(&self.text) {
+            (expression, Some(unit)) => Some(format.convert(evaluate_number(expression)?, unit)),
+            (expression, None) => evaluate_number(expression),
+        }
+    }
+
+    // Numeric value, where lengths are in world units
+    pub fn get_value(&self) -> f32 {
+        let value = self.evaluate_text().unwrap_or_default();
+
+        match self.length_format {
+            Some(format) => format.to_pixels(value, format.unit),
+            None => value,
+        }
+    }
+
+    // Shows a numeric value, where lengths are in world units
+    pub fn set_value(&mut self, value: f32) {
+        let text = format_value(self.length_format.map_or(value, |format| format.from_pixels(value)));
+
+        if text != self.text {
+            self.set(&text);
+        }
+    }
+
+    pub fn poll_value(&mut self) -> Option<f32> {
+        self.poll()?;
+        Some(self.get_value())
+    }
+
+    // Changes the unit a length is shown in, keeping the same length
+    pub fn set_length_format(&mut self, format: LengthFormat) {
+        let previous = match self.length_format {
+            Some(previous) if previous != format => previous,
+            _ => return,
+        };
+
+        let convert = |text: &str| {
+            let pixels = evaluate_number(text).map_or(0.0, |value| previous.to_pixels(value, previous.unit));
+            format_value(format.from_pixels(pixels))
+        };
+
+        self.text = convert(&self.text);
+        self.pre_edit = convert(&self.pre_edit);
+        self.default_text = convert(&self.default_text);
+        self.suffix = format!(" {}", format.unit.get_suffix());
+        self.length_format = Some(format);
+        self.move_caret(self.text.chars().count(), false);
+    }
+
     pub fn render(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, area: &AABB) {
+        if !self.focused {
+            self.set_length_format(ctx.length_format);
+        }
+
         self.render_background(ctx, area);
         self.render_text(ctx, text_system, font.clone(), area);
         self.render_label(ctx, text_system, font, area);
@@ -125,20 +341,74 @@ impl TextInput {
     }
 
     fn type_character(&mut self, character: char) {
-        self.text.push(character);
-        self.text_display = None;
+        self.erase_selection();
+        let index = self.get_byte_index(self.caret);
+        self.text.insert(index, character);
+        self.caret += 1;
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.move_caret(0, false);
+    }
+
+    // Selected characters, in order
+    pub fn get_selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.caret)?;
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    fn get_byte_index(&self, char_index: usize) -> usize {
+        self.text.char_indices().nth(char_index).map_or(self.text.len(), |(index, _)| index)
     }
 
-    fn backspace(&mut self) {
-        if !self.text.is_empty() {
-            self.text.pop();
-            self.text_display = None;
+    // Moves the caret, extending the selection from where it was when 'select' is held
+    fn move_caret(&mut self, caret: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
         }
+
+        self.caret = caret;
     }
 
-    fn clear(&mut self) {
-        self.text.clear();
-        self.text_display = None;
+    // Where Left/Right moves to, which is the edge of the selection when there is one
+    fn get_step_target(&self, forward: bool, select: bool) -> usize {
+        match (self.get_selection(), select) {
+            (Some(selection), false) if forward => selection.end,
+            (Some(selection), false) => selection.start,
+            _ if forward => (self.caret + 1).min(self.text.chars().count()),
+            _ => self.caret.saturating_sub(1),
+        }
+    }
+
+    // Removes the selection, or else the characters 'around' gives for the caret
+    fn erase(&mut self, around: impl Fn(usize) -> Range<usize>) {
+        if self.get_selection().is_none() {
+            let range = around(self.caret);
+            self.anchor = Some(range.start);
+            self.caret = range.end;
+        }
+
+        self.erase_selection();
+    }
+
+    fn erase_selection(&mut self) {
+        if let Some(selection) = self.get_selection() {
+            let range = self.get_byte_index(selection.start)..self.get_byte_index(selection.end);
+            self.text.replace_range(range, "");
+            self.caret = selection.start;
+        }
+
+        self.anchor = None;
+    }
+
+    fn copy(&self) {
+        if let Some(selection) = self.get_selection() {
+            let copied: String = self.text.chars().skip(selection.start).take(selection.len()).collect();
+            CLIPBOARD.with(|clipboard| *clipboard.borrow_mut() = copied);
+        }
     }
 
     pub fn poll(&mut self) -> Option<&str> {
@@ -152,15 +422,14 @@ impl TextInput {
 
     fn render_background(&self, ctx: &mut RenderCtx, area: &AABB) {
         let mesh = ctx.square_xyzuv;
-        let identity = glm::identity::<f32, 4>();
+        let identity = Affine2::identity();
         let quad_dimensions = glm::vec2(area.width() + 32.0, area.height() + 32.0);
         let dead_space = quad_dimensions - glm::vec2(area.width(), area.height());
-        let transformation = glm::translation(&glm::vec3(
+        let transformation = Affine2::translation(&glm::vec2(
             area.min_x + quad_dimensions.x * 0.5 - dead_space.x * 0.5,
             area.min_y + quad_dimensions.y * 0.5 - dead_space.y * 0.5,
-            0.0,
         ));
-        let transformation = glm::scale(&transformation, &glm::vec3(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5, 0.0));
+        let transformation = transformation.scale(&glm::vec2(quad_dimensions.x * 0.5, quad_dimensions.y * 0.5));
 
         let uniforms = glium::uniform! {
             transformation: transformation.as_values(),
@@ -181,7 +450,7 @@ impl TextInput {
             .unwrap();
     }
 
-    fn render_text(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, input_area: &AABB) {
+    fn render_text(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, input_area: &AABB) {
         let input_area_center = glm::vec2(input_area.min_x + input_area.width() / 2.0, input_area.min_y + input_area.height() / 2.0);
         let relative_position = glm::vec2(0.0, 4.0);
 
@@ -192,33 +461,50 @@ impl TextInput {
                 ctx.color_scheme.foreground
             }
         } else {
-            Color::from_hex("#777777")
+            ctx.color_scheme.label
         };
 
-        draw_text_centered(
-            &mut self.text_display,
-            text_system,
-            font,
-            &format!("{}{}", &self.text, &self.suffix),
-            &(input_area_center + relative_position),
-            ctx,
-            color,
-        );
+        let content = format!("{}{}", &self.text, &self.suffix);
+        let location = input_area_center + relative_position;
+
+        if self.focused {
+            self.render_caret(ctx, &font, &content, &location);
+        }
+
+        draw_text_centered(text_system, font, &content, &location, ctx, color);
     }
 
-    fn render_label(&mut self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, input_area: &AABB) {
+    // Highlights the selection and marks where typing goes, 'location' is where 'content' is centered
+    fn render_caret(&self, ctx: &mut RenderCtx, font: &FontTexture, content: &str, location: &glm::Vec2) {
+        let left = location.x - 0.5 * get_text_width(font, content);
+        let get_x = |char_index: usize| left + get_text_width(font, &self.text[..self.get_byte_index(char_index)]);
+        let top = location.y - 14.0;
+
+        if let Some(selection) = self.get_selection() {
+            let (start, end) = (get_x(selection.start), get_x(selection.end));
+            ctx.ribbon_mesh
+                .render(ctx, glm::vec2(start, top), glm::vec2(end - start, 18.0), &ctx.color_scheme.dark_ribbon);
+        }
+
+        ctx.ribbon_mesh
+            .render(ctx, glm::vec2(get_x(self.caret) - 0.5, top), glm::vec2(1.5, 18.0), &ctx.color_scheme.foreground);
+    }
+
+    fn get_label_area(area: &AABB) -> AABB {
+        AABB::new(area.min_x, area.min_y - 32.0, area.width(), 24.0)
+    }
+
+    fn render_label(&self, ctx: &mut RenderCtx, text_system: &TextSystem, font: Rc<FontTexture>, input_area: &AABB) {
         let input_area_center = glm::vec2(input_area.min_x + input_area.width() / 2.0, input_area.min_y + input_area.height() / 2.0);
         let relative_position = glm::vec2(0.0, -28.0);
 
-        draw_text_centered(
-            &mut self.label_display,
-            text_system,
-            font,
-            &self.label,
-            &(input_area_center + relative_position),
-            ctx,
-            Color::from_hex("#777777"),
-        );
+        let color = if self.scrub_start.is_some() {
+            ctx.color_scheme.foreground
+        } else {
+            ctx.color_scheme.label
+        };
+
+        draw_text_centered(text_system, font, &self.label, &(input_area_center + relative_position), ctx, color);
     }
 
     pub fn is_focused(&self) -> bool {
@@ -226,39 +512,192 @@ impl TextInput {
     }
 
     pub fn set(&mut self, content: &str) {
-        self.clear();
-
-        for character in content.chars() {
-            self.type_character(character);
-        }
+        self.text = content.into();
+        self.move_caret(self.text.chars().count(), false);
     }
 
     fn ensure_not_empty(&mut self) {
         if self.text.is_empty() {
-            self.text = self.default_text.clone();
-            self.text_display = None;
+            self.set(&self.default_text.clone());
         }
     }
 
-    pub fn numeric_map(virtual_keycode: VirtualKeyCode) -> Option<char> {
-        match virtual_keycode {
-            VirtualKeyCode::Key0 => Some('0'),
-            VirtualKeyCode::Key1 => Some('1'),
-            VirtualKeyCode::Key2 => Some('2'),
-            VirtualKeyCode::Key3 => Some('3'),
-            VirtualKeyCode::Key4 => Some('4'),
-            VirtualKeyCode::Key5 => Some('5'),
-            VirtualKeyCode::Key6 => Some('6'),
-            VirtualKeyCode::Key7 => Some('7'),
-            VirtualKeyCode::Key8 => Some('8'),
-            VirtualKeyCode::Key9 => Some('9'),
-            VirtualKeyCode::Period => Some('.'),
-            VirtualKeyCode::Minus => Some('-'),
-            _ => None,
-        }
+    // Numbers can be typed as expressions, like '1920/2+16'
+    pub fn numeric_map(character: char) -> Option<char> {
+        (character.is_ascii_digit() || ".-+*/%() ".contains(character)).then_some(character)
+    }
+
+    // Lengths can also end in a unit
+    pub fn length_map(character: char) -> Option<char> {
+        Self::numeric_map(character).or_else(|| character.is_ascii_alphabetic().then_some(character))
+    }
+
+    pub fn hex_map(character: char) -> Option<char> {
+        character.is_ascii_hexdigit().then(|| character.to_ascii_uppercase())
+    }
+
+    pub fn text_map(character: char) -> Option<char> {
+        Some(character)
     }
 
     pub fn text(&self) -> &str {
         &self.text
     }
 }
+
+// Rounds away floating point noise, e.g. from evaluating expressions
+fn format_number(value: f32) -> String {
+    ((value * 10000.0).round() / 10000.0).to_string()
+}
+
+// Rounds to a couple decimal places for showing values that weren't typed
+fn format_value(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+
+    // Avoid showing negative zero
+    if rounded == 0.0 {
+        "0".into()
+    } else {
+        rounded.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+    use crate::{
+        aabb::AABB,
+        capture::{Capture, KeyCapture},
+        interaction::DragInteraction,
+        units::{LengthFormat, Unit},
+    };
+    use glium::glutin::event::{ModifiersState, MouseButton, VirtualKeyCode};
+    use nalgebra_glm as glm;
+
+    #[test]
+    fn receive_characters() {
+        let mut numeric = TextInput::new("0".into(), "Width".into(), "".into());
+        assert_eq!(numeric.receive_character('1'), KeyCapture::Miss);
+
+        numeric.focus();
+        numeric.set("");
+        "-1.5a".chars().for_each(|character| {
+            numeric.receive_character(character);
+        });
+        assert_eq!(numeric.text(), "-1.5");
+
+        let mut hex = TextInput::hex("".into(), "Hex".into());
+        hex.focus();
+        "ff0Z".chars().for_each(|character| {
+            hex.receive_character(character);
+        });
+        assert_eq!(hex.text(), "FF0");
+
+        let mut text = TextInput::plain("".into(), "Export Size".into());
+        text.focus();
+        "1920×1080 é".chars().for_each(|character| {
+            text.receive_character(character);
+        });
+        text.receive_character('\u{8}');
+        assert_eq!(text.text(), "1920×1080 é");
+    }
+
+    #[test]
+    fn edit_with_caret() {
+        let none = ModifiersState::empty();
+        let mut input = TextInput::plain("".into(), "Name".into());
+        let mut other = TextInput::plain("".into(), "Other".into());
+        input.focus();
+        "hello world".chars().for_each(|character| {
+            input.receive_character(character);
+        });
+
+        // Select "hello" and replace it
+        input.key_press(VirtualKeyCode::Home, none);
+        (0..5).for_each(|_| {
+            input.key_press(VirtualKeyCode::Right, ModifiersState::SHIFT);
+        });
+        assert_eq!(input.get_selection(), Some(0..5));
+        input.receive_character('Y');
+        assert_eq!(input.text(), "Y world");
+
+        // Delete in front of the caret, then backspace behind it
+        input.key_press(VirtualKeyCode::Delete, none);
+        input.key_press(VirtualKeyCode::End, none);
+        input.key_press(VirtualKeyCode::Back, none);
+        assert_eq!(input.text(), "Yworl");
+
+        // Cut everything and paste it twice into another input
+        input.key_press(VirtualKeyCode::A, ModifiersState::CTRL);
+        input.key_press(VirtualKeyCode::X, ModifiersState::CTRL);
+        assert_eq!(input.text(), "");
+
+        other.focus();
+        other.key_press(VirtualKeyCode::V, ModifiersState::CTRL);
+        other.key_press(VirtualKeyCode::Left, none);
+        other.key_press(VirtualKeyCode::V, ModifiersState::CTRL);
+        assert_eq!(other.text(), "YworYworll");
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        let none = ModifiersState::empty();
+        let mut input = TextInput::new("0".into(), "Width".into(), "".into());
+        input.focus();
+        input.set("1920/2+16");
+        input.key_press(VirtualKeyCode::Return, none);
+        assert!(!input.is_focused());
+        assert_eq!(input.poll(), Some("976"));
+
+        // Mistakes keep the input open on Enter, and are undone when leaving it
+        input.focus();
+        input.set("45*");
+        input.key_press(VirtualKeyCode::Return, none);
+        assert!(input.is_focused());
+        input.unfocus();
+        assert_eq!(input.text(), "976");
+    }
+
+    #[test]
+    fn enter_lengths() {
+        let none = ModifiersState::empty();
+        let mut input = TextInput::length(96.0, "Width".into());
+        input.set_length_format(LengthFormat { unit: Unit::In, dpi: 96.0 });
+        assert_eq!(input.text(), "1");
+
+        // Lengths typed in another unit are converted to the one being shown
+        input.focus();
+        input.set("2.54*3 cm");
+        input.key_press(VirtualKeyCode::Return, none);
+        assert_eq!(input.poll_value(), Some(288.0));
+        assert_eq!(input.text(), "3");
+
+        input.set_length_format(LengthFormat { unit: Unit::Px, dpi: 72.0 });
+        assert_eq!(input.text(), "288");
+        input.set_value(36.0);
+        assert_eq!(input.get_value(), 36.0);
+    }
+
+    #[test]
+    fn scrub_numbers() {
+        let area = AABB::new(0.0, 100.0, 176.0, 27.0);
+        let mut input = TextInput::new("10".into(), "Camera X".into(), "".into());
+        assert_eq!(input.click(MouseButton::Left, &glm::vec2(88.0, 80.0), &area), Capture::TakeFocus);
+        assert!(!input.is_focused());
+
+        let drag = |x: f32, modifiers: ModifiersState| DragInteraction {
+            delta: glm::vec2(0.0, 0.0),
+            start: glm::vec2(88.0, 80.0),
+            current: glm::vec2(x, 80.0),
+            modifiers,
+        };
+
+        input.drag(&drag(113.0, ModifiersState::empty()));
+        assert_eq!(input.poll(), Some("35"));
+        input.drag(&drag(83.0, ModifiersState::SHIFT));
+        assert_eq!(input.poll(), Some("9.5"));
+
+        input.release();
+        assert_eq!(input.drag(&drag(200.0, ModifiersState::empty())), Capture::Miss);
+    }
+}
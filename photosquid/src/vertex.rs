@@ -9,5 +9,12 @@ pub struct VertexXYUV {
     pub uvs: [f32; 2],
 }
 
+#[derive(Copy, Clone)]
+pub struct VertexXYRGBA {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
 glium::implement_vertex!(Vertex, position);
 glium::implement_vertex!(VertexXYUV, position, uvs);
+glium::implement_vertex!(VertexXYRGBA, position, color);